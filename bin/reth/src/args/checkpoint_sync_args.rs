@@ -0,0 +1,92 @@
+//! clap [Args](clap::Args) for syncing from a trusted checkpoint
+use clap::Args;
+use reth_primitives::H256;
+use std::path::PathBuf;
+
+/// Parameters for starting the sync from a trusted checkpoint instead of genesis
+#[derive(Debug, Args, PartialEq, Default, Clone)]
+#[command(next_help_heading = "Checkpoint sync")]
+pub struct CheckpointSyncArgs {
+    /// Start syncing from the trusted finalized block with the given hash instead of genesis.
+    ///
+    /// The state of the block is imported from the state dump given by
+    /// `--checkpoint-sync.state` and verified against `--checkpoint-sync.state-root`.
+    /// The pipeline continues from the checkpoint, while the headers and bodies below it are
+    /// backfilled in reverse in the background. Receipts below the checkpoint are not available.
+    ///
+    /// Only applies to an empty database. Fetching the state over snap/1 is not supported.
+    #[arg(
+        long = "checkpoint-sync",
+        value_name = "HASH",
+        requires = "state_root",
+        verbatim_doc_comment
+    )]
+    pub hash: Option<H256>,
+
+    /// The trusted state root of the checkpoint block.
+    #[arg(long = "checkpoint-sync.state-root", value_name = "STATE_ROOT", requires = "state")]
+    pub state_root: Option<H256>,
+
+    /// Path to the JSONL state dump of the checkpoint block, as written by `reth db dump-state`.
+    #[arg(long = "checkpoint-sync.state", value_name = "FILE", requires = "hash")]
+    pub state: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[clap(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_checkpoint_sync_args() {
+        let args = CommandParser::<CheckpointSyncArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, CheckpointSyncArgs::default());
+
+        let hash = H256::random();
+        let state_root = H256::random();
+        let args = CommandParser::<CheckpointSyncArgs>::parse_from([
+            "reth",
+            "--checkpoint-sync",
+            &format!("{hash:?}"),
+            "--checkpoint-sync.state-root",
+            &format!("{state_root:?}"),
+            "--checkpoint-sync.state",
+            "state.jsonl",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            CheckpointSyncArgs {
+                hash: Some(hash),
+                state_root: Some(state_root),
+                state: Some("state.jsonl".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_sync_requires_state() {
+        let hash = format!("{:?}", H256::random());
+        assert!(CommandParser::<CheckpointSyncArgs>::try_parse_from([
+            "reth",
+            "--checkpoint-sync",
+            &hash,
+            "--checkpoint-sync.state-root",
+            &hash,
+        ])
+        .is_err());
+        assert!(CommandParser::<CheckpointSyncArgs>::try_parse_from([
+            "reth",
+            "--checkpoint-sync.state",
+            "state.jsonl"
+        ])
+        .is_err());
+    }
+}
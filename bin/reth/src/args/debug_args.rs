@@ -2,6 +2,7 @@
 
 use clap::Args;
 use reth_primitives::{TxHash, H256};
+use std::path::PathBuf;
 
 /// Parameters for debugging purposes
 #[derive(Debug, Args, PartialEq, Default)]
@@ -57,4 +58,11 @@ pub struct DebugArgs {
         conflicts_with = "hook_transaction"
     )]
     pub hook_all: bool,
+
+    /// Append every `newPayload`, `forkchoiceUpdated` and `getPayload` call received by the Engine
+    /// API, together with its response, to the given file.
+    ///
+    /// The file can be replayed with `reth debug replay-engine`.
+    #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
+    pub engine_api_store: Option<PathBuf>,
 }
@@ -2,7 +2,7 @@
 
 /// NetworkArg struct for configuring the network
 mod network_args;
-pub use network_args::{DiscoveryArgs, NetworkArgs};
+pub use network_args::{DiscoveryArgs, NetworkArgs, ServingArgs, TxPropagationArgs};
 
 /// RpcServerArg struct for configuring the RPC
 mod rpc_server_args;
@@ -43,4 +43,8 @@ pub use dev_args::DevArgs;
 mod pruning_args;
 pub use pruning_args::PruningArgs;
 
+/// CheckpointSyncArgs for starting the sync from a trusted checkpoint
+mod checkpoint_sync_args;
+pub use checkpoint_sync_args::CheckpointSyncArgs;
+
 pub mod utils;
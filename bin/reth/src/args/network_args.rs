@@ -4,7 +4,11 @@ use crate::version::P2P_CLIENT_VERSION;
 use clap::Args;
 use reth_config::Config;
 use reth_net_nat::NatResolver;
-use reth_network::{HelloMessage, NetworkConfigBuilder};
+use reth_network::{
+    eth_requests::{RequestBudget, ServingPolicy, ServingRole, DEFAULT_MAX_CONCURRENT_READS},
+    transactions::{PeerRateLimit, PropagationPeers, PropagationPolicy},
+    HelloMessage, NetworkConfigBuilder,
+};
 use reth_primitives::{mainnet_nodes, ChainSpec, NodeRecord};
 use secp256k1::SecretKey;
 use std::{path::PathBuf, sync::Arc};
@@ -53,7 +57,9 @@ pub struct NetworkArgs {
     pub no_persist_peers: bool,
 
     #[allow(rustdoc::invalid_html_tags)]
-    /// NAT resolution method (any|none|upnp|publicip|extip:<IP>)
+    /// NAT resolution method (any|none|upnp|publicip|extip:<IP>|pmp|pmp:<GATEWAY>)
+    ///
+    /// With `pmp` the RLPx and discovery ports are also mapped on the NAT-PMP/PCP gateway.
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
 
@@ -68,6 +74,20 @@ pub struct NetworkArgs {
     /// Maximum number of inbound requests. default: 30
     #[arg(long)]
     pub max_inbound_peers: Option<usize>,
+
+    /// Capture the eth wire traffic of all sessions to this file.
+    ///
+    /// The capture can be inspected with `reth p2p replay`.
+    #[arg(long, value_name = "FILE")]
+    pub capture_file: Option<PathBuf>,
+
+    /// Transaction propagation settings.
+    #[command(flatten)]
+    pub tx_propagation: TxPropagationArgs,
+
+    /// Settings for serving requests of peers.
+    #[command(flatten)]
+    pub serving: ServingArgs,
 }
 
 impl NetworkArgs {
@@ -100,6 +120,11 @@ impl NetworkArgs {
             .boot_nodes(self.bootnodes.clone().unwrap_or(chain_bootnodes))
             .chain_spec(chain_spec);
 
+        if let Some(capture_file) = &self.capture_file {
+            network_config_builder = network_config_builder
+                .sessions_config(config.sessions.clone().with_capture_file(capture_file));
+        }
+
         // Configure node identity
         let peer_id = network_config_builder.get_peer_id();
         network_config_builder = network_config_builder
@@ -118,6 +143,89 @@ impl NetworkArgs {
     }
 }
 
+/// Arguments to configure how transactions are propagated to peers
+#[derive(Debug, Args, PartialEq, Default)]
+pub struct TxPropagationArgs {
+    /// Only propagate transactions to trusted peers.
+    ///
+    /// This includes trusted peers that are added at runtime.
+    #[arg(long = "txpropagation.trusted-only")]
+    pub trusted_only: bool,
+
+    /// Do not propagate locally submitted transactions.
+    #[arg(long = "txpropagation.no-local")]
+    pub no_local: bool,
+
+    /// Do not propagate new blob transactions.
+    ///
+    /// Blob transactions are still announced to newly connected peers.
+    #[arg(long = "txpropagation.no-blobs")]
+    pub no_blobs: bool,
+
+    /// Maximum number of transactions propagated to a single peer per second.
+    #[arg(long = "txpropagation.max-per-peer", value_name = "COUNT")]
+    pub max_per_peer: Option<usize>,
+}
+
+impl TxPropagationArgs {
+    /// Returns the [PropagationPolicy] for the transactions manager.
+    pub fn propagation_policy(&self) -> PropagationPolicy {
+        let mut policy = PropagationPolicy::default()
+            .with_propagate_local(!self.no_local)
+            .with_propagate_blob_transactions(!self.no_blobs);
+
+        if self.trusted_only {
+            policy = policy.with_trusted_peers_only();
+        }
+
+        if let Some(max_per_peer) = self.max_per_peer {
+            policy = policy.with_peer_rate_limit(PeerRateLimit::per_second(max_per_peer));
+        }
+
+        policy
+    }
+}
+
+/// Arguments to configure which requests of peers are served
+#[derive(Debug, Args, PartialEq)]
+pub struct ServingArgs {
+    /// Which requests are served to peers (full|headers|receipts).
+    ///
+    /// With `headers` only block headers are served, with `receipts` block headers and receipts.
+    #[arg(long = "serving.role", value_name = "ROLE", default_value = "full")]
+    pub role: ServingRole,
+
+    /// Maximum number of requests served to a single peer per second.
+    ///
+    /// Trusted peers are exempt from this limit and their requests are preferred.
+    #[arg(long = "serving.max-requests-per-peer", value_name = "COUNT")]
+    pub max_requests_per_peer: Option<usize>,
+
+    /// Do not serve requests while the node is initially syncing.
+    #[arg(long = "serving.no-serve-while-syncing")]
+    pub no_serve_while_syncing: bool,
+
+    /// Maximum number of requests that are served concurrently.
+    #[arg(long = "serving.max-concurrent-reads", value_name = "COUNT", default_value_t = DEFAULT_MAX_CONCURRENT_READS)]
+    pub max_concurrent_reads: usize,
+}
+
+impl ServingArgs {
+    /// Returns the [ServingPolicy] for the eth request handler.
+    pub fn serving_policy(&self) -> ServingPolicy {
+        let mut policy = ServingPolicy::default()
+            .with_role(self.role)
+            .with_serve_while_initially_syncing(!self.no_serve_while_syncing)
+            .with_max_concurrent_reads(self.max_concurrent_reads);
+
+        if let Some(max_requests) = self.max_requests_per_peer {
+            policy = policy.with_peer_budget(RequestBudget::per_second(max_requests));
+        }
+
+        policy
+    }
+}
+
 /// Arguments to setup discovery
 #[derive(Debug, Args)]
 pub struct DiscoveryArgs {
@@ -174,6 +282,9 @@ mod tests {
         let args =
             CommandParser::<NetworkArgs>::parse_from(["reth", "--nat", "extip:0.0.0.0"]).args;
         assert_eq!(args.nat, NatResolver::ExternalIp("0.0.0.0".parse().unwrap()));
+
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth", "--nat", "pmp"]).args;
+        assert_eq!(args.nat, NatResolver::Pmp(None));
     }
 
     #[test]
@@ -194,4 +305,49 @@ mod tests {
         assert_eq!(args.max_outbound_peers, Some(75));
         assert_eq!(args.max_inbound_peers, Some(15));
     }
+
+    #[test]
+    fn parse_tx_propagation_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.tx_propagation, TxPropagationArgs::default());
+        assert_eq!(args.tx_propagation.propagation_policy(), PropagationPolicy::default());
+
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--txpropagation.trusted-only",
+            "--txpropagation.no-local",
+            "--txpropagation.max-per-peer",
+            "100",
+        ])
+        .args;
+        let policy = args.tx_propagation.propagation_policy();
+        assert_eq!(policy.peers, PropagationPeers::Trusted);
+        assert!(!policy.propagate_local);
+        assert!(policy.propagate_blob_transactions);
+        assert_eq!(policy.peer_rate_limit, Some(PeerRateLimit::per_second(100)));
+    }
+
+    #[test]
+    fn parse_serving_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.serving.serving_policy(), ServingPolicy::default());
+
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--serving.role",
+            "receipts",
+            "--serving.max-requests-per-peer",
+            "10",
+            "--serving.no-serve-while-syncing",
+        ])
+        .args;
+        let policy = args.serving.serving_policy();
+        assert_eq!(policy.role, ServingRole::HeadersAndReceipts);
+        assert_eq!(policy.peer_budget, Some(RequestBudget::per_second(10)));
+        assert!(!policy.serve_while_initially_syncing);
+        assert!(policy.prefer_trusted_peers);
+
+        assert!(CommandParser::<NetworkArgs>::try_parse_from(["reth", "--serving.role", "bodies"])
+            .is_err());
+    }
 }
@@ -9,6 +9,7 @@ use clap::{
     Arg, Args, Command,
 };
 use futures::TryFutureExt;
+use reth_db::mdbx::backup::{BackupManifest, DatabaseBackup};
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
     BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
@@ -20,16 +21,19 @@ use reth_rpc::{
             DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_ENV_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_MAX_LEN,
         },
         gas_oracle::GasPriceOracleConfig,
-        RPC_DEFAULT_GAS_CAP,
+        KeystoreSigner, RPC_DEFAULT_GAS_CAP,
     },
-    JwtError, JwtSecret,
+    AdminBackupApi, JwtError, JwtSecret, MethodCosts, RateLimitConfig, RequestLimitConfig,
+    DEFAULT_RATE_LIMIT_CAPACITY, DEFAULT_RATE_LIMIT_REFILL_PER_SECOND,
 };
+use reth_rpc_api::AdminBackupApiServer;
 use reth_rpc_builder::{
     auth::{AuthServerConfig, AuthServerHandle},
     constants,
     error::RpcError,
-    EthConfig, IpcServerBuilder, RethRpcModule, RpcModuleBuilder, RpcModuleConfig,
-    RpcModuleSelection, RpcServerConfig, RpcServerHandle, ServerBuilder, TransportRpcModuleConfig,
+    BatchRequestConfig, EthConfig, IpcServerBuilder, RethRpcModule, RpcModuleBuilder,
+    RpcModuleConfig, RpcModuleSelection, RpcServerConfig, RpcServerHandle, ServerBuilder,
+    TransportRpcModuleConfig,
 };
 use reth_rpc_engine_api::{EngineApi, EngineApiServer};
 use reth_tasks::TaskSpawner;
@@ -37,7 +41,8 @@ use reth_transaction_pool::TransactionPool;
 use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr, SocketAddr},
-    path::PathBuf,
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 use tracing::{debug, info};
 
@@ -51,6 +56,10 @@ pub(crate) const RPC_DEFAULT_MAX_REQUEST_SIZE_MB: u32 = 15;
 pub(crate) const RPC_DEFAULT_MAX_RESPONSE_SIZE_MB: u32 = 115;
 /// Default number of incoming connections.
 pub(crate) const RPC_DEFAULT_MAX_CONNECTIONS: u32 = 100;
+/// Default max number of requests in a batch request.
+pub(crate) const RPC_DEFAULT_MAX_BATCH_SIZE: u32 = 1000;
+/// Default max number of requests that are handled concurrently per connection.
+pub(crate) const RPC_DEFAULT_MAX_CONCURRENT_REQUESTS_PER_CONN: u32 = 1024;
 /// Default number of incoming connections.
 pub(crate) const RPC_DEFAULT_MAX_TRACING_REQUESTS: u32 = 25;
 
@@ -106,6 +115,13 @@ pub struct RpcServerArgs {
     #[arg(long, default_value_t = constants::DEFAULT_IPC_ENDPOINT.to_string())]
     pub ipcpath: String,
 
+    /// Publish canonical state notifications on a unix socket at the given path.
+    ///
+    /// Processes that read the database, like a standalone RPC server, can subscribe to the
+    /// notifications with `reth_provider::ipc::CanonStateIpcClient`.
+    #[arg(long = "notifications.ipcpath", value_name = "PATH")]
+    pub notifications_ipcpath: Option<PathBuf>,
+
     /// Auth server address to listen on
     #[arg(long = "authrpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
     pub auth_addr: IpAddr,
@@ -134,10 +150,88 @@ pub struct RpcServerArgs {
     #[arg(long, value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_CONNECTIONS)]
     pub rpc_max_connections: u32,
 
+    /// Maximum number of requests in a batch request.
+    ///
+    /// Larger batch requests are rejected with an error. Batch requests used to be unlimited, so
+    /// clients that send larger batches have to split them or this limit has to be raised.
+    #[arg(long, value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_BATCH_SIZE)]
+    pub rpc_max_batch_size: u32,
+
+    /// Maximum size of a batch response in megabytes.
+    ///
+    /// Batch responses that exceed the limit are replaced with an error. Defaults to the value of
+    /// `--rpc-max-response-size`, which is also the limit for batch responses over WS.
+    #[arg(long, value_name = "SIZE")]
+    pub rpc_max_batch_response_size: Option<u32>,
+
+    /// Maximum number of requests that are handled concurrently per connection.
+    ///
+    /// HTTP and IPC requests that exceed the limit are rejected with an error. WS connections stop
+    /// reading new requests until one of the pending requests is answered.
+    #[arg(long, value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_CONCURRENT_REQUESTS_PER_CONN)]
+    pub rpc_max_concurrent_requests_per_connection: u32,
+
     /// Maximum number of concurrent tracing requests.
     #[arg(long, value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_TRACING_REQUESTS)]
     pub rpc_max_tracing_requests: u32,
 
+    /// Enable compute unit quotas per caller for HTTP and IPC requests.
+    ///
+    /// Calls over WS connections can't be limited, so this can't be used with `--ws`.
+    ///
+    /// HTTP callers are identified by the `sub` claim of their JWT, see
+    /// `--rpc.ratelimit.jwtsecret`, by the `X-Forwarded-For` header, see
+    /// `--rpc.ratelimit.trustproxy`, or by their IP address. All IPC callers share a quota.
+    #[arg(long = "rpc.ratelimit", verbatim_doc_comment, conflicts_with = "ws")]
+    pub rpc_rate_limit: bool,
+
+    /// Number of compute units a caller can spend at once.
+    #[arg(long = "rpc.ratelimit.capacity", value_name = "UNITS", default_value_t = DEFAULT_RATE_LIMIT_CAPACITY)]
+    pub rpc_rate_limit_capacity: u64,
+
+    /// Number of compute units that are restored per second.
+    #[arg(long = "rpc.ratelimit.refill", value_name = "UNITS", default_value_t = DEFAULT_RATE_LIMIT_REFILL_PER_SECOND)]
+    pub rpc_rate_limit_refill: u64,
+
+    /// Sets the compute cost of a method, e.g. `eth_call=20`. Can be used multiple times.
+    #[arg(long = "rpc.ratelimit.cost", value_name = "METHOD=COST", value_parser = parse_method_cost)]
+    pub rpc_rate_limit_costs: Vec<(String, u64)>,
+
+    /// Path to the hex encoded secret used to validate the JWTs that identify callers.
+    #[arg(long = "rpc.ratelimit.jwtsecret", value_name = "PATH", value_parser = parse_jwt_secret)]
+    pub rpc_rate_limit_jwtsecret: Option<JwtSecret>,
+
+    /// Identify callers by the `X-Forwarded-For` and `X-Real-IP` headers.
+    ///
+    /// Only enable this if the HTTP server is exclusively reachable through a reverse proxy that
+    /// sets these headers, otherwise callers can claim any address.
+    #[arg(long = "rpc.ratelimit.trustproxy")]
+    pub rpc_rate_limit_trust_proxy: bool,
+
+    /// Path to a keystore directory with Web3 Secret Storage key files.
+    ///
+    /// The accounts can be used with `eth_sign`, `eth_signTransaction` and `eth_sendTransaction`.
+    #[arg(long = "rpc.keystore", value_name = "DIR", requires = "rpc_keystore_password")]
+    pub rpc_keystore: Option<PathBuf>,
+
+    /// Path to a file with the passwords of the keystore files, one per line.
+    #[arg(long = "rpc.keystore.password", value_name = "PATH", requires = "rpc_keystore")]
+    pub rpc_keystore_password: Option<PathBuf>,
+
+    /// Allow the keystore accounts to be used over HTTP and WS.
+    ///
+    /// By default, the node refuses to start with a keystore if the `eth` namespace is served
+    /// over HTTP or WS, because anyone who can reach these servers could sign with the accounts.
+    #[arg(long = "rpc.keystore.allow-insecure", requires = "rpc_keystore")]
+    pub rpc_keystore_allow_insecure: bool,
+
+    /// Directory that `admin_backupDatabase` writes backups into.
+    ///
+    /// The endpoint is only served over IPC, and only if this is set. The requested destination
+    /// is resolved relative to this directory.
+    #[arg(long = "rpc.backup-dir", value_name = "DIR", verbatim_doc_comment)]
+    pub rpc_backup_dir: Option<PathBuf>,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long,
@@ -172,9 +266,10 @@ impl RpcServerArgs {
     /// for the auth server that handles the `engine_` API that's accessed by the consensus
     /// layer.
     #[allow(clippy::too_many_arguments)]
-    pub async fn start_servers<Provider, Pool, Network, Tasks, Events, Engine, Conf>(
+    pub async fn start_servers<Provider, DB, Pool, Network, Tasks, Events, Engine, Conf>(
         &self,
         provider: Provider,
+        database: Arc<DB>,
         pool: Pool,
         network: Network,
         executor: Tasks,
@@ -193,6 +288,7 @@ impl RpcServerArgs {
             + Clone
             + Unpin
             + 'static,
+        DB: DatabaseBackup + 'static,
         Pool: TransactionPool + Clone + 'static,
         Network: NetworkInfo + Peers + Clone + 'static,
         Tasks: TaskSpawner + Clone + 'static,
@@ -201,10 +297,40 @@ impl RpcServerArgs {
         Conf: RethNodeCommandConfig,
     {
         let auth_config = self.auth_server_config(jwt_secret)?;
-
         let module_config = self.transport_rpc_module_config();
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
+        if self.rpc_keystore.is_some() &&
+            !self.rpc_keystore_allow_insecure &&
+            !self.keystore_local_only(&module_config)
+        {
+            eyre::bail!(
+                "--rpc.keystore requires `eth` to be disabled on HTTP and WS, or \
+                 --rpc.keystore.allow-insecure"
+            )
+        }
+
+        // decrypting the key files is deliberately slow, so it must not block the runtime
+        let keystore_signer = match (self.rpc_keystore.clone(), self.rpc_keystore_password.clone())
+        {
+            (Some(keystore), Some(password_file)) => {
+                debug!(target: "reth::cli", ?keystore, "Decrypting keystore files");
+                let signer = tokio::task::spawn_blocking(move || {
+                    KeystoreSigner::from_dir_with_password_file(keystore, password_file)
+                })
+                .await??;
+                Some(signer)
+            }
+            _ => None,
+        };
+
+        let backup_api = self.rpc_backup_dir.clone().map(|backup_dir| {
+            let backup = move |dest: &Path| {
+                database.backup(dest).map(to_rpc_backup_manifest).map_err(|err| err.to_string())
+            };
+            AdminBackupApi::new(backup, backup_dir, Box::new(executor.clone()))
+        });
+
         let (mut rpc_modules, auth_module, mut registry) = RpcModuleBuilder::default()
             .with_provider(provider)
             .with_pool(pool)
@@ -213,6 +339,20 @@ impl RpcServerArgs {
             .with_executor(executor)
             .build_with_auth_server(module_config, engine_api);
 
+        if let Some(signer) = keystore_signer {
+            info!(target: "reth::cli", accounts=?signer.addresses(), "Loaded keystore accounts");
+            registry.eth_api().add_keystore_signer(signer);
+        }
+
+        // backups are written to the node's filesystem, so they're only served to local callers
+        let admin_over_ipc = rpc_modules
+            .module_config()
+            .ipc()
+            .map_or(false, |modules| modules.contains(&RethRpcModule::Admin));
+        if let Some(backup_api) = backup_api.filter(|_| admin_over_ipc) {
+            rpc_modules.merge_ipc(backup_api.into_rpc())?;
+        }
+
         // apply configured customization
         conf.extend_rpc_modules(self, &mut registry, &mut rpc_modules)?;
 
@@ -312,6 +452,14 @@ impl RpcServerArgs {
         )
         .await
     }
+
+    /// Returns true if the keystore accounts are not reachable over HTTP or WS.
+    fn keystore_local_only(&self, module_config: &TransportRpcModuleConfig) -> bool {
+        let serves_eth = |modules: Option<&RpcModuleSelection>| {
+            modules.map_or(false, |modules| modules.contains(&RethRpcModule::Eth))
+        };
+        !serves_eth(module_config.http()) && !serves_eth(module_config.ws())
+    }
 }
 
 impl RethRpcConfig for RpcServerArgs {
@@ -335,6 +483,11 @@ impl RethRpcConfig for RpcServerArgs {
         self.rpc_max_response_size * 1024 * 1024
     }
 
+    fn rpc_max_batch_response_size_bytes(&self) -> u32 {
+        self.rpc_max_batch_response_size
+            .map_or_else(|| self.rpc_max_response_size_bytes(), |size| size * 1024 * 1024)
+    }
+
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
         GasPriceOracleConfig::new(
             self.gas_price_oracle.blocks,
@@ -377,6 +530,8 @@ impl RethRpcConfig for RpcServerArgs {
             .max_request_body_size(self.rpc_max_request_size_bytes())
             .max_response_body_size(self.rpc_max_response_size_bytes())
             .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection)
+            .set_message_buffer_capacity(self.rpc_max_concurrent_requests_per_connection)
+            .set_batch_request_config(BatchRequestConfig::Limit(self.rpc_max_batch_size))
     }
 
     fn ipc_server_builder(&self) -> IpcServerBuilder {
@@ -384,7 +539,30 @@ impl RethRpcConfig for RpcServerArgs {
             .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection)
             .max_request_body_size(self.rpc_max_request_size_bytes())
             .max_response_body_size(self.rpc_max_response_size_bytes())
+            .max_batch_response_body_size(self.rpc_max_batch_response_size_bytes())
             .max_connections(self.rpc_max_connections)
+            .max_concurrent_requests_per_connection(self.rpc_max_concurrent_requests_per_connection)
+            .set_batch_request_config(BatchRequestConfig::Limit(self.rpc_max_batch_size))
+    }
+
+    fn rate_limit_config(&self) -> Option<RateLimitConfig> {
+        if !self.rpc_rate_limit {
+            return None
+        }
+
+        let costs = self
+            .rpc_rate_limit_costs
+            .iter()
+            .fold(MethodCosts::default(), |costs, (method, cost)| costs.with_cost(method, *cost));
+        let mut config =
+            RateLimitConfig::new(self.rpc_rate_limit_capacity, self.rpc_rate_limit_refill)
+                .with_costs(costs)
+                .with_trust_forwarded_headers(self.rpc_rate_limit_trust_proxy)
+                .with_max_request_size(self.rpc_max_request_size_bytes());
+        if let Some(secret) = self.rpc_rate_limit_jwtsecret.clone() {
+            config = config.with_jwt_secret(secret);
+        }
+        Some(config)
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
@@ -409,7 +587,14 @@ impl RethRpcConfig for RpcServerArgs {
                 config.with_ipc(self.ipc_server_builder()).with_ipc_endpoint(self.ipcpath.clone());
         }
 
-        config
+        if let Some(rate_limit) = self.rate_limit_config() {
+            config = config.with_rate_limit(rate_limit);
+        }
+
+        config.with_request_limit(RequestLimitConfig::new(
+            self.rpc_max_concurrent_requests_per_connection,
+            self.rpc_max_batch_response_size_bytes(),
+        ))
     }
 
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError> {
@@ -437,6 +622,19 @@ impl RethRpcConfig for RpcServerArgs {
     }
 }
 
+/// clap value parser for a `METHOD=COST` pair.
+fn parse_method_cost(value: &str) -> Result<(String, u64), String> {
+    let (method, cost) =
+        value.split_once('=').ok_or_else(|| format!("expected METHOD=COST, got {value}"))?;
+    let cost = cost.trim().parse().map_err(|err| format!("invalid cost {cost}: {err}"))?;
+    Ok((method.trim().to_string(), cost))
+}
+
+/// clap value parser that reads a [JwtSecret] from the given file.
+fn parse_jwt_secret(path: &str) -> Result<JwtSecret, JwtError> {
+    JwtSecret::from_file(path.as_ref())
+}
+
 /// clap value parser for [RpcModuleSelection].
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
@@ -469,6 +667,31 @@ impl TypedValueParser for RpcModuleSelectionValueParser {
     }
 }
 
+/// Converts the manifest of a database backup into the `admin_backupDatabase` response.
+fn to_rpc_backup_manifest(manifest: BackupManifest) -> reth_rpc_types::BackupManifest {
+    let BackupManifest {
+        db_version,
+        created_at,
+        genesis_hash,
+        tip_number,
+        tip_hash,
+        stage_checkpoints,
+        size,
+    } = manifest;
+    reth_rpc_types::BackupManifest {
+        db_version,
+        created_at,
+        genesis_hash,
+        tip_number,
+        tip_hash,
+        stage_checkpoints: stage_checkpoints
+            .into_iter()
+            .map(|(stage, checkpoint)| (stage, checkpoint.block_number))
+            .collect(),
+        size,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -497,6 +720,120 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_batch_limit_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_max_batch_size, RPC_DEFAULT_MAX_BATCH_SIZE);
+        assert_eq!(args.rpc_max_batch_response_size_bytes(), args.rpc_max_response_size_bytes());
+        assert_eq!(
+            args.rpc_max_concurrent_requests_per_connection,
+            RPC_DEFAULT_MAX_CONCURRENT_REQUESTS_PER_CONN
+        );
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc-max-batch-size",
+            "10",
+            "--rpc-max-concurrent-requests-per-connection",
+            "5",
+            "--rpc-max-batch-response-size",
+            "2",
+        ])
+        .args;
+        assert_eq!(args.rpc_max_batch_size, 10);
+        assert_eq!(args.rpc_max_concurrent_requests_per_connection, 5);
+        assert_eq!(args.rpc_max_batch_response_size_bytes(), 2 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_rate_limit_config() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.rate_limit_config().is_none());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.ratelimit",
+            "--rpc.ratelimit.capacity",
+            "500",
+            "--rpc.ratelimit.cost",
+            "eth_call=50",
+            "--rpc.ratelimit.cost",
+            "eth_blockNumber=2",
+        ])
+        .args;
+        let config = args.rate_limit_config().unwrap();
+        assert_eq!(config.capacity, 500);
+        assert_eq!(config.refill_per_second, DEFAULT_RATE_LIMIT_REFILL_PER_SECOND);
+        assert_eq!(config.costs.cost("eth_call"), 50);
+        assert_eq!(config.costs.cost("eth_blockNumber"), 2);
+        assert_eq!(config.costs.cost("debug_traceTransaction"), 300);
+        assert!(config.jwt_secret.is_none());
+        assert!(!config.trust_forwarded_headers);
+
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.ratelimit.cost",
+            "eth_call",
+        ]);
+        assert!(args.is_err());
+
+        let args =
+            CommandParser::<RpcServerArgs>::try_parse_from(["reth", "--rpc.ratelimit", "--ws"]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_keystore_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.rpc_keystore.is_none());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.keystore",
+            "keystore",
+            "--rpc.keystore.password",
+            "password.txt",
+        ])
+        .args;
+        assert_eq!(args.rpc_keystore, Some(PathBuf::from("keystore")));
+        assert_eq!(args.rpc_keystore_password, Some(PathBuf::from("password.txt")));
+        assert!(!args.rpc_keystore_allow_insecure);
+        assert!(args.keystore_local_only(&args.transport_rpc_module_config()));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--http",
+            "--http.api",
+            "eth",
+            "--rpc.keystore",
+            "keystore",
+            "--rpc.keystore.password",
+            "password.txt",
+        ])
+        .args;
+        assert!(!args.keystore_local_only(&args.transport_rpc_module_config()));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--http",
+            "--http.api",
+            "net",
+            "--ws",
+            "--ws.api",
+            "net",
+            "--rpc.keystore",
+            "keystore",
+            "--rpc.keystore.password",
+            "password.txt",
+        ])
+        .args;
+        assert!(args.keystore_local_only(&args.transport_rpc_module_config()));
+
+        let args =
+            CommandParser::<RpcServerArgs>::try_parse_from(["reth", "--rpc.keystore", "keystore"]);
+        assert!(args.is_err());
+    }
+
     #[test]
     fn test_rpc_server_args_parser() {
         let args =
@@ -0,0 +1,107 @@
+use crate::{
+    args::{utils::genesis_value_parser, DatabaseArgs},
+    dirs::{DataDirPath, MaybePlatformPath},
+    init::{import_state_dump, init_genesis, read_state_dump_header},
+};
+use clap::Parser;
+use eyre::Context;
+use reth_db::init_db;
+use reth_primitives::{ChainSpec, H256};
+use std::{fs::File, io::BufReader, path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// Initializes the database with the state of a block from a state dump.
+#[derive(Debug, Parser)]
+pub struct InitStateCommand {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    ///
+    /// Built-in chains:
+    /// - mainnet
+    /// - goerli
+    /// - sepolia
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        verbatim_doc_comment,
+        default_value = "mainnet",
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    /// The JSONL state dump to import, as written by `reth db dump-state`.
+    #[arg(value_name = "FILE", verbatim_doc_comment)]
+    path: PathBuf,
+
+    /// The hash of the block the state dump was taken at.
+    ///
+    /// The header in the state dump must match this hash.
+    #[arg(long, value_name = "HASH", verbatim_doc_comment)]
+    block: H256,
+}
+
+impl InitStateCommand {
+    /// Execute the `init-state` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth init-state starting");
+
+        // add network name to data dir
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db_path = data_dir.db_path();
+        info!(target: "reth::cli", path = ?db_path, "Opening database");
+        let db = Arc::new(init_db(&db_path, self.db.log_level)?);
+        info!(target: "reth::cli", "Database opened");
+
+        init_genesis(db.clone(), self.chain.clone())?;
+
+        let mut reader = BufReader::new(
+            File::open(&self.path)
+                .wrap_err_with(|| format!("Could not open state dump {:?}", self.path))?,
+        );
+        let dump = read_state_dump_header(&mut reader)?;
+        let header = dump.header.seal_slow();
+        if header.hash != self.block {
+            eyre::bail!("State dump is for block {:?}, expected {:?}", header.hash, self.block)
+        }
+
+        info!(target: "reth::cli", number = header.number, hash = ?header.hash, "Importing state");
+        let number = header.number;
+        import_state_dump(db, self.chain, header, dump.total_difficulty, reader)?;
+
+        info!(target: "reth::cli", number, "State imported");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_init_state_command() {
+        let hash = H256::random();
+        let cmd = InitStateCommand::try_parse_from([
+            "reth",
+            "state.jsonl",
+            "--block",
+            &format!("{hash:?}"),
+        ])
+        .unwrap();
+        assert_eq!(cmd.path, PathBuf::from("state.jsonl"));
+        assert_eq!(cmd.block, hash);
+    }
+}
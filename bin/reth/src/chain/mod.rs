@@ -2,6 +2,8 @@
 
 mod import;
 mod init;
+mod init_state;
 
 pub use import::ImportCommand;
 pub use init::InitCommand;
+pub use init_state::InitStateCommand;
@@ -2,7 +2,7 @@
 
 use reth_revm::primitives::bytes::BytesMut;
 use reth_rlp::Encodable;
-use reth_rpc::{eth::gas_oracle::GasPriceOracleConfig, JwtError, JwtSecret};
+use reth_rpc::{eth::gas_oracle::GasPriceOracleConfig, JwtError, JwtSecret, RateLimitConfig};
 use reth_rpc_builder::{
     auth::AuthServerConfig, error::RpcError, EthConfig, IpcServerBuilder, RpcServerConfig,
     ServerBuilder, TransportRpcModuleConfig,
@@ -26,6 +26,9 @@ pub trait RethRpcConfig {
     /// Returns the max response size in bytes.
     fn rpc_max_response_size_bytes(&self) -> u32;
 
+    /// Returns the max batch response size in bytes.
+    fn rpc_max_batch_response_size_bytes(&self) -> u32;
+
     /// Extracts the gas price oracle config from the args.
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig;
 
@@ -41,6 +44,9 @@ pub trait RethRpcConfig {
     /// Returns the default ipc server builder
     fn ipc_server_builder(&self) -> IpcServerBuilder;
 
+    /// Returns the compute unit quotas for http requests, if enabled.
+    fn rate_limit_config(&self) -> Option<RateLimitConfig>;
+
     /// Creates the [RpcServerConfig] from cli args.
     fn rpc_server_config(&self) -> RpcServerConfig;
 
@@ -85,6 +85,7 @@ impl<Ext: RethCliExt> Cli<Ext> {
         match self.command {
             Commands::Node(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Init(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::InitState(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Db(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Stage(command) => runner.run_blocking_until_ctrl_c(command.execute()),
@@ -128,6 +129,9 @@ pub enum Commands<Ext: RethCliExt = ()> {
     /// Initialize the database from a genesis file.
     #[command(name = "init")]
     Init(chain::InitCommand),
+    /// Initialize the database from a state dump.
+    #[command(name = "init-state")]
+    InitState(chain::InitStateCommand),
     /// This syncs RLP encoded blocks from a file.
     #[command(name = "import")]
     Import(chain::ImportCommand),
@@ -0,0 +1,33 @@
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_db::{mdbx::backup::DatabaseBackup, DatabaseEnvRO};
+use std::path::PathBuf;
+use tracing::info;
+
+/// The arguments for the `reth db backup` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to write the backup to.
+    ///
+    /// It must not contain a database yet. The backup is consistent even if the node is running,
+    /// and a `manifest.json` describing it is written next to the database file.
+    #[arg(value_name = "DEST", verbatim_doc_comment)]
+    pub dest: PathBuf,
+}
+
+impl Command {
+    /// Execute `db backup` command
+    pub fn execute(self, db: &DatabaseEnvRO) -> eyre::Result<()> {
+        info!(target: "reth::cli", dest = ?self.dest, "Backing up database");
+        let manifest = db.backup(&self.dest)?;
+        info!(
+            target: "reth::cli",
+            tip = manifest.tip_number,
+            hash = ?manifest.tip_hash,
+            size = %human_bytes(manifest.size as f64),
+            "Database backed up to {:?}",
+            self.dest
+        );
+        Ok(())
+    }
+}
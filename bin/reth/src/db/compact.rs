@@ -0,0 +1,84 @@
+use clap::Parser;
+use eyre::WrapErr;
+use human_bytes::human_bytes;
+use reth_db::{version::db_version_file_path, DatabaseEnvRO};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+use tracing::info;
+
+/// Name of the MDBX data file inside a database directory.
+const DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// Interval between progress reports while the database is copied.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The arguments for the `reth db compact` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to write the compacted database to.
+    ///
+    /// It must not contain a database yet. Once the copy is done, stop the node and replace the
+    /// database directory with it.
+    #[arg(value_name = "DEST", verbatim_doc_comment)]
+    pub dest: PathBuf,
+}
+
+impl Command {
+    /// Execute `db compact` command
+    pub fn execute(self, db: &DatabaseEnvRO, db_path: &Path) -> eyre::Result<()> {
+        let data_file = self.dest.join(DATA_FILE_NAME);
+        if data_file.exists() {
+            eyre::bail!("Database already exists at {data_file:?}")
+        }
+        fs::create_dir_all(&self.dest)
+            .wrap_err_with(|| format!("Could not create directory {:?}", self.dest))?;
+
+        // Only the pages in use are copied.
+        let page_size = db.stat()?.page_size() as u64;
+        let used_pages = db.info()?.last_pgno() as u64 + 1 - db.freelist()? as u64;
+        let expected_size = used_pages * page_size;
+        info!(target: "reth::cli", dest = ?self.dest, expected_size = %human_bytes(expected_size as f64), "Compacting database");
+
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        let progress_file = data_file.clone();
+        let progress = thread::spawn(move || {
+            while let Err(mpsc::RecvTimeoutError::Timeout) = done_rx.recv_timeout(PROGRESS_INTERVAL)
+            {
+                let copied =
+                    fs::metadata(&progress_file).map(|meta| meta.len()).unwrap_or_default();
+                info!(
+                    target: "reth::cli",
+                    copied = %human_bytes(copied as f64),
+                    progress = %format!("{:.2}%", copied as f64 / expected_size.max(1) as f64 * 100.0),
+                    "Compacting database"
+                );
+            }
+        });
+        let result = db.copy(&data_file, true);
+        drop(done_tx);
+        let _ = progress.join();
+        result?;
+
+        let version_file = db_version_file_path(db_path);
+        if version_file.exists() {
+            fs::copy(version_file, db_version_file_path(&self.dest))?;
+        }
+
+        let before = fs::metadata(db_path.join(DATA_FILE_NAME))?.len();
+        let after = fs::metadata(&data_file)?.len();
+        info!(
+            target: "reth::cli",
+            before = %human_bytes(before as f64),
+            after = %human_bytes(after as f64),
+            "Database compacted, stop the node and replace {db_path:?} with {:?} to use it",
+            self.dest
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::init::export_state_dump;
+use clap::Parser;
+use reth_db::{database::Database, tables, transaction::DbTx};
+use reth_primitives::{stage::StageId, BlockNumber};
+use std::{fs::File, io::BufWriter, path::PathBuf};
+use tracing::info;
+
+/// The arguments for the `reth db dump-state` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The file to write the JSONL state dump to.
+    #[arg(value_name = "FILE")]
+    pub output: PathBuf,
+
+    /// The block to export the state at. Defaults to the latest executed block.
+    ///
+    /// The state of earlier blocks is read from the account and storage history, which must not
+    /// be pruned.
+    #[arg(long, value_name = "BLOCK_NUMBER", verbatim_doc_comment)]
+    pub block: Option<BlockNumber>,
+}
+
+impl Command {
+    /// Execute `db dump-state` command
+    pub fn execute<DB: Database>(self, db: &DB) -> eyre::Result<()> {
+        let tx = db.tx()?;
+        let block = match self.block {
+            Some(block) => block,
+            None => {
+                tx.get::<tables::SyncStage>(StageId::Execution.to_string())?
+                    .unwrap_or_default()
+                    .block_number
+            }
+        };
+
+        info!(target: "reth::cli", block, output = ?self.output, "Dumping state");
+        let writer = BufWriter::new(File::create(&self.output)?);
+        let accounts = export_state_dump(&tx, block, writer)?;
+        info!(target: "reth::cli", block, accounts, "State dumped");
+
+        Ok(())
+    }
+}
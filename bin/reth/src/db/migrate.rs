@@ -0,0 +1,53 @@
+use clap::Parser;
+use reth_db::{
+    migration::{migrate, migrations, MigrationProgress},
+    version::{get_db_version, DB_VERSION},
+    DatabaseEnv,
+};
+use std::path::Path;
+use tracing::info;
+
+/// The arguments for the `reth db migrate` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only list the migrations that would be applied.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl Command {
+    /// Execute `db migrate` command
+    pub fn execute(self, db: &DatabaseEnv, db_path: &Path) -> eyre::Result<()> {
+        let version = get_db_version(db_path)?;
+        if version >= DB_VERSION {
+            info!(target: "reth::cli", version, "Database is up to date");
+            return Ok(())
+        }
+
+        let migrations = migrations::<DatabaseEnv>();
+        for migration in migrations.iter().filter(|migration| migration.from_version() >= version) {
+            info!(
+                target: "reth::cli",
+                from = migration.from_version(),
+                to = migration.from_version() + 1,
+                "{}",
+                migration.description()
+            );
+        }
+        if self.dry_run {
+            return Ok(())
+        }
+
+        let applied = migrate(db, db_path, &migrations, DB_VERSION, |progress| {
+            let MigrationProgress { from_version, table, migrated, done } = progress;
+            if done {
+                info!(target: "reth::cli", from_version, table, migrated, "Migrated table");
+            } else {
+                info!(target: "reth::cli", from_version, table, migrated, "Migrating table");
+            }
+        })?;
+        info!(target: "reth::cli", applied, version = DB_VERSION, "Database migrated");
+
+        Ok(())
+    }
+}
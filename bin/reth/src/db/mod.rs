@@ -20,13 +20,21 @@ use std::{
     sync::Arc,
 };
 
+mod backup;
 mod clear;
+mod compact;
 mod diff;
+mod dump_state;
 mod get;
 mod list;
+mod migrate;
+mod restore;
 /// DB List TUI
 mod tui;
 
+/// Share of free pages in the database file above which compaction is suggested.
+const COMPACTION_FREE_RATIO: f64 = 0.25;
+
 /// `reth db` command
 #[derive(Debug, Parser)]
 pub struct Command {
@@ -84,6 +92,16 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Copies the database while omitting free pages, so the copy only takes the space in use
+    Compact(compact::Command),
+    /// Exports the state at a block as a JSONL state dump
+    DumpState(dump_state::Command),
+    /// Writes a consistent copy of the database and a manifest describing it
+    Backup(backup::Command),
+    /// Restores a backup created with `reth db backup` after checking it against its manifest
+    Restore(restore::Command),
+    /// Upgrades a database created with an older version to the current database version
+    Migrate(migrate::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -113,7 +131,10 @@ impl Command {
                     "Total Size",
                 ]);
 
-                tool.db.view(|tx| {
+                // Pages on the freelist are part of the database file, but not used by any table.
+                let freelist_size = tool.db.freelist()? * tool.db.stat()?.page_size() as usize;
+
+                let total_size = tool.db.view(|tx| {
                     let mut tables =
                         Tables::ALL.iter().map(|table| table.name()).collect::<Vec<_>>();
                     tables.sort();
@@ -165,10 +186,27 @@ impl Command {
                         .add_cell(Cell::new(human_bytes(total_size as f64)));
                     stats_table.add_row(row);
 
-                    Ok::<(), eyre::Report>(())
+                    let mut row = Row::new();
+                    row.add_cell(Cell::new("Freelist size"))
+                        .add_cell(Cell::new(""))
+                        .add_cell(Cell::new(""))
+                        .add_cell(Cell::new(""))
+                        .add_cell(Cell::new(""))
+                        .add_cell(Cell::new(human_bytes(freelist_size as f64)));
+                    stats_table.add_row(row);
+
+                    Ok::<usize, eyre::Report>(total_size)
                 })??;
 
                 println!("{stats_table}");
+
+                let free_ratio = freelist_size as f64 / (total_size + freelist_size).max(1) as f64;
+                if free_ratio > COMPACTION_FREE_RATIO {
+                    println!(
+                        "{:.1}% of the database file is free, `reth db compact` can reclaim it.",
+                        free_ratio * 100.0
+                    );
+                }
             }
             Subcommands::List(command) => {
                 let db = open_db_read_only(&db_path, self.db.log_level)?;
@@ -209,6 +247,25 @@ impl Command {
                 let db = open_db(&db_path, self.db.log_level)?;
                 command.execute(&db)?;
             }
+            Subcommands::Compact(command) => {
+                let db = open_db_read_only(&db_path, self.db.log_level)?;
+                command.execute(&db, &db_path)?;
+            }
+            Subcommands::DumpState(command) => {
+                let db = open_db_read_only(&db_path, self.db.log_level)?;
+                command.execute(&db)?;
+            }
+            Subcommands::Backup(command) => {
+                let db = open_db_read_only(&db_path, self.db.log_level)?;
+                command.execute(&db)?;
+            }
+            Subcommands::Restore(command) => {
+                command.execute(&db_path, self.chain.genesis_hash())?;
+            }
+            Subcommands::Migrate(command) => {
+                let db = open_db(&db_path, self.db.log_level)?;
+                command.execute(&db, &db_path)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -220,6 +277,9 @@ impl Command {
 
                 if let Some(version) = local_db_version {
                     println!("Local database version: {version}");
+                    if version < DB_VERSION {
+                        println!("Run `reth db migrate` to upgrade the local database");
+                    }
                 } else {
                     println!("Local database is uninitialized");
                 }
@@ -243,4 +303,36 @@ mod tests {
         let cmd = Command::try_parse_from(["reth", "stats", "--datadir", "../mainnet"]).unwrap();
         assert_eq!(cmd.datadir.as_ref(), Some(Path::new("../mainnet")));
     }
+
+    #[test]
+    fn parse_dump_state() {
+        let cmd = Command::try_parse_from(["reth", "dump-state", "state.jsonl"]).unwrap();
+        assert!(matches!(
+            cmd.command,
+            Subcommands::DumpState(dump_state::Command { block: None, .. })
+        ));
+
+        let cmd = Command::try_parse_from(["reth", "dump-state", "state.jsonl", "--block", "100"])
+            .unwrap();
+        assert!(matches!(
+            cmd.command,
+            Subcommands::DumpState(dump_state::Command { block: Some(100), .. })
+        ));
+    }
+
+    #[test]
+    fn parse_migrate() {
+        let cmd = Command::try_parse_from(["reth", "migrate", "--dry-run"]).unwrap();
+        assert!(matches!(cmd.command, Subcommands::Migrate(migrate) if migrate.dry_run));
+    }
+
+    #[test]
+    fn parse_backup_and_restore() {
+        let cmd = Command::try_parse_from(["reth", "backup", "/backups/reth"]).unwrap();
+        assert!(
+            matches!(cmd.command, Subcommands::Backup(backup) if backup.dest == Path::new("/backups/reth"))
+        );
+        let cmd = Command::try_parse_from(["reth", "restore", "/backups/reth"]).unwrap();
+        assert!(matches!(cmd.command, Subcommands::Restore(_)));
+    }
 }
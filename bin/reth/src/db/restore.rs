@@ -0,0 +1,31 @@
+use clap::Parser;
+use reth_db::mdbx::backup::restore_backup;
+use reth_primitives::H256;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// The arguments for the `reth db restore` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory of a backup created with `reth db backup` or `admin_backupDatabase`.
+    ///
+    /// The backup is checked against its manifest and the chain before it is restored. The
+    /// database directory must not contain a database yet.
+    #[arg(value_name = "BACKUP", verbatim_doc_comment)]
+    pub backup: PathBuf,
+}
+
+impl Command {
+    /// Execute `db restore` command
+    pub fn execute(self, db_path: &Path, genesis_hash: H256) -> eyre::Result<()> {
+        info!(target: "reth::cli", backup = ?self.backup, "Restoring database");
+        let manifest = restore_backup(&self.backup, db_path, genesis_hash)?;
+        info!(
+            target: "reth::cli",
+            tip = manifest.tip_number,
+            hash = ?manifest.tip_hash,
+            "Database restored to {db_path:?}"
+        );
+        Ok(())
+    }
+}
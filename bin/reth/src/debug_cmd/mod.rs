@@ -6,6 +6,7 @@ use crate::runner::CliContext;
 mod execution;
 mod in_memory_merkle;
 mod merkle;
+mod replay_engine;
 
 /// `reth debug` command
 #[derive(Debug, Parser)]
@@ -23,6 +24,8 @@ pub enum Subcommands {
     Merkle(merkle::Command),
     /// Debug in-memory state root calculation.
     InMemoryMerkle(in_memory_merkle::Command),
+    /// Replay recorded Engine API calls against a fresh consensus engine.
+    ReplayEngine(replay_engine::Command),
 }
 
 impl Command {
@@ -32,6 +35,7 @@ impl Command {
             Subcommands::Execution(command) => command.execute(ctx).await,
             Subcommands::Merkle(command) => command.execute(ctx).await,
             Subcommands::InMemoryMerkle(command) => command.execute(ctx).await,
+            Subcommands::ReplayEngine(command) => command.execute(ctx).await,
         }
     }
 }
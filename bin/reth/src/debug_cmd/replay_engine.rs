@@ -0,0 +1,255 @@
+//! Command for replaying recorded Engine API calls.
+use crate::{
+    args::{utils::genesis_value_parser, DatabaseArgs},
+    dirs::{DataDirPath, MaybePlatformPath},
+    init::init_genesis,
+    runner::CliContext,
+};
+use clap::Parser;
+use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
+use reth_beacon_consensus::{BeaconConsensus, BeaconConsensusEngine, MIN_BLOCKS_FOR_PIPELINE_RUN};
+use reth_blockchain_tree::{
+    config::BlockchainTreeConfig, externals::TreeExternals, BlockchainTree, ShareableBlockchainTree,
+};
+use reth_db::{init_db, mdbx::backup::DatabaseBackup, open_db_read_only};
+use reth_interfaces::{
+    consensus::Consensus, sync::NoopSyncStateUpdater, test_utils::NoopFullBlockClient,
+};
+use reth_payload_builder::PayloadBuilderService;
+use reth_primitives::ChainSpec;
+use reth_provider::{
+    providers::BlockchainProvider, BlockReader, EvmEnvProvider, HeaderProvider, ProviderFactory,
+    StateProviderFactory,
+};
+use reth_revm::Factory;
+use reth_rpc_engine_api::{
+    read_recorded_calls, EngineApi, EngineApiCall, EngineApiResult, RecordedEngineCall,
+};
+use reth_stages::Pipeline;
+use reth_transaction_pool::{blobstore::NoopBlobStore, noop::NoopTransactionPool};
+use serde::Serialize;
+use std::{path::PathBuf, sync::Arc};
+use tokio::sync::{mpsc::unbounded_channel, oneshot};
+use tracing::*;
+
+/// `reth debug replay-engine` command
+///
+/// Replays the Engine API calls recorded with `--debug.engine-api-store` against a fresh consensus
+/// engine, in the order they were received.
+///
+/// The engine writes to the database, so the calls are replayed against a consistent copy of the
+/// node's database and the data dir itself is never modified. The transaction pool is empty, so
+/// `getPayload` responses are expected to differ from the recorded ones.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    ///
+    /// Built-in chains:
+    /// - mainnet
+    /// - goerli
+    /// - sepolia
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        verbatim_doc_comment,
+        default_value = "mainnet",
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    /// The directory to copy the database to before replaying.
+    ///
+    /// It must not contain a database yet. Defaults to a temporary directory that is removed once
+    /// the replay finished.
+    #[arg(long = "db.copy", value_name = "DIR", verbatim_doc_comment)]
+    db_copy: Option<PathBuf>,
+
+    /// The path to the file with the recorded Engine API calls.
+    #[arg(value_name = "FILE")]
+    path: PathBuf,
+}
+
+impl Command {
+    /// Execute `debug replay-engine` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let calls = read_recorded_calls(&self.path)?;
+        info!(target: "reth::cli", path = ?self.path, calls = calls.len(), "Loaded recorded engine API calls");
+
+        // the engine writes to the database, so the calls are replayed against a copy of it
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db_path = data_dir.db_path();
+        // the temporary directory is removed once the replay finished and it's dropped
+        let (copy_path, _temp_dir) = match self.db_copy.clone() {
+            Some(copy_path) => (copy_path, None),
+            None => {
+                let temp_dir = tempfile::Builder::new().prefix("reth-replay-engine").tempdir()?;
+                (temp_dir.path().join("db"), Some(temp_dir))
+            }
+        };
+        if db_path.exists() {
+            info!(target: "reth::cli", src = ?db_path, dest = ?copy_path, "Copying database");
+            open_db_read_only(&db_path, self.db.log_level)?.backup(&copy_path)?;
+        }
+        let db = Arc::new(init_db(&copy_path, self.db.log_level)?);
+
+        debug!(target: "reth::cli", chain=%self.chain.chain, genesis=?self.chain.genesis_hash(), "Initializing genesis");
+        init_genesis(db.clone(), self.chain.clone())?;
+
+        let consensus: Arc<dyn Consensus> = Arc::new(BeaconConsensus::new(Arc::clone(&self.chain)));
+
+        // configure blockchain tree
+        let tree_externals = TreeExternals::new(
+            db.clone(),
+            Arc::clone(&consensus),
+            Factory::new(self.chain.clone()),
+            Arc::clone(&self.chain),
+        );
+        let tree_config = BlockchainTreeConfig::default();
+        let (canon_state_notification_sender, _receiver) =
+            tokio::sync::broadcast::channel(tree_config.max_reorg_depth() as usize * 2);
+        let blockchain_tree = ShareableBlockchainTree::new(BlockchainTree::new(
+            tree_externals,
+            canon_state_notification_sender,
+            tree_config,
+            None,
+        )?);
+
+        // setup the blockchain provider
+        let factory = ProviderFactory::new(Arc::clone(&db), Arc::clone(&self.chain));
+        let blockchain_db = BlockchainProvider::new(factory, blockchain_tree)?;
+
+        // payloads are built from an empty pool
+        let payload_generator = BasicPayloadJobGenerator::new(
+            blockchain_db.clone(),
+            NoopTransactionPool::default(),
+            ctx.task_executor.clone(),
+            BasicPayloadJobGeneratorConfig::default(),
+            Arc::clone(&self.chain),
+        );
+        let (payload_service, payload_builder) = PayloadBuilderService::new(payload_generator);
+        ctx.task_executor.spawn_critical("payload builder service", Box::pin(payload_service));
+
+        // the engine never downloads blocks, all blocks must come from the recorded payloads
+        let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
+        let (beacon_consensus_engine, beacon_engine_handle) = BeaconConsensusEngine::with_channel(
+            NoopFullBlockClient::default(),
+            Pipeline::builder().build(Arc::clone(&db), Arc::clone(&self.chain)),
+            blockchain_db.clone(),
+            Box::new(ctx.task_executor.clone()),
+            Box::new(NoopSyncStateUpdater::default()),
+            None,
+            false,
+            payload_builder.clone(),
+            None,
+            MIN_BLOCKS_FOR_PIPELINE_RUN,
+            consensus_engine_tx,
+            consensus_engine_rx,
+            None,
+        )?;
+        info!(target: "reth::cli", "Consensus engine initialized");
+
+        let (tx, mut rx) = oneshot::channel();
+        ctx.task_executor.spawn_critical_blocking("consensus engine", async move {
+            let res = beacon_consensus_engine.await;
+            let _ = tx.send(res);
+        });
+
+        let engine_api = EngineApi::new(
+            blockchain_db,
+            Arc::clone(&self.chain),
+            beacon_engine_handle,
+            payload_builder.into(),
+            Box::new(ctx.task_executor.clone()),
+            Box::<NoopBlobStore>::default(),
+        );
+
+        let mut mismatches = 0;
+        let mut previous_timestamp = None;
+        for (idx, recorded) in calls.into_iter().enumerate() {
+            if let Ok(res) = rx.try_recv() {
+                res?;
+                eyre::bail!("consensus engine exited before the replay finished");
+            }
+
+            let RecordedEngineCall { timestamp, call, response, error, .. } = recorded;
+            let method = call.method();
+            let since_previous =
+                previous_timestamp.map(|previous| timestamp.saturating_sub(previous));
+            previous_timestamp = Some(timestamp);
+            debug!(target: "reth::cli", idx, method, timestamp, ?since_previous, "Replaying engine API call");
+
+            let recorded = match (response, error) {
+                (Some(response), _) => Ok(response),
+                (None, error) => Err(error.unwrap_or_default()),
+            };
+            let replayed = replay_call(&engine_api, call).await;
+
+            if replayed != recorded {
+                mismatches += 1;
+                warn!(target: "reth::cli", idx, method, ?recorded, ?replayed, "Replayed response differs from the recorded one");
+            }
+        }
+
+        info!(target: "reth::cli", mismatches, "Finished replaying engine API calls");
+        Ok(())
+    }
+}
+
+/// Sends the call to the Engine API and returns the serialized response or the error message.
+async fn replay_call<Provider>(
+    engine_api: &EngineApi<Provider>,
+    call: EngineApiCall,
+) -> Result<serde_json::Value, String>
+where
+    Provider: HeaderProvider + BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
+{
+    match call {
+        EngineApiCall::NewPayloadV1(payload) => to_json(engine_api.new_payload_v1(payload).await),
+        EngineApiCall::NewPayloadV2(payload) => to_json(engine_api.new_payload_v2(payload).await),
+        EngineApiCall::NewPayloadV3(payload, versioned_hashes, parent_beacon_block_root) => {
+            to_json(
+                engine_api
+                    .new_payload_v3(payload, versioned_hashes, parent_beacon_block_root)
+                    .await,
+            )
+        }
+        EngineApiCall::ForkchoiceUpdatedV1(state, attrs) => {
+            to_json(engine_api.fork_choice_updated_v1(state, attrs).await)
+        }
+        EngineApiCall::ForkchoiceUpdatedV2(state, attrs) => {
+            to_json(engine_api.fork_choice_updated_v2(state, attrs).await)
+        }
+        EngineApiCall::ForkchoiceUpdatedV3(state, attrs) => {
+            to_json(engine_api.fork_choice_updated_v3(state, attrs).await)
+        }
+        EngineApiCall::GetPayloadV1(payload_id) => {
+            to_json(engine_api.get_payload_v1(payload_id).await)
+        }
+        EngineApiCall::GetPayloadV2(payload_id) => {
+            to_json(engine_api.get_payload_v2(payload_id).await)
+        }
+        EngineApiCall::GetPayloadV3(payload_id) => {
+            to_json(engine_api.get_payload_v3(payload_id).await)
+        }
+    }
+}
+
+fn to_json<T: Serialize>(res: EngineApiResult<T>) -> Result<serde_json::Value, String> {
+    res.map_err(|err| err.to_string())
+        .and_then(|res| serde_json::to_value(res).map_err(|err| err.to_string()))
+}
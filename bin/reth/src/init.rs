@@ -1,14 +1,48 @@
 //! Reth genesis initialization utility functions.
 use reth_db::{
-    cursor::DbCursorRO,
+    cursor::{DbCursorRO, DbDupCursorRO},
     database::{Database, DatabaseGAT},
+    models::{BlockNumberAddress, StoredBlockBodyIndices},
     tables,
     transaction::{DbTx, DbTxMut},
 };
-use reth_primitives::{stage::StageId, Account, Bytecode, ChainSpec, StorageEntry, H256, U256};
-use reth_provider::{DatabaseProviderRW, HashingWriter, HistoryWriter, PostState, ProviderFactory};
-use std::{collections::BTreeMap, sync::Arc};
-use tracing::debug;
+use reth_interfaces::provider::ProviderError;
+use reth_primitives::{
+    stage::{StageCheckpoint, StageId},
+    Account, Address, BlockNumber, Bytecode, Bytes, ChainSpec, Header, PrunePart, SealedHeader,
+    StorageEntry, TxNumber, H256, U256,
+};
+use reth_provider::{
+    AccountReader, DatabaseProviderRW, HashingWriter, HistoricalStateProviderRef, HistoryWriter,
+    LowestAvailableBlocks, PostState, ProviderFactory, StageCheckpointWriter, StateProvider,
+};
+use reth_trie::StateRoot;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{BufRead, Write},
+    sync::Arc,
+};
+use tracing::{debug, info};
+
+/// Number of accounts and storage slots imported from a state dump before the changes are
+/// committed.
+const STATE_DUMP_COMMIT_THRESHOLD: usize = 100_000;
+
+/// Maximum number of storage slots written to a single line of a state dump.
+pub const STATE_DUMP_STORAGE_CHUNK_SIZE: usize = 10_000;
+
+/// The key of the [tables::SyncStage] entry that marks a state import as in progress.
+///
+/// The state is imported in several transactions, so the entry is written together with the
+/// removal of the genesis state and only deleted once the imported state root has been verified.
+const STATE_IMPORT_MARKER: &str = "StateImport";
+
+/// The number of the first transaction after an imported state.
+///
+/// The transaction numbers below it are reserved for the blocks below the imported block, which
+/// are backfilled in reverse.
+pub const STATE_IMPORT_FIRST_TX_NUM: TxNumber = 1 << 40;
 
 /// Database initialization error type.
 #[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
@@ -23,6 +57,42 @@ pub enum InitDatabaseError {
         database_hash: H256,
     },
 
+    /// State can only be imported into a database that has not synced past genesis.
+    #[error("Cannot import state into a database that has already synced to block {tip}")]
+    DatabaseNotEmpty {
+        /// The highest block in the database.
+        tip: u64,
+    },
+
+    /// A previous state import did not complete, so the database holds a partial state.
+    #[error("The import of the state at block {block} did not complete, import the state again")]
+    IncompleteStateImport {
+        /// The block the state was imported at.
+        block: BlockNumber,
+    },
+
+    /// The state at the block is not available.
+    #[error("The state at block {block} is not available, the latest executed block is {latest}")]
+    StateNotAvailable {
+        /// The requested block.
+        block: BlockNumber,
+        /// The latest executed block.
+        latest: BlockNumber,
+    },
+
+    /// The state dump could not be read.
+    #[error("Failed to read state dump: {0}")]
+    StateDump(String),
+
+    /// The state root computed from the imported state did not match the header.
+    #[error("State root mismatch: expected {expected}, got {got}")]
+    StateRootMismatch {
+        /// The state root of the header.
+        expected: H256,
+        /// The state root computed from the imported state.
+        got: H256,
+    },
+
     /// Low-level database error.
     #[error(transparent)]
     DBError(#[from] reth_db::DatabaseError),
@@ -174,19 +244,426 @@ pub fn insert_genesis_header<DB: Database>(
     Ok(())
 }
 
+/// The first line of a state dump, describing the block the state belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateDumpHeader {
+    /// The header of the block.
+    pub header: Header,
+    /// The total difficulty at the block.
+    pub total_difficulty: U256,
+}
+
+/// A line after the [StateDumpHeader] of a state dump.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StateDumpEntry {
+    /// An account.
+    Account(StateDumpAccount),
+    /// A chunk of the storage of the preceding account.
+    Storage(StateDumpStorage),
+}
+
+/// An account in a state dump. Its storage follows in [StateDumpStorage] lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDumpAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The bytecode of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+}
+
+/// Up to [STATE_DUMP_STORAGE_CHUNK_SIZE] non-zero storage slots of an account in a state dump.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDumpStorage {
+    /// The address of the account.
+    pub address: Address,
+    /// The storage slots.
+    pub storage: BTreeMap<H256, U256>,
+}
+
+/// Writes the state at the given block as a state dump and returns the number of exported
+/// accounts.
+///
+/// The state of the latest executed block is read from the plain state. The state of an earlier
+/// block is read from the history, so the account and storage history after the block must not
+/// be pruned. The accounts and storage slots changed after the block are held in memory.
+pub fn export_state_dump<'a, TX: DbTx<'a>>(
+    tx: &TX,
+    block: BlockNumber,
+    mut writer: impl Write,
+) -> Result<usize, InitDatabaseError> {
+    let latest = tx
+        .get::<tables::SyncStage>(StageId::Execution.to_string())?
+        .unwrap_or_default()
+        .block_number;
+    if block > latest {
+        return Err(InitDatabaseError::StateNotAvailable { block, latest })
+    }
+
+    let header = tx.get::<tables::Headers>(block)?.ok_or(InitDatabaseError::InternalError(
+        ProviderError::HeaderNotFound(block.into()).into(),
+    ))?;
+    let total_difficulty = tx
+        .get::<tables::HeaderTD>(block)?
+        .ok_or(InitDatabaseError::InternalError(
+            ProviderError::TotalDifficultyNotFound { number: block }.into(),
+        ))?
+        .into();
+    write_state_dump_line(&mut writer, &StateDumpHeader { header, total_difficulty })?;
+
+    // Accounts and storage slots that were changed after the block may no longer be in the plain
+    // state, so they are looked up in the history.
+    let mut changed_accounts = BTreeSet::new();
+    let mut changed_storage = BTreeMap::<Address, BTreeSet<H256>>::new();
+    let historical = if block < latest {
+        for entry in tx.cursor_read::<tables::AccountChangeSet>()?.walk_range(block + 1..=latest)? {
+            changed_accounts.insert(entry?.1.address);
+        }
+        for entry in tx
+            .cursor_read::<tables::StorageChangeSet>()?
+            .walk_range(BlockNumberAddress::range(block + 1..=latest))?
+        {
+            let (key, entry) = entry?;
+            changed_storage.entry(key.address()).or_default().insert(entry.key);
+        }
+
+        let lowest_available_block = |part| -> Result<_, InitDatabaseError> {
+            Ok(tx
+                .get::<tables::PruneCheckpoints>(part)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .map(|number| number + 1))
+        };
+        let lowest_available_blocks = LowestAvailableBlocks {
+            account_history_block_number: lowest_available_block(PrunePart::AccountHistory)?,
+            storage_history_block_number: lowest_available_block(PrunePart::StorageHistory)?,
+        };
+        // +1 as the changeset that we want is the one that was applied after this block.
+        Some(HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            tx,
+            block + 1,
+            lowest_available_blocks,
+        ))
+    } else {
+        None
+    };
+
+    let mut storage_cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    let mut accounts = 0;
+    let mut export_account = |address: Address,
+                              plain: Option<Account>|
+     -> Result<(), InitDatabaseError> {
+        let account = match &historical {
+            Some(historical) => historical.basic_account(address)?,
+            None => plain,
+        };
+        let Some(account) = account else { return Ok(()) };
+
+        let code = match account.bytecode_hash {
+            Some(hash) => {
+                tx.get::<tables::Bytecodes>(hash)?.map(|bytecode| bytecode.original_bytes().into())
+            }
+            None => None,
+        };
+        write_state_dump_line(
+            &mut writer,
+            &StateDumpEntry::Account(StateDumpAccount {
+                address,
+                nonce: account.nonce,
+                balance: account.balance,
+                code,
+            }),
+        )?;
+
+        let storage_value = |key: H256, plain: Option<U256>| -> Result<U256, InitDatabaseError> {
+            Ok(match &historical {
+                Some(historical) => historical.storage(address, key)?,
+                None => plain,
+            }
+            .unwrap_or_default())
+        };
+        let mut changed =
+            changed_storage.remove(&address).unwrap_or_default().into_iter().peekable();
+        let mut storage = StateDumpStorage { address, storage: BTreeMap::new() };
+        let mut entry = storage_cursor.seek_exact(address)?.map(|(_, entry)| entry);
+        while let Some(StorageEntry { key, value }) = entry {
+            while let Some(key) = changed.next_if(|changed| *changed < key) {
+                push_state_dump_slot(&mut writer, &mut storage, key, storage_value(key, None)?)?;
+            }
+            changed.next_if_eq(&key);
+            push_state_dump_slot(&mut writer, &mut storage, key, storage_value(key, Some(value))?)?;
+            entry = storage_cursor.next_dup_val()?;
+        }
+        for key in changed {
+            push_state_dump_slot(&mut writer, &mut storage, key, storage_value(key, None)?)?;
+        }
+        if !storage.storage.is_empty() {
+            write_state_dump_line(&mut writer, &StateDumpEntry::Storage(storage))?;
+        }
+
+        accounts += 1;
+        if accounts % STATE_DUMP_COMMIT_THRESHOLD == 0 {
+            info!(target: "reth::cli", accounts, "Exported state dump accounts");
+        }
+        Ok(())
+    };
+
+    let mut changed_accounts = changed_accounts.into_iter().peekable();
+    for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        let (address, account) = entry?;
+        while let Some(changed) = changed_accounts.next_if(|changed| *changed < address) {
+            export_account(changed, None)?;
+        }
+        changed_accounts.next_if_eq(&address);
+        export_account(address, Some(account))?;
+    }
+    for changed in changed_accounts {
+        export_account(changed, None)?;
+    }
+    drop(export_account);
+    writer.flush().map_err(|err| InitDatabaseError::StateDump(err.to_string()))?;
+
+    Ok(accounts)
+}
+
+/// Adds a storage slot to the given chunk, unless it is empty, and writes the chunk once it is
+/// full.
+fn push_state_dump_slot(
+    writer: &mut impl Write,
+    chunk: &mut StateDumpStorage,
+    key: H256,
+    value: U256,
+) -> Result<(), InitDatabaseError> {
+    if value == U256::ZERO {
+        return Ok(())
+    }
+    chunk.storage.insert(key, value);
+    if chunk.storage.len() >= STATE_DUMP_STORAGE_CHUNK_SIZE {
+        let storage = std::mem::take(&mut chunk.storage);
+        write_state_dump_line(
+            writer,
+            &StateDumpEntry::Storage(StateDumpStorage { address: chunk.address, storage }),
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes a single JSON line of a state dump.
+fn write_state_dump_line(
+    writer: &mut impl Write,
+    line: &impl Serialize,
+) -> Result<(), InitDatabaseError> {
+    serde_json::to_writer(&mut *writer, line)
+        .map_err(|err| InitDatabaseError::StateDump(err.to_string()))?;
+    writer.write_all(b"\n").map_err(|err| InitDatabaseError::StateDump(err.to_string()))
+}
+
+/// Reads the [StateDumpHeader] from the first line of a state dump.
+pub fn read_state_dump_header(
+    reader: &mut impl BufRead,
+) -> Result<StateDumpHeader, InitDatabaseError> {
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|err| InitDatabaseError::StateDump(err.to_string()))?;
+    serde_json::from_str(&line)
+        .map_err(|err| InitDatabaseError::StateDump(format!("invalid header: {err}")))
+}
+
+/// Imports the accounts of a state dump taken at the given block.
+///
+/// The database must not have synced past genesis. The genesis state is replaced by the imported
+/// state, the hashed state and tries are built from it and the state root is verified against the
+/// header. Finally the header is written and all stage checkpoints are set to the block, so the
+/// pipeline continues syncing from there.
+///
+/// The state is committed in batches, so the import is marked as pending until the state root
+/// has been verified, see [pending_state_import]. An interrupted or failed import can be retried.
+pub fn import_state_dump<DB: Database>(
+    db: Arc<DB>,
+    chain: Arc<ChainSpec>,
+    header: SealedHeader,
+    total_difficulty: U256,
+    reader: impl BufRead,
+) -> Result<(), InitDatabaseError> {
+    let tx = db.tx()?;
+    if let Some((tip, _)) = tx.cursor_read::<tables::CanonicalHeaders>()?.last()? {
+        if tip > 0 {
+            return Err(InitDatabaseError::DatabaseNotEmpty { tip })
+        }
+    }
+    drop(tx);
+
+    let factory = ProviderFactory::new(&db, chain);
+    let mut provider_rw = factory.provider_rw()?;
+    clear_genesis_state(provider_rw.tx_ref())?;
+    provider_rw.tx_ref().put::<tables::SyncStage>(
+        STATE_IMPORT_MARKER.to_string(),
+        StageCheckpoint::new(header.number),
+    )?;
+
+    let mut accounts = Vec::new();
+    let mut storages = Vec::new();
+    let mut entries = 0;
+    let mut total_accounts = 0usize;
+    let mut last_address = None;
+    for line in reader.lines() {
+        let line = line.map_err(|err| InitDatabaseError::StateDump(err.to_string()))?;
+        if line.trim().is_empty() {
+            continue
+        }
+        let entry: StateDumpEntry = serde_json::from_str(&line)
+            .map_err(|err| InitDatabaseError::StateDump(format!("invalid entry: {err}")))?;
+
+        match entry {
+            StateDumpEntry::Account(account) => {
+                entries += 1;
+                total_accounts += 1;
+                last_address = Some(account.address);
+                insert_state_dump_account(provider_rw.tx_ref(), account, &mut accounts)?;
+            }
+            StateDumpEntry::Storage(storage) => {
+                if last_address != Some(storage.address) {
+                    return Err(InitDatabaseError::StateDump(format!(
+                        "storage of {} does not follow its account",
+                        storage.address
+                    )))
+                }
+                entries += storage.storage.len();
+                insert_state_dump_storage(provider_rw.tx_ref(), storage, &mut storages)?;
+            }
+        }
+
+        if entries >= STATE_DUMP_COMMIT_THRESHOLD {
+            provider_rw.insert_account_for_hashing(accounts.drain(..))?;
+            provider_rw.insert_storage_for_hashing(storages.drain(..))?;
+            provider_rw.commit()?;
+            provider_rw = factory.provider_rw()?;
+            entries = 0;
+            info!(target: "reth::cli", accounts = total_accounts, "Imported state dump accounts");
+        }
+    }
+    provider_rw.insert_account_for_hashing(accounts)?;
+    provider_rw.insert_storage_for_hashing(storages)?;
+    provider_rw.commit()?;
+    info!(target: "reth::cli", accounts = total_accounts, "Computing state root");
+
+    let provider_rw = factory.provider_rw()?;
+    let (state_root, trie_updates) = StateRoot::new(provider_rw.tx_ref())
+        .root_with_updates()
+        .map_err(Into::<reth_db::DatabaseError>::into)?;
+    if state_root != header.state_root {
+        return Err(InitDatabaseError::StateRootMismatch {
+            expected: header.state_root,
+            got: state_root,
+        })
+    }
+    trie_updates.flush(provider_rw.tx_ref())?;
+
+    let tx = provider_rw.tx_ref();
+    tx.put::<tables::CanonicalHeaders>(header.number, header.hash)?;
+    tx.put::<tables::HeaderNumbers>(header.hash, header.number)?;
+    tx.put::<tables::BlockBodyIndices>(
+        header.number,
+        StoredBlockBodyIndices { first_tx_num: STATE_IMPORT_FIRST_TX_NUM, tx_count: 0 },
+    )?;
+    tx.put::<tables::HeaderTD>(header.number, total_difficulty.into())?;
+    tx.put::<tables::Headers>(header.number, header.header.clone())?;
+    tx.delete::<tables::SyncStage>(STATE_IMPORT_MARKER.to_string(), None)?;
+    provider_rw.update_pipeline_stages(header.number, true)?;
+    provider_rw.commit()?;
+
+    Ok(())
+}
+
+/// Returns the block of a state import that did not complete, if any.
+///
+/// The node must not start from such a database, as its state is neither the genesis state nor
+/// the state of the imported block.
+pub fn pending_state_import<DB: Database>(
+    db: &DB,
+) -> Result<Option<BlockNumber>, InitDatabaseError> {
+    Ok(db
+        .view(|tx| tx.get::<tables::SyncStage>(STATE_IMPORT_MARKER.to_string()))??
+        .map(|checkpoint| checkpoint.block_number))
+}
+
+/// Removes the genesis state and its history, so it can be replaced by an imported state.
+fn clear_genesis_state<'a, TX: DbTxMut<'a>>(tx: &TX) -> Result<(), InitDatabaseError> {
+    tx.clear::<tables::PlainAccountState>()?;
+    tx.clear::<tables::PlainStorageState>()?;
+    tx.clear::<tables::Bytecodes>()?;
+    tx.clear::<tables::HashedAccount>()?;
+    tx.clear::<tables::HashedStorage>()?;
+    tx.clear::<tables::AccountsTrie>()?;
+    tx.clear::<tables::StoragesTrie>()?;
+    tx.clear::<tables::AccountChangeSet>()?;
+    tx.clear::<tables::StorageChangeSet>()?;
+    tx.clear::<tables::AccountHistory>()?;
+    tx.clear::<tables::StorageHistory>()?;
+    Ok(())
+}
+
+/// Writes the plain state of a state dump account and queues it for hashing.
+fn insert_state_dump_account<'a, TX: DbTxMut<'a>>(
+    tx: &TX,
+    account: StateDumpAccount,
+    accounts: &mut Vec<(Address, Option<Account>)>,
+) -> Result<(), InitDatabaseError> {
+    let StateDumpAccount { address, nonce, balance, code } = account;
+
+    let mut bytecode_hash = None;
+    if let Some(code) = code.filter(|code| !code.is_empty()) {
+        let bytecode = Bytecode::new_raw(code.0);
+        bytecode_hash = Some(bytecode.hash);
+        tx.put::<tables::Bytecodes>(bytecode.hash, bytecode)?;
+    }
+    let account = Account { nonce, balance, bytecode_hash };
+    tx.put::<tables::PlainAccountState>(address, account)?;
+    accounts.push((address, Some(account)));
+
+    Ok(())
+}
+
+/// Writes the plain state of a chunk of state dump storage and queues it for hashing.
+fn insert_state_dump_storage<'a, TX: DbTxMut<'a>>(
+    tx: &TX,
+    storage: StateDumpStorage,
+    storages: &mut Vec<(Address, Vec<StorageEntry>)>,
+) -> Result<(), InitDatabaseError> {
+    let StateDumpStorage { address, storage } = storage;
+
+    let storage = storage
+        .into_iter()
+        .filter(|(_, value)| *value != U256::ZERO)
+        .map(|(key, value)| StorageEntry { key, value })
+        .collect::<Vec<_>>();
+    for entry in &storage {
+        tx.put::<tables::PlainStorageState>(address, *entry)?;
+    }
+    if !storage.is_empty() {
+        storages.push((address, storage));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use reth_db::{
-        models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
         table::{Table, TableRow},
         test_utils::create_test_rw_db,
         DatabaseEnv,
     };
     use reth_primitives::{
-        Address, Chain, ForkTimestamps, Genesis, GenesisAccount, IntegerList, GOERLI,
-        GOERLI_GENESIS, MAINNET, MAINNET_GENESIS, SEPOLIA, SEPOLIA_GENESIS,
+        proofs::genesis_state_root, Chain, ForkTimestamps, Genesis, GenesisAccount, IntegerList,
+        GOERLI, GOERLI_GENESIS, MAINNET, MAINNET_GENESIS, SEPOLIA, SEPOLIA_GENESIS,
     };
     use std::collections::HashMap;
 
@@ -299,4 +776,209 @@ mod tests {
             )],
         );
     }
+
+    #[test]
+    fn import_state_dump_at_block() {
+        let address = Address::from_low_u64_be(1);
+        let storage_key = H256::from_low_u64_be(1);
+        let other_storage_key = H256::from_low_u64_be(2);
+        let alloc = HashMap::from([(
+            address,
+            GenesisAccount {
+                nonce: Some(1),
+                balance: U256::from(10),
+                code: Some(Bytes::from(vec![0x60, 0x00])),
+                storage: Some(HashMap::from([
+                    (storage_key, H256::from_low_u64_be(2)),
+                    (other_storage_key, H256::from_low_u64_be(3)),
+                ])),
+            },
+        )]);
+        let header =
+            Header { number: 10, state_root: genesis_state_root(&alloc), ..Default::default() }
+                .seal_slow();
+        // The storage of the account is split into two chunks
+        let dump = [
+            StateDumpEntry::Account(StateDumpAccount {
+                address,
+                nonce: 1,
+                balance: U256::from(10),
+                code: Some(Bytes::from(vec![0x60, 0x00])),
+            }),
+            StateDumpEntry::Storage(StateDumpStorage {
+                address,
+                storage: BTreeMap::from([(storage_key, U256::from(2))]),
+            }),
+            StateDumpEntry::Storage(StateDumpStorage {
+                address,
+                storage: BTreeMap::from([(other_storage_key, U256::from(3))]),
+            }),
+        ]
+        .iter()
+        .map(|entry| serde_json::to_string(entry).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let db = create_test_rw_db();
+        init_genesis(db.clone(), SEPOLIA.clone()).unwrap();
+        import_state_dump(db.clone(), SEPOLIA.clone(), header.clone(), U256::ZERO, dump.as_bytes())
+            .unwrap();
+
+        let tx = db.tx().expect("failed to init tx");
+        assert_eq!(
+            collect_table_entries::<Arc<DatabaseEnv>, tables::PlainStorageState>(&tx).unwrap(),
+            vec![
+                (address, StorageEntry { key: storage_key, value: U256::from(2) }),
+                (address, StorageEntry { key: other_storage_key, value: U256::from(3) })
+            ]
+        );
+        assert_eq!(
+            tx.get::<tables::PlainAccountState>(address).unwrap().map(|account| account.nonce),
+            Some(1)
+        );
+        assert_eq!(tx.get::<tables::CanonicalHeaders>(10).unwrap(), Some(header.hash));
+        assert_eq!(pending_state_import(&db).unwrap(), None);
+        for stage in StageId::ALL {
+            let checkpoint = tx.get::<tables::SyncStage>(stage.to_string()).unwrap();
+            assert_eq!(checkpoint.map(|checkpoint| checkpoint.block_number), Some(10));
+        }
+
+        // The database is no longer at genesis
+        assert_eq!(
+            import_state_dump(db, SEPOLIA.clone(), header, U256::ZERO, dump.as_bytes())
+                .unwrap_err(),
+            InitDatabaseError::DatabaseNotEmpty { tip: 10 }
+        );
+    }
+
+    #[test]
+    fn import_state_dump_root_mismatch() {
+        let header = Header { number: 10, ..Default::default() }.seal_slow();
+        let dump = serde_json::to_string(&StateDumpAccount {
+            address: Address::from_low_u64_be(1),
+            balance: U256::from(10),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let db = create_test_rw_db();
+        init_genesis(db.clone(), SEPOLIA.clone()).unwrap();
+        let err =
+            import_state_dump(db.clone(), SEPOLIA.clone(), header, U256::ZERO, dump.as_bytes())
+                .unwrap_err();
+        assert!(matches!(err, InitDatabaseError::StateRootMismatch { .. }));
+
+        // The genesis state was already replaced, so the import stays pending
+        assert_eq!(pending_state_import(&db).unwrap(), Some(10));
+    }
+
+    #[test]
+    fn import_state_dump_storage_without_account() {
+        let header = Header { number: 10, ..Default::default() }.seal_slow();
+        let dump = serde_json::to_string(&StateDumpEntry::Storage(StateDumpStorage {
+            address: Address::from_low_u64_be(1),
+            storage: BTreeMap::from([(H256::from_low_u64_be(1), U256::from(1))]),
+        }))
+        .unwrap();
+
+        let db = create_test_rw_db();
+        init_genesis(db.clone(), SEPOLIA.clone()).unwrap();
+        let err = import_state_dump(db, SEPOLIA.clone(), header, U256::ZERO, dump.as_bytes())
+            .unwrap_err();
+        assert!(matches!(err, InitDatabaseError::StateDump(_)));
+    }
+
+    #[test]
+    fn export_state_dump_at_historical_block() {
+        let db = create_test_rw_db();
+        init_genesis(db.clone(), SEPOLIA.clone()).unwrap();
+
+        // Block 1 changes a genesis account and creates a new one.
+        let changed = *SEPOLIA.genesis().alloc.keys().next().unwrap();
+        let created = Address::from_low_u64_be(1);
+        let tx = db.tx_mut().unwrap();
+        let before = tx.get::<tables::PlainAccountState>(changed).unwrap().unwrap();
+        tx.put::<tables::PlainAccountState>(changed, Account { nonce: 5, ..before }).unwrap();
+        tx.put::<tables::PlainAccountState>(created, Account::default()).unwrap();
+        tx.put::<tables::AccountChangeSet>(
+            1,
+            AccountBeforeTx { address: changed, info: Some(before) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSet>(1, AccountBeforeTx { address: created, info: None })
+            .unwrap();
+        tx.put::<tables::AccountHistory>(
+            ShardedKey::new(changed, u64::MAX),
+            IntegerList::new([0, 1]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountHistory>(
+            ShardedKey::new(created, u64::MAX),
+            IntegerList::new([1]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::SyncStage>(StageId::Execution.to_string(), StageCheckpoint::new(1))
+            .unwrap();
+        tx.commit().unwrap();
+
+        let mut dump = Vec::new();
+        let accounts = export_state_dump(&db.tx().unwrap(), 0, &mut dump).unwrap();
+        assert_eq!(accounts, SEPOLIA.genesis().alloc.len());
+
+        let mut reader = dump.as_slice();
+        read_state_dump_header(&mut reader).unwrap();
+        let accounts = reader
+            .lines()
+            .filter_map(|line| match serde_json::from_str(&line.unwrap()).unwrap() {
+                StateDumpEntry::Account(account) => Some((account.address, account)),
+                StateDumpEntry::Storage(_) => None,
+            })
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(accounts[&changed].nonce, before.nonce);
+        assert!(!accounts.contains_key(&created));
+
+        assert_eq!(
+            export_state_dump(&db.tx().unwrap(), 2, &mut Vec::new()).unwrap_err(),
+            InitDatabaseError::StateNotAvailable { block: 2, latest: 1 }
+        );
+    }
+
+    #[test]
+    fn export_and_import_state_dump() {
+        let db = create_test_rw_db();
+        init_genesis(db.clone(), SEPOLIA.clone()).unwrap();
+        let mut dump = Vec::new();
+        let accounts = export_state_dump(&db.tx().unwrap(), 0, &mut dump).unwrap();
+        assert_eq!(accounts, SEPOLIA.genesis().alloc.len());
+
+        let mut reader = dump.as_slice();
+        let header = read_state_dump_header(&mut reader).unwrap();
+        assert_eq!(header.header.state_root, SEPOLIA.genesis_header().state_root);
+
+        // Importing the dump recomputes the genesis state root
+        let db = create_test_rw_db();
+        init_genesis(db.clone(), SEPOLIA.clone()).unwrap();
+        import_state_dump(
+            db.clone(),
+            SEPOLIA.clone(),
+            header.header.seal_slow(),
+            header.total_difficulty,
+            reader,
+        )
+        .unwrap();
+        assert_eq!(
+            db.tx().unwrap().entries::<tables::PlainAccountState>().unwrap(),
+            SEPOLIA.genesis().alloc.len()
+        );
+    }
+
+    #[test]
+    fn read_state_dump_header_line() {
+        let header = StateDumpHeader {
+            header: Header { number: 10, ..Default::default() },
+            total_difficulty: U256::from(100),
+        };
+        let dump = format!("{}\n", serde_json::to_string(&header).unwrap());
+        assert_eq!(read_state_dump_header(&mut dump.as_bytes()).unwrap(), header);
+    }
 }
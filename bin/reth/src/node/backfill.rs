@@ -0,0 +1,369 @@
+//! Reverse backfill of the blocks below a checkpoint the node was started from.
+
+use reth_consensus_common::validation::validate_block_standalone;
+use reth_db::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::{StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals},
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_interfaces::p2p::{
+    bodies::client::BodiesClient,
+    headers::client::{HeadersClient, HeadersRequest},
+    priority::Priority,
+};
+use reth_primitives::{
+    Address, BlockHashOrNumber, BlockNumber, ChainSpec, HeadersDirection, SealedBlock,
+    SealedHeader, TransactionSigned, U256,
+};
+use std::{sync::Arc, time::Duration};
+use tracing::*;
+
+/// Number of headers requested at once.
+const BACKFILL_BATCH_SIZE: u64 = 1024;
+
+/// Number of block bodies requested at once.
+const BACKFILL_BODIES_BATCH_SIZE: u64 = 128;
+
+/// The delay before retrying a failed request, doubled after every consecutive failure.
+const BACKFILL_MIN_BACKOFF: Duration = Duration::from_millis(500);
+
+/// The maximum delay before retrying a failed request.
+const BACKFILL_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Downloads the canonical blocks below a checkpoint in reverse, from the lowest block in the
+/// database down to genesis.
+///
+/// The headers are verified by following the parent hashes of the lowest known header, the
+/// bodies are verified against their headers. Headers are backfilled before bodies. Progress is
+/// derived from the database, so an interrupted backfill resumes where it stopped.
+///
+/// The transactions of the imported checkpoint start at
+/// [STATE_IMPORT_FIRST_TX_NUM](crate::init::STATE_IMPORT_FIRST_TX_NUM), so the transactions of
+/// the backfilled blocks are numbered downwards from there.
+#[derive(Debug)]
+pub struct BlockBackfill<DB, Client> {
+    db: DB,
+    client: Client,
+    chain_spec: Arc<ChainSpec>,
+}
+
+// === impl BlockBackfill ===
+
+impl<DB, Client> BlockBackfill<DB, Client>
+where
+    DB: Database,
+    Client: HeadersClient + BodiesClient,
+{
+    /// Creates a new [BlockBackfill].
+    pub fn new(db: DB, client: Client, chain_spec: Arc<ChainSpec>) -> Self {
+        Self { db, client, chain_spec }
+    }
+
+    /// Returns the lowest block with a body above genesis, if the blocks below it are not
+    /// complete.
+    pub fn checkpoint(&self) -> eyre::Result<Option<BlockNumber>> {
+        Ok(self.lowest_body()?.map(|(number, _)| number))
+    }
+
+    /// Backfills the headers and bodies until the block at the given block number is in the
+    /// database.
+    pub async fn run_until(&self, target: BlockNumber) -> eyre::Result<()> {
+        let target = target.max(1);
+        self.backfill_headers(target).await?;
+        self.backfill_bodies(target).await
+    }
+
+    /// Backfills the headers until the header at the given block number is in the database.
+    pub async fn backfill_headers(&self, target: BlockNumber) -> eyre::Result<()> {
+        let target = target.max(1);
+        let mut backoff = BACKFILL_MIN_BACKOFF;
+        while let Some((lowest, td)) = self.lowest_header()? {
+            if lowest.number <= target {
+                break
+            }
+
+            let limit = (lowest.number - target).min(BACKFILL_BATCH_SIZE);
+            let request = HeadersRequest {
+                start: BlockHashOrNumber::Hash(lowest.parent_hash),
+                limit,
+                direction: HeadersDirection::Falling,
+            };
+            let (peer_id, headers) =
+                match self.client.get_headers_with_priority(request, Priority::Normal).await {
+                    Ok(response) => response.split(),
+                    Err(error) => {
+                        warn!(target: "reth::cli", %error, "Failed to fetch headers for backfill");
+                        backoff = wait(backoff).await;
+                        continue
+                    }
+                };
+
+            // Verify that the response is a chain of ancestors of the lowest header.
+            let mut child = lowest.clone();
+            let mut sealed = Vec::with_capacity(headers.len());
+            for header in headers {
+                let header = header.seal_slow();
+                if header.hash != child.parent_hash || header.number + 1 != child.number {
+                    break
+                }
+                child = header.clone();
+                sealed.push(header);
+            }
+            if sealed.is_empty() {
+                self.client.report_bad_message(peer_id);
+                backoff = wait(backoff).await;
+                continue
+            }
+            backoff = BACKFILL_MIN_BACKOFF;
+
+            if child.number == 1 {
+                let genesis = self.db.view(|tx| tx.get::<tables::CanonicalHeaders>(0))??;
+                if genesis != Some(child.parent_hash) {
+                    eyre::bail!(
+                        "Backfilled headers do not connect to genesis: expected {:?}, got {}",
+                        genesis,
+                        child.parent_hash
+                    )
+                }
+            }
+
+            self.write_headers(lowest, td, sealed)?;
+            debug!(target: "reth::cli", block = child.number, "Backfilled headers");
+        }
+
+        Ok(())
+    }
+
+    /// Backfills the bodies until the body at the given block number is in the database.
+    ///
+    /// The headers of the blocks must already be in the database.
+    async fn backfill_bodies(&self, target: BlockNumber) -> eyre::Result<()> {
+        let mut backoff = BACKFILL_MIN_BACKOFF;
+        while let Some((lowest, indices)) = self.lowest_body()? {
+            if lowest <= target {
+                break
+            }
+
+            let start = lowest.saturating_sub(BACKFILL_BODIES_BATCH_SIZE).max(target);
+            let headers = self.canonical_headers(start..lowest)?;
+            let hashes = headers.iter().map(|header| header.hash).collect();
+            let (peer_id, bodies) =
+                match self.client.get_block_bodies_with_priority(hashes, Priority::Normal).await {
+                    Ok(response) => response.split(),
+                    Err(error) => {
+                        warn!(target: "reth::cli", %error, "Failed to fetch bodies for backfill");
+                        backoff = wait(backoff).await;
+                        continue
+                    }
+                };
+
+            // Verify the bodies against their headers, keeping the valid prefix of the response.
+            let mut blocks = Vec::with_capacity(bodies.len());
+            for (header, body) in headers.into_iter().zip(bodies) {
+                let block = SealedBlock::new(header, body);
+                if let Err(error) = validate_block_standalone(&block, &self.chain_spec) {
+                    debug!(target: "reth::cli", %error, ?peer_id, "Invalid body in backfill");
+                    break
+                }
+                let Some(senders) =
+                    TransactionSigned::recover_signers(&block.body, block.body.len())
+                else {
+                    break
+                };
+                blocks.push((block, senders));
+            }
+            if blocks.is_empty() {
+                self.client.report_bad_message(peer_id);
+                backoff = wait(backoff).await;
+                continue
+            }
+            backoff = BACKFILL_MIN_BACKOFF;
+
+            let lowest = blocks.last().map(|(block, _)| block.number);
+            self.write_bodies(indices, blocks)?;
+            debug!(target: "reth::cli", block = lowest, "Backfilled bodies");
+        }
+
+        Ok(())
+    }
+
+    /// Returns the lowest header above genesis and its total difficulty, if the header below it
+    /// is missing.
+    fn lowest_header(&self) -> eyre::Result<Option<(SealedHeader, U256)>> {
+        let tx = self.db.tx()?;
+        let mut cursor = tx.cursor_read::<tables::CanonicalHeaders>()?;
+        cursor.first()?;
+        let Some((number, hash)) = cursor.next()? else { return Ok(None) };
+        if number <= 1 {
+            return Ok(None)
+        }
+
+        let header = tx
+            .get::<tables::Headers>(number)?
+            .ok_or_else(|| eyre::eyre!("Header {number} not found"))?;
+        let td = tx
+            .get::<tables::HeaderTD>(number)?
+            .ok_or_else(|| eyre::eyre!("Total difficulty of block {number} not found"))?;
+        Ok(Some((SealedHeader { header, hash }, td.into())))
+    }
+
+    /// Returns the lowest block with a body above genesis and its body indices, if the body
+    /// below it is missing.
+    fn lowest_body(&self) -> eyre::Result<Option<(BlockNumber, StoredBlockBodyIndices)>> {
+        let tx = self.db.tx()?;
+        let mut cursor = tx.cursor_read::<tables::BlockBodyIndices>()?;
+        cursor.first()?;
+        Ok(cursor.next()?.filter(|(number, _)| *number > 1))
+    }
+
+    /// Returns the canonical headers in the given range, ordered from highest to lowest.
+    fn canonical_headers(
+        &self,
+        range: std::ops::Range<BlockNumber>,
+    ) -> eyre::Result<Vec<SealedHeader>> {
+        let tx = self.db.tx()?;
+        range
+            .rev()
+            .map(|number| {
+                let hash = tx
+                    .get::<tables::CanonicalHeaders>(number)?
+                    .ok_or_else(|| eyre::eyre!("Canonical hash of block {number} not found"))?;
+                let header = tx
+                    .get::<tables::Headers>(number)?
+                    .ok_or_else(|| eyre::eyre!("Header {number} not found"))?;
+                Ok(SealedHeader { header, hash })
+            })
+            .collect()
+    }
+
+    /// Writes the given headers, ordered from highest to lowest, below the lowest header.
+    fn write_headers(
+        &self,
+        lowest: SealedHeader,
+        mut td: U256,
+        headers: Vec<SealedHeader>,
+    ) -> eyre::Result<()> {
+        let tx = self.db.tx_mut()?;
+        let mut child = lowest;
+        for header in headers {
+            td = td.saturating_sub(child.difficulty);
+            tx.put::<tables::CanonicalHeaders>(header.number, header.hash)?;
+            tx.put::<tables::HeaderNumbers>(header.hash, header.number)?;
+            tx.put::<tables::HeaderTD>(header.number, td.into())?;
+            tx.put::<tables::Headers>(header.number, header.header.clone())?;
+            child = header;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Writes the given blocks, ordered from highest to lowest, below the block with the given
+    /// body indices. The transactions are numbered downwards from the first transaction of that
+    /// block.
+    fn write_bodies(
+        &self,
+        mut child: StoredBlockBodyIndices,
+        blocks: Vec<(SealedBlock, Vec<Address>)>,
+    ) -> eyre::Result<()> {
+        let tx = self.db.tx_mut()?;
+        for (block, senders) in blocks {
+            let tx_count = block.body.len() as u64;
+            let first_tx_num = child.first_tx_num.checked_sub(tx_count).ok_or_else(|| {
+                eyre::eyre!("No transaction numbers left to backfill block {}", block.number)
+            })?;
+            let indices = StoredBlockBodyIndices { first_tx_num, tx_count };
+
+            for ((tx_num, transaction), sender) in (first_tx_num..).zip(block.body).zip(senders) {
+                tx.put::<tables::TxHashNumber>(transaction.hash(), tx_num)?;
+                tx.put::<tables::TxSenders>(tx_num, sender)?;
+                tx.put::<tables::Transactions>(tx_num, transaction.into())?;
+            }
+            if !indices.is_empty() {
+                tx.put::<tables::TransactionBlock>(indices.last_tx_num(), block.number)?;
+            }
+            if !block.ommers.is_empty() {
+                tx.put::<tables::BlockOmmers>(
+                    block.number,
+                    StoredBlockOmmers { ommers: block.ommers },
+                )?;
+            }
+            if let Some(withdrawals) = block.withdrawals.filter(|w| !w.is_empty()) {
+                tx.put::<tables::BlockWithdrawals>(
+                    block.number,
+                    StoredBlockWithdrawals { withdrawals },
+                )?;
+            }
+            tx.put::<tables::BlockBodyIndices>(block.number, indices.clone())?;
+            child = indices;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+/// Waits for the given backoff and returns the backoff for the next consecutive failure.
+async fn wait(backoff: Duration) -> Duration {
+    tokio::time::sleep(backoff).await;
+    (backoff * 2).min(BACKFILL_MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::{init_genesis, STATE_IMPORT_FIRST_TX_NUM};
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_interfaces::test_utils::TestFullBlockClient;
+    use reth_primitives::{BlockBody, Header, MAINNET};
+
+    #[tokio::test]
+    async fn backfill_headers_and_bodies() {
+        let db = create_test_rw_db();
+        let genesis_hash = init_genesis(Arc::clone(&db), MAINNET.clone()).unwrap();
+
+        let client = TestFullBlockClient::default();
+        let mut parent_hash = genesis_hash;
+        let mut headers = Vec::new();
+        for number in 1..=3 {
+            let header = Header { number, parent_hash, ..Default::default() }.seal_slow();
+            parent_hash = header.hash;
+            client.insert(header.clone(), BlockBody::default());
+            headers.push(header);
+        }
+
+        // The checkpoint was imported at block 3.
+        let checkpoint = headers.pop().unwrap();
+        let tx = db.tx_mut().unwrap();
+        tx.put::<tables::CanonicalHeaders>(3, checkpoint.hash).unwrap();
+        tx.put::<tables::HeaderNumbers>(checkpoint.hash, 3).unwrap();
+        tx.put::<tables::HeaderTD>(3, U256::ZERO.into()).unwrap();
+        tx.put::<tables::Headers>(3, checkpoint.header.clone()).unwrap();
+        tx.put::<tables::BlockBodyIndices>(
+            3,
+            StoredBlockBodyIndices { first_tx_num: STATE_IMPORT_FIRST_TX_NUM, tx_count: 0 },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let backfill = BlockBackfill::new(Arc::clone(&db), client, MAINNET.clone());
+        assert_eq!(backfill.checkpoint().unwrap(), Some(3));
+        backfill.run_until(0).await.unwrap();
+        assert_eq!(backfill.checkpoint().unwrap(), None);
+
+        let tx = db.tx().unwrap();
+        for header in headers {
+            assert_eq!(
+                tx.get::<tables::CanonicalHeaders>(header.number).unwrap(),
+                Some(header.hash)
+            );
+            assert_eq!(
+                tx.get::<tables::BlockBodyIndices>(header.number).unwrap(),
+                Some(StoredBlockBodyIndices {
+                    first_tx_num: STATE_IMPORT_FIRST_TX_NUM,
+                    tx_count: 0
+                })
+            );
+        }
+    }
+}
@@ -99,6 +99,15 @@ impl NodeState {
                     self.eta = Eta::default();
                 }
             }
+            PipelineEvent::Unwinding { stage_id, input, progress } => {
+                info!(
+                    stage = %stage_id,
+                    from = input.checkpoint.block_number,
+                    to = input.unwind_to,
+                    %progress,
+                    "Unwinding stage",
+                );
+            }
             _ => (),
         }
     }
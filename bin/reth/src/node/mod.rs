@@ -5,16 +5,19 @@ use crate::{
     args::{
         get_secret_key,
         utils::{genesis_value_parser, parse_socket_address},
-        DatabaseArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs,
-        RpcServerArgs, TxPoolArgs,
+        CheckpointSyncArgs, DatabaseArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
+        PruningArgs, RpcServerArgs, TxPoolArgs,
     },
     cli::{
         config::RethRpcConfig,
         ext::{RethCliExt, RethNodeCommandConfig},
     },
     dirs::{DataDirPath, MaybePlatformPath},
-    init::init_genesis,
-    node::cl_events::ConsensusLayerHealthEvents,
+    init::{
+        import_state_dump, init_genesis, pending_state_import, read_state_dump_header,
+        InitDatabaseError,
+    },
+    node::{backfill::BlockBackfill, cl_events::ConsensusLayerHealthEvents},
     prometheus_exporter,
     runner::CliContext,
     utils::get_single_header,
@@ -30,7 +33,7 @@ use reth_blockchain_tree::{
     config::BlockchainTreeConfig, externals::TreeExternals, BlockchainTree, ShareableBlockchainTree,
 };
 use reth_config::{config::PruneConfig, Config};
-use reth_db::{database::Database, init_db, DatabaseEnv};
+use reth_db::{database::Database, init_db, tables, transaction::DbTx, DatabaseEnv};
 use reth_discv4::DEFAULT_DISCOVERY_PORT;
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
@@ -44,6 +47,7 @@ use reth_interfaces::{
         headers::{client::HeadersClient, downloader::HeaderDownloader},
     },
 };
+use reth_net_nat::pmp::{MappingProtocol, NatPmpClient, PortMapper};
 use reth_network::{error::NetworkError, NetworkConfig, NetworkHandle, NetworkManager};
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
@@ -52,13 +56,16 @@ use reth_primitives::{
     stage::StageId,
     BlockHashOrNumber, BlockNumber, ChainSpec, DisplayHardforks, Head, SealedHeader, H256,
 };
+#[cfg(unix)]
+use reth_provider::ipc::CanonStateIpcServer;
 use reth_provider::{
-    providers::BlockchainProvider, BlockHashReader, BlockReader, CanonStateSubscriptions,
-    HeaderProvider, ProviderFactory, StageCheckpointReader,
+    providers::BlockchainProvider, BlockHashReader, BlockReader, BytecodeReader,
+    CanonStateNotificationStream, CanonStateSubscriptions, HeaderProvider, ProviderFactory,
+    StageCheckpointReader,
 };
 use reth_revm::Factory;
 use reth_revm_inspectors::stack::Hook;
-use reth_rpc_engine_api::EngineApi;
+use reth_rpc_engine_api::{EngineApi, EngineApiRecorder};
 use reth_stages::{
     prelude::*,
     stages::{
@@ -74,6 +81,8 @@ use reth_transaction_pool::{
 };
 use secp256k1::SecretKey;
 use std::{
+    fs::File,
+    io::BufReader,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     path::PathBuf,
     sync::Arc,
@@ -81,6 +90,7 @@ use std::{
 use tokio::sync::{mpsc::unbounded_channel, oneshot, watch};
 use tracing::*;
 
+pub mod backfill;
 pub mod cl_events;
 pub mod events;
 
@@ -179,6 +189,10 @@ pub struct NodeCommand<Ext: RethCliExt = ()> {
     #[clap(flatten)]
     pub pruning: PruningArgs,
 
+    /// All checkpoint sync related arguments with --checkpoint-sync prefix
+    #[clap(flatten)]
+    pub checkpoint_sync: CheckpointSyncArgs,
+
     /// Additional cli arguments
     #[clap(flatten)]
     pub ext: Ext::Node,
@@ -202,6 +216,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            checkpoint_sync,
             ..
         } = self;
         NodeCommand {
@@ -219,6 +234,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            checkpoint_sync,
             ext,
         }
     }
@@ -251,6 +267,13 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
 
         let genesis_hash = init_genesis(db.clone(), self.chain.clone())?;
 
+        if let Some(hash) = self.checkpoint_sync.hash {
+            self.import_checkpoint(&db, hash)?;
+        }
+        if let Some(block) = pending_state_import(&db)? {
+            return Err(InitDatabaseError::IncompleteStateImport { block }.into())
+        }
+
         info!(target: "reth::cli", "{}", DisplayHardforks::from(self.chain.hardforks().clone()));
 
         let consensus: Arc<dyn Consensus> = if self.dev.dev {
@@ -301,8 +324,11 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             .with_additional_tasks(1)
             .build_with_tasks(blockchain_db.clone(), ctx.task_executor.clone(), blob_store.clone());
 
-        let transaction_pool =
-            reth_transaction_pool::Pool::eth_pool(validator, blob_store, self.txpool.pool_config());
+        let transaction_pool = reth_transaction_pool::Pool::eth_pool(
+            validator,
+            blob_store.clone(),
+            self.txpool.pool_config(),
+        );
         info!(target: "reth::cli", "Transaction pool initialized");
 
         // spawn txpool maintenance task
@@ -323,6 +349,14 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
         }
 
+        if let Some(path) = self.rpc.notifications_ipcpath.clone() {
+            spawn_canon_state_ipc_server(
+                &ctx.task_executor,
+                blockchain_db.canonical_state_stream(),
+                path,
+            )?;
+        }
+
         info!(target: "reth::cli", "Connecting to P2P network");
         let network_secret_path =
             self.network.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret_path());
@@ -350,6 +384,20 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         debug!(target: "reth::cli", peer_id = ?network.peer_id(), "Full peer ID");
         let network_client = network.fetch_client().await?;
 
+        let backfill =
+            BlockBackfill::new(Arc::clone(&db), network_client.clone(), Arc::clone(&self.chain));
+        if let Some(checkpoint) = backfill.checkpoint()? {
+            info!(target: "reth::cli", checkpoint, "Backfilling blocks below checkpoint");
+            // Blocks after the checkpoint can access the hashes of the 256 blocks before them.
+            backfill.backfill_headers(checkpoint.saturating_sub(256)).await?;
+            ctx.task_executor.spawn(Box::pin(async move {
+                match backfill.run_until(0).await {
+                    Ok(()) => info!(target: "reth::cli", "Block backfill finished"),
+                    Err(error) => error!(target: "reth::cli", %error, "Block backfill failed"),
+                }
+            }));
+        }
+
         let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
 
         debug!(target: "reth::cli", "Spawning payload builder service");
@@ -493,13 +541,18 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             events::handle_events(Some(network.clone()), Some(head.number), events),
         );
 
-        let engine_api = EngineApi::new(
+        let mut engine_api = EngineApi::new(
             blockchain_db.clone(),
             self.chain.clone(),
             beacon_engine_handle,
             payload_builder.into(),
             Box::new(ctx.task_executor.clone()),
+            Box::new(blob_store),
         );
+        if let Some(store_path) = &self.debug.engine_api_store {
+            debug!(target: "reth::cli", path = ?store_path, "Recording engine API calls");
+            engine_api = engine_api.with_recorder(EngineApiRecorder::new(store_path)?);
+        }
         info!(target: "reth::cli", "Engine API handler initialized");
 
         // extract the jwt secret from the args if possible
@@ -514,6 +567,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             .rpc
             .start_servers(
                 blockchain_db.clone(),
+                Arc::clone(&db),
                 transaction_pool.clone(),
                 network.clone(),
                 ctx.task_executor.clone(),
@@ -637,19 +691,27 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         default_peers_path: PathBuf,
     ) -> Result<NetworkHandle, NetworkError>
     where
-        C: BlockReader + HeaderProvider + Clone + Unpin + 'static,
+        C: BlockReader + HeaderProvider + BytecodeReader + Clone + Unpin + 'static,
         Pool: TransactionPool + Unpin + 'static,
     {
         let client = config.client.clone();
+        let discovery_port =
+            config.discovery_v4_config.is_some().then_some(config.discovery_addr.port());
+        let propagation_policy = self.network.tx_propagation.propagation_policy();
+        let serving_policy = self.network.serving.serving_policy();
         let (handle, network, txpool, eth) = NetworkManager::builder(config)
             .await?
-            .transactions(pool)
-            .request_handler(client)
+            .transactions_with_policy(pool, propagation_policy)
+            .request_handler_with_policy(client, serving_policy)
             .split_with_handle();
 
         task_executor.spawn_critical("p2p txpool", txpool);
         task_executor.spawn_critical("p2p eth request handler", eth);
 
+        if let Some(gateway) = self.network.nat.port_mapping_gateway() {
+            spawn_port_mapper(task_executor, gateway, handle.local_addr().port(), discovery_port);
+        }
+
         let known_peers_file = self.network.persistent_peers_file(default_peers_path);
         task_executor.spawn_critical_with_signal("p2p network task", |shutdown| {
             run_network_until_shutdown(shutdown, network, known_peers_file)
@@ -658,6 +720,49 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         Ok(handle)
     }
 
+    /// Imports the state of the trusted checkpoint block, unless the database already contains the
+    /// block.
+    fn import_checkpoint(&self, db: &Arc<DatabaseEnv>, hash: H256) -> eyre::Result<()> {
+        if db.view(|tx| tx.get::<tables::HeaderNumbers>(hash))??.is_some() {
+            debug!(target: "reth::cli", ?hash, "Checkpoint already imported, skipping.");
+            return Ok(())
+        }
+
+        let (Some(state_root), Some(path)) =
+            (self.checkpoint_sync.state_root, self.checkpoint_sync.state.as_ref())
+        else {
+            eyre::bail!("Checkpoint sync requires a state root and a state dump")
+        };
+
+        info!(target: "reth::cli", ?hash, path = ?path, "Importing checkpoint state");
+        let mut reader = BufReader::new(
+            File::open(path).wrap_err_with(|| format!("Could not open state dump {path:?}"))?,
+        );
+        let dump = read_state_dump_header(&mut reader)?;
+        let header = dump.header.seal_slow();
+        if header.hash != hash {
+            eyre::bail!("State dump is for block {:?}, expected checkpoint {hash:?}", header.hash)
+        }
+        if header.state_root != state_root {
+            eyre::bail!(
+                "Checkpoint state root mismatch: header has {:?}, expected {state_root:?}",
+                header.state_root
+            )
+        }
+
+        let number = header.number;
+        import_state_dump(
+            Arc::clone(db),
+            Arc::clone(&self.chain),
+            header,
+            dump.total_difficulty,
+            reader,
+        )?;
+        info!(target: "reth::cli", number, ?hash, "Imported checkpoint state");
+
+        Ok(())
+    }
+
     fn lookup_head(&self, db: Arc<DatabaseEnv>) -> Result<Head, reth_interfaces::Error> {
         let factory = ProviderFactory::new(db, self.chain.clone());
         let provider = factory.provider()?;
@@ -894,6 +999,55 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
     }
 }
 
+/// Spawns a task that maps the RLPx and discovery ports on the NAT-PMP/PCP gateway and keeps
+/// them mapped until shutdown.
+fn spawn_port_mapper(
+    task_executor: &TaskExecutor,
+    gateway: SocketAddr,
+    rlpx_port: u16,
+    discovery_port: Option<u16>,
+) {
+    task_executor.spawn_with_signal(|shutdown| async move {
+        let client = match NatPmpClient::new(gateway).await {
+            Ok(client) => client,
+            Err(err) => {
+                warn!(target: "reth::cli", %gateway, ?err, "Failed to connect to NAT-PMP gateway");
+                return
+            }
+        };
+
+        info!(target: "reth::cli", %gateway, rlpx_port, ?discovery_port, "Mapping ports via NAT-PMP");
+        let mut mapper = PortMapper::new(client).with_port(MappingProtocol::Tcp, rlpx_port);
+        if let Some(discovery_port) = discovery_port {
+            mapper = mapper.with_port(MappingProtocol::Udp, discovery_port);
+        }
+        mapper.run_until_shutdown(shutdown).await
+    });
+}
+
+/// Publishes the canonical state notifications on the unix socket at the given path.
+#[cfg(unix)]
+fn spawn_canon_state_ipc_server(
+    task_executor: &TaskExecutor,
+    notifications: CanonStateNotificationStream,
+    path: PathBuf,
+) -> eyre::Result<()> {
+    let server = CanonStateIpcServer::bind(&path)
+        .wrap_err_with(|| format!("Could not bind notifications socket at {}", path.display()))?;
+    info!(target: "reth::cli", path = %path.display(), "Publishing canonical state notifications");
+    task_executor.spawn_critical("canonical state ipc server", server.serve(notifications));
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn spawn_canon_state_ipc_server(
+    _task_executor: &TaskExecutor,
+    _notifications: CanonStateNotificationStream,
+    _path: PathBuf,
+) -> eyre::Result<()> {
+    eyre::bail!("Canonical state notifications over IPC are only supported on unix")
+}
+
 /// Drives the [NetworkManager] future until a [Shutdown](reth_tasks::shutdown::Shutdown) signal is
 /// received. If configured, this writes known peers to `persistent_peers_file` afterwards.
 async fn run_network_until_shutdown<C>(
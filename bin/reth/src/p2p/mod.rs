@@ -10,13 +10,19 @@ use crate::{
 };
 use backon::{ConstantBuilder, Retryable};
 use clap::{Parser, Subcommand};
+use eyre::Context;
 use reth_config::Config;
 use reth_db::open_db;
 use reth_discv4::NatResolver;
 use reth_interfaces::p2p::bodies::client::BodiesClient;
-use reth_primitives::{BlockHashOrNumber, ChainSpec, NodeRecord};
+use reth_network::capture::{CaptureDirection, CaptureReader, CaptureReplay, ReplayOutcome};
+use reth_primitives::{BlockHashOrNumber, ChainSpec, NodeRecord, PeerId};
 use reth_provider::ProviderFactory;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::UNIX_EPOCH,
+};
 
 /// `reth p2p` command
 #[derive(Debug, Parser)]
@@ -99,10 +105,28 @@ pub enum Subcommands {
         #[arg(value_parser = hash_or_num_value_parser)]
         id: BlockHashOrNumber,
     },
+    /// Replay a session traffic capture through the eth wire decoder.
+    ///
+    /// Reports all messages that fail to decode or violate the protocol.
+    Replay {
+        /// The capture file, as written by `--capture-file`.
+        #[arg(value_name = "FILE")]
+        path: PathBuf,
+        /// Only replay the traffic of this peer.
+        #[arg(long, value_name = "PEER_ID")]
+        peer: Option<PeerId>,
+        /// Print every replayed record instead of only failures.
+        #[arg(long)]
+        verbose: bool,
+    },
 }
 impl Command {
     /// Execute `p2p` command
     pub async fn execute(&self) -> eyre::Result<()> {
+        if let Subcommands::Replay { path, peer, verbose } = &self.command {
+            return replay_capture(path, *peer, *verbose)
+        }
+
         let tempdir = tempfile::TempDir::new()?;
         let noop_db = Arc::new(open_db(&tempdir.into_path(), self.db.log_level)?);
 
@@ -180,8 +204,64 @@ impl Command {
                 let body = result.into_iter().next().unwrap();
                 println!("Successfully downloaded body: {body:?}")
             }
+            Subcommands::Replay { .. } => unreachable!("handled before the network is started"),
         }
 
         Ok(())
     }
 }
+
+/// Replays all records of the capture file and prints the ones that failed.
+fn replay_capture(path: &Path, peer: Option<PeerId>, verbose: bool) -> eyre::Result<()> {
+    let reader = CaptureReader::open(path)
+        .wrap_err_with(|| format!("Could not open capture file {}", path.display()))?;
+    let mut replay = CaptureReplay::default();
+    let (mut total, mut failed) = (0usize, 0usize);
+
+    for record in reader {
+        let record = record?;
+        if peer.map_or(false, |peer| peer != record.peer_id) {
+            continue
+        }
+
+        total += 1;
+        let outcome = replay.replay(&record);
+        if outcome.is_error() {
+            failed += 1;
+        } else if !verbose {
+            continue
+        }
+
+        let direction = match record.direction {
+            CaptureDirection::Inbound => "<-",
+            CaptureDirection::Outbound => "->",
+        };
+        let outcome = match outcome {
+            ReplayOutcome::Message(msg) => format!("{:?}", msg.message_id()),
+            ReplayOutcome::DecodeError(err) => {
+                format!("decode error: {err} (frame: 0x{})", hex::encode(&record.payload))
+            }
+            ReplayOutcome::ProtocolBreach { message, error } => {
+                format!("protocol breach: {error} ({:?})", message.message_id())
+            }
+            ReplayOutcome::UnsolicitedResponse(msg) => {
+                format!("unsolicited response: {:?}", msg.message_id())
+            }
+            ReplayOutcome::Disconnect(reason) => format!("disconnect: {reason:?}"),
+            ReplayOutcome::StreamError(err) => format!("stream error: {err}"),
+            ReplayOutcome::Hello(hello) => format!("hello: {}", hello.client_version),
+            ReplayOutcome::Status(status) => format!("status: {status}"),
+            ReplayOutcome::Ping => "ping".to_string(),
+            ReplayOutcome::Pong => "pong".to_string(),
+        };
+        println!(
+            "{} {} {direction} {:?}: {outcome}",
+            humantime::format_rfc3339_micros(UNIX_EPOCH + record.timestamp),
+            record.peer_id,
+            record.version,
+        );
+    }
+
+    println!("Replayed {total} records, {failed} failed");
+    Ok(())
+}
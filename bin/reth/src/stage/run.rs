@@ -3,14 +3,19 @@
 //! Stage debugging tool
 use crate::{
     args::{get_secret_key, utils::chain_spec_value_parser, DatabaseArgs, NetworkArgs, StageEnum},
-    dirs::{DataDirPath, MaybePlatformPath},
+    dirs::{ChainPath, DataDirPath, MaybePlatformPath},
     prometheus_exporter,
     version::SHORT_VERSION,
 };
 use clap::Parser;
 use reth_beacon_consensus::BeaconConsensus;
 use reth_config::Config;
-use reth_db::init_db;
+use reth_db::{
+    database::Database,
+    init_db,
+    profiling::{DatabaseProfile, ProfiledDatabase, Profiler},
+    DatabaseEnv,
+};
 use reth_downloaders::bodies::bodies::BodiesDownloaderBuilder;
 use reth_primitives::{ChainSpec, PruneModes};
 use reth_provider::{ProviderFactory, StageCheckpointReader};
@@ -22,7 +27,15 @@ use reth_stages::{
     },
     ExecInput, ExecOutput, PipelineError, Stage, UnwindInput,
 };
-use std::{any::Any, net::SocketAddr, path::PathBuf, sync::Arc};
+use serde::Serialize;
+use std::{
+    any::Any,
+    fmt::Write,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tracing::*;
 
 /// `reth stage` command
@@ -102,6 +115,14 @@ pub struct Command {
     // e.g. query the DB size, or any table data.
     #[arg(long, short)]
     commit: bool,
+
+    /// Writes a profiling report of the stage execution to the given file.
+    ///
+    /// The report breaks down the execution time into database reads, writes, commits and the
+    /// remaining compute time, and lists the operations and bytes per table. The report is
+    /// written as HTML if the file has an `.html` extension, as JSON otherwise.
+    #[arg(long, value_name = "FILE")]
+    profile: Option<PathBuf>,
 }
 
 impl Command {
@@ -125,9 +146,6 @@ impl Command {
         let db = Arc::new(init_db(db_path, self.db.log_level)?);
         info!(target: "reth::cli", "Database opened");
 
-        let factory = ProviderFactory::new(&db, self.chain.clone());
-        let mut provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
-
         if let Some(listen_addr) = self.metrics {
             info!(target: "reth::cli", "Starting metrics endpoint at {}", listen_addr);
             prometheus_exporter::initialize(
@@ -138,6 +156,39 @@ impl Command {
             .await?;
         }
 
+        match &self.profile {
+            Some(path) => {
+                let profiled_db = ProfiledDatabase::new(Arc::clone(&db));
+                let report = self
+                    .run_stage(&profiled_db, &db, config, &data_dir, Some(profiled_db.profiler()))
+                    .await?
+                    .expect("profiler is set");
+                report.write(path)?;
+                info!(target: "reth::cli", path = ?path, "Wrote profiling report");
+            }
+            None => {
+                self.run_stage(&db, &db, config, &data_dir, None).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Unwinds and executes the stage on the given database.
+    ///
+    /// If a profiler is given, it's reset after the unwind and a report of the execution is
+    /// returned.
+    async fn run_stage<DB: Database>(
+        &self,
+        db: &DB,
+        raw_db: &Arc<DatabaseEnv>,
+        config: Config,
+        data_dir: &ChainPath<DataDirPath>,
+        profiler: Option<&Profiler>,
+    ) -> eyre::Result<Option<ProfileReport>> {
+        let factory = ProviderFactory::new(db, self.chain.clone());
+        let mut provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
+
         let batch_size = self.batch_size.unwrap_or(self.to - self.from + 1);
 
         let (mut exec_stage, mut unwind_stage): (Box<dyn Stage<_>>, Option<Box<dyn Stage<_>>>) =
@@ -170,7 +221,7 @@ impl Command {
                             p2p_secret_key,
                             default_peers_path,
                         )
-                        .build(Arc::new(ProviderFactory::new(db.clone(), self.chain.clone())))
+                        .build(Arc::new(ProviderFactory::new(raw_db.clone(), self.chain.clone())))
                         .start_network()
                         .await?;
                     let fetch_client = Arc::new(network.fetch_client().await?);
@@ -186,7 +237,7 @@ impl Command {
                                 config.stages.bodies.downloader_min_concurrent_requests..=
                                     config.stages.bodies.downloader_max_concurrent_requests,
                             )
-                            .build(fetch_client, consensus.clone(), db.clone()),
+                            .build(fetch_client, consensus.clone(), raw_db.clone()),
                         consensus: consensus.clone(),
                     };
 
@@ -251,6 +302,11 @@ impl Command {
             }
         }
 
+        if let Some(profiler) = profiler {
+            profiler.reset();
+        }
+        let started_at = Instant::now();
+
         let mut input = ExecInput {
             target: Some(self.to),
             checkpoint: Some(checkpoint.with_block_number(self.from)),
@@ -271,6 +327,152 @@ impl Command {
             provider_rw.commit()?;
         }
 
+        Ok(profiler.map(|profiler| {
+            ProfileReport::new(
+                exec_stage.id().to_string(),
+                self.from,
+                self.to,
+                started_at.elapsed(),
+                profiler.snapshot(),
+            )
+        }))
+    }
+}
+
+/// Profiling report of a stage execution.
+#[derive(Debug, Serialize)]
+struct ProfileReport {
+    /// The profiled stage.
+    stage: String,
+    /// The first block of the executed range.
+    from: u64,
+    /// The last block of the executed range.
+    to: u64,
+    /// Total execution time in seconds.
+    total_secs: f64,
+    /// Time spent in database reads in seconds.
+    db_read_secs: f64,
+    /// Time spent in database writes in seconds.
+    db_write_secs: f64,
+    /// Time spent committing transactions in seconds.
+    commit_secs: f64,
+    /// Time spent outside of the database, e.g. in the EVM or hashing, in seconds.
+    compute_secs: f64,
+    /// Number of committed transactions.
+    commits: u64,
+    /// Operations per table, ordered by the time spent on the table.
+    tables: Vec<TableReport>,
+}
+
+/// Database operations on a single table.
+#[derive(Debug, Serialize)]
+struct TableReport {
+    table: &'static str,
+    reads: u64,
+    writes: u64,
+    cursor_reads: u64,
+    cursor_writes: u64,
+    bytes_read: u64,
+    bytes_written: u64,
+    read_secs: f64,
+    write_secs: f64,
+}
+
+impl ProfileReport {
+    fn new(stage: String, from: u64, to: u64, total: Duration, profile: DatabaseProfile) -> Self {
+        let (read, write) = (profile.read_time(), profile.write_time());
+        let compute = total.saturating_sub(read + write + profile.commit_time);
+
+        let mut tables = profile
+            .tables
+            .iter()
+            .map(|(&table, stats)| TableReport {
+                table,
+                reads: stats.reads,
+                writes: stats.writes,
+                cursor_reads: stats.cursor_reads,
+                cursor_writes: stats.cursor_writes,
+                bytes_read: stats.bytes_read,
+                bytes_written: stats.bytes_written,
+                read_secs: stats.read_time.as_secs_f64(),
+                write_secs: stats.write_time.as_secs_f64(),
+            })
+            .collect::<Vec<_>>();
+        tables
+            .sort_by(|a, b| (b.read_secs + b.write_secs).total_cmp(&(a.read_secs + a.write_secs)));
+
+        Self {
+            stage,
+            from,
+            to,
+            total_secs: total.as_secs_f64(),
+            db_read_secs: read.as_secs_f64(),
+            db_write_secs: write.as_secs_f64(),
+            commit_secs: profile.commit_time.as_secs_f64(),
+            compute_secs: compute.as_secs_f64(),
+            commits: profile.commits,
+            tables,
+        }
+    }
+
+    /// Writes the report as HTML if the path has an `.html` extension, as JSON otherwise.
+    fn write(&self, path: &Path) -> eyre::Result<()> {
+        let is_html = path.extension().map_or(false, |ext| ext.eq_ignore_ascii_case("html"));
+        let content = if is_html { self.to_html() } else { serde_json::to_string_pretty(self)? };
+        std::fs::write(path, content)?;
         Ok(())
     }
+
+    fn to_html(&self) -> String {
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{stage} profile</title>\n\
+             <style>body {{ font-family: sans-serif; }} table {{ border-collapse: collapse; }} \
+             td, th {{ border: 1px solid #ccc; padding: 4px 8px; text-align: right; }}</style>\n\
+             </head>\n<body>\n<h1>{stage} blocks {from}..={to}</h1>\n<table>\n",
+            stage = self.stage,
+            from = self.from,
+            to = self.to,
+        );
+        let breakdown = [
+            ("DB read", self.db_read_secs),
+            ("DB write", self.db_write_secs),
+            ("Commit", self.commit_secs),
+            ("Compute", self.compute_secs),
+            ("Total", self.total_secs),
+        ];
+        for (name, secs) in breakdown {
+            let share = if self.total_secs > 0.0 { secs / self.total_secs * 100.0 } else { 0.0 };
+            let _ =
+                writeln!(html, "<tr><th>{name}</th><td>{secs:.3}s</td><td>{share:.1}%</td></tr>");
+        }
+        let _ =
+            writeln!(html, "<tr><th>Commits</th><td>{}</td><td></td></tr>\n</table>", self.commits);
+
+        html.push_str(
+            "<h2>Tables</h2>\n<table>\n<tr><th>Table</th><th>Reads</th><th>Writes</th>\
+             <th>Cursor reads</th><th>Cursor writes</th><th>Bytes read</th>\
+             <th>Bytes written</th><th>Read time</th><th>Write time</th></tr>\n",
+        );
+        for table in &self.tables {
+            let _ = writeln!(
+                html,
+                "<tr><th>{}</th><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+                 <td>{}</td><td>{:.3}s</td><td>{:.3}s</td></tr>",
+                table.table,
+                table.reads,
+                table.writes,
+                table.cursor_reads,
+                table.cursor_writes,
+                table.bytes_read,
+                table.bytes_written,
+                table.read_secs,
+                table.write_secs,
+            );
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    }
 }
@@ -4,11 +4,13 @@ use crate::{
     args::{utils::genesis_value_parser, DatabaseArgs},
     dirs::{DataDirPath, MaybePlatformPath},
 };
-use clap::{Parser, Subcommand};
+use clap::{builder::RangedU64ValueParser, Parser, Subcommand};
 use reth_db::{cursor::DbCursorRO, database::Database, open_db, tables, transaction::DbTx};
 use reth_primitives::{BlockHashOrNumber, ChainSpec};
 use reth_provider::{BlockExecutionWriter, ProviderFactory};
+use reth_stages::DEFAULT_UNWIND_BATCH_SIZE;
 use std::{ops::RangeInclusive, sync::Arc};
+use tracing::info;
 
 /// `reth stage unwind` command
 #[derive(Debug, Parser)]
@@ -44,6 +46,18 @@ pub struct Command {
     #[clap(flatten)]
     db: DatabaseArgs,
 
+    /// The maximum number of blocks that are unwound in a single transaction.
+    ///
+    /// The progress is committed after every batch. An interrupted unwind can be resumed by
+    /// running the command with the same `to-block` target again.
+    #[arg(
+        long,
+        default_value_t = DEFAULT_UNWIND_BATCH_SIZE,
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..),
+        global = true
+    )]
+    batch_size: u64,
+
     #[clap(subcommand)]
     command: Subcommands,
 }
@@ -66,16 +80,34 @@ impl Command {
             eyre::bail!("Cannot unwind genesis block")
         }
 
+        if range.is_empty() {
+            println!("Nothing to unwind");
+            return Ok(())
+        }
+
         let factory = ProviderFactory::new(&db, self.chain.clone());
-        let provider = factory.provider_rw()?;
+        let total = range.end() - range.start() + 1;
+        let mut unwound = 0;
+
+        // unwind from the tip in batches, so that every committed batch leaves the database at a
+        // consistent block
+        let mut end = *range.end();
+        while end >= *range.start() {
+            let start = end.saturating_sub(self.batch_size - 1).max(*range.start());
 
-        let blocks_and_execution = provider
-            .take_block_and_execution_range(&self.chain, range)
-            .map_err(|err| eyre::eyre!("Transaction error on unwind: {err:?}"))?;
+            let provider = factory.provider_rw()?;
+            let blocks_and_execution = provider
+                .take_block_and_execution_range(&self.chain, start..=end)
+                .map_err(|err| eyre::eyre!("Transaction error on unwind: {err:?}"))?;
+            provider.commit()?;
 
-        provider.commit()?;
+            unwound += blocks_and_execution.len() as u64;
+            info!(target: "reth::cli", tip = start - 1, unwound, total, "Unwound batch");
 
-        println!("Unwound {} blocks", blocks_and_execution.len());
+            end = start - 1;
+        }
+
+        println!("Unwound {unwound} blocks");
 
         Ok(())
     }
@@ -125,5 +157,10 @@ mod tests {
 
         let cmd = Command::parse_from(["reth", "--datadir", "dir", "num-blocks", "100"]);
         assert_eq!(cmd.command, Subcommands::NumBlocks { amount: 100 });
+        assert_eq!(cmd.batch_size, DEFAULT_UNWIND_BATCH_SIZE);
+
+        let cmd = Command::parse_from(["reth", "to-block", "100", "--batch-size", "10"]);
+        assert_eq!(cmd.batch_size, 10);
+        assert!(Command::try_parse_from(["reth", "--batch-size", "0", "to-block", "100"]).is_err());
     }
 }
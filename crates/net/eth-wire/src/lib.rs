@@ -38,5 +38,7 @@ pub use crate::{
     disconnect::{CanDisconnect, DisconnectReason},
     ethstream::{EthStream, UnauthedEthStream, MAX_MESSAGE_SIZE},
     hello::HelloMessage,
-    p2pstream::{P2PMessage, P2PMessageID, P2PStream, ProtocolVersion, UnauthedP2PStream},
+    p2pstream::{
+        P2PMessage, P2PMessageID, P2PStream, PingPongEvent, ProtocolVersion, UnauthedP2PStream,
+    },
 };
@@ -228,6 +228,23 @@ pub struct P2PStream<S> {
     /// Whether this stream is currently in the process of disconnecting by sending a disconnect
     /// message.
     disconnecting: bool,
+
+    /// Ping and pong messages exchanged with the peer, only tracked if enabled via
+    /// [`P2PStream::track_ping_pong`].
+    ping_pong: Option<VecDeque<PingPongEvent>>,
+}
+
+/// A [`P2PMessage::Ping`] or [`P2PMessage::Pong`] that was exchanged with the peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PingPongEvent {
+    /// A ping was sent to the peer.
+    PingSent,
+    /// A ping was received from the peer.
+    PingReceived,
+    /// A pong was sent to the peer.
+    PongSent,
+    /// A pong was received from the peer.
+    PongReceived,
 }
 
 impl<S> P2PStream<S> {
@@ -244,6 +261,26 @@ impl<S> P2PStream<S> {
             outgoing_messages: VecDeque::new(),
             outgoing_message_buffer_capacity: MAX_P2P_CAPACITY,
             disconnecting: false,
+            ping_pong: None,
+        }
+    }
+
+    /// Starts tracking the ping and pong messages exchanged with the peer, which are otherwise
+    /// handled internally.
+    ///
+    /// Tracked messages must be drained with [`P2PStream::drain_ping_pong`].
+    pub fn track_ping_pong(&mut self) {
+        self.ping_pong.get_or_insert_with(VecDeque::new);
+    }
+
+    /// Returns all ping and pong messages exchanged since the last call, in order.
+    pub fn drain_ping_pong(&mut self) -> impl Iterator<Item = PingPongEvent> + '_ {
+        self.ping_pong.iter_mut().flat_map(|events| events.drain(..))
+    }
+
+    fn on_ping_pong(&mut self, event: PingPongEvent) {
+        if let Some(events) = &mut self.ping_pong {
+            events.push_back(event);
         }
     }
 
@@ -282,6 +319,7 @@ impl<S> P2PStream<S> {
         let mut pong_bytes = BytesMut::with_capacity(pong.length());
         pong.encode(&mut pong_bytes);
         self.outgoing_messages.push_back(pong_bytes.freeze());
+        self.on_ping_pong(PingPongEvent::PongSent);
     }
 
     /// Queues in a _snappy_ encoded [`P2PMessage::Ping`] message.
@@ -290,6 +328,7 @@ impl<S> P2PStream<S> {
         let mut ping_bytes = BytesMut::with_capacity(ping.length());
         ping.encode(&mut ping_bytes);
         self.outgoing_messages.push_back(ping_bytes.freeze());
+        self.on_ping_pong(PingPongEvent::PingSent);
     }
 
     /// Starts to gracefully disconnect the connection by sending a Disconnect message and stop
@@ -400,6 +439,7 @@ where
             match id {
                 _ if id == P2PMessageID::Ping as u8 => {
                     tracing::trace!("Received Ping, Sending Pong");
+                    this.on_ping_pong(PingPongEvent::PingReceived);
                     this.send_pong();
                     // This is required because the `Sink` may not be polled externally, and if
                     // that happens, the pong will never be sent.
@@ -423,6 +463,7 @@ where
                 }
                 _ if id == P2PMessageID::Pong as u8 => {
                     // if we were waiting for a pong, this will reset the pinger state
+                    this.on_ping_pong(PingPongEvent::PongReceived);
                     this.pinger.on_pong()?
                 }
                 _ if id > MAX_P2P_MESSAGE_ID && id <= MAX_RESERVED_MESSAGE_ID => {
@@ -11,18 +11,21 @@
     attr(deny(warnings, rust_2018_idioms), allow(dead_code, unused_variables))
 ))]
 
-//! Helpers for resolving the external IP.
+//! Helpers for resolving the external IP and mapping ports.
 //!
 //! ## Feature Flags
 //!
 //! - `serde` (default): Enable serde support
 
+pub mod pmp;
+
 use igd::aio::search_gateway;
 use pin_project_lite::pin_project;
+use pmp::{default_gateway, NatPmpClient, NAT_PMP_PORT};
 use std::{
     fmt,
     future::{poll_fn, Future},
-    net::{AddrParseError, IpAddr},
+    net::{AddrParseError, IpAddr, SocketAddr},
     pin::Pin,
     str::FromStr,
     task::{ready, Context, Poll},
@@ -46,6 +49,9 @@ pub enum NatResolver {
     PublicIp,
     /// Use the given [IpAddr]
     ExternalIp(IpAddr),
+    /// Resolve via NAT-PMP/PCP and map the node's ports on the given gateway, or the default
+    /// gateway if none is set.
+    Pmp(Option<IpAddr>),
     /// Resolve nothing
     None,
 }
@@ -57,6 +63,18 @@ impl NatResolver {
     pub async fn external_addr(self) -> Option<IpAddr> {
         external_addr_with(self).await
     }
+
+    /// Returns the address of the NAT-PMP/PCP gateway ports should be mapped on, if this is a
+    /// [NatResolver::Pmp].
+    pub fn port_mapping_gateway(&self) -> Option<SocketAddr> {
+        match self {
+            NatResolver::Pmp(gateway) => {
+                let gateway = gateway.or_else(default_gateway)?;
+                Some(SocketAddr::new(gateway, NAT_PMP_PORT))
+            }
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for NatResolver {
@@ -66,6 +84,8 @@ impl fmt::Display for NatResolver {
             NatResolver::Upnp => f.write_str("upnp"),
             NatResolver::PublicIp => f.write_str("publicip"),
             NatResolver::ExternalIp(ip) => write!(f, "extip:{ip}"),
+            NatResolver::Pmp(None) => f.write_str("pmp"),
+            NatResolver::Pmp(Some(gateway)) => write!(f, "pmp:{gateway}"),
             NatResolver::None => f.write_str("none"),
         }
     }
@@ -91,7 +111,11 @@ impl FromStr for NatResolver {
             "upnp" => NatResolver::Upnp,
             "none" => NatResolver::None,
             "publicip" | "public-ip" => NatResolver::PublicIp,
+            "pmp" => NatResolver::Pmp(None),
             s => {
+                if let Some(gateway) = s.strip_prefix("pmp:") {
+                    return Ok(NatResolver::Pmp(Some(gateway.parse::<IpAddr>()?)))
+                }
                 let Some(ip) = s.strip_prefix("extip:") else {
                     return Err(ParseNatResolverError::UnknownVariant(format!(
                         "Unknown Nat Resolver: {s}"
@@ -188,6 +212,7 @@ pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
         NatResolver::Upnp => resolve_external_ip_upnp().await,
         NatResolver::PublicIp => resolve_external_ip().await,
         NatResolver::ExternalIp(ip) => Some(ip),
+        NatResolver::Pmp(_) => resolve_external_ip_pmp(resolver.port_mapping_gateway()?).await,
         NatResolver::None => None,
     }
 }
@@ -253,6 +278,19 @@ async fn resolve_external_ip_upnp() -> Option<IpAddr> {
         .ok()
 }
 
+async fn resolve_external_ip_pmp(gateway: SocketAddr) -> Option<IpAddr> {
+    NatPmpClient::new(gateway)
+        .await
+        .ok()?
+        .external_address()
+        .await
+        .map_err(|err| {
+            debug!(target: "net::nat", ?err, %gateway, "Failed to resolve external IP via NAT-PMP");
+            err
+        })
+        .ok()
+}
+
 async fn resolve_external_ip() -> Option<IpAddr> {
     public_ip::addr().await
 }
@@ -291,5 +329,11 @@ mod tests {
         let s = "extip:0.0.0.0";
         assert_eq!(ip, s.parse().unwrap());
         assert_eq!(ip.to_string().as_str(), s);
+
+        assert_eq!(NatResolver::Pmp(None), "pmp".parse().unwrap());
+        let pmp = NatResolver::Pmp(Some(IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1))));
+        let s = "pmp:192.168.0.1";
+        assert_eq!(pmp, s.parse().unwrap());
+        assert_eq!(pmp.to_string().as_str(), s);
     }
 }
@@ -0,0 +1,696 @@
+//! Port mapping via NAT-PMP ([RFC 6886](https://datatracker.ietf.org/doc/html/rfc6886)) and its
+//! successor PCP ([RFC 6887](https://datatracker.ietf.org/doc/html/rfc6887)).
+//!
+//! The [NatPmpClient] prefers PCP and falls back to NAT-PMP if the gateway only speaks the older
+//! protocol. The [PortMapper] keeps a set of mappings alive by renewing them before their lease
+//! expires and removes them on shutdown.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::{Duration, Instant},
+};
+use tokio::net::UdpSocket;
+use tracing::{debug, trace};
+
+/// The port NAT-PMP and PCP servers listen on.
+pub const NAT_PMP_PORT: u16 = 5351;
+
+/// The lifetime we request for port mappings, as recommended by RFC 6886.
+pub const DEFAULT_MAPPING_LIFETIME: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// Initial timeout for a request, doubled on every retransmission.
+const INITIAL_REQUEST_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Default number of attempts before a request is considered failed.
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+const NAT_PMP_VERSION: u8 = 0;
+const PCP_VERSION: u8 = 2;
+
+/// Bit set in the opcode of all responses.
+const RESPONSE_FLAG: u8 = 0x80;
+
+const NAT_PMP_OP_EXTERNAL_ADDRESS: u8 = 0;
+const PCP_OP_MAP: u8 = 1;
+
+const RESULT_SUCCESS: u16 = 0;
+const RESULT_UNSUPPORTED_VERSION: u16 = 1;
+
+const NAT_PMP_EXTERNAL_ADDRESS_RESPONSE_LEN: usize = 12;
+const NAT_PMP_MAP_RESPONSE_LEN: usize = 16;
+const PCP_MAP_LEN: usize = 60;
+
+/// Transport protocol of a port mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MappingProtocol {
+    /// UDP, used by discovery.
+    Udp,
+    /// TCP, used by RLPx.
+    Tcp,
+}
+
+// === impl MappingProtocol ===
+
+impl MappingProtocol {
+    /// The NAT-PMP opcode for mapping this protocol.
+    fn nat_pmp_opcode(&self) -> u8 {
+        match self {
+            MappingProtocol::Udp => 1,
+            MappingProtocol::Tcp => 2,
+        }
+    }
+
+    /// The IANA protocol number, used by PCP.
+    fn iana_number(&self) -> u8 {
+        match self {
+            MappingProtocol::Udp => 17,
+            MappingProtocol::Tcp => 6,
+        }
+    }
+}
+
+/// A port mapping granted by the gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortMapping {
+    /// Protocol of the mapping.
+    pub protocol: MappingProtocol,
+    /// The local port.
+    pub internal_port: u16,
+    /// The port on the gateway's external address.
+    pub external_port: u16,
+    /// How long the mapping is valid for.
+    pub lifetime: Duration,
+    /// The external address of the mapping, only reported by PCP.
+    pub external_ip: Option<IpAddr>,
+}
+
+/// Errors that can occur when talking to a NAT-PMP or PCP gateway.
+#[derive(Debug, thiserror::Error)]
+pub enum NatPmpError {
+    /// Failed to send or receive.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The gateway did not respond.
+    #[error("gateway did not respond")]
+    Timeout,
+    /// The gateway does not support the protocol version of the request.
+    #[error("unsupported protocol version")]
+    UnsupportedVersion,
+    /// The gateway rejected the request with the given result code.
+    #[error("request failed with result code {0}")]
+    ResultCode(u16),
+    /// The gateway sent a response that could not be decoded.
+    #[error("invalid response")]
+    InvalidResponse,
+}
+
+/// The protocol version spoken by the gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GatewayVersion {
+    Pcp,
+    NatPmp,
+}
+
+/// A client that requests port mappings from a NAT-PMP or PCP gateway.
+#[derive(Debug)]
+pub struct NatPmpClient {
+    socket: UdpSocket,
+    gateway: SocketAddr,
+    /// The version the gateway responded to, detected on the first mapping request.
+    version: Option<GatewayVersion>,
+    /// The PCP nonces of the mappings, required to renew or delete them.
+    nonces: HashMap<(MappingProtocol, u16), [u8; 12]>,
+    max_attempts: u32,
+}
+
+// === impl NatPmpClient ===
+
+impl NatPmpClient {
+    /// Creates a new client that talks to the gateway at the given address.
+    pub async fn new(gateway: SocketAddr) -> Result<Self, NatPmpError> {
+        let local: SocketAddr = match gateway {
+            SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+        };
+        let socket = UdpSocket::bind(local).await?;
+        socket.connect(gateway).await?;
+        Ok(Self {
+            socket,
+            gateway,
+            version: None,
+            nonces: Default::default(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        })
+    }
+
+    /// Sets the number of attempts before a request is considered failed.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Returns the address of the gateway.
+    pub fn gateway(&self) -> SocketAddr {
+        self.gateway
+    }
+
+    /// Requests the external address of the gateway via NAT-PMP.
+    pub async fn external_address(&self) -> Result<IpAddr, NatPmpError> {
+        let request = [NAT_PMP_VERSION, NAT_PMP_OP_EXTERNAL_ADDRESS];
+        let mut buf = [0u8; 64];
+        let len = self.request(&request, NAT_PMP_OP_EXTERNAL_ADDRESS, &mut buf).await?;
+        let response = &buf[..len];
+        check_nat_pmp_result(response)?;
+        if len < NAT_PMP_EXTERNAL_ADDRESS_RESPONSE_LEN {
+            return Err(NatPmpError::InvalidResponse)
+        }
+        Ok(IpAddr::V4(Ipv4Addr::new(response[8], response[9], response[10], response[11])))
+    }
+
+    /// Requests a mapping of the `internal_port` to the `external_port` on the gateway, the
+    /// gateway is free to assign a different external port.
+    ///
+    /// Requesting an existing mapping again renews its lease.
+    pub async fn map_port(
+        &mut self,
+        protocol: MappingProtocol,
+        internal_port: u16,
+        external_port: u16,
+        lifetime: Duration,
+    ) -> Result<PortMapping, NatPmpError> {
+        if self.version == Some(GatewayVersion::NatPmp) {
+            return self.nat_pmp_map(protocol, internal_port, external_port, lifetime).await
+        }
+
+        match self.pcp_map(protocol, internal_port, external_port, lifetime).await {
+            Err(NatPmpError::UnsupportedVersion) => {
+                trace!(target: "net::nat", gateway=%self.gateway, "Gateway does not support PCP, falling back to NAT-PMP");
+                self.version = Some(GatewayVersion::NatPmp);
+                self.nat_pmp_map(protocol, internal_port, external_port, lifetime).await
+            }
+            res => {
+                if res.is_ok() {
+                    self.version = Some(GatewayVersion::Pcp);
+                }
+                res
+            }
+        }
+    }
+
+    /// Removes the mapping of the `internal_port`.
+    pub async fn remove_mapping(
+        &mut self,
+        protocol: MappingProtocol,
+        internal_port: u16,
+    ) -> Result<(), NatPmpError> {
+        self.map_port(protocol, internal_port, 0, Duration::ZERO).await?;
+        self.nonces.remove(&(protocol, internal_port));
+        Ok(())
+    }
+
+    async fn nat_pmp_map(
+        &self,
+        protocol: MappingProtocol,
+        internal_port: u16,
+        external_port: u16,
+        lifetime: Duration,
+    ) -> Result<PortMapping, NatPmpError> {
+        let opcode = protocol.nat_pmp_opcode();
+        let mut request = [0u8; 12];
+        request[0] = NAT_PMP_VERSION;
+        request[1] = opcode;
+        request[4..6].copy_from_slice(&internal_port.to_be_bytes());
+        request[6..8].copy_from_slice(&external_port.to_be_bytes());
+        request[8..12].copy_from_slice(&lifetime_secs(lifetime).to_be_bytes());
+
+        let mut buf = [0u8; 64];
+        let len = self.request(&request, opcode, &mut buf).await?;
+        let response = &buf[..len];
+        check_nat_pmp_result(response)?;
+        if len < NAT_PMP_MAP_RESPONSE_LEN {
+            return Err(NatPmpError::InvalidResponse)
+        }
+
+        Ok(PortMapping {
+            protocol,
+            internal_port: u16::from_be_bytes([response[8], response[9]]),
+            external_port: u16::from_be_bytes([response[10], response[11]]),
+            lifetime: Duration::from_secs(u32::from_be_bytes([
+                response[12],
+                response[13],
+                response[14],
+                response[15],
+            ]) as u64),
+            external_ip: None,
+        })
+    }
+
+    async fn pcp_map(
+        &mut self,
+        protocol: MappingProtocol,
+        internal_port: u16,
+        external_port: u16,
+        lifetime: Duration,
+    ) -> Result<PortMapping, NatPmpError> {
+        let client_ip = self.socket.local_addr()?.ip();
+        let nonce = *self.nonces.entry((protocol, internal_port)).or_insert_with(rand::random);
+
+        let mut request = [0u8; PCP_MAP_LEN];
+        // common header
+        request[0] = PCP_VERSION;
+        request[1] = PCP_OP_MAP;
+        request[4..8].copy_from_slice(&lifetime_secs(lifetime).to_be_bytes());
+        request[8..24].copy_from_slice(&to_ipv6(client_ip).octets());
+        // map opcode
+        request[24..36].copy_from_slice(&nonce);
+        request[36] = protocol.iana_number();
+        request[40..42].copy_from_slice(&internal_port.to_be_bytes());
+        request[42..44].copy_from_slice(&external_port.to_be_bytes());
+        let any = match client_ip {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        };
+        request[44..60].copy_from_slice(&to_ipv6(any).octets());
+
+        let mut buf = [0u8; 1100];
+        let len = self.request(&request, PCP_OP_MAP, &mut buf).await?;
+        let response = &buf[..len];
+
+        if response[0] == NAT_PMP_VERSION {
+            // a NAT-PMP only gateway
+            return match check_nat_pmp_result(response) {
+                Err(err) => Err(err),
+                Ok(()) => Err(NatPmpError::InvalidResponse),
+            }
+        }
+        if response[0] != PCP_VERSION || len < 4 {
+            return Err(NatPmpError::UnsupportedVersion)
+        }
+        match response[3] as u16 {
+            RESULT_SUCCESS => {}
+            RESULT_UNSUPPORTED_VERSION => return Err(NatPmpError::UnsupportedVersion),
+            code => return Err(NatPmpError::ResultCode(code)),
+        }
+        if len < PCP_MAP_LEN || response[24..36] != nonce {
+            return Err(NatPmpError::InvalidResponse)
+        }
+
+        let mut external_ip = [0u8; 16];
+        external_ip.copy_from_slice(&response[44..60]);
+        let external_ip = Ipv6Addr::from(external_ip);
+
+        Ok(PortMapping {
+            protocol,
+            internal_port: u16::from_be_bytes([response[40], response[41]]),
+            external_port: u16::from_be_bytes([response[42], response[43]]),
+            lifetime: Duration::from_secs(u32::from_be_bytes([
+                response[4],
+                response[5],
+                response[6],
+                response[7],
+            ]) as u64),
+            external_ip: Some(
+                external_ip.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(IpAddr::V6(external_ip)),
+            ),
+        })
+    }
+
+    /// Sends the request and waits for the response to the `opcode`, retransmitting with
+    /// exponential backoff.
+    ///
+    /// Returns the length of the response.
+    async fn request(
+        &self,
+        request: &[u8],
+        opcode: u8,
+        buf: &mut [u8],
+    ) -> Result<usize, NatPmpError> {
+        let mut timeout = INITIAL_REQUEST_TIMEOUT;
+        for _ in 0..self.max_attempts {
+            self.socket.send(request).await?;
+
+            let deadline = tokio::time::Instant::now() + timeout;
+            loop {
+                match tokio::time::timeout_at(deadline, self.socket.recv(buf)).await {
+                    Ok(Ok(len)) => {
+                        // ignore stale responses to other requests
+                        if len >= 4 && buf[1] == RESPONSE_FLAG | opcode {
+                            return Ok(len)
+                        }
+                    }
+                    Ok(Err(err)) => return Err(err.into()),
+                    Err(_) => break,
+                }
+            }
+
+            timeout *= 2;
+        }
+        Err(NatPmpError::Timeout)
+    }
+}
+
+/// Keeps a set of port mappings alive for as long as it runs.
+#[derive(Debug)]
+pub struct PortMapper {
+    client: NatPmpClient,
+    /// The ports to map, external ports are requested to be equal to the internal ones.
+    ports: Vec<(MappingProtocol, u16)>,
+    lifetime: Duration,
+    /// The currently active mappings.
+    active: Vec<ActiveMapping>,
+}
+
+/// A mapping held by the gateway until it expires or is removed.
+#[derive(Debug)]
+struct ActiveMapping {
+    mapping: PortMapping,
+    expires_at: Instant,
+}
+
+// === impl ActiveMapping ===
+
+impl ActiveMapping {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at <= now
+    }
+
+    fn is_for(&self, protocol: MappingProtocol, port: u16) -> bool {
+        self.mapping.protocol == protocol && self.mapping.internal_port == port
+    }
+}
+
+// === impl PortMapper ===
+
+impl PortMapper {
+    /// Creates a new instance without any ports.
+    pub fn new(client: NatPmpClient) -> Self {
+        Self { client, ports: Vec::new(), lifetime: DEFAULT_MAPPING_LIFETIME, active: Vec::new() }
+    }
+
+    /// Adds a port that should be mapped.
+    pub fn with_port(mut self, protocol: MappingProtocol, port: u16) -> Self {
+        self.ports.push((protocol, port));
+        self
+    }
+
+    /// Sets the lifetime to request for the mappings.
+    pub fn with_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Returns the currently active mappings.
+    pub fn active_mappings(&self) -> impl Iterator<Item = &PortMapping> + '_ {
+        self.active.iter().map(|active| &active.mapping)
+    }
+
+    /// Requests or renews all mappings.
+    ///
+    /// A mapping that fails to renew is kept until its lease expires, since the gateway still
+    /// holds it.
+    pub async fn map_all(&mut self) {
+        let now = Instant::now();
+        self.active.retain(|active| !active.is_expired(now));
+
+        for (protocol, port) in self.ports.clone() {
+            match self.client.map_port(protocol, port, port, self.lifetime).await {
+                Ok(mapping) => {
+                    debug!(target: "net::nat", ?mapping, gateway=%self.client.gateway(), "Mapped port");
+                    let expires_at = Instant::now() + mapping.lifetime;
+                    self.active.retain(|active| !active.is_for(protocol, port));
+                    self.active.push(ActiveMapping { mapping, expires_at });
+                }
+                Err(err) => {
+                    debug!(target: "net::nat", ?err, ?protocol, port, gateway=%self.client.gateway(), "Failed to map port");
+                }
+            }
+        }
+    }
+
+    /// Removes all active mappings.
+    ///
+    /// Mappings that could not be removed stay active until their lease expires.
+    pub async fn remove_all(&mut self) {
+        let mut remaining = Vec::new();
+        for active in std::mem::take(&mut self.active) {
+            if active.is_expired(Instant::now()) {
+                continue
+            }
+            let mapping = active.mapping;
+            if let Err(err) =
+                self.client.remove_mapping(mapping.protocol, mapping.internal_port).await
+            {
+                debug!(target: "net::nat", ?err, ?mapping, "Failed to remove port mapping");
+                remaining.push(active);
+            }
+        }
+        self.active = remaining;
+    }
+
+    /// The interval after which the mappings are renewed: half of the shortest granted lifetime.
+    fn renew_interval(&self) -> Duration {
+        let lifetime = self
+            .active
+            .iter()
+            .map(|active| active.mapping.lifetime)
+            .min()
+            .unwrap_or(self.lifetime)
+            .min(self.lifetime);
+        (lifetime / 2).max(Duration::from_secs(1))
+    }
+
+    /// Maps all ports and renews them until the `shutdown` future resolves, after which the
+    /// mappings are removed.
+    pub async fn run_until_shutdown<F>(mut self, shutdown: F)
+    where
+        F: Future<Output = ()>,
+    {
+        tokio::pin!(shutdown);
+        self.map_all().await;
+
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(self.renew_interval()) => {
+                    self.map_all().await;
+                }
+                _ = &mut shutdown => break,
+            }
+        }
+
+        self.remove_all().await;
+    }
+}
+
+/// Returns the default IPv4 gateway of this host, if it can be determined.
+#[cfg(target_os = "linux")]
+pub fn default_gateway() -> Option<IpAddr> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+    routes.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let destination = fields.nth(1)?;
+        let gateway = fields.next()?;
+        if destination != "00000000" {
+            return None
+        }
+        // the address is printed in host byte order
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        Some(IpAddr::V4(Ipv4Addr::from(gateway.to_ne_bytes())))
+    })
+}
+
+/// Returns the default IPv4 gateway of this host, if it can be determined.
+#[cfg(not(target_os = "linux"))]
+pub fn default_gateway() -> Option<IpAddr> {
+    None
+}
+
+/// Checks the result code of a NAT-PMP response.
+fn check_nat_pmp_result(response: &[u8]) -> Result<(), NatPmpError> {
+    if response.len() < 4 || response[0] != NAT_PMP_VERSION {
+        return Err(NatPmpError::InvalidResponse)
+    }
+    match u16::from_be_bytes([response[2], response[3]]) {
+        RESULT_SUCCESS => Ok(()),
+        RESULT_UNSUPPORTED_VERSION => Err(NatPmpError::UnsupportedVersion),
+        code => Err(NatPmpError::ResultCode(code)),
+    }
+}
+
+/// PCP encodes all addresses as IPv6, with IPv4 addresses mapped.
+fn to_ipv6(ip: IpAddr) -> Ipv6Addr {
+    match ip {
+        IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+        IpAddr::V6(ip) => ip,
+    }
+}
+
+fn lifetime_secs(lifetime: Duration) -> u32 {
+    lifetime.as_secs().try_into().unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXTERNAL_IP: Ipv4Addr = Ipv4Addr::new(203, 0, 113, 1);
+
+    /// Spawns a mock gateway that only speaks NAT-PMP, or PCP if `pcp` is set.
+    ///
+    /// Returns the address of the gateway and a receiver for the (protocol, port, lifetime) of
+    /// all received mapping requests.
+    async fn spawn_gateway(
+        pcp: bool,
+    ) -> (SocketAddr, tokio::sync::mpsc::UnboundedReceiver<(u8, u16, u32)>) {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1100];
+            loop {
+                let (len, from) = socket.recv_from(&mut buf).await.unwrap();
+                let request = &buf[..len];
+                let mut response = Vec::new();
+                match (request[0], request[1]) {
+                    (PCP_VERSION, PCP_OP_MAP) if pcp => {
+                        let port = u16::from_be_bytes([request[40], request[41]]);
+                        let lifetime = u32::from_be_bytes(request[4..8].try_into().unwrap());
+                        let _ = tx.send((request[36], port, lifetime));
+                        response.extend_from_slice(&[
+                            PCP_VERSION,
+                            RESPONSE_FLAG | PCP_OP_MAP,
+                            0,
+                            0,
+                        ]);
+                        response.extend_from_slice(&request[4..8]);
+                        response.extend_from_slice(&[0u8; 16]);
+                        response.extend_from_slice(&request[24..44]);
+                        response.extend_from_slice(&EXTERNAL_IP.to_ipv6_mapped().octets());
+                    }
+                    (PCP_VERSION, op) => {
+                        response.extend_from_slice(&[NAT_PMP_VERSION, RESPONSE_FLAG | op]);
+                        response.extend_from_slice(&RESULT_UNSUPPORTED_VERSION.to_be_bytes());
+                        response.extend_from_slice(&[0u8; 4]);
+                    }
+                    (NAT_PMP_VERSION, NAT_PMP_OP_EXTERNAL_ADDRESS) => {
+                        response.extend_from_slice(&[NAT_PMP_VERSION, RESPONSE_FLAG, 0, 0]);
+                        response.extend_from_slice(&[0u8; 4]);
+                        response.extend_from_slice(&EXTERNAL_IP.octets());
+                    }
+                    (NAT_PMP_VERSION, op) => {
+                        let port = u16::from_be_bytes([request[4], request[5]]);
+                        let lifetime = u32::from_be_bytes(request[8..12].try_into().unwrap());
+                        let _ = tx.send((op, port, lifetime));
+                        response.extend_from_slice(&[NAT_PMP_VERSION, RESPONSE_FLAG | op, 0, 0]);
+                        response.extend_from_slice(&[0u8; 4]);
+                        response.extend_from_slice(&request[4..12]);
+                    }
+                    _ => continue,
+                }
+                socket.send_to(&response, from).await.unwrap();
+            }
+        });
+
+        (addr, rx)
+    }
+
+    #[tokio::test]
+    async fn nat_pmp_external_address() {
+        let (gateway, _) = spawn_gateway(false).await;
+        let client = NatPmpClient::new(gateway).await.unwrap();
+        assert_eq!(client.external_address().await.unwrap(), IpAddr::V4(EXTERNAL_IP));
+    }
+
+    #[tokio::test]
+    async fn nat_pmp_fallback_map_and_remove() {
+        let (gateway, mut requests) = spawn_gateway(false).await;
+        let mut client = NatPmpClient::new(gateway).await.unwrap();
+
+        let mapping =
+            client.map_port(MappingProtocol::Tcp, 30303, 30303, Duration::from_secs(60)).await;
+        let mapping = mapping.unwrap();
+        assert_eq!(client.version, Some(GatewayVersion::NatPmp));
+        assert_eq!(mapping.external_port, 30303);
+        assert_eq!(mapping.lifetime, Duration::from_secs(60));
+        assert_eq!(requests.recv().await, Some((2, 30303, 60)));
+
+        client.remove_mapping(MappingProtocol::Tcp, 30303).await.unwrap();
+        assert_eq!(requests.recv().await, Some((2, 30303, 0)));
+    }
+
+    #[tokio::test]
+    async fn pcp_map() {
+        let (gateway, mut requests) = spawn_gateway(true).await;
+        let mut client = NatPmpClient::new(gateway).await.unwrap();
+
+        let mapping =
+            client.map_port(MappingProtocol::Udp, 30303, 30303, Duration::from_secs(60)).await;
+        let mapping = mapping.unwrap();
+        assert_eq!(client.version, Some(GatewayVersion::Pcp));
+        assert_eq!(mapping.external_ip, Some(IpAddr::V4(EXTERNAL_IP)));
+        assert_eq!(mapping.external_port, 30303);
+        assert_eq!(requests.recv().await, Some((17, 30303, 60)));
+    }
+
+    #[tokio::test]
+    async fn port_mapper_renews_and_removes() {
+        let (gateway, mut requests) = spawn_gateway(false).await;
+        let client = NatPmpClient::new(gateway).await.unwrap();
+        let mapper = PortMapper::new(client)
+            .with_port(MappingProtocol::Tcp, 30303)
+            .with_port(MappingProtocol::Udp, 30304)
+            .with_lifetime(Duration::from_secs(2));
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let mapper = tokio::spawn(mapper.run_until_shutdown(async move {
+            let _ = shutdown_rx.await;
+        }));
+
+        // initial mapping and renewal after half the lifetime
+        for _ in 0..2 {
+            assert_eq!(requests.recv().await, Some((2, 30303, 2)));
+            assert_eq!(requests.recv().await, Some((1, 30304, 2)));
+        }
+
+        shutdown_tx.send(()).unwrap();
+        mapper.await.unwrap();
+
+        let mut removed = vec![requests.recv().await.unwrap(), requests.recv().await.unwrap()];
+        removed.sort();
+        assert_eq!(removed, vec![(1, 30304, 0), (2, 30303, 0)]);
+    }
+
+    #[tokio::test]
+    async fn port_mapper_keeps_mappings_that_fail_to_renew() {
+        // a gateway that never responds
+        let gateway = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let client =
+            NatPmpClient::new(gateway.local_addr().unwrap()).await.unwrap().with_max_attempts(1);
+        let mut mapper = PortMapper::new(client).with_port(MappingProtocol::Tcp, 30303);
+
+        let mapping = PortMapping {
+            protocol: MappingProtocol::Tcp,
+            internal_port: 30303,
+            external_port: 30303,
+            lifetime: Duration::from_secs(60),
+            external_ip: None,
+        };
+        mapper
+            .active
+            .push(ActiveMapping { mapping, expires_at: Instant::now() + mapping.lifetime });
+
+        mapper.map_all().await;
+        assert_eq!(mapper.active_mappings().collect::<Vec<_>>(), vec![&mapping]);
+
+        mapper.remove_all().await;
+        assert_eq!(mapper.active_mappings().collect::<Vec<_>>(), vec![&mapping]);
+
+        // expired mappings are dropped
+        mapper.active[0].expires_at = Instant::now();
+        mapper.map_all().await;
+        assert_eq!(mapper.active_mappings().count(), 0);
+    }
+}
@@ -22,7 +22,8 @@ use async_trait::async_trait;
 use reth_eth_wire::{DisconnectReason, EthVersion, Status};
 use reth_primitives::{NodeRecord, PeerId};
 use reth_rpc_types::NetworkStatus;
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, pin::Pin, sync::Arc};
+use tokio_stream::Stream;
 
 pub use error::NetworkError;
 pub use reputation::{Reputation, ReputationChangeKind};
@@ -99,6 +100,55 @@ pub trait Peers: PeersInfo {
 
     /// Get the reputation of a peer.
     async fn reputation_by_id(&self, peer_id: PeerId) -> Result<Option<Reputation>, NetworkError>;
+
+    /// Returns a new stream of [PeerEvent]s, starting with the next event.
+    ///
+    /// By default, the stream never yields any events.
+    fn peer_events(&self) -> PeerEventStream {
+        Box::pin(tokio_stream::empty())
+    }
+}
+
+/// A stream of [PeerEvent]s.
+pub type PeerEventStream = Pin<Box<dyn Stream<Item = PeerEvent> + Send>>;
+
+/// Events about the peers of the network.
+#[derive(Debug, Clone)]
+pub enum PeerEvent {
+    /// A session with the peer was established.
+    SessionEstablished {
+        /// The identifier of the remote peer
+        peer_id: PeerId,
+        /// The peer's address we're connected to
+        remote_addr: SocketAddr,
+        /// The client's name and version
+        client_version: Arc<String>,
+        /// The negotiated eth version.
+        version: EthVersion,
+    },
+    /// The session with the peer was closed.
+    SessionClosed {
+        /// The identifier of the remote peer
+        peer_id: PeerId,
+        /// Why the session was closed, if known.
+        reason: Option<DisconnectReason>,
+    },
+    /// The peer was added to the peer set.
+    PeerAdded(PeerId),
+    /// The peer was removed from the peer set.
+    PeerRemoved(PeerId),
+}
+
+impl PeerEvent {
+    /// Returns the id of the peer the event is about.
+    pub fn peer_id(&self) -> PeerId {
+        match self {
+            PeerEvent::SessionEstablished { peer_id, .. } |
+            PeerEvent::SessionClosed { peer_id, .. } |
+            PeerEvent::PeerAdded(peer_id) |
+            PeerEvent::PeerRemoved(peer_id) => *peer_id,
+        }
+    }
 }
 
 /// Represents the kind of peer
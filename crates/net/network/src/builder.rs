@@ -1,8 +1,9 @@
 //! Builder support for configuring the entire setup.
 
 use crate::{
-    eth_requests::EthRequestHandler, transactions::TransactionsManager, NetworkHandle,
-    NetworkManager,
+    eth_requests::{EthRequestHandler, ServingPolicy},
+    transactions::{PropagationPolicy, TransactionsManager},
+    NetworkHandle, NetworkManager,
 };
 use reth_transaction_pool::TransactionPool;
 use tokio::sync::mpsc;
@@ -38,12 +39,22 @@ impl<C, Tx, Eth> NetworkBuilder<C, Tx, Eth> {
     pub fn transactions<Pool: TransactionPool>(
         self,
         pool: Pool,
+    ) -> NetworkBuilder<C, TransactionsManager<Pool>, Eth> {
+        self.transactions_with_policy(pool, PropagationPolicy::default())
+    }
+
+    /// Creates a new [`TransactionsManager`] that propagates transactions according to the given
+    /// [`PropagationPolicy`] and wires it to the network.
+    pub fn transactions_with_policy<Pool: TransactionPool>(
+        self,
+        pool: Pool,
+        policy: PropagationPolicy,
     ) -> NetworkBuilder<C, TransactionsManager<Pool>, Eth> {
         let NetworkBuilder { mut network, request_handler, .. } = self;
         let (tx, rx) = mpsc::unbounded_channel();
         network.set_transactions(tx);
         let handle = network.handle().clone();
-        let transactions = TransactionsManager::new(handle, pool, rx);
+        let transactions = TransactionsManager::with_policy(handle, pool, rx, policy);
         NetworkBuilder { network, request_handler, transactions }
     }
 
@@ -51,12 +62,24 @@ impl<C, Tx, Eth> NetworkBuilder<C, Tx, Eth> {
     pub fn request_handler<Client>(
         self,
         client: Client,
+    ) -> NetworkBuilder<C, Tx, EthRequestHandler<Client>> {
+        self.request_handler_with_policy(client, ServingPolicy::default())
+    }
+
+    /// Creates a new [`EthRequestHandler`] that serves requests according to the given
+    /// [`ServingPolicy`] and wires it to the network.
+    pub fn request_handler_with_policy<Client>(
+        self,
+        client: Client,
+        policy: ServingPolicy,
     ) -> NetworkBuilder<C, Tx, EthRequestHandler<Client>> {
         let NetworkBuilder { mut network, transactions, .. } = self;
         let (tx, rx) = mpsc::channel(ETH_REQUEST_CHANNEL_CAPACITY);
         network.set_eth_request_handler(tx);
-        let peers = network.handle().peers_handle().clone();
-        let request_handler = EthRequestHandler::new(client, peers, rx);
+        let handle = network.handle().clone();
+        let peers = handle.peers_handle().clone();
+        let request_handler =
+            EthRequestHandler::new(client, peers, rx).with_policy(policy).with_sync_state(handle);
         NetworkBuilder { network, request_handler, transactions }
     }
 }
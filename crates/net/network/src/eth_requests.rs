@@ -1,23 +1,30 @@
 //! Blocks/Headers management for the p2p network.
 
 use crate::{metrics::EthRequestHandlerMetrics, peers::PeersHandle};
-use futures::StreamExt;
+use futures::{stream::FuturesUnordered, StreamExt};
 use reth_eth_wire::{
     BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders, GetNodeData, GetReceipts, NodeData,
     Receipts,
 };
-use reth_interfaces::p2p::error::RequestResult;
-use reth_primitives::{BlockBody, BlockHashOrNumber, Header, HeadersDirection, PeerId};
-use reth_provider::{BlockReader, HeaderProvider, ReceiptProvider};
+use reth_interfaces::{p2p::error::RequestResult, sync::SyncStateProvider};
+use reth_primitives::{BlockBody, BlockHashOrNumber, Bytes, Header, HeadersDirection, PeerId};
+use reth_provider::{BlockReader, BytecodeReader, HeaderProvider, ReceiptProvider};
 use std::{
     borrow::Borrow,
+    collections::{HashMap, VecDeque},
     future::Future,
     hash::Hash,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::{mpsc::Receiver, oneshot},
+    task::JoinHandle,
 };
-use tokio::sync::{mpsc::Receiver, oneshot};
 use tokio_stream::wrappers::ReceiverStream;
+use tracing::trace;
 
 // Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/eth/handler.go#L34-L56>
 
@@ -31,6 +38,11 @@ const MAX_RECEIPTS_SERVE: usize = 1024;
 /// Used to limit lookups.
 const MAX_HEADERS_SERVE: usize = 1024;
 
+/// Maximum number of node data entries to serve.
+///
+/// Used to limit lookups.
+const MAX_NODE_DATA_SERVE: usize = 1024;
+
 /// Maximum number of block headers to serve.
 ///
 /// Used to limit lookups. With 24KB block sizes nowadays, the practical limit will always be
@@ -50,21 +62,57 @@ const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
 /// Estimated size in bytes of an RLP encoded header.
 const APPROX_HEADER_SIZE: usize = 500;
 
+/// Default maximum number of requests that are served concurrently.
+///
+/// Each served request is a series of database lookups that runs on the blocking pool.
+pub const DEFAULT_MAX_CONCURRENT_READS: usize = 4;
+
+/// Maximum number of accepted requests that are queued until a read slot becomes available.
+///
+/// Once reached, no new requests are read from the network until the queue drains, which applies
+/// backpressure to the bounded request channel.
+const MAX_QUEUED_REQUESTS: usize = 256;
+
+/// Number of tracked peer budgets above which budgets of peers whose interval is over are
+/// forgotten.
+const MAX_TRACKED_PEER_BUDGETS: usize = 1024;
+
+/// Maximum number of requests of trusted peers that are served in a row while requests of other
+/// peers are waiting.
+///
+/// This reserves a share of the read slots for other peers, so trusted peers can't starve them.
+const MAX_CONSECUTIVE_TRUSTED_READS: usize = 3;
+
 /// Manages eth related requests on top of the p2p network.
 ///
 /// This can be spawned to another task and is supposed to be run as background service.
+///
+/// Which requests are served and how many is configured by the [`ServingPolicy`].
 #[must_use = "Manager does nothing unless polled."]
 pub struct EthRequestHandler<C> {
     /// The client type that can interact with the chain.
     client: C,
-    /// Used for reporting peers.
-    #[allow(unused)]
+    /// Used to look up trusted peers.
     // TODO use to report spammers
     peers: PeersHandle,
     /// Incoming request from the [NetworkManager](crate::NetworkManager).
     incoming_requests: ReceiverStream<IncomingEthRequest>,
+    /// Configures which requests are served.
+    policy: ServingPolicy,
+    /// Used to check whether the node is initially syncing.
+    sync_state: Option<Box<dyn SyncStateProvider>>,
+    /// Request budgets of the peers that sent requests within the current interval.
+    budgets: HashMap<PeerId, PeerBudget>,
+    /// Accepted requests of trusted peers, these are preferred.
+    queued_trusted: VecDeque<IncomingEthRequest>,
+    /// Accepted requests of all other peers.
+    queued: VecDeque<IncomingEthRequest>,
+    /// Number of requests of trusted peers served since the last request of another peer.
+    consecutive_trusted_reads: usize,
+    /// Requests that are currently served on the blocking pool.
+    inflight_reads: FuturesUnordered<JoinHandle<()>>,
     /// Metrics for the eth request handler.
-    metrics: EthRequestHandlerMetrics,
+    metrics: Arc<EthRequestHandlerMetrics>,
 }
 
 // === impl EthRequestHandler ===
@@ -72,165 +120,323 @@ impl<C> EthRequestHandler<C> {
     /// Create a new instance
     pub fn new(client: C, peers: PeersHandle, incoming: Receiver<IncomingEthRequest>) -> Self {
         let metrics = Default::default();
-        Self { client, peers, incoming_requests: ReceiverStream::new(incoming), metrics }
+        Self {
+            client,
+            peers,
+            incoming_requests: ReceiverStream::new(incoming),
+            policy: Default::default(),
+            sync_state: None,
+            budgets: Default::default(),
+            queued_trusted: Default::default(),
+            queued: Default::default(),
+            consecutive_trusted_reads: 0,
+            inflight_reads: Default::default(),
+            metrics,
+        }
+    }
+
+    /// Sets the [`ServingPolicy`] that decides which requests are served.
+    pub fn with_policy(mut self, policy: ServingPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the type that tracks whether the node is syncing.
+    ///
+    /// Required for [`ServingPolicy::serve_while_initially_syncing`] to take effect.
+    pub fn with_sync_state(mut self, sync_state: impl SyncStateProvider + 'static) -> Self {
+        self.sync_state = Some(Box::new(sync_state));
+        self
+    }
+
+    /// Returns the configured [`ServingPolicy`].
+    pub fn policy(&self) -> &ServingPolicy {
+        &self.policy
+    }
+
+    /// Returns the number of accepted requests that wait for a read slot.
+    fn num_queued(&self) -> usize {
+        self.queued_trusted.len() + self.queued.len()
+    }
+
+    /// Returns `true` if requests are refused because the node is still initially syncing.
+    fn is_refusing_while_syncing(&self) -> bool {
+        !self.policy.serve_while_initially_syncing &&
+            self.sync_state.as_ref().map_or(false, |state| state.is_initially_syncing())
+    }
+
+    /// Consumes one request of the peer's budget, returns `false` if the budget is exhausted.
+    fn try_consume_budget(&mut self, peer_id: PeerId) -> bool {
+        let Some(budget) = self.policy.peer_budget else { return true };
+        let now = Instant::now();
+        if self.budgets.len() > MAX_TRACKED_PEER_BUDGETS {
+            // forget peers whose interval is over, they start with a fresh budget anyway
+            self.budgets
+                .retain(|_, peer| now.duration_since(peer.interval_start) < budget.interval);
+        }
+        self.budgets.entry(peer_id).or_insert_with(|| PeerBudget::new(budget, now)).try_consume(now)
+    }
+
+    /// Decides whether the request is served, refused requests are answered right away with an
+    /// empty response.
+    fn on_request(&mut self, request: IncomingEthRequest) {
+        match &request {
+            IncomingEthRequest::GetBlockHeaders { .. } => {
+                self.metrics.received_headers_requests.increment(1)
+            }
+            IncomingEthRequest::GetBlockBodies { .. } => {
+                self.metrics.received_bodies_requests.increment(1)
+            }
+            IncomingEthRequest::GetNodeData { .. } => {
+                self.metrics.received_node_data_requests.increment(1)
+            }
+            IncomingEthRequest::GetReceipts { .. } => {
+                self.metrics.received_receipts_requests.increment(1)
+            }
+        }
+
+        let peer_id = request.peer_id();
+        let trusted = self.policy.prefer_trusted_peers && self.peers.is_trusted(&peer_id);
+        if !self.policy.role.serves(&request) ||
+            self.is_refusing_while_syncing() ||
+            (!trusted && !self.try_consume_budget(peer_id))
+        {
+            trace!(target: "net::eth", ?peer_id, "refusing request");
+            self.metrics.refused_requests.increment(1);
+            request.respond_empty();
+            return
+        }
+
+        if trusted {
+            self.queued_trusted.push_back(request);
+        } else {
+            self.queued.push_back(request);
+        }
+    }
+
+    /// Returns the next queued request to serve.
+    ///
+    /// Requests of trusted peers are preferred, but after [`MAX_CONSECUTIVE_TRUSTED_READS`] of
+    /// them a waiting request of another peer is served.
+    fn next_queued(&mut self) -> Option<IncomingEthRequest> {
+        if self.consecutive_trusted_reads >= MAX_CONSECUTIVE_TRUSTED_READS {
+            if let Some(request) = self.queued.pop_front() {
+                self.consecutive_trusted_reads = 0;
+                return Some(request)
+            }
+        }
+        if let Some(request) = self.queued_trusted.pop_front() {
+            self.consecutive_trusted_reads += 1;
+            return Some(request)
+        }
+        self.consecutive_trusted_reads = 0;
+        self.queued.pop_front()
     }
 }
 
 impl<C> EthRequestHandler<C>
 where
-    C: BlockReader + HeaderProvider + ReceiptProvider,
+    C: BlockReader + HeaderProvider + ReceiptProvider + BytecodeReader + Clone + 'static,
 {
-    /// Returns the list of requested headers
-    fn get_headers_response(&self, request: GetBlockHeaders) -> Vec<Header> {
-        let GetBlockHeaders { start_block, limit, skip, direction } = request;
-
-        let mut headers = Vec::new();
-
-        let mut block: BlockHashOrNumber = match start_block {
-            BlockHashOrNumber::Hash(start) => start.into(),
-            BlockHashOrNumber::Number(num) => {
-                let Some(hash) = self.client.block_hash(num).unwrap_or_default() else {
-                    return headers
-                };
-                hash.into()
-            }
-        };
+    /// Serves queued requests on the blocking pool until all read slots are occupied, requests
+    /// of trusted peers first, see [`EthRequestHandler::next_queued`].
+    fn spawn_reads(&mut self) {
+        while self.inflight_reads.len() < self.policy.max_concurrent_reads.max(1) {
+            let Some(request) = self.next_queued() else { break };
+            let client = self.client.clone();
+            let metrics = Arc::clone(&self.metrics);
+            self.inflight_reads.push(tokio::task::spawn_blocking(move || {
+                serve_request(&client, &metrics, request)
+            }));
+        }
+    }
+}
 
-        let skip = skip as u64;
-        let mut total_bytes = APPROX_HEADER_SIZE;
+/// Serves the request and sends the response.
+fn serve_request<C>(client: &C, metrics: &EthRequestHandlerMetrics, request: IncomingEthRequest)
+where
+    C: BlockReader + HeaderProvider + ReceiptProvider + BytecodeReader,
+{
+    match request {
+        IncomingEthRequest::GetBlockHeaders { request, response, .. } => {
+            let _ = response.send(Ok(BlockHeaders(get_headers_response(client, request))));
+        }
+        IncomingEthRequest::GetBlockBodies { request, response, .. } => {
+            let _ = response.send(Ok(get_bodies_response(client, request)));
+        }
+        IncomingEthRequest::GetNodeData { request, response, .. } => {
+            let node_data = get_node_data_response(client, request);
+            metrics.served_node_data_entries.increment(node_data.0.len() as u64);
+            let _ = response.send(Ok(node_data));
+        }
+        IncomingEthRequest::GetReceipts { request, response, .. } => {
+            let _ = response.send(Ok(get_receipts_response(client, request)));
+        }
+    }
+}
 
-        for _ in 0..limit {
-            if let Some(header) = self.client.header_by_hash_or_number(block).unwrap_or_default() {
-                match direction {
-                    HeadersDirection::Rising => {
-                        if let Some(next) = (header.number + 1).checked_add(skip) {
+/// Returns the list of requested headers
+fn get_headers_response<C>(client: &C, request: GetBlockHeaders) -> Vec<Header>
+where
+    C: BlockReader + HeaderProvider,
+{
+    let GetBlockHeaders { start_block, limit, skip, direction } = request;
+
+    let mut headers = Vec::new();
+
+    let mut block: BlockHashOrNumber = match start_block {
+        BlockHashOrNumber::Hash(start) => start.into(),
+        BlockHashOrNumber::Number(num) => {
+            let Some(hash) = client.block_hash(num).unwrap_or_default() else { return headers };
+            hash.into()
+        }
+    };
+
+    let skip = skip as u64;
+    let mut total_bytes = APPROX_HEADER_SIZE;
+
+    for _ in 0..limit {
+        if let Some(header) = client.header_by_hash_or_number(block).unwrap_or_default() {
+            match direction {
+                HeadersDirection::Rising => {
+                    if let Some(next) = (header.number + 1).checked_add(skip) {
+                        block = next.into()
+                    } else {
+                        break
+                    }
+                }
+                HeadersDirection::Falling => {
+                    if skip > 0 {
+                        // prevent under flows for block.number == 0 and `block.number - skip <
+                        // 0`
+                        if let Some(next) =
+                            header.number.checked_sub(1).and_then(|num| num.checked_sub(skip))
+                        {
                             block = next.into()
                         } else {
                             break
                         }
-                    }
-                    HeadersDirection::Falling => {
-                        if skip > 0 {
-                            // prevent under flows for block.number == 0 and `block.number - skip <
-                            // 0`
-                            if let Some(next) =
-                                header.number.checked_sub(1).and_then(|num| num.checked_sub(skip))
-                            {
-                                block = next.into()
-                            } else {
-                                break
-                            }
-                        } else {
-                            block = header.parent_hash.into()
-                        }
+                    } else {
+                        block = header.parent_hash.into()
                     }
                 }
+            }
 
-                headers.push(header);
+            headers.push(header);
 
-                if headers.len() >= MAX_HEADERS_SERVE {
-                    break
-                }
+            if headers.len() >= MAX_HEADERS_SERVE {
+                break
+            }
 
-                total_bytes += APPROX_HEADER_SIZE;
+            total_bytes += APPROX_HEADER_SIZE;
 
-                if total_bytes > SOFT_RESPONSE_LIMIT {
-                    break
-                }
-            } else {
+            if total_bytes > SOFT_RESPONSE_LIMIT {
                 break
             }
+        } else {
+            break
         }
-
-        headers
     }
 
-    fn on_headers_request(
-        &mut self,
-        _peer_id: PeerId,
-        request: GetBlockHeaders,
-        response: oneshot::Sender<RequestResult<BlockHeaders>>,
-    ) {
-        self.metrics.received_headers_requests.increment(1);
-        let headers = self.get_headers_response(request);
-        let _ = response.send(Ok(BlockHeaders(headers)));
-    }
+    headers
+}
 
-    fn on_bodies_request(
-        &mut self,
-        _peer_id: PeerId,
-        request: GetBlockBodies,
-        response: oneshot::Sender<RequestResult<BlockBodies>>,
-    ) {
-        self.metrics.received_bodies_requests.increment(1);
-        let mut bodies = Vec::new();
+/// Returns the requested block bodies.
+fn get_bodies_response<C: BlockReader>(client: &C, request: GetBlockBodies) -> BlockBodies {
+    let mut bodies = Vec::new();
 
-        let mut total_bytes = APPROX_BODY_SIZE;
+    let mut total_bytes = APPROX_BODY_SIZE;
 
-        for hash in request.0 {
-            if let Some(block) = self.client.block_by_hash(hash).unwrap_or_default() {
-                let body = BlockBody {
-                    transactions: block.body,
-                    ommers: block.ommers,
-                    withdrawals: block.withdrawals,
-                };
+    for hash in request.0 {
+        if let Some(block) = client.block_by_hash(hash).unwrap_or_default() {
+            let body = BlockBody {
+                transactions: block.body,
+                ommers: block.ommers,
+                withdrawals: block.withdrawals,
+            };
 
-                bodies.push(body);
+            bodies.push(body);
 
-                total_bytes += APPROX_BODY_SIZE;
+            total_bytes += APPROX_BODY_SIZE;
 
-                if total_bytes > SOFT_RESPONSE_LIMIT {
-                    break
-                }
+            if total_bytes > SOFT_RESPONSE_LIMIT {
+                break
+            }
 
-                if bodies.len() >= MAX_BODIES_SERVE {
-                    break
-                }
-            } else {
+            if bodies.len() >= MAX_BODIES_SERVE {
                 break
             }
+        } else {
+            break
         }
-
-        let _ = response.send(Ok(BlockBodies(bodies)));
     }
 
-    fn on_receipts_request(
-        &mut self,
-        _peer_id: PeerId,
-        request: GetReceipts,
-        response: oneshot::Sender<RequestResult<Receipts>>,
-    ) {
-        let mut receipts = Vec::new();
-
-        let mut total_bytes = APPROX_RECEIPT_SIZE;
-
-        for hash in request.0 {
-            if let Some(receipts_by_block) =
-                self.client.receipts_by_block(BlockHashOrNumber::Hash(hash)).unwrap_or_default()
-            {
-                receipts.push(
-                    receipts_by_block
-                        .into_iter()
-                        .map(|receipt| receipt.with_bloom())
-                        .collect::<Vec<_>>(),
-                );
-
-                total_bytes += APPROX_RECEIPT_SIZE;
-
-                if total_bytes > SOFT_RESPONSE_LIMIT {
-                    break
-                }
+    BlockBodies(bodies)
+}
 
-                if receipts.len() >= MAX_RECEIPTS_SERVE {
-                    break
-                }
-            } else {
+/// Returns the requested node data.
+///
+/// Only contract code can be looked up by hash: the account and storage tries are stored by
+/// path in compact form, so trie nodes can't be resolved from their hash and are skipped, as
+/// are all other unknown hashes.
+fn get_node_data_response<C: BytecodeReader>(client: &C, request: GetNodeData) -> NodeData {
+    let mut node_data = Vec::new();
+
+    let mut total_bytes = 0;
+
+    for hash in request.0 {
+        if let Some(bytecode) = client.bytecode_by_code_hash(hash).unwrap_or_default() {
+            let code: Bytes = bytecode.original_bytes().into();
+
+            total_bytes += code.len();
+
+            node_data.push(code);
+
+            if total_bytes > SOFT_RESPONSE_LIMIT {
+                break
+            }
+
+            if node_data.len() >= MAX_NODE_DATA_SERVE {
                 break
             }
         }
+    }
+
+    NodeData(node_data)
+}
+
+/// Returns the requested receipts.
+fn get_receipts_response<C: ReceiptProvider>(client: &C, request: GetReceipts) -> Receipts {
+    let mut receipts = Vec::new();
 
-        let _ = response.send(Ok(Receipts(receipts)));
+    let mut total_bytes = APPROX_RECEIPT_SIZE;
+
+    for hash in request.0 {
+        if let Some(receipts_by_block) =
+            client.receipts_by_block(BlockHashOrNumber::Hash(hash)).unwrap_or_default()
+        {
+            receipts.push(
+                receipts_by_block
+                    .into_iter()
+                    .map(|receipt| receipt.with_bloom())
+                    .collect::<Vec<_>>(),
+            );
+
+            total_bytes += APPROX_RECEIPT_SIZE;
+
+            if total_bytes > SOFT_RESPONSE_LIMIT {
+                break
+            }
+
+            if receipts.len() >= MAX_RECEIPTS_SERVE {
+                break
+            }
+        } else {
+            break
+        }
     }
+
+    Receipts(receipts)
 }
 
 /// An endless future.
@@ -238,7 +444,7 @@ where
 /// This should be spawned or used as part of `tokio::select!`.
 impl<C> Future for EthRequestHandler<C>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + BytecodeReader + Clone + Unpin + 'static,
 {
     type Output = ();
 
@@ -246,26 +452,203 @@ where
         let this = self.get_mut();
 
         loop {
-            match this.incoming_requests.poll_next_unpin(cx) {
-                Poll::Pending => return Poll::Pending,
-                Poll::Ready(None) => return Poll::Ready(()),
-                Poll::Ready(Some(incoming)) => match incoming {
-                    IncomingEthRequest::GetBlockHeaders { peer_id, request, response } => {
-                        this.on_headers_request(peer_id, request, response)
+            let mut terminated = false;
+
+            // read new requests, as long as there's room in the queue
+            while this.num_queued() < MAX_QUEUED_REQUESTS {
+                match this.incoming_requests.poll_next_unpin(cx) {
+                    Poll::Pending => break,
+                    Poll::Ready(None) => {
+                        terminated = true;
+                        break
                     }
-                    IncomingEthRequest::GetBlockBodies { peer_id, request, response } => {
-                        this.on_bodies_request(peer_id, request, response)
-                    }
-                    IncomingEthRequest::GetNodeData { .. } => {}
-                    IncomingEthRequest::GetReceipts { peer_id, request, response } => {
-                        this.on_receipts_request(peer_id, request, response)
-                    }
-                },
+                    Poll::Ready(Some(incoming)) => this.on_request(incoming),
+                }
+            }
+
+            this.spawn_reads();
+
+            // free the slots of finished reads
+            let mut freed_slots = false;
+            while let Poll::Ready(Some(_)) = this.inflight_reads.poll_next_unpin(cx) {
+                freed_slots = true;
+            }
+
+            if freed_slots {
+                continue
+            }
+
+            if terminated && this.inflight_reads.is_empty() && this.num_queued() == 0 {
+                return Poll::Ready(())
+            }
+
+            return Poll::Pending
+        }
+    }
+}
+
+/// Configures which `eth` requests the [`EthRequestHandler`] serves.
+///
+/// By default all requests of all peers are served.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ServingPolicy {
+    /// Which kinds of requests are served.
+    pub role: ServingRole,
+    /// Limits the number of requests a single peer can make within an interval.
+    ///
+    /// Requests beyond the budget are answered with an empty response.
+    pub peer_budget: Option<RequestBudget>,
+    /// Whether the trusted peers of the peer set are not subject to the budget and their requests
+    /// are preferred.
+    ///
+    /// Requests of other peers still get a share of the read slots.
+    pub prefer_trusted_peers: bool,
+    /// Whether requests are served while the node is initially syncing.
+    pub serve_while_initially_syncing: bool,
+    /// Maximum number of requests that are served concurrently.
+    pub max_concurrent_reads: usize,
+}
+
+// === impl ServingPolicy ===
+
+impl ServingPolicy {
+    /// Sets which kinds of requests are served.
+    pub fn with_role(mut self, role: ServingRole) -> Self {
+        self.role = role;
+        self
+    }
+
+    /// Sets the per peer request budget.
+    pub fn with_peer_budget(mut self, budget: RequestBudget) -> Self {
+        self.peer_budget = Some(budget);
+        self
+    }
+
+    /// Sets whether trusted peers are exempt from the budget and their requests are preferred.
+    pub fn with_prefer_trusted_peers(mut self, prefer: bool) -> Self {
+        self.prefer_trusted_peers = prefer;
+        self
+    }
+
+    /// Sets whether requests are served while the node is initially syncing.
+    pub fn with_serve_while_initially_syncing(mut self, serve: bool) -> Self {
+        self.serve_while_initially_syncing = serve;
+        self
+    }
+
+    /// Sets the maximum number of requests that are served concurrently.
+    pub fn with_max_concurrent_reads(mut self, max_concurrent_reads: usize) -> Self {
+        self.max_concurrent_reads = max_concurrent_reads;
+        self
+    }
+}
+
+impl Default for ServingPolicy {
+    fn default() -> Self {
+        Self {
+            role: ServingRole::Full,
+            peer_budget: None,
+            prefer_trusted_peers: true,
+            serve_while_initially_syncing: true,
+            max_concurrent_reads: DEFAULT_MAX_CONCURRENT_READS,
+        }
+    }
+}
+
+/// The kinds of requests a node serves to its peers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ServingRole {
+    /// Serve all requests.
+    #[default]
+    Full,
+    /// Only serve block headers.
+    HeadersOnly,
+    /// Serve block headers and receipts, for example as a history provider.
+    HeadersAndReceipts,
+}
+
+// === impl ServingRole ===
+
+impl ServingRole {
+    /// Returns `true` if the request is served in this role.
+    pub fn serves(&self, request: &IncomingEthRequest) -> bool {
+        match request {
+            IncomingEthRequest::GetBlockHeaders { .. } => true,
+            IncomingEthRequest::GetReceipts { .. } => {
+                matches!(self, ServingRole::Full | ServingRole::HeadersAndReceipts)
+            }
+            IncomingEthRequest::GetBlockBodies { .. } | IncomingEthRequest::GetNodeData { .. } => {
+                matches!(self, ServingRole::Full)
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for ServingRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(ServingRole::Full),
+            "headers" => Ok(ServingRole::HeadersOnly),
+            "receipts" => Ok(ServingRole::HeadersAndReceipts),
+            _ => {
+                Err(format!("unknown serving role: {s}, expected one of: full, headers, receipts"))
             }
         }
     }
 }
 
+/// Limits the number of requests a single peer can make within an interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestBudget {
+    /// Maximum number of requests served per interval.
+    pub max_requests: usize,
+    /// The length of the interval.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub interval: Duration,
+}
+
+impl RequestBudget {
+    /// Creates a budget of `max_requests` per second.
+    pub fn per_second(max_requests: usize) -> Self {
+        Self { max_requests, interval: Duration::from_secs(1) }
+    }
+}
+
+/// Tracks the remaining request budget of a peer for the current interval.
+#[derive(Debug)]
+struct PeerBudget {
+    budget: RequestBudget,
+    remaining: usize,
+    interval_start: Instant,
+}
+
+// === impl PeerBudget ===
+
+impl PeerBudget {
+    fn new(budget: RequestBudget, now: Instant) -> Self {
+        Self { budget, remaining: budget.max_requests, interval_start: now }
+    }
+
+    /// Consumes one request of the budget, returns false if the budget is exhausted.
+    fn try_consume(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.interval_start) >= self.budget.interval {
+            self.interval_start = now;
+            self.remaining = self.budget.max_requests;
+        }
+        if self.remaining == 0 {
+            return false
+        }
+        self.remaining -= 1;
+        true
+    }
+}
+
 /// Represents a handled [`GetBlockHeaders`] requests
 ///
 /// This is the key type for spam detection cache. The counter is ignored during `PartialEq` and
@@ -319,3 +702,174 @@ pub enum IncomingEthRequest {
         response: oneshot::Sender<RequestResult<Receipts>>,
     },
 }
+
+// === impl IncomingEthRequest ===
+
+impl IncomingEthRequest {
+    /// Returns the peer that sent the request.
+    pub fn peer_id(&self) -> PeerId {
+        match self {
+            IncomingEthRequest::GetBlockHeaders { peer_id, .. } |
+            IncomingEthRequest::GetBlockBodies { peer_id, .. } |
+            IncomingEthRequest::GetNodeData { peer_id, .. } |
+            IncomingEthRequest::GetReceipts { peer_id, .. } => *peer_id,
+        }
+    }
+
+    /// Answers the request with an empty response, as if none of the requested data was found.
+    fn respond_empty(self) {
+        match self {
+            IncomingEthRequest::GetBlockHeaders { response, .. } => {
+                let _ = response.send(Ok(BlockHeaders::default()));
+            }
+            IncomingEthRequest::GetBlockBodies { response, .. } => {
+                let _ = response.send(Ok(BlockBodies::default()));
+            }
+            IncomingEthRequest::GetNodeData { response, .. } => {
+                let _ = response.send(Ok(NodeData::default()));
+            }
+            IncomingEthRequest::GetReceipts { response, .. } => {
+                let _ = response.send(Ok(Receipts::default()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peers::{PeersConfig, PeersManager};
+    use reth_primitives::{keccak256, Address, NodeRecord, H256, U256};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use std::{collections::HashSet, net::SocketAddr};
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_serve_node_data_bytecode() {
+        let provider = MockEthProvider::default();
+        let code = Bytes::from(vec![0x60, 0x00, 0x60, 0x00, 0xf3]);
+        provider.add_account(
+            Address::random(),
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(code.clone()),
+        );
+
+        let peers = PeersManager::new(PeersConfig::default()).handle();
+        let (tx, rx) = mpsc::channel(1);
+        let mut handler = EthRequestHandler::new(provider, peers, rx);
+
+        let (response, response_rx) = oneshot::channel();
+        tx.send(IncomingEthRequest::GetNodeData {
+            peer_id: PeerId::random(),
+            request: GetNodeData(vec![H256::random(), keccak256(&code)]),
+            response,
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        // the handler resolves once the request channel is closed
+        (&mut handler).await;
+
+        let NodeData(node_data) = response_rx.await.unwrap().unwrap();
+        assert_eq!(node_data, vec![code]);
+    }
+
+    /// Sends a headers request for a single header and returns the number of served headers.
+    async fn request_header(
+        handler: &mut EthRequestHandler<MockEthProvider>,
+        tx: &mpsc::Sender<IncomingEthRequest>,
+        peer_id: PeerId,
+        hash: H256,
+    ) -> usize {
+        let (response, mut response_rx) = oneshot::channel();
+        tx.send(IncomingEthRequest::GetBlockHeaders {
+            peer_id,
+            request: GetBlockHeaders {
+                start_block: hash.into(),
+                limit: 1,
+                skip: 0,
+                direction: HeadersDirection::Rising,
+            },
+            response,
+        })
+        .await
+        .unwrap();
+
+        // drive the handler until the response is sent
+        tokio::select! {
+            _ = &mut *handler => unreachable!("request channel is open"),
+            res = &mut response_rx => res.unwrap().unwrap().0.len(),
+        }
+    }
+
+    /// Returns the handle of a peer set that trusts the given peer.
+    fn trusted_peers_handle(trusted: PeerId) -> PeersHandle {
+        PeersManager::new(PeersConfig::default().with_trusted_nodes(HashSet::from([
+            NodeRecord::new(SocketAddr::from(([127, 0, 0, 1], 30303)), trusted),
+        ])))
+        .handle()
+    }
+
+    #[tokio::test]
+    async fn test_serving_policy() {
+        let provider = MockEthProvider::default();
+        let header = Header::default();
+        let hash = header.hash_slow();
+        provider.add_header(hash, header);
+
+        let trusted = PeerId::random();
+        let peer = PeerId::random();
+
+        let peers = trusted_peers_handle(trusted);
+        let (tx, rx) = mpsc::channel(1);
+        let mut handler = EthRequestHandler::new(provider, peers, rx).with_policy(
+            ServingPolicy::default()
+                .with_role(ServingRole::HeadersOnly)
+                .with_peer_budget(RequestBudget { max_requests: 1, interval: Duration::MAX }),
+        );
+
+        assert_eq!(request_header(&mut handler, &tx, peer, hash).await, 1);
+        // budget of the peer is exhausted
+        assert_eq!(request_header(&mut handler, &tx, peer, hash).await, 0);
+        // trusted peers are exempt
+        assert_eq!(request_header(&mut handler, &tx, trusted, hash).await, 1);
+        assert_eq!(request_header(&mut handler, &tx, trusted, hash).await, 1);
+
+        // receipts are not served in this role
+        let (response, response_rx) = oneshot::channel();
+        tx.send(IncomingEthRequest::GetReceipts {
+            peer_id: trusted,
+            request: GetReceipts(vec![hash]),
+            response,
+        })
+        .await
+        .unwrap();
+        drop(tx);
+        (&mut handler).await;
+        assert_eq!(response_rx.await.unwrap().unwrap(), Receipts::default());
+    }
+
+    #[tokio::test]
+    async fn test_trusted_peers_do_not_starve_others() {
+        let trusted = PeerId::random();
+        let peer = PeerId::random();
+
+        let peers = trusted_peers_handle(trusted);
+        let (_tx, rx) = mpsc::channel(1);
+        let mut handler = EthRequestHandler::new(MockEthProvider::default(), peers, rx);
+
+        for peer_id in [peer, peer, trusted, trusted, trusted, trusted, trusted] {
+            let (response, _) = oneshot::channel();
+            handler.on_request(IncomingEthRequest::GetReceipts {
+                peer_id,
+                request: GetReceipts(vec![]),
+                response,
+            });
+        }
+
+        let served = std::iter::from_fn(|| handler.next_queued())
+            .map(|request| request.peer_id())
+            .collect::<Vec<_>>();
+        assert_eq!(served, vec![trusted, trusted, trusted, peer, trusted, trusted, peer]);
+    }
+}
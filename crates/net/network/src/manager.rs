@@ -40,7 +40,7 @@ use reth_eth_wire::{
 };
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
 use reth_net_common::bandwidth_meter::BandwidthMeter;
-use reth_network_api::ReputationChangeKind;
+use reth_network_api::{PeerEvent, ReputationChangeKind};
 use reth_primitives::{listener::EventListeners, ForkId, NodeRecord, PeerId, H256};
 use reth_provider::{BlockNumReader, BlockReader};
 use reth_rpc_types::{EthProtocolInfo, NetworkStatus};
@@ -923,6 +923,25 @@ pub enum NetworkEvent {
     PeerRemoved(PeerId),
 }
 
+impl From<NetworkEvent> for PeerEvent {
+    fn from(event: NetworkEvent) -> Self {
+        match event {
+            NetworkEvent::SessionClosed { peer_id, reason } => {
+                PeerEvent::SessionClosed { peer_id, reason }
+            }
+            NetworkEvent::SessionEstablished {
+                peer_id,
+                remote_addr,
+                client_version,
+                version,
+                ..
+            } => PeerEvent::SessionEstablished { peer_id, remote_addr, client_version, version },
+            NetworkEvent::PeerAdded(peer_id) => PeerEvent::PeerAdded(peer_id),
+            NetworkEvent::PeerRemoved(peer_id) => PeerEvent::PeerRemoved(peer_id),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum DiscoveredEvent {
     EventQueued { peer_id: PeerId, socket_addr: SocketAddr, fork_id: Option<ForkId> },
@@ -71,6 +71,8 @@ pub struct TransactionsManagerMetrics {
     pub(crate) inflight_transaction_requests: Gauge,
     /// How often we failed to send a request to the peer because the channel was full.
     pub(crate) egress_peer_channel_full: Counter,
+    /// Total number of transactions not propagated because of a peer's rate limit
+    pub(crate) rate_limited_propagations: Counter,
 }
 
 /// Metrics for Disconnection types
@@ -151,4 +153,16 @@ pub struct EthRequestHandlerMetrics {
 
     /// Number of received bodies requests
     pub(crate) received_bodies_requests: Counter,
+
+    /// Number of received node data requests
+    pub(crate) received_node_data_requests: Counter,
+
+    /// Number of received receipts requests
+    pub(crate) received_receipts_requests: Counter,
+
+    /// Number of requests that were refused by the serving policy
+    pub(crate) refused_requests: Counter,
+
+    /// Number of served node data entries
+    pub(crate) served_node_data_entries: Counter,
 }
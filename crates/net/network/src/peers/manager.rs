@@ -8,6 +8,7 @@ use crate::{
     session::{Direction, PendingSessionHandshakeError},
 };
 use futures::StreamExt;
+use parking_lot::RwLock;
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_net_common::ban_list::BanList;
 use reth_network_api::{PeerKind, ReputationChangeKind};
@@ -18,6 +19,7 @@ use std::{
     io::{self, ErrorKind},
     net::{IpAddr, SocketAddr},
     path::Path,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
@@ -34,6 +36,8 @@ use tracing::{debug, info, trace};
 pub struct PeersHandle {
     /// Sender half of command channel back to the [`PeersManager`]
     manager_tx: mpsc::UnboundedSender<PeerCommand>,
+    /// The ids of all trusted peers, kept up to date by the [`PeersManager`].
+    trusted_peer_ids: Arc<RwLock<HashSet<PeerId>>>,
 }
 
 // === impl PeersHandle ===
@@ -66,6 +70,16 @@ impl PeersHandle {
         rx.await.unwrap_or(None)
     }
 
+    /// Returns `true` if the peer is currently in the trusted set.
+    pub fn is_trusted(&self, peer_id: &PeerId) -> bool {
+        self.trusted_peer_ids.read().contains(peer_id)
+    }
+
+    /// Returns the number of peers currently in the trusted set.
+    pub fn num_trusted_peers(&self) -> usize {
+        self.trusted_peer_ids.read().len()
+    }
+
     /// Returns all peers in the peerset.
     pub async fn all_peers(&self) -> Vec<NodeRecord> {
         let (tx, rx) = oneshot::channel();
@@ -113,6 +127,8 @@ pub(crate) struct PeersManager {
     last_tick: Instant,
     /// Maximum number of backoff attempts before we give up on a peer and dropping.
     max_backoff_count: u32,
+    /// The ids of all trusted peers, shared with all [`PeersHandle`]s.
+    trusted_peer_ids: Arc<RwLock<HashSet<PeerId>>>,
 }
 
 impl PeersManager {
@@ -138,8 +154,10 @@ impl PeersManager {
 
         let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
 
+        let mut trusted_peer_ids = HashSet::with_capacity(trusted_nodes.len());
         for NodeRecord { address, tcp_port, udp_port: _, id } in trusted_nodes {
             peers.entry(id).or_insert_with(|| Peer::trusted(SocketAddr::from((address, tcp_port))));
+            trusted_peer_ids.insert(id);
         }
 
         for NodeRecord { address, tcp_port, udp_port: _, id } in basic_nodes {
@@ -165,12 +183,16 @@ impl PeersManager {
             connect_trusted_nodes_only,
             last_tick: Instant::now(),
             max_backoff_count,
+            trusted_peer_ids: Arc::new(RwLock::new(trusted_peer_ids)),
         }
     }
 
     /// Returns a new [`PeersHandle`] that can send commands to this type.
     pub(crate) fn handle(&self) -> PeersHandle {
-        PeersHandle { manager_tx: self.manager_tx.clone() }
+        PeersHandle {
+            manager_tx: self.manager_tx.clone(),
+            trusted_peer_ids: Arc::clone(&self.trusted_peer_ids),
+        }
     }
 
     /// Returns the number of peers in the peer set
@@ -573,6 +595,13 @@ impl PeersManager {
             return
         }
 
+        if kind == PeerKind::Trusted {
+            self.trusted_peer_ids.write().insert(peer_id);
+        } else if self.peers.contains_key(&peer_id) {
+            // the kind of an existing peer is overwritten below
+            self.trusted_peer_ids.write().remove(&peer_id);
+        }
+
         match self.peers.entry(peer_id) {
             Entry::Occupied(mut entry) => {
                 let peer = entry.get_mut();
@@ -634,6 +663,7 @@ impl PeersManager {
         let peer = entry.get_mut();
 
         peer.kind = PeerKind::Basic;
+        self.trusted_peer_ids.write().remove(&peer_id);
     }
 
     /// Returns the idle peer with the highest reputation.
@@ -2077,6 +2107,33 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_handle_tracks_trusted_peers() {
+        let trusted_peer = PeerId::random();
+        let config = PeersConfig::default().with_trusted_nodes(HashSet::from([NodeRecord {
+            address: IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)),
+            tcp_port: 8008,
+            udp_port: 8008,
+            id: trusted_peer,
+        }]));
+        let mut peers = PeersManager::new(config);
+        let handle = peers.handle();
+        assert!(handle.is_trusted(&trusted_peer));
+
+        let added_peer = PeerId::random();
+        let added_sock = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8009);
+        peers.add_peer(added_peer, added_sock, None);
+        assert!(!handle.is_trusted(&added_peer));
+
+        peers.add_trusted_peer(added_peer, added_sock);
+        assert!(handle.is_trusted(&added_peer));
+        assert_eq!(handle.num_trusted_peers(), 2);
+
+        peers.remove_peer_from_trusted_set(added_peer);
+        assert!(!handle.is_trusted(&added_peer));
+        assert_eq!(handle.num_trusted_peers(), 1);
+    }
+
     #[tokio::test]
     async fn test_connect_trusted_nodes_only() {
         let trusted_peer = PeerId::random();
@@ -3,6 +3,7 @@
 use crate::{
     message::{NewBlockMessage, PeerMessage, PeerRequest, PeerResponse, PeerResponseResult},
     session::{
+        capture::CaptureStream,
         config::INITIAL_REQUEST_TIMEOUT,
         handle::{ActiveSessionMessage, SessionCommand},
         SessionId,
@@ -14,7 +15,7 @@ use futures::{stream::Fuse, SinkExt, StreamExt};
 use reth_ecies::stream::ECIESStream;
 use reth_eth_wire::{
     capability::Capabilities,
-    errors::{EthHandshakeError, EthStreamError, P2PStreamError},
+    errors::{EthHandshakeError, EthStreamError},
     message::{EthBroadcastMessage, RequestPair},
     DisconnectReason, EthMessage, EthStream, P2PStream,
 };
@@ -65,7 +66,7 @@ pub(crate) struct ActiveSession {
     /// Keeps track of request ids.
     pub(crate) next_id: u64,
     /// The underlying connection.
-    pub(crate) conn: EthStream<P2PStream<ECIESStream<MeteredStream<TcpStream>>>>,
+    pub(crate) conn: EthStream<CaptureStream<P2PStream<ECIESStream<MeteredStream<TcpStream>>>>>,
     /// Identifier of the node we're connected to.
     pub(crate) remote_peer_id: PeerId,
     /// The address we're connected to.
@@ -386,11 +387,7 @@ impl ActiveSession {
 
     /// Starts the disconnect process
     fn start_disconnect(&mut self, reason: DisconnectReason) -> Result<(), EthStreamError> {
-        self.conn
-            .inner_mut()
-            .start_disconnect(reason)
-            .map_err(P2PStreamError::from)
-            .map_err(Into::into)
+        self.conn.inner_mut().start_disconnect(reason).map_err(Into::into)
     }
 
     /// Flushes the disconnect message and emits the corresponding message
@@ -850,6 +847,7 @@ mod tests {
                 self.hello.clone(),
                 self.status,
                 self.fork_filter.clone(),
+                None,
             ));
 
             let mut stream = ReceiverStream::new(pending_sessions_rx);
@@ -882,7 +880,7 @@ mod tests {
                         pending_message_to_session: None,
                         internal_request_tx: ReceiverStream::new(messages_rx).fuse(),
                         inflight_requests: Default::default(),
-                        conn,
+                        conn: CaptureStream::wrap(conn, None),
                         queued_outgoing: Default::default(),
                         received_requests_from_remote: Default::default(),
                         internal_request_timeout_interval: tokio::time::interval(
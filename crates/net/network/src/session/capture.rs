@@ -0,0 +1,873 @@
+//! Opt-in capture of the wire traffic of established sessions.
+//!
+//! If enabled via [`SessionsConfig::with_capture_file`](crate::SessionsConfig::with_capture_file),
+//! every `eth` message that is sent or received over an established session is appended to a
+//! capture file, together with a timestamp and the id of the remote peer. The `Hello` and `Status`
+//! handshake messages, `p2p` pings, pongs and disconnects and errors of the underlying stream are
+//! recorded as well.
+//!
+//! Records are handed to a background thread that writes them to the file, so capturing never
+//! blocks a session. If the writer falls behind, records are dropped.
+//!
+//! Messages are recorded as the exact frames that are fed into (or produced by) the
+//! [`EthStream`], so a capture can be fed back through an [`EthStream`] to reproduce decode errors
+//! and protocol breaches, see [`CaptureReplay`] and `reth p2p replay`.
+//!
+//! # File format
+//!
+//! A capture starts with the magic bytes [`CAPTURE_MAGIC`], followed by the format version
+//! [`CAPTURE_VERSION`] as big endian `u16`. The header is followed by a sequence of records:
+//!
+//! | field     | size     | description                                         |
+//! |-----------|----------|-----------------------------------------------------|
+//! | timestamp | 8        | microseconds since the unix epoch, big endian       |
+//! | peer id   | 64       | id of the remote peer                               |
+//! | direction | 1        | `0` = inbound, `1` = outbound                       |
+//! | kind      | 1        | see [`CaptureKind`]                                 |
+//! | version   | 1        | negotiated `eth` version of the session             |
+//! | length    | 4        | length of the payload, big endian                   |
+//! | payload   | `length` | see [`CaptureKind`], at most [`MAX_MESSAGE_SIZE`]   |
+
+use futures::{FutureExt, Sink, Stream, StreamExt};
+use reth_eth_wire::{
+    errors::{EthStreamError, P2PStreamError},
+    CanDisconnect, DisconnectReason, EthMessage, EthStream, EthVersion, HelloMessage, P2PStream,
+    PingPongEvent, Status, MAX_MESSAGE_SIZE,
+};
+use reth_primitives::{
+    bytes::{Bytes, BytesMut},
+    PeerId,
+};
+use reth_rlp::{Decodable, Encodable};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+    pin::Pin,
+    sync::mpsc::{self, Receiver, SyncSender, TryRecvError, TrySendError},
+    task::{ready, Context, Poll},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::debug;
+
+/// Magic bytes every capture file starts with.
+pub const CAPTURE_MAGIC: [u8; 8] = *b"RETHCAP\0";
+
+/// The version of the capture file format.
+pub const CAPTURE_VERSION: u16 = 1;
+
+/// Maximum number of records queued for the background writer.
+const CAPTURE_QUEUE_CAPACITY: usize = 4096;
+
+/// The direction of a captured record, relative to the local node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureDirection {
+    /// Received from the remote peer.
+    Inbound = 0,
+    /// Sent to the remote peer.
+    Outbound = 1,
+}
+
+impl TryFrom<u8> for CaptureDirection {
+    type Error = io::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CaptureDirection::Inbound),
+            1 => Ok(CaptureDirection::Outbound),
+            _ => Err(invalid_data(format!("invalid capture direction {value}"))),
+        }
+    }
+}
+
+/// What kind of event a captured record represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureKind {
+    /// An `eth` message frame, the payload is the message id followed by the RLP encoded message.
+    Eth = 0,
+    /// A `p2p` disconnect, the payload is the single byte [`DisconnectReason`].
+    Disconnect = 1,
+    /// An error returned by the underlying stream, the payload is the error message.
+    Error = 2,
+    /// A `p2p` `Hello` message, the payload is the RLP encoded [`HelloMessage`].
+    Hello = 3,
+    /// The `eth` `Status` handshake message, the payload is the RLP encoded [`Status`].
+    Status = 4,
+    /// A `p2p` ping, without payload.
+    Ping = 5,
+    /// A `p2p` pong, without payload.
+    Pong = 6,
+}
+
+impl TryFrom<u8> for CaptureKind {
+    type Error = io::Error;
+
+    fn try_from(value: u8) -> Result<Self, io::Error> {
+        match value {
+            0 => Ok(CaptureKind::Eth),
+            1 => Ok(CaptureKind::Disconnect),
+            2 => Ok(CaptureKind::Error),
+            3 => Ok(CaptureKind::Hello),
+            4 => Ok(CaptureKind::Status),
+            5 => Ok(CaptureKind::Ping),
+            6 => Ok(CaptureKind::Pong),
+            _ => Err(invalid_data(format!("invalid capture record kind {value}"))),
+        }
+    }
+}
+
+/// A single record of a capture file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureRecord {
+    /// Time since the unix epoch at which the record was captured.
+    pub timestamp: Duration,
+    /// The remote peer of the session.
+    pub peer_id: PeerId,
+    /// Whether this was received or sent.
+    pub direction: CaptureDirection,
+    /// What the payload represents.
+    pub kind: CaptureKind,
+    /// The negotiated `eth` version of the session.
+    pub version: EthVersion,
+    /// The raw payload of the record.
+    pub payload: Bytes,
+}
+
+impl CaptureRecord {
+    /// Returns the [`DisconnectReason`] if this is a [`CaptureKind::Disconnect`] record.
+    pub fn disconnect_reason(&self) -> Option<DisconnectReason> {
+        if self.kind != CaptureKind::Disconnect {
+            return None
+        }
+        self.payload.first().and_then(|reason| DisconnectReason::try_from(*reason).ok())
+    }
+
+    /// Returns the error message if this is a [`CaptureKind::Error`] record.
+    pub fn error_message(&self) -> Option<String> {
+        (self.kind == CaptureKind::Error)
+            .then(|| String::from_utf8_lossy(&self.payload).into_owned())
+    }
+
+    /// Decodes the [`HelloMessage`] if this is a [`CaptureKind::Hello`] record.
+    pub fn hello(&self) -> Option<Result<HelloMessage, reth_rlp::DecodeError>> {
+        (self.kind == CaptureKind::Hello).then(|| HelloMessage::decode(&mut &self.payload[..]))
+    }
+
+    /// Decodes the [`Status`] if this is a [`CaptureKind::Status`] record.
+    pub fn status(&self) -> Option<Result<Status, reth_rlp::DecodeError>> {
+        (self.kind == CaptureKind::Status).then(|| Status::decode(&mut &self.payload[..]))
+    }
+
+    /// Writes the encoded record to the given writer.
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let micros = u64::try_from(self.timestamp.as_micros()).unwrap_or(u64::MAX);
+        let len = u32::try_from(self.payload.len())
+            .map_err(|_| invalid_data("capture payload too large"))?;
+        writer.write_all(&micros.to_be_bytes())?;
+        writer.write_all(self.peer_id.as_bytes())?;
+        writer.write_all(&[self.direction as u8, self.kind as u8, self.version as u8])?;
+        writer.write_all(&len.to_be_bytes())?;
+        writer.write_all(&self.payload)
+    }
+}
+
+/// A shared handle to a capture file that records are appended to.
+///
+/// This is cheap to clone, all clones write to the same file. The file is written by a background
+/// thread that exits once all clones are dropped.
+#[derive(Clone)]
+pub struct CaptureWriter {
+    to_writer: SyncSender<WriterCommand>,
+}
+
+// === impl CaptureWriter ===
+
+impl CaptureWriter {
+    /// Creates a new capture file at the given path, truncating an existing file, writes the
+    /// header and spawns the background writer.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&CAPTURE_MAGIC)?;
+        writer.write_all(&CAPTURE_VERSION.to_be_bytes())?;
+        writer.flush()?;
+
+        let (to_writer, commands) = mpsc::sync_channel(CAPTURE_QUEUE_CAPACITY);
+        std::thread::Builder::new()
+            .name("capture-writer".to_string())
+            .spawn(move || run_writer(writer, commands))?;
+        Ok(Self { to_writer })
+    }
+
+    /// Queues the record to be appended to the capture.
+    ///
+    /// This never blocks, an error is returned if the queue of the background writer is full.
+    pub fn write_record(&self, record: CaptureRecord) -> io::Result<()> {
+        self.to_writer.try_send(WriterCommand::Record(record)).map_err(|err| match err {
+            TrySendError::Full(_) => {
+                io::Error::new(io::ErrorKind::WouldBlock, "capture queue is full")
+            }
+            TrySendError::Disconnected(_) => writer_stopped(),
+        })
+    }
+
+    /// Waits until all queued records are written and flushed to the file.
+    pub fn flush(&self) -> io::Result<()> {
+        let (tx, rx) = mpsc::sync_channel(1);
+        self.to_writer.send(WriterCommand::Flush(tx)).map_err(|_| writer_stopped())?;
+        rx.recv().map_err(|_| writer_stopped())?
+    }
+}
+
+impl fmt::Debug for CaptureWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CaptureWriter").finish_non_exhaustive()
+    }
+}
+
+/// Commands for the background writer of a [`CaptureWriter`].
+enum WriterCommand {
+    Record(CaptureRecord),
+    Flush(SyncSender<io::Result<()>>),
+}
+
+/// Writes records to the file until all senders are dropped.
+///
+/// The file is flushed whenever the queue is drained, so records reach the disk shortly after
+/// they were captured.
+fn run_writer(mut writer: BufWriter<File>, commands: Receiver<WriterCommand>) {
+    loop {
+        let command = match commands.try_recv() {
+            Ok(command) => command,
+            Err(TryRecvError::Empty) => {
+                if let Err(err) = writer.flush() {
+                    debug!(target: "net::session", ?err, "failed to flush capture file");
+                }
+                match commands.recv() {
+                    Ok(command) => command,
+                    Err(_) => break,
+                }
+            }
+            Err(TryRecvError::Disconnected) => break,
+        };
+        match command {
+            WriterCommand::Record(record) => {
+                if let Err(err) = record.write_to(&mut writer) {
+                    debug!(target: "net::session", ?err, "failed to write capture record");
+                }
+            }
+            WriterCommand::Flush(ack) => {
+                let _ = ack.send(writer.flush());
+            }
+        }
+    }
+    let _ = writer.flush();
+}
+
+fn writer_stopped() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "capture writer stopped")
+}
+
+/// Reads the records of a capture file.
+#[derive(Debug)]
+pub struct CaptureReader<R> {
+    reader: R,
+}
+
+// === impl CaptureReader ===
+
+impl CaptureReader<BufReader<File>> {
+    /// Opens the capture file at the given path.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    /// Creates a new reader and validates the capture header.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != CAPTURE_MAGIC {
+            return Err(invalid_data("not a capture file"))
+        }
+        let mut version = [0u8; 2];
+        reader.read_exact(&mut version)?;
+        let version = u16::from_be_bytes(version);
+        if version != CAPTURE_VERSION {
+            return Err(invalid_data(format!("unsupported capture version {version}")))
+        }
+        Ok(Self { reader })
+    }
+
+    /// Reads the next record, returns `None` once the end of the capture is reached.
+    ///
+    /// Returns an [`io::ErrorKind::UnexpectedEof`] error if the capture ends in the middle of a
+    /// record.
+    pub fn next_record(&mut self) -> io::Result<Option<CaptureRecord>> {
+        let mut timestamp = [0u8; 8];
+        if !read_exact_or_eof(&mut self.reader, &mut timestamp)? {
+            return Ok(None)
+        }
+
+        let mut peer_id = [0u8; 64];
+        self.reader.read_exact(&mut peer_id)?;
+
+        let mut meta = [0u8; 3];
+        self.reader.read_exact(&mut meta)?;
+        let direction = CaptureDirection::try_from(meta[0])?;
+        let kind = CaptureKind::try_from(meta[1])?;
+        let version = EthVersion::try_from(meta[2]).map_err(invalid_data)?;
+
+        let mut len = [0u8; 4];
+        self.reader.read_exact(&mut len)?;
+        let len = u32::from_be_bytes(len) as usize;
+        if len > MAX_MESSAGE_SIZE {
+            return Err(invalid_data(format!(
+                "capture record of {len} bytes exceeds the maximum message size"
+            )))
+        }
+        let mut payload = vec![0u8; len];
+        self.reader.read_exact(&mut payload)?;
+
+        Ok(Some(CaptureRecord {
+            timestamp: Duration::from_micros(u64::from_be_bytes(timestamp)),
+            peer_id: PeerId::from(peer_id),
+            direction,
+            kind,
+            version,
+            payload: payload.into(),
+        }))
+    }
+}
+
+/// Fills the buffer from the reader.
+///
+/// Returns `false` if the reader is at EOF before the first byte, and an
+/// [`io::ErrorKind::UnexpectedEof`] error if it reaches EOF after that.
+fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<bool> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "capture ends in the middle of a record",
+                ))
+            }
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(true)
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = io::Result<CaptureRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+/// Records the traffic of a single session.
+#[derive(Debug, Clone)]
+pub(crate) struct SessionCapture {
+    writer: CaptureWriter,
+    peer_id: PeerId,
+    version: EthVersion,
+}
+
+// === impl SessionCapture ===
+
+impl SessionCapture {
+    pub(crate) fn new(writer: CaptureWriter, peer_id: PeerId, version: EthVersion) -> Self {
+        Self { writer, peer_id, version }
+    }
+
+    fn record(&self, direction: CaptureDirection, kind: CaptureKind, payload: &[u8]) {
+        let record = CaptureRecord {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default(),
+            peer_id: self.peer_id,
+            direction,
+            kind,
+            version: self.version,
+            payload: Bytes::copy_from_slice(payload),
+        };
+        if let Err(err) = self.writer.write_record(record) {
+            debug!(target: "net::session", ?err, peer_id=?self.peer_id, "failed to capture record");
+        }
+    }
+
+    /// Records a `Hello` message of the `p2p` handshake.
+    pub(crate) fn on_hello(&self, direction: CaptureDirection, hello: &HelloMessage) {
+        let mut payload = Vec::with_capacity(hello.length());
+        hello.encode(&mut payload);
+        self.record(direction, CaptureKind::Hello, &payload)
+    }
+
+    /// Records a `Status` message of the `eth` handshake.
+    pub(crate) fn on_status(&self, direction: CaptureDirection, status: &Status) {
+        let mut payload = Vec::with_capacity(status.length());
+        status.encode(&mut payload);
+        self.record(direction, CaptureKind::Status, &payload)
+    }
+
+    /// Records a failed `eth` handshake.
+    pub(crate) fn on_handshake_error(&self, err: &EthStreamError) {
+        self.record(CaptureDirection::Inbound, CaptureKind::Error, err.to_string().as_bytes())
+    }
+
+    fn on_ping_pong(&self, event: PingPongEvent) {
+        let (direction, kind) = match event {
+            PingPongEvent::PingSent => (CaptureDirection::Outbound, CaptureKind::Ping),
+            PingPongEvent::PingReceived => (CaptureDirection::Inbound, CaptureKind::Ping),
+            PingPongEvent::PongSent => (CaptureDirection::Outbound, CaptureKind::Pong),
+            PingPongEvent::PongReceived => (CaptureDirection::Inbound, CaptureKind::Pong),
+        };
+        self.record(direction, kind, &[])
+    }
+
+    fn on_disconnect(&self, direction: CaptureDirection, reason: DisconnectReason) {
+        self.record(direction, CaptureKind::Disconnect, &[reason as u8])
+    }
+
+    fn on_error(&self, err: &P2PStreamError) {
+        match err.as_disconnected() {
+            Some(reason) => self.on_disconnect(CaptureDirection::Inbound, reason),
+            None => self.record(
+                CaptureDirection::Inbound,
+                CaptureKind::Error,
+                err.to_string().as_bytes(),
+            ),
+        }
+    }
+}
+
+/// A stream that sits between the [`EthStream`] and the [`P2PStream`] of an established session
+/// and records all frames if capturing is enabled.
+#[derive(Debug)]
+pub(crate) struct CaptureStream<S> {
+    inner: S,
+    capture: Option<SessionCapture>,
+}
+
+// === impl CaptureStream ===
+
+impl<S> CaptureStream<P2PStream<S>> {
+    /// Replaces the inner stream of the authenticated [`EthStream`] with a [`CaptureStream`].
+    pub(crate) fn wrap(
+        conn: EthStream<P2PStream<S>>,
+        capture: Option<SessionCapture>,
+    ) -> EthStream<Self> {
+        let version = conn.version();
+        let mut inner = conn.into_inner();
+        if capture.is_some() {
+            inner.track_ping_pong();
+        }
+        EthStream::new(version, Self { inner, capture })
+    }
+
+    /// Records the pings and pongs the [`P2PStream`] exchanged since the last call.
+    fn capture_ping_pong(&mut self) {
+        if let Some(capture) = &self.capture {
+            for event in self.inner.drain_ping_pong() {
+                capture.on_ping_pong(event);
+            }
+        }
+    }
+
+    /// Returns `true` if the underlying [`P2PStream`] is disconnecting.
+    pub(crate) fn is_disconnecting(&self) -> bool {
+        self.inner.is_disconnecting()
+    }
+
+    /// Starts to disconnect the underlying [`P2PStream`].
+    pub(crate) fn start_disconnect(
+        &mut self,
+        reason: DisconnectReason,
+    ) -> Result<(), P2PStreamError> {
+        if let Some(capture) = &self.capture {
+            capture.on_disconnect(CaptureDirection::Outbound, reason);
+        }
+        self.inner.start_disconnect(reason).map_err(Into::into)
+    }
+}
+
+impl<S> Stream for CaptureStream<P2PStream<S>>
+where
+    P2PStream<S>: Stream<Item = Result<BytesMut, P2PStreamError>> + Unpin,
+{
+    type Item = Result<BytesMut, P2PStreamError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let res = Pin::new(&mut this.inner).poll_next(cx);
+        // pings are answered while polling, before the next message is returned
+        this.capture_ping_pong();
+        let res = ready!(res);
+        if let Some(capture) = &this.capture {
+            match &res {
+                Some(Ok(bytes)) => {
+                    capture.record(CaptureDirection::Inbound, CaptureKind::Eth, bytes)
+                }
+                Some(Err(err)) => capture.on_error(err),
+                None => {}
+            }
+        }
+        Poll::Ready(res)
+    }
+}
+
+impl<S> Sink<Bytes> for CaptureStream<P2PStream<S>>
+where
+    P2PStream<S>: Sink<Bytes, Error = P2PStreamError> + Unpin,
+{
+    type Error = P2PStreamError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        // pings are sent when the sink is polled
+        let res = Pin::new(&mut this.inner).poll_ready(cx);
+        this.capture_ping_pong();
+        res
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Bytes) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        if let Some(capture) = &this.capture {
+            capture.record(CaptureDirection::Outbound, CaptureKind::Eth, &item);
+        }
+        Pin::new(&mut this.inner).start_send(item)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> CanDisconnect<Bytes> for CaptureStream<S>
+where
+    S: CanDisconnect<Bytes> + Sink<Bytes, Error = P2PStreamError> + Send,
+{
+    async fn disconnect(&mut self, reason: DisconnectReason) -> Result<(), P2PStreamError> {
+        if let Some(capture) = &self.capture {
+            capture.on_disconnect(CaptureDirection::Outbound, reason);
+        }
+        self.inner.disconnect(reason).await
+    }
+}
+
+/// The outcome of replaying a single [`CaptureRecord`], see [`CaptureReplay`].
+#[derive(Debug)]
+pub enum ReplayOutcome {
+    /// The frame was decoded into a valid message.
+    Message(EthMessage),
+    /// The frame was rejected by the [`EthStream`].
+    DecodeError(EthStreamError),
+    /// The frame was decoded but the message violates the protocol, an active session
+    /// disconnects the peer in this case.
+    ProtocolBreach {
+        /// The offending message.
+        message: EthMessage,
+        /// Why the message was rejected.
+        error: EthStreamError,
+    },
+    /// A response for which no matching request was sent, an active session penalizes the peer in
+    /// this case.
+    UnsolicitedResponse(EthMessage),
+    /// The session was disconnected.
+    Disconnect(Option<DisconnectReason>),
+    /// The underlying stream returned an error.
+    StreamError(String),
+    /// A `Hello` message of the `p2p` handshake.
+    Hello(HelloMessage),
+    /// A `Status` message of the `eth` handshake.
+    Status(Status),
+    /// A `p2p` ping.
+    Ping,
+    /// A `p2p` pong.
+    Pong,
+}
+
+impl ReplayOutcome {
+    /// Returns `true` if replaying the record failed, or revealed misbehaviour of the peer.
+    pub fn is_error(&self) -> bool {
+        !matches!(
+            self,
+            ReplayOutcome::Message(_) |
+                ReplayOutcome::Disconnect(_) |
+                ReplayOutcome::Hello(_) |
+                ReplayOutcome::Status(_) |
+                ReplayOutcome::Ping |
+                ReplayOutcome::Pong
+        )
+    }
+}
+
+/// Feeds captured frames back through an [`EthStream`] and applies the checks an active session
+/// applies to incoming messages.
+///
+/// Records are expected in capture order, request ids of sent requests are tracked per peer so
+/// that unsolicited responses can be detected.
+#[derive(Debug, Default)]
+pub struct CaptureReplay {
+    /// Ids of requests sent to each peer that have not been answered yet.
+    inflight_requests: HashMap<PeerId, HashSet<u64>>,
+}
+
+// === impl CaptureReplay ===
+
+impl CaptureReplay {
+    /// Replays a single record.
+    pub fn replay(&mut self, record: &CaptureRecord) -> ReplayOutcome {
+        match record.kind {
+            CaptureKind::Disconnect => return ReplayOutcome::Disconnect(record.disconnect_reason()),
+            CaptureKind::Error => {
+                return ReplayOutcome::StreamError(record.error_message().unwrap_or_default())
+            }
+            CaptureKind::Hello => {
+                return match HelloMessage::decode(&mut &record.payload[..]) {
+                    Ok(hello) => ReplayOutcome::Hello(hello),
+                    Err(err) => ReplayOutcome::DecodeError(err.into()),
+                }
+            }
+            CaptureKind::Status => {
+                return match Status::decode(&mut &record.payload[..]) {
+                    Ok(status) => ReplayOutcome::Status(status),
+                    Err(err) => ReplayOutcome::DecodeError(err.into()),
+                }
+            }
+            CaptureKind::Ping => return ReplayOutcome::Ping,
+            CaptureKind::Pong => return ReplayOutcome::Pong,
+            CaptureKind::Eth => {}
+        }
+
+        let frame = BytesMut::from(&record.payload[..]);
+        let mut stream = EthStream::new(
+            record.version,
+            futures::stream::iter(std::iter::once(Ok::<_, P2PStreamError>(frame))),
+        );
+        let msg = match stream.next().now_or_never().flatten() {
+            Some(Ok(msg)) => msg,
+            Some(Err(err)) => return ReplayOutcome::DecodeError(err),
+            None => unreachable!("in-memory stream yields the frame"),
+        };
+
+        match record.direction {
+            CaptureDirection::Inbound => self.on_inbound(record.peer_id, msg),
+            CaptureDirection::Outbound => {
+                if let Some((request_id, false)) = request_id(&msg) {
+                    self.inflight_requests.entry(record.peer_id).or_default().insert(request_id);
+                }
+                ReplayOutcome::Message(msg)
+            }
+        }
+    }
+
+    /// Applies the checks of the active session to an incoming message.
+    fn on_inbound(&mut self, peer_id: PeerId, msg: EthMessage) -> ReplayOutcome {
+        if let EthMessage::NewPooledTransactionHashes68(ref hashes) = msg {
+            if hashes.hashes.len() != hashes.types.len() ||
+                hashes.hashes.len() != hashes.sizes.len()
+            {
+                let error = EthStreamError::TransactionHashesInvalidLenOfFields {
+                    hashes_len: hashes.hashes.len(),
+                    types_len: hashes.types.len(),
+                    sizes_len: hashes.sizes.len(),
+                };
+                return ReplayOutcome::ProtocolBreach { message: msg, error }
+            }
+        }
+
+        if let Some((request_id, true)) = request_id(&msg) {
+            let known = self
+                .inflight_requests
+                .get_mut(&peer_id)
+                .map(|inflight| inflight.remove(&request_id))
+                .unwrap_or_default();
+            if !known {
+                return ReplayOutcome::UnsolicitedResponse(msg)
+            }
+        }
+
+        ReplayOutcome::Message(msg)
+    }
+}
+
+/// Returns the request id of a request or response message and whether it's a response.
+fn request_id(msg: &EthMessage) -> Option<(u64, bool)> {
+    let id = match msg {
+        EthMessage::GetBlockHeaders(req) => (req.request_id, false),
+        EthMessage::GetBlockBodies(req) => (req.request_id, false),
+        EthMessage::GetPooledTransactions(req) => (req.request_id, false),
+        EthMessage::GetNodeData(req) => (req.request_id, false),
+        EthMessage::GetReceipts(req) => (req.request_id, false),
+        EthMessage::BlockHeaders(resp) => (resp.request_id, true),
+        EthMessage::BlockBodies(resp) => (resp.request_id, true),
+        EthMessage::PooledTransactions(resp) => (resp.request_id, true),
+        EthMessage::NodeData(resp) => (resp.request_id, true),
+        EthMessage::Receipts(resp) => (resp.request_id, true),
+        EthMessage::Status(_) |
+        EthMessage::NewBlockHashes(_) |
+        EthMessage::NewBlock(_) |
+        EthMessage::Transactions(_) |
+        EthMessage::NewPooledTransactionHashes66(_) |
+        EthMessage::NewPooledTransactionHashes68(_) => return None,
+    };
+    Some(id)
+}
+
+fn invalid_data<E>(err: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_eth_wire::{
+        message::RequestPair, BlockBodies, GetBlockBodies, NewPooledTransactionHashes68,
+    };
+    use reth_primitives::H256;
+    use reth_rlp::Encodable;
+
+    fn record(kind: CaptureKind, payload: &[u8]) -> CaptureRecord {
+        CaptureRecord {
+            timestamp: Duration::from_micros(1_690_000_000_123_456),
+            peer_id: PeerId::random(),
+            direction: CaptureDirection::Inbound,
+            kind,
+            version: EthVersion::Eth68,
+            payload: Bytes::copy_from_slice(payload),
+        }
+    }
+
+    #[test]
+    fn capture_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cap");
+
+        let status = Status::default();
+        let mut status_payload = Vec::new();
+        status.encode(&mut status_payload);
+
+        let records = vec![
+            record(CaptureKind::Status, &status_payload),
+            record(CaptureKind::Eth, &[0x03, 0xc0]),
+            record(CaptureKind::Ping, &[]),
+            record(CaptureKind::Error, b"invalid rlp"),
+            record(CaptureKind::Disconnect, &[DisconnectReason::ProtocolBreach as u8]),
+        ];
+
+        let writer = CaptureWriter::create(&path).unwrap();
+        for record in &records {
+            writer.clone().write_record(record.clone()).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let read = CaptureReader::open(&path).unwrap().collect::<io::Result<Vec<_>>>().unwrap();
+        assert_eq!(read, records);
+        assert_eq!(read[0].status(), Some(Ok(status)));
+        assert_eq!(read[3].error_message().as_deref(), Some("invalid rlp"));
+        assert_eq!(read[4].disconnect_reason(), Some(DisconnectReason::ProtocolBreach));
+        assert_eq!(read[1].disconnect_reason(), None);
+        assert!(matches!(CaptureReplay::default().replay(&read[2]), ReplayOutcome::Ping));
+    }
+
+    fn encode_capture(records: &[CaptureRecord]) -> Vec<u8> {
+        let mut buf = CAPTURE_MAGIC.to_vec();
+        buf.extend_from_slice(&CAPTURE_VERSION.to_be_bytes());
+        for record in records {
+            record.write_to(&mut buf).unwrap();
+        }
+        buf
+    }
+
+    #[test]
+    fn reject_truncated_record() {
+        let buf = encode_capture(&[record(CaptureKind::Eth, &[0x03, 0xc0])]);
+
+        // truncated in the middle of the timestamp and of the payload
+        for len in [buf.len() - 80, buf.len() - 1] {
+            let mut reader = CaptureReader::new(&buf[..len]).unwrap();
+            let err = reader.next_record().unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        }
+
+        let mut reader = CaptureReader::new(&buf[..]).unwrap();
+        assert!(reader.next_record().unwrap().is_some());
+        assert!(reader.next_record().unwrap().is_none());
+    }
+
+    #[test]
+    fn reject_oversized_record() {
+        let mut buf = encode_capture(&[record(CaptureKind::Eth, &[0x03, 0xc0])]);
+        // overwrite the length of the record
+        let len_offset = buf.len() - 2 - 4;
+        buf[len_offset..len_offset + 4]
+            .copy_from_slice(&(MAX_MESSAGE_SIZE as u32 + 1).to_be_bytes());
+
+        let err = CaptureReader::new(&buf[..]).unwrap().next_record().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    fn eth_record(direction: CaptureDirection, peer_id: PeerId, msg: EthMessage) -> CaptureRecord {
+        let mut payload = BytesMut::new();
+        msg.message_id().encode(&mut payload);
+        msg.encode(&mut payload);
+        CaptureRecord { direction, peer_id, ..record(CaptureKind::Eth, &payload) }
+    }
+
+    #[test]
+    fn replay_detects_breaches() {
+        let peer_id = PeerId::random();
+        let mut replay = CaptureReplay::default();
+
+        let request = EthMessage::GetBlockBodies(RequestPair {
+            request_id: 1,
+            message: GetBlockBodies(vec![H256::random()]),
+        });
+        let response =
+            EthMessage::BlockBodies(RequestPair { request_id: 1, message: BlockBodies(vec![]) });
+
+        // a response without a request
+        let outcome =
+            replay.replay(&eth_record(CaptureDirection::Inbound, peer_id, response.clone()));
+        assert!(matches!(outcome, ReplayOutcome::UnsolicitedResponse(_)));
+
+        let outcome = replay.replay(&eth_record(CaptureDirection::Outbound, peer_id, request));
+        assert!(matches!(outcome, ReplayOutcome::Message(_)));
+        let outcome = replay.replay(&eth_record(CaptureDirection::Inbound, peer_id, response));
+        assert!(!outcome.is_error());
+
+        let hashes = EthMessage::NewPooledTransactionHashes68(NewPooledTransactionHashes68 {
+            types: vec![0x02],
+            sizes: vec![],
+            hashes: vec![H256::random()],
+        });
+        let outcome = replay.replay(&eth_record(CaptureDirection::Inbound, peer_id, hashes));
+        assert!(matches!(outcome, ReplayOutcome::ProtocolBreach { .. }));
+
+        // malformed frame
+        let outcome = replay
+            .replay(&CaptureRecord { peer_id, ..record(CaptureKind::Eth, &[0x05, 0xff, 0x00]) });
+        assert!(matches!(outcome, ReplayOutcome::DecodeError(_)));
+    }
+
+    #[test]
+    fn reject_invalid_header() {
+        let err = CaptureReader::new(&b"NOTACAPTURE"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     peers::{DEFAULT_MAX_PEERS_INBOUND, DEFAULT_MAX_PEERS_OUTBOUND},
     session::{Direction, ExceedsSessionLimit},
 };
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 /// Default request timeout for a single request.
 ///
@@ -38,6 +38,11 @@ pub struct SessionsConfig {
     /// `PROTOCOL_BREACH_REQUEST_TIMEOUT`) this is considered a protocol violation and results in a
     /// dropped session.
     pub protocol_breach_request_timeout: Duration,
+    /// If set, the traffic of all established sessions is captured to this file.
+    ///
+    /// See also [`capture`](crate::capture).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub capture_file: Option<PathBuf>,
 }
 
 impl Default for SessionsConfig {
@@ -56,6 +61,7 @@ impl Default for SessionsConfig {
             limits: Default::default(),
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
+            capture_file: None,
         }
     }
 }
@@ -70,6 +76,12 @@ impl SessionsConfig {
         self.session_event_buffer = n;
         self
     }
+
+    /// Captures the wire traffic of all established sessions to the given file.
+    pub fn with_capture_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.capture_file = Some(path.into());
+        self
+    }
 }
 
 /// Limits for sessions.
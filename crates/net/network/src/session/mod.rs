@@ -2,7 +2,11 @@
 use crate::{
     message::PeerMessage,
     metrics::SessionManagerMetrics,
-    session::{active::ActiveSession, config::SessionCounter},
+    session::{
+        active::ActiveSession,
+        capture::{CaptureDirection, CaptureStream, CaptureWriter, SessionCapture},
+        config::SessionCounter,
+    },
 };
 use fnv::FnvHashMap;
 use futures::{future::Either, io, FutureExt, StreamExt};
@@ -34,9 +38,10 @@ use tokio::{
     sync::{mpsc, oneshot},
 };
 use tokio_stream::wrappers::ReceiverStream;
-use tracing::{instrument, trace};
+use tracing::{info, instrument, trace, warn};
 
 mod active;
+pub mod capture;
 mod config;
 mod handle;
 pub use crate::message::PeerRequestSender;
@@ -102,6 +107,8 @@ pub struct SessionManager {
     bandwidth_meter: BandwidthMeter,
     /// Metrics for the session manager.
     metrics: SessionManagerMetrics,
+    /// If set, the traffic of all established sessions is recorded.
+    capture: Option<CaptureWriter>,
 }
 
 // === impl SessionManager ===
@@ -120,6 +127,18 @@ impl SessionManager {
         let (pending_sessions_tx, pending_sessions_rx) = mpsc::channel(config.session_event_buffer);
         let (active_session_tx, active_session_rx) = mpsc::channel(config.session_event_buffer);
 
+        let capture =
+            config.capture_file.as_ref().and_then(|path| match CaptureWriter::create(path) {
+                Ok(writer) => {
+                    info!(target: "net::session", ?path, "Capturing session traffic");
+                    Some(writer)
+                }
+                Err(err) => {
+                    warn!(target: "net::session", ?err, ?path, "Failed to create capture file");
+                    None
+                }
+            });
+
         Self {
             next_id: 0,
             counter: SessionCounter::new(config.limits),
@@ -139,6 +158,7 @@ impl SessionManager {
             active_session_rx: ReceiverStream::new(active_session_rx),
             bandwidth_meter,
             metrics: Default::default(),
+            capture,
         }
     }
 
@@ -213,6 +233,7 @@ impl SessionManager {
         let hello_message = self.hello_message.clone();
         let status = self.status;
         let fork_filter = self.fork_filter.clone();
+        let capture = self.capture.clone();
         self.spawn(start_pending_incoming_session(
             disconnect_rx,
             session_id,
@@ -223,6 +244,7 @@ impl SessionManager {
             hello_message,
             status,
             fork_filter,
+            capture,
         ));
 
         let handle = PendingSessionHandle {
@@ -246,6 +268,7 @@ impl SessionManager {
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
             let band_with_meter = self.bandwidth_meter.clone();
+            let capture = self.capture.clone();
             self.spawn(start_pending_outbound_session(
                 disconnect_rx,
                 pending_events,
@@ -257,6 +280,7 @@ impl SessionManager {
                 status,
                 fork_filter,
                 band_with_meter,
+                capture,
             ));
 
             let handle = PendingSessionHandle {
@@ -439,6 +463,11 @@ impl SessionManager {
                 // negotiated version
                 let version = conn.version();
 
+                let capture = self
+                    .capture
+                    .clone()
+                    .map(|writer| SessionCapture::new(writer, peer_id, version));
+
                 let session = ActiveSession {
                     next_id: 0,
                     remote_peer_id: peer_id,
@@ -450,7 +479,7 @@ impl SessionManager {
                     pending_message_to_session: None,
                     internal_request_tx: ReceiverStream::new(messages_rx).fuse(),
                     inflight_requests: Default::default(),
-                    conn,
+                    conn: CaptureStream::wrap(conn, capture),
                     queued_outgoing: Default::default(),
                     received_requests_from_remote: Default::default(),
                     internal_request_timeout_interval: tokio::time::interval(
@@ -725,6 +754,7 @@ pub(crate) async fn start_pending_incoming_session(
     hello: HelloMessage,
     status: Status,
     fork_filter: ForkFilter,
+    capture: Option<CaptureWriter>,
 ) {
     authenticate(
         disconnect_rx,
@@ -737,6 +767,7 @@ pub(crate) async fn start_pending_incoming_session(
         hello,
         status,
         fork_filter,
+        capture,
     )
     .await
 }
@@ -755,6 +786,7 @@ async fn start_pending_outbound_session(
     status: Status,
     fork_filter: ForkFilter,
     bandwidth_meter: BandwidthMeter,
+    capture: Option<CaptureWriter>,
 ) {
     let stream = match TcpStream::connect(remote_addr).await {
         Ok(stream) => MeteredStream::new_with_meter(stream, bandwidth_meter),
@@ -781,6 +813,7 @@ async fn start_pending_outbound_session(
         hello,
         status,
         fork_filter,
+        capture,
     )
     .await
 }
@@ -798,6 +831,7 @@ async fn authenticate(
     hello: HelloMessage,
     status: Status,
     fork_filter: ForkFilter,
+    capture: Option<CaptureWriter>,
 ) {
     let local_addr = stream.inner().local_addr().ok();
     let stream = match get_eciess_stream(stream, secret_key, direction).await {
@@ -826,6 +860,7 @@ async fn authenticate(
         hello,
         status,
         fork_filter,
+        capture,
     )
     .boxed();
 
@@ -874,7 +909,10 @@ async fn authenticate_stream(
     hello: HelloMessage,
     status: Status,
     fork_filter: ForkFilter,
+    capture: Option<CaptureWriter>,
 ) -> PendingSessionEvent {
+    let our_hello = capture.as_ref().map(|_| hello.clone());
+
     // conduct the p2p handshake and return the authenticated stream
     let (p2p_stream, their_hello) = match stream.handshake(hello).await {
         Ok(stream_res) => stream_res,
@@ -892,10 +930,24 @@ async fn authenticate_stream(
     //
     // Before trying status handshake, set up the version to shared_capability
     let status = Status { version: p2p_stream.shared_capability().version(), ..status };
+
+    // the handshake messages are recorded with the negotiated version, once it is known
+    let capture = capture
+        .zip(EthVersion::try_from(status.version).ok())
+        .map(|(writer, version)| SessionCapture::new(writer, their_hello.id, version));
+    if let (Some(capture), Some(our_hello)) = (&capture, &our_hello) {
+        capture.on_hello(CaptureDirection::Outbound, our_hello);
+        capture.on_hello(CaptureDirection::Inbound, &their_hello);
+        capture.on_status(CaptureDirection::Outbound, &status);
+    }
+
     let eth_unauthed = UnauthedEthStream::new(p2p_stream);
     let (eth_stream, their_status) = match eth_unauthed.handshake(status, fork_filter).await {
         Ok(stream_res) => stream_res,
         Err(err) => {
+            if let Some(capture) = &capture {
+                capture.on_handshake_error(&err);
+            }
             return PendingSessionEvent::Disconnected {
                 remote_addr,
                 session_id,
@@ -904,6 +956,10 @@ async fn authenticate_stream(
             }
         }
     };
+    if let Some(capture) = &capture {
+        capture.on_status(CaptureDirection::Inbound, &their_status);
+    }
+
     PendingSessionEvent::Established {
         session_id,
         remote_addr,
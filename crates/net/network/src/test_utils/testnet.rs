@@ -8,7 +8,7 @@ use futures::{FutureExt, StreamExt};
 use pin_project::pin_project;
 use reth_eth_wire::{capability::Capability, DisconnectReason, HelloBuilder};
 use reth_primitives::PeerId;
-use reth_provider::{test_utils::NoopProvider, BlockReader, HeaderProvider};
+use reth_provider::{test_utils::NoopProvider, BlockReader, BytecodeReader, HeaderProvider};
 use secp256k1::SecretKey;
 use std::{
     fmt,
@@ -122,7 +122,7 @@ where
 
 impl<C> Testnet<C>
 where
-    C: BlockReader + HeaderProvider + Unpin + 'static,
+    C: BlockReader + HeaderProvider + BytecodeReader + Clone + Unpin + 'static,
 {
     /// Spawns the testnet to a separate task
     pub fn spawn(self) -> TestnetHandle<C> {
@@ -173,7 +173,7 @@ impl<C> fmt::Debug for Testnet<C> {
 
 impl<C> Future for Testnet<C>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + BytecodeReader + Clone + Unpin + 'static,
 {
     type Output = ();
 
@@ -246,7 +246,7 @@ where
 
 impl<C> Future for Peer<C>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + BytecodeReader + Clone + Unpin + 'static,
 {
     type Output = ();
 
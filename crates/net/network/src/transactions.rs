@@ -5,6 +5,7 @@ use crate::{
     manager::NetworkEvent,
     message::{PeerRequest, PeerRequestSender},
     metrics::{TransactionsManagerMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
+    peers::PeersHandle,
     NetworkHandle,
 };
 use futures::{stream::FuturesUnordered, Future, FutureExt, StreamExt};
@@ -27,15 +28,16 @@ use reth_transaction_pool::{
     PropagatedTransactions, TransactionPool, ValidPoolTransaction,
 };
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
     num::NonZeroUsize,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc, oneshot, oneshot::error::RecvError};
 use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
 /// Cache limit of transactions to keep track of for a single peer.
 const PEER_TRANSACTION_CACHE_LIMIT: usize = 1024 * 10;
@@ -122,18 +124,33 @@ pub struct TransactionsManager<Pool> {
     pending_transactions: ReceiverStream<TxHash>,
     /// Incoming events from the [`NetworkManager`](crate::NetworkManager).
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent>,
+    /// Rules that determine which transactions are propagated to which peers.
+    policy: PropagationPolicy,
     /// TransactionsManager metrics
     metrics: TransactionsManagerMetrics,
 }
 
 impl<Pool: TransactionPool> TransactionsManager<Pool> {
-    /// Sets up a new instance.
+    /// Sets up a new instance with the default [`PropagationPolicy`].
     ///
     /// Note: This expects an existing [`NetworkManager`](crate::NetworkManager) instance.
     pub fn new(
         network: NetworkHandle,
         pool: Pool,
         from_network: mpsc::UnboundedReceiver<NetworkTransactionEvent>,
+    ) -> Self {
+        Self::with_policy(network, pool, from_network, PropagationPolicy::default())
+    }
+
+    /// Sets up a new instance that propagates transactions according to the given
+    /// [`PropagationPolicy`].
+    ///
+    /// Note: This expects an existing [`NetworkManager`](crate::NetworkManager) instance.
+    pub fn with_policy(
+        network: NetworkHandle,
+        pool: Pool,
+        from_network: mpsc::UnboundedReceiver<NetworkTransactionEvent>,
+        policy: PropagationPolicy,
     ) -> Self {
         let network_events = network.event_listener();
         let (command_tx, command_rx) = mpsc::unbounded_channel();
@@ -142,6 +159,16 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
         // the network
         let pending = pool.pending_transactions_listener();
 
+        match &policy.peers {
+            PropagationPeers::Trusted if network.peers_handle().num_trusted_peers() == 0 => {
+                warn!(target: "net::tx", "Transactions are only propagated to trusted peers, but no trusted peers are configured");
+            }
+            PropagationPeers::Only(peers) if peers.is_empty() => {
+                warn!(target: "net::tx", "Transactions are only propagated to an empty set of peers");
+            }
+            _ => {}
+        }
+
         Self {
             pool,
             network,
@@ -157,6 +184,7 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
                 from_network,
                 NETWORK_POOL_TRANSACTIONS_SCOPE,
             ),
+            policy,
             metrics: Default::default(),
         }
     }
@@ -173,6 +201,11 @@ where
         TransactionsHandle { manager_tx: self.command_tx.clone() }
     }
 
+    /// Returns the [`PropagationPolicy`] this manager propagates transactions with.
+    pub fn policy(&self) -> &PropagationPolicy {
+        &self.policy
+    }
+
     #[inline]
     fn update_import_metrics(&self) {
         self.metrics.pending_pool_imports.set(self.pool_imports.len() as f64);
@@ -224,10 +257,20 @@ where
         trace!(target: "net::tx", "Start propagating transactions");
 
         // This fetches all transaction from the pool, including the blob transactions, which are
-        // only ever sent as hashes.
-        let propagated = self.propagate_transactions(
-            self.pool.get_all(hashes).into_iter().map(PropagateTransaction::new).collect(),
-        );
+        // only ever sent as hashes, and drops those that are excluded by the policy.
+        let to_propagate = self
+            .pool
+            .get_all(hashes)
+            .into_iter()
+            .filter(|tx| self.policy.should_propagate(tx))
+            .map(PropagateTransaction::new)
+            .collect::<Vec<_>>();
+
+        if to_propagate.is_empty() {
+            return
+        }
+
+        let propagated = self.propagate_transactions(to_propagate);
 
         // notify pool so events get fired
         self.pool.on_propagated(propagated);
@@ -246,19 +289,43 @@ where
         let mut propagated = PropagatedTransactions::default();
 
         // send full transactions to a fraction fo the connected peers (square root of the total
-        // number of connected peers)
-        let max_num_full = (self.peers.len() as f64).sqrt() as usize + 1;
+        // number of peers we're allowed to propagate to)
+        let peers_handle = self.network.peers_handle();
+        let num_propagation_peers = self
+            .peers
+            .keys()
+            .filter(|peer_id| self.policy.peers.contains(peer_id, peers_handle))
+            .count();
+        let max_num_full = (num_propagation_peers as f64).sqrt() as usize + 1;
 
         // Note: Assuming ~random~ order due to random state of the peers map hasher
-        for (peer_idx, (peer_id, peer)) in self.peers.iter_mut().enumerate() {
+        let peers = self
+            .peers
+            .iter_mut()
+            .filter(|(peer_id, _)| self.policy.peers.contains(peer_id, peers_handle));
+        for (peer_idx, (peer_id, peer)) in peers.enumerate() {
             // filter all transactions unknown to the peer
             let mut hashes = PooledTransactionsHashesBuilder::new(peer.version);
             let mut full_transactions = FullTransactionsBuilder::default();
+            let mut num_rate_limited = 0;
 
             // Iterate through the transactions to propagate and fill the hashes and full
             // transaction lists, before deciding whether or not to send full transactions to the
             // peer.
             for tx in to_propagate.iter() {
+                if peer.transactions.contains(&tx.hash()) {
+                    continue
+                }
+
+                // respect the per peer send rate, transactions that exceed it are not marked as
+                // known by the peer
+                if let Some(rate_limit) = peer.rate_limit.as_mut() {
+                    if !rate_limit.try_acquire() {
+                        num_rate_limited += 1;
+                        continue
+                    }
+                }
+
                 if peer.transactions.insert(tx.hash()) {
                     hashes.push(tx);
 
@@ -275,6 +342,12 @@ where
                     }
                 }
             }
+
+            if num_rate_limited > 0 {
+                self.metrics.rate_limited_propagations.increment(num_rate_limited);
+                trace!(target: "net::tx", ?peer_id, num_rate_limited, "Peer propagation rate limit exceeded");
+            }
+
             let mut new_pooled_hashes = hashes.build();
 
             if !new_pooled_hashes.is_empty() {
@@ -427,19 +500,27 @@ where
                         request_tx: messages,
                         version,
                         client_version,
+                        rate_limit: self.policy.peer_rate_limit.map(PeerRateLimiter::new),
                     },
                 );
 
                 // Send a `NewPooledTransactionHashes` to the peer with up to
                 // `NEW_POOLED_TRANSACTION_HASHES_SOFT_LIMIT` transactions in the
                 // pool
-                if !self.network.is_initially_syncing() {
+                if !self.network.is_initially_syncing() &&
+                    self.policy.peers.contains(&peer_id, self.network.peers_handle())
+                {
                     let peer = self.peers.get_mut(&peer_id).expect("is present; qed");
 
                     let mut msg_builder = PooledTransactionsHashesBuilder::new(version);
 
-                    let pooled_txs =
-                        self.pool.pooled_transactions_max(NEW_POOLED_TRANSACTION_HASHES_SOFT_LIMIT);
+                    // blob transactions are always included in the announcement
+                    let pooled_txs = self
+                        .pool
+                        .pooled_transactions_max(NEW_POOLED_TRANSACTION_HASHES_SOFT_LIMIT)
+                        .into_iter()
+                        .filter(|tx| self.policy.should_announce(tx))
+                        .collect::<Vec<_>>();
                     if pooled_txs.is_empty() {
                         // do not send a message if there are no transactions in the pool
                         return
@@ -825,6 +906,167 @@ struct Peer {
     /// The peer's client version.
     #[allow(unused)]
     client_version: Arc<String>,
+    /// Limits the rate at which transactions are propagated to the peer, if configured.
+    rate_limit: Option<PeerRateLimiter>,
+}
+
+/// Determines which transactions the [`TransactionsManager`] propagates and to which peers.
+///
+/// By default all transactions that the pool considers propagatable are sent to all connected
+/// peers: full transactions to a fraction of them and hashes to the rest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PropagationPolicy {
+    /// The peers transactions are propagated to.
+    pub peers: PropagationPeers,
+    /// Whether transactions that were submitted locally are propagated.
+    ///
+    /// If disabled, local transactions are neither broadcast nor announced to peers.
+    pub propagate_local: bool,
+    /// Whether blob transactions are propagated when they enter the pool.
+    ///
+    /// Blob transactions are only ever sent as hashes. If disabled, they are still included in
+    /// the announcement of pooled transactions sent to newly connected peers.
+    pub propagate_blob_transactions: bool,
+    /// Limits the number of transactions propagated to a single peer.
+    pub peer_rate_limit: Option<PeerRateLimit>,
+}
+
+// === impl PropagationPolicy ===
+
+impl PropagationPolicy {
+    /// Sets the peers transactions are propagated to.
+    pub fn with_peers(mut self, peers: PropagationPeers) -> Self {
+        self.peers = peers;
+        self
+    }
+
+    /// Only propagate transactions to peers that are in the trusted set at the time of
+    /// propagation.
+    pub fn with_trusted_peers_only(mut self) -> Self {
+        self.peers = PropagationPeers::Trusted;
+        self
+    }
+
+    /// Sets whether local transactions are propagated.
+    pub fn with_propagate_local(mut self, propagate_local: bool) -> Self {
+        self.propagate_local = propagate_local;
+        self
+    }
+
+    /// Sets whether blob transactions are propagated when they enter the pool.
+    pub fn with_propagate_blob_transactions(mut self, propagate_blob_transactions: bool) -> Self {
+        self.propagate_blob_transactions = propagate_blob_transactions;
+        self
+    }
+
+    /// Sets the per peer propagation rate limit.
+    pub fn with_peer_rate_limit(mut self, rate_limit: PeerRateLimit) -> Self {
+        self.peer_rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Returns true if the new pending transaction should be propagated.
+    fn should_propagate<T: PoolTransaction>(&self, tx: &ValidPoolTransaction<T>) -> bool {
+        if tx.is_eip4844() && !self.propagate_blob_transactions {
+            return false
+        }
+        self.should_announce(tx)
+    }
+
+    /// Returns true if the pooled transaction can be announced to a newly connected peer.
+    fn should_announce<T: PoolTransaction>(&self, tx: &ValidPoolTransaction<T>) -> bool {
+        self.propagate_local || !tx.is_local()
+    }
+}
+
+impl Default for PropagationPolicy {
+    fn default() -> Self {
+        Self {
+            peers: PropagationPeers::All,
+            propagate_local: true,
+            propagate_blob_transactions: true,
+            peer_rate_limit: None,
+        }
+    }
+}
+
+/// The set of peers transactions are propagated to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PropagationPeers {
+    /// Propagate to all connected peers.
+    #[default]
+    All,
+    /// Only propagate to peers that are currently trusted, including trusted peers added at
+    /// runtime.
+    Trusted,
+    /// Only propagate to the given peers, for example private relays.
+    Only(HashSet<PeerId>),
+}
+
+// === impl PropagationPeers ===
+
+impl PropagationPeers {
+    /// Returns true if transactions may be propagated to the given peer.
+    ///
+    /// The trusted set is read from the given [`PeersHandle`].
+    pub fn contains(&self, peer_id: &PeerId, peers_handle: &PeersHandle) -> bool {
+        match self {
+            PropagationPeers::All => true,
+            PropagationPeers::Trusted => peers_handle.is_trusted(peer_id),
+            PropagationPeers::Only(peers) => peers.contains(peer_id),
+        }
+    }
+}
+
+/// Limits the number of transactions that are propagated to a single peer within an interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeerRateLimit {
+    /// Maximum number of transactions sent to the peer per interval.
+    pub max_transactions: usize,
+    /// The length of the interval.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub interval: Duration,
+}
+
+impl PeerRateLimit {
+    /// Creates a limit of `max_transactions` per second.
+    pub fn per_second(max_transactions: usize) -> Self {
+        Self { max_transactions, interval: Duration::from_secs(1) }
+    }
+}
+
+/// Tracks the remaining propagation budget of a peer for the current interval.
+#[derive(Debug)]
+struct PeerRateLimiter {
+    limit: PeerRateLimit,
+    remaining: usize,
+    interval_start: Instant,
+}
+
+// === impl PeerRateLimiter ===
+
+impl PeerRateLimiter {
+    fn new(limit: PeerRateLimit) -> Self {
+        Self { limit, remaining: limit.max_transactions, interval_start: Instant::now() }
+    }
+
+    /// Consumes one unit of the budget, returns false if the budget is exhausted.
+    fn try_acquire(&mut self) -> bool {
+        let now = Instant::now();
+        if now.duration_since(self.interval_start) >= self.limit.interval {
+            self.interval_start = now;
+            self.remaining = self.limit.max_transactions;
+        }
+        if self.remaining == 0 {
+            return false
+        }
+        self.remaining -= 1;
+        true
+    }
 }
 
 /// Commands to send to the [`TransactionsManager`](crate::transactions::TransactionsManager)
@@ -853,14 +1095,19 @@ pub enum NetworkTransactionEvent {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{test_utils::Testnet, NetworkConfigBuilder, NetworkManager};
+    use crate::{
+        peers::{PeersConfig, PeersManager},
+        test_utils::Testnet,
+        NetworkConfigBuilder, NetworkManager,
+    };
     use reth_interfaces::sync::{NetworkSyncUpdater, SyncState};
     use reth_network_api::NetworkInfo;
+    use reth_primitives::NodeRecord;
     use reth_provider::test_utils::NoopProvider;
     use reth_rlp::Decodable;
     use reth_transaction_pool::test_utils::{testing_pool, MockTransaction};
     use secp256k1::SecretKey;
-    use std::future::poll_fn;
+    use std::{future::poll_fn, net::SocketAddr};
 
     #[tokio::test(flavor = "multi_thread")]
     #[cfg_attr(not(feature = "geth-tests"), ignore)]
@@ -1211,4 +1458,38 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_propagation_peers() {
+        let trusted = PeerId::random();
+        let peers = PeersManager::new(PeersConfig::default().with_trusted_nodes(HashSet::from([
+            NodeRecord::new(SocketAddr::from(([127, 0, 0, 1], 30303)), trusted),
+        ])));
+        let handle = peers.handle();
+
+        let policy = PropagationPolicy::default();
+        assert!(policy.peers.contains(&trusted, &handle));
+        assert!(policy.peers.contains(&PeerId::random(), &handle));
+
+        let policy = policy.with_trusted_peers_only();
+        assert!(policy.peers.contains(&trusted, &handle));
+        assert!(!policy.peers.contains(&PeerId::random(), &handle));
+    }
+
+    #[test]
+    fn test_peer_rate_limiter() {
+        let mut limiter = PeerRateLimiter::new(PeerRateLimit {
+            max_transactions: 2,
+            interval: Duration::from_secs(60),
+        });
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+
+        let mut limiter =
+            PeerRateLimiter::new(PeerRateLimit { max_transactions: 1, interval: Duration::ZERO });
+        assert!(limiter.try_acquire());
+        // the interval elapsed so the budget is refilled
+        assert!(limiter.try_acquire());
+    }
 }
@@ -618,4 +618,11 @@ impl BlobTransactionSidecar {
         self.commitments.len() * BYTES_PER_COMMITMENT + // commitments
         self.proofs.len() * BYTES_PER_PROOF // proofs
     }
+
+    /// Returns the versioned hashes of the blob commitments, in the same order as the blobs.
+    pub fn versioned_hashes(&self) -> impl Iterator<Item = H256> + '_ {
+        self.commitments
+            .iter()
+            .map(|commitment| kzg_to_versioned_hash(KzgCommitment::from(*commitment.deref())))
+    }
 }
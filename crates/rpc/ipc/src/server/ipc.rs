@@ -10,10 +10,14 @@ use jsonrpsee::{
     server::{
         logger,
         logger::{Logger, TransportProtocol},
-        IdProvider,
+        BatchRequestConfig, IdProvider,
     },
     types::{
-        error::{reject_too_many_subscriptions, ErrorCode},
+        error::{
+            reject_too_big_batch_request, reject_too_many_subscriptions, ErrorCode,
+            BATCHES_NOT_SUPPORTED_CODE, BATCHES_NOT_SUPPORTED_MSG, SERVER_IS_BUSY_CODE,
+            SERVER_IS_BUSY_MSG,
+        },
         ErrorObject, Id, InvalidRequest, Notification, Params, Request,
     },
     BoundedSubscriptions, CallOrSubscription, MethodCallback, MethodSink, Methods,
@@ -30,6 +34,8 @@ type Notif<'a> = Notification<'a, Option<&'a JsonRawValue>>;
 pub(crate) struct Batch<'a, L: Logger> {
     data: Vec<u8>,
     call: CallData<'a, L>,
+    max_len: usize,
+    max_response_size: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -53,12 +59,15 @@ pub(crate) async fn process_batch_request<L>(b: Batch<'_, L>) -> Option<String>
 where
     L: Logger,
 {
-    let Batch { data, call } = b;
+    let Batch { data, call, max_len, max_response_size } = b;
 
     if let Ok(batch) = serde_json::from_slice::<Vec<&JsonRawValue>>(&data) {
+        if batch.len() > max_len {
+            return Some(batch_response_error(Id::Null, reject_too_big_batch_request(max_len)))
+        }
+
         let mut got_notif = false;
-        let mut batch_response =
-            BatchResponseBuilder::new_with_limit(call.max_response_body_size as usize);
+        let mut batch_response = BatchResponseBuilder::new_with_limit(max_response_size);
 
         let mut pending_calls: FuturesOrdered<_> = batch
             .into_iter()
@@ -242,8 +251,9 @@ pub(crate) struct HandleRequest<L: Logger> {
     pub(crate) methods: Methods,
     pub(crate) max_request_body_size: u32,
     pub(crate) max_response_body_size: u32,
+    pub(crate) max_batch_response_body_size: u32,
     pub(crate) max_log_length: u32,
-    pub(crate) batch_requests_supported: bool,
+    pub(crate) batch_requests_config: BatchRequestConfig,
     pub(crate) logger: L,
     pub(crate) conn: Arc<OwnedSemaphorePermit>,
     pub(crate) bounded_subscriptions: BoundedSubscriptions,
@@ -258,7 +268,9 @@ pub(crate) async fn handle_request<L: Logger>(
     let HandleRequest {
         methods,
         max_response_body_size,
+        max_batch_response_body_size,
         max_log_length,
+        batch_requests_config,
         logger,
         conn,
         bounded_subscriptions,
@@ -307,10 +319,50 @@ pub(crate) async fn handle_request<L: Logger>(
             None => None,
         }
     } else {
-        process_batch_request(Batch { data: request.into_bytes(), call }).await
+        match batch_requests_config {
+            BatchRequestConfig::Disabled => Some(batch_response_error(
+                Id::Null,
+                ErrorObject::owned(
+                    BATCHES_NOT_SUPPORTED_CODE,
+                    BATCHES_NOT_SUPPORTED_MSG,
+                    None::<()>,
+                ),
+            )),
+            BatchRequestConfig::Limit(limit) => {
+                process_batch_request(Batch {
+                    data: request.into_bytes(),
+                    call,
+                    max_len: limit as usize,
+                    max_response_size: max_batch_response_body_size as usize,
+                })
+                .await
+            }
+            BatchRequestConfig::Unlimited => {
+                process_batch_request(Batch {
+                    data: request.into_bytes(),
+                    call,
+                    max_len: usize::MAX,
+                    max_response_size: max_batch_response_body_size as usize,
+                })
+                .await
+            }
+        }
     };
 
     drop(conn);
 
     res
 }
+
+/// Returns the error response for a request that is rejected because the connection has too many
+/// requests in flight.
+pub(crate) fn reject_server_busy(request: &str) -> String {
+    let id = serde_json::from_str::<InvalidRequest<'_>>(request)
+        .map(|req| req.id.into_owned())
+        .unwrap_or(Id::Null);
+    MethodResponse::error(
+        id,
+        ErrorObject::owned(SERVER_IS_BUSY_CODE, SERVER_IS_BUSY_MSG, None::<()>),
+    )
+    .result
+}
@@ -7,7 +7,9 @@ use crate::server::{
 use futures::{FutureExt, Stream, StreamExt};
 use jsonrpsee::{
     core::{Error, TEN_MB_SIZE_BYTES},
-    server::{logger::Logger, IdProvider, RandomIntegerIdProvider, ServerHandle},
+    server::{
+        logger::Logger, BatchRequestConfig, IdProvider, RandomIntegerIdProvider, ServerHandle,
+    },
     BoundedSubscriptions, MethodSink, Methods,
 };
 use std::{
@@ -19,9 +21,9 @@ use std::{
 };
 use tokio::{
     io::{AsyncRead, AsyncWrite},
-    sync::{oneshot, watch, OwnedSemaphorePermit},
+    sync::{oneshot, watch, OwnedSemaphorePermit, Semaphore},
 };
-use tower::{layer::util::Identity, Service};
+use tower::{layer::util::Identity, Layer, Service};
 use tracing::{debug, trace, warn};
 
 // re-export so can be used during builder setup
@@ -46,7 +48,17 @@ pub struct IpcServer<B = Identity, L = ()> {
     service_builder: tower::ServiceBuilder<B>,
 }
 
-impl IpcServer {
+impl<B> IpcServer<B>
+where
+    B: Layer<TowerService<()>> + Send + 'static,
+    <B as Layer<TowerService<()>>>::Service: Service<
+            String,
+            Response = Option<String>,
+            Error = Box<dyn std::error::Error + Send + Sync + 'static>,
+        > + Send
+        + 'static,
+    <<B as Layer<TowerService<()>>>::Service as Service<String>>::Future: Send + Unpin,
+{
     /// Returns the configured [Endpoint]
     pub fn endpoint(&self) -> &Endpoint {
         &self.endpoint
@@ -110,7 +122,12 @@ impl IpcServer {
         let message_buffer_capacity = self.cfg.message_buffer_capacity;
         let max_request_body_size = self.cfg.max_request_body_size;
         let max_response_body_size = self.cfg.max_response_body_size;
+        let max_batch_response_body_size =
+            self.cfg.max_batch_response_body_size.unwrap_or(max_response_body_size);
         let max_log_length = self.cfg.max_log_length;
+        let batch_requests_config = self.cfg.batch_requests_config;
+        let max_concurrent_requests_per_connection =
+            self.cfg.max_concurrent_requests_per_connection;
         let id_provider = self.id_provider;
         let max_subscriptions_per_connection = self.cfg.max_subscriptions_per_connection;
         let logger = self.logger;
@@ -157,7 +174,9 @@ impl IpcServer {
                             methods: methods.clone(),
                             max_request_body_size,
                             max_response_body_size,
+                            max_batch_response_body_size,
                             max_log_length,
+                            batch_requests_config,
                             id_provider: id_provider.clone(),
                             stop_handle: stop_handle.clone(),
                             max_subscriptions_per_connection,
@@ -168,6 +187,9 @@ impl IpcServer {
                                 max_subscriptions_per_connection,
                             ),
                             method_sink,
+                            request_permits: Arc::new(Semaphore::new(
+                                max_concurrent_requests_per_connection as usize,
+                            )),
                         },
                     };
 
@@ -193,7 +215,7 @@ impl IpcServer {
     }
 }
 
-impl std::fmt::Debug for IpcServer {
+impl<B> std::fmt::Debug for IpcServer<B> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("IpcServer")
             .field("endpoint", &self.endpoint.path())
@@ -213,10 +235,14 @@ pub(crate) struct ServiceData<L: Logger> {
     pub(crate) max_request_body_size: u32,
     /// Max request body size.
     pub(crate) max_response_body_size: u32,
+    /// Max size of a batch response.
+    pub(crate) max_batch_response_body_size: u32,
     /// Max length for logging for request and response
     ///
     /// Logs bigger than this limit will be truncated.
     pub(crate) max_log_length: u32,
+    /// How batch requests are handled.
+    pub(crate) batch_requests_config: BatchRequestConfig,
     /// Subscription ID provider.
     pub(crate) id_provider: Arc<dyn IdProvider>,
     /// Stop handle.
@@ -235,6 +261,8 @@ pub(crate) struct ServiceData<L: Logger> {
     ///
     /// This is used for subscriptions.
     pub(crate) method_sink: MethodSink,
+    /// Limits the number of requests that are handled concurrently for this connection.
+    pub(crate) request_permits: Arc<Semaphore>,
 }
 
 /// JsonRPSee service compatible with `tower`.
@@ -266,13 +294,20 @@ impl<L: Logger> Service<String> for TowerService<L> {
     fn call(&mut self, request: String) -> Self::Future {
         trace!("{:?}", request);
 
+        let Ok(permit) = self.inner.request_permits.clone().try_acquire_owned() else {
+            trace!("too many concurrent requests on connection {}", self.inner.conn_id);
+            let response = ipc::reject_server_busy(&request);
+            return Box::pin(async move { Ok(Some(response)) })
+        };
+
         // handle the request
         let data = ipc::HandleRequest {
             methods: self.inner.methods.clone(),
             max_request_body_size: self.inner.max_request_body_size,
             max_response_body_size: self.inner.max_response_body_size,
+            max_batch_response_body_size: self.inner.max_batch_response_body_size,
             max_log_length: self.inner.max_log_length,
-            batch_requests_supported: true,
+            batch_requests_config: self.inner.batch_requests_config,
             logger: self.inner.logger.clone(),
             conn: self.inner.conn.clone(),
             bounded_subscriptions: self.inner.bounded_subscriptions.clone(),
@@ -286,7 +321,11 @@ impl<L: Logger> Service<String> for TowerService<L> {
         // example tracing calls are relatively CPU expensive on serde::serialize alone, moving this
         // work to a separate task takes the pressure off the connection so all concurrent responses
         // are also serialized concurrently and the connection can focus on read+write
-        let f = tokio::task::spawn(async move { ipc::handle_request(request, data).await });
+        let f = tokio::task::spawn(async move {
+            let response = ipc::handle_request(request, data).await;
+            drop(permit);
+            response
+        });
         Box::pin(async move { f.await.map_err(|err| err.into()) })
     }
 }
@@ -377,6 +416,8 @@ pub struct Settings {
     max_request_body_size: u32,
     /// Maximum size in bytes of a response.
     max_response_body_size: u32,
+    /// Maximum size in bytes of a batch response, defaults to the maximum size of a response.
+    max_batch_response_body_size: Option<u32>,
     /// Max length for logging for requests and responses
     ///
     /// Logs bigger than this limit will be truncated.
@@ -385,6 +426,10 @@ pub struct Settings {
     max_connections: u32,
     /// Maximum number of subscriptions per connection.
     max_subscriptions_per_connection: u32,
+    /// Maximum number of requests that are handled concurrently per connection.
+    max_concurrent_requests_per_connection: u32,
+    /// How batch requests are handled.
+    batch_requests_config: BatchRequestConfig,
     /// Number of messages that server is allowed `buffer` until backpressure kicks in.
     message_buffer_capacity: u32,
     /// Custom tokio runtime to run the server on.
@@ -396,9 +441,12 @@ impl Default for Settings {
         Self {
             max_request_body_size: TEN_MB_SIZE_BYTES,
             max_response_body_size: TEN_MB_SIZE_BYTES,
+            max_batch_response_body_size: None,
             max_log_length: 4096,
             max_connections: 100,
             max_subscriptions_per_connection: 1024,
+            max_concurrent_requests_per_connection: 1024,
+            batch_requests_config: BatchRequestConfig::Unlimited,
             message_buffer_capacity: 1024,
             tokio_runtime: None,
         }
@@ -439,6 +487,13 @@ impl<B, L> Builder<B, L> {
         self
     }
 
+    /// Set the maximum size of a batch response body in bytes. Default is the maximum size of a
+    /// response body.
+    pub fn max_batch_response_body_size(mut self, size: u32) -> Self {
+        self.settings.max_batch_response_body_size = Some(size);
+        self
+    }
+
     /// Set the maximum size of a log
     pub fn max_log_length(mut self, size: u32) -> Self {
         self.settings.max_log_length = size;
@@ -457,6 +512,25 @@ impl<B, L> Builder<B, L> {
         self
     }
 
+    /// Set the maximum number of requests that are handled concurrently per connection. Default is
+    /// 1024.
+    ///
+    /// Requests that exceed the limit are rejected with a "server is busy" error.
+    pub fn max_concurrent_requests_per_connection(mut self, max: u32) -> Self {
+        self.settings.max_concurrent_requests_per_connection = max;
+        self
+    }
+
+    /// Configure how [batch requests](https://www.jsonrpc.org/specification#batch) are handled by
+    /// the server.
+    ///
+    /// Default: batch requests are allowed and can be arbitrary big but the total size of the
+    /// batch response is limited by the max response body size.
+    pub fn set_batch_request_config(mut self, cfg: BatchRequestConfig) -> Self {
+        self.settings.batch_requests_config = cfg;
+        self
+    }
+
     /// The server enforces backpressure which means that
     /// `n` messages can be buffered and if the client
     /// can't keep with up the server.
@@ -574,7 +648,10 @@ mod tests {
     use crate::client::IpcClientBuilder;
     use futures::future::{select, Either};
     use jsonrpsee::{
-        core::client::{ClientT, Subscription, SubscriptionClientT},
+        core::{
+            client::{ClientT, Subscription, SubscriptionClientT},
+            params::BatchRequestBuilder,
+        },
         rpc_params, PendingSubscriptionSink, RpcModule, SubscriptionMessage,
     };
     use parity_tokio_ipc::dummy_endpoint;
@@ -656,6 +733,52 @@ mod tests {
         assert_eq!(response, msg);
     }
 
+    #[tokio::test]
+    async fn test_batch_request_limit() {
+        let endpoint = dummy_endpoint();
+        let server = Builder::default()
+            .set_batch_request_config(BatchRequestConfig::Limit(2))
+            .build(&endpoint)
+            .unwrap();
+        let mut module = RpcModule::new(());
+        module.register_method("eth_chainId", |_, _| "0x1").unwrap();
+        let handle = server.start(module).await.unwrap();
+        tokio::spawn(handle.stopped());
+
+        let client = IpcClientBuilder::default().build(endpoint).await.unwrap();
+
+        let mut batch = BatchRequestBuilder::new();
+        batch.insert("eth_chainId", rpc_params![]).unwrap();
+        batch.insert("eth_chainId", rpc_params![]).unwrap();
+        let response = client.batch_request::<String>(batch.clone()).await.unwrap();
+        assert_eq!(response.num_successful_calls(), 2);
+
+        batch.insert("eth_chainId", rpc_params![]).unwrap();
+        assert!(client.batch_request::<String>(batch).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_batch_response_size_limit() {
+        let endpoint = dummy_endpoint();
+        let server = Builder::default().max_batch_response_body_size(64).build(&endpoint).unwrap();
+        let mut module = RpcModule::new(());
+        module.register_method("eth_chainId", |_, _| "0x1").unwrap();
+        let handle = server.start(module).await.unwrap();
+        tokio::spawn(handle.stopped());
+
+        let client = IpcClientBuilder::default().build(endpoint).await.unwrap();
+
+        // single responses are not limited by the batch budget
+        let response: String = client.request("eth_chainId", rpc_params![]).await.unwrap();
+        assert_eq!(response, "0x1");
+
+        let mut batch = BatchRequestBuilder::new();
+        for _ in 0..4 {
+            batch.insert("eth_chainId", rpc_params![]).unwrap();
+        }
+        assert!(client.batch_request::<String>(batch).await.is_err());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_rpc_subscription() {
         let endpoint = dummy_endpoint();
@@ -1,6 +1,7 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::NodeRecord;
-use reth_rpc_types::{NodeInfo, PeerInfo};
+use reth_primitives::{NodeRecord, PeerId};
+use reth_rpc_types::{BackupManifest, NodeInfo, PeerEvent, PeerInfo};
+use std::path::PathBuf;
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -36,14 +37,32 @@ pub trait AdminApi {
     async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
 
     /// Creates an RPC subscription which serves events received from the network.
+    ///
+    /// If peer ids are given, only events about these peers are served.
     #[subscription(
         name = "peerEvents",
         unsubscribe = "peerEvents_unsubscribe",
-        item = String
+        item = PeerEvent
     )]
-    async fn subscribe_peer_events(&self) -> jsonrpsee::core::SubscriptionResult;
+    async fn subscribe_peer_events(
+        &self,
+        peers: Option<Vec<PeerId>>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// Returns the ENR of the node.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
 }
+
+/// Admin namespace rpc interface for backing up the database of a running node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+#[async_trait::async_trait]
+pub trait AdminBackupApi {
+    /// Writes a consistent copy of the database and a manifest describing it into the given
+    /// directory, relative to the backup directory of the node.
+    ///
+    /// Returns the manifest of the backup.
+    #[method(name = "backupDatabase")]
+    async fn backup_database(&self, dest: PathBuf) -> RpcResult<BackupManifest>;
+}
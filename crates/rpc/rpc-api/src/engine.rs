@@ -2,9 +2,10 @@ use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{Address, BlockHash, BlockId, BlockNumberOrTag, Bytes, H256, U256, U64};
 use reth_rpc_types::{
     engine::{
-        ExecutionPayloadBodiesV1, ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3,
-        ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3, ForkchoiceState,
-        ForkchoiceUpdated, PayloadAttributes, PayloadId, PayloadStatus, TransitionConfiguration,
+        BlobAndProofV1, ExecutionPayloadBodiesV1, ExecutionPayloadEnvelopeV2,
+        ExecutionPayloadEnvelopeV3, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3,
+        ForkchoiceState, ForkchoiceUpdated, PayloadAttributes, PayloadId, PayloadStatus,
+        TransitionConfiguration,
     },
     state::StateOverride,
     BlockOverrides, CallRequest, Filter, Log, RichBlock, SyncStatus,
@@ -117,6 +118,17 @@ pub trait EngineApi {
         count: U64,
     ) -> RpcResult<ExecutionPayloadBodiesV1>;
 
+    /// Returns the blobs and proofs for the given versioned hashes from the transaction pool.
+    ///
+    /// The entry is `null` for every blob that is not in the pool.
+    ///
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_getblobsv1>
+    #[method(name = "getBlobsV1")]
+    async fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<H256>,
+    ) -> RpcResult<Vec<Option<BlobAndProofV1>>>;
+
     /// See also <https://github.com/ethereum/execution-apis/blob/6709c2a795b707202e93c4f2867fa0bf2640a84f/src/engine/paris.md#engine_exchangetransitionconfigurationv1>
     ///
     /// Note: This method will be deprecated after the cancun hardfork:
@@ -202,6 +202,10 @@ pub trait EthApi {
     #[method(name = "maxPriorityFeePerGas")]
     async fn max_priority_fee_per_gas(&self) -> RpcResult<U256>;
 
+    /// Introduced in EIP-4844, returns the base fee per blob gas of the next block.
+    #[method(name = "blobBaseFee")]
+    async fn blob_base_fee(&self) -> RpcResult<U256>;
+
     /// Returns the Transaction fee history
     ///
     /// Introduced in EIP-1159 for getting information on the appropriate priority fee to use.
@@ -1,6 +1,9 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::Address;
-use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
+use reth_primitives::{Address, H256};
+use reth_rpc_types::txpool::{
+    TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolSenderStatus, TxpoolStatus,
+    TxpoolTxEvent,
+};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +37,18 @@ pub trait TxPoolApi {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent>;
+
+    /// Returns the nonce gaps of the given sender and, for each of its transactions, the sub-pool
+    /// it is placed in and why it isn't pending.
+    ///
+    /// Returns `None` if the pool holds no transactions of the sender.
+    #[method(name = "senderStatus")]
+    async fn txpool_sender_status(&self, from: Address) -> RpcResult<Option<TxpoolSenderStatus>>;
+
+    /// Returns the recorded events of a transaction that is or recently was in the pool, oldest
+    /// first, e.g. why it was evicted or by which transaction it was replaced.
+    ///
+    /// The history is bounded, so this is empty for transactions that were seen long ago.
+    #[method(name = "txHistory")]
+    async fn txpool_tx_history(&self, hash: H256) -> RpcResult<Vec<TxpoolTxEvent>>;
 }
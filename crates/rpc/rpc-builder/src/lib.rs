@@ -119,11 +119,12 @@ use reth_provider::{
 use reth_rpc::{
     eth::{
         cache::{cache_new_blocks_task, EthStateCache},
+        filter_reorgs_task,
         gas_oracle::GasPriceOracle,
     },
     AdminApi, DebugApi, EngineEthApi, EthApi, EthFilter, EthPubSub, EthSubscriptionIdProvider,
-    NetApi, OtterscanApi, RPCApi, RethApi, TraceApi, TracingCallGuard, TracingCallPool, TxPoolApi,
-    Web3Api,
+    NetApi, OtterscanApi, RPCApi, RateLimitConfig, RateLimitLayer, RequestLimitConfig,
+    RequestLimitLayer, RethApi, TraceApi, TracingCallGuard, TracingCallPool, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::{servers::*, EngineApiServer};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -160,7 +161,7 @@ mod metrics;
 
 // re-export for convenience
 pub use crate::eth::{EthConfig, EthHandlers};
-pub use jsonrpsee::server::ServerBuilder;
+pub use jsonrpsee::server::{BatchRequestConfig, ServerBuilder};
 pub use reth_ipc::server::{Builder as IpcServerBuilder, Endpoint};
 use reth_network_api::noop::NoopNetwork;
 use reth_transaction_pool::noop::NoopTransactionPool;
@@ -534,6 +535,15 @@ impl RpcModuleSelection {
         }
     }
 
+    /// Returns true if the given module is part of the selection
+    pub fn contains(&self, module: &RethRpcModule) -> bool {
+        match self {
+            RpcModuleSelection::All => true,
+            RpcModuleSelection::Standard => Self::STANDARD_MODULES.contains(module),
+            RpcModuleSelection::Selection(sel) => sel.contains(module),
+        }
+    }
+
     /// Creates a new [RpcModule] based on the configured reth modules.
     ///
     /// Note: This will always create new instance of the module handlers and is therefor only
@@ -770,11 +780,12 @@ impl<Provider, Pool, Network, Tasks, Events>
     RethModuleRegistry<Provider, Pool, Network, Tasks, Events>
 where
     Network: NetworkInfo + Peers + Clone + 'static,
+    Tasks: TaskSpawner + Clone + 'static,
 {
     /// Register Admin Namespace
     pub fn register_admin(&mut self) -> &mut Self {
-        self.modules
-            .insert(RethRpcModule::Admin, AdminApi::new(self.network.clone()).into_rpc().into());
+        let admin = AdminApi::with_spawner(self.network.clone(), Box::new(self.executor.clone()));
+        self.modules.insert(RethRpcModule::Admin, admin.into_rpc().into());
         self
     }
 
@@ -929,9 +940,12 @@ where
                 self.modules
                     .entry(namespace)
                     .or_insert_with(|| match namespace {
-                        RethRpcModule::Admin => {
-                            AdminApi::new(self.network.clone()).into_rpc().into()
-                        }
+                        RethRpcModule::Admin => AdminApi::with_spawner(
+                            self.network.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Debug => DebugApi::new(
                             self.provider.clone(),
                             eth_api.clone(),
@@ -1032,15 +1046,22 @@ where
                 self.pool.clone(),
                 cache.clone(),
                 self.config.eth.max_logs_per_response,
-                executor.clone(),
+                executor,
+            );
+            let active_filters = filter.active_filters().clone();
+            let canon_state_notifications = self.events.canonical_state_stream();
+            self.executor.spawn_critical(
+                "eth filter reorgs task",
+                Box::pin(async move {
+                    filter_reorgs_task(active_filters, canon_state_notifications).await;
+                }),
             );
 
-            let pubsub = EthPubSub::with_spawner(
+            let pubsub = EthPubSub::new(
                 self.provider.clone(),
                 self.pool.clone(),
                 self.events.clone(),
                 self.network.clone(),
-                executor,
             );
 
             let eth = EthHandlers { api, cache, filter, pubsub, tracing_call_pool };
@@ -1089,6 +1110,10 @@ pub struct RpcServerConfig {
     ipc_server_config: Option<IpcServerBuilder>,
     /// The Endpoint where to launch the ipc server
     ipc_endpoint: Option<Endpoint>,
+    /// Compute unit quotas for http and ipc requests
+    rate_limit: Option<RateLimitConfig>,
+    /// Per connection limits for http requests
+    request_limit: Option<RequestLimitConfig>,
 }
 
 impl fmt::Debug for RpcServerConfig {
@@ -1101,6 +1126,8 @@ impl fmt::Debug for RpcServerConfig {
             .field("ws_addr", &self.ws_addr)
             .field("ipc_server_config", &self.ipc_server_config)
             .field("ipc_endpoint", &self.ipc_endpoint.as_ref().map(|endpoint| endpoint.path()))
+            .field("rate_limit", &self.rate_limit)
+            .field("request_limit", &self.request_limit)
             .finish()
     }
 }
@@ -1212,6 +1239,25 @@ impl RpcServerConfig {
         self
     }
 
+    /// Configures compute unit quotas for the http and ipc servers.
+    ///
+    /// Calls over ws connections can't be limited, so building the server fails if a ws server is
+    /// configured as well, see [RateLimitLayer].
+    pub fn with_rate_limit(mut self, config: RateLimitConfig) -> Self {
+        self.rate_limit = Some(config);
+        self
+    }
+
+    /// Configures the per connection limits for http requests.
+    ///
+    /// This applies to the http requests of the http and ws server(s), see [RequestLimitLayer].
+    /// Calls over ws connections are bounded by the message buffer capacity of the
+    /// [ServerBuilder] instead.
+    pub fn with_request_limit(mut self, config: RequestLimitConfig) -> Self {
+        self.request_limit = Some(config);
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be be launched on [RpcServerConfig::start].
@@ -1244,7 +1290,10 @@ impl RpcServerConfig {
     /// Builds the ws and http server(s).
     ///
     /// If both are on the same port, they are combined into one server.
-    async fn build_ws_http(&mut self) -> Result<WsHttpServer, RpcError> {
+    async fn build_ws_http(
+        &mut self,
+        rate_limit: RateLimitLayer,
+    ) -> Result<WsHttpServer, RpcError> {
         let http_socket_addr = self.http_addr.unwrap_or(SocketAddr::V4(SocketAddrV4::new(
             Ipv4Addr::LOCALHOST,
             DEFAULT_HTTP_RPC_PORT,
@@ -1254,6 +1303,10 @@ impl RpcServerConfig {
             .ws_addr
             .unwrap_or(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, DEFAULT_WS_RPC_PORT)));
         let metrics = RpcServerMetrics::default();
+        let request_limit = self
+            .request_limit
+            .map(RequestLimitLayer::new)
+            .unwrap_or_else(RequestLimitLayer::disabled);
         // If both are configured on the same port, we combine them into one server.
         if self.http_addr == self.ws_addr &&
             self.http_server_config.is_some() &&
@@ -1286,6 +1339,8 @@ impl RpcServerConfig {
                 cors,
                 ServerKind::WsHttp(http_socket_addr),
                 metrics.clone(),
+                rate_limit.clone(),
+                request_limit.clone(),
             )
             .await?;
             return Ok(WsHttpServer {
@@ -1308,6 +1363,8 @@ impl RpcServerConfig {
                 self.ws_cors_domains.take(),
                 ServerKind::WS(ws_socket_addr),
                 metrics.clone(),
+                rate_limit.clone(),
+                request_limit.clone(),
             )
             .await?;
             ws_local_addr = Some(addr);
@@ -1322,6 +1379,8 @@ impl RpcServerConfig {
                 self.http_cors_domains.take(),
                 ServerKind::Http(http_socket_addr),
                 metrics.clone(),
+                rate_limit.clone(),
+                request_limit.clone(),
             )
             .await?;
             http_local_addr = Some(addr);
@@ -1341,14 +1400,29 @@ impl RpcServerConfig {
     ///
     /// Note: The server ist not started and does nothing unless polled, See also [RpcServer::start]
     pub async fn build(mut self) -> Result<RpcServer, RpcError> {
+        if self.rate_limit.is_some() && self.ws_server_config.is_some() {
+            return Err(RpcError::Custom(
+                "rate limits can't be enforced for ws connections, disable the ws server"
+                    .to_string(),
+            ))
+        }
+
+        // the quotas are shared by all servers
+        let rate_limit = self
+            .rate_limit
+            .clone()
+            .map(RateLimitLayer::new)
+            .unwrap_or_else(RateLimitLayer::disabled);
+
         let mut server = RpcServer::empty();
-        server.ws_http = self.build_ws_http().await?;
+        server.ws_http = self.build_ws_http(rate_limit.clone()).await?;
 
         if let Some(builder) = self.ipc_server_config {
             let ipc_path = self
                 .ipc_endpoint
                 .unwrap_or_else(|| Endpoint::new(DEFAULT_IPC_ENDPOINT.to_string()));
-            let ipc = builder.build(ipc_path.path())?;
+            let middleware = tower::ServiceBuilder::new().layer(rate_limit);
+            let ipc = builder.set_middleware(middleware).build(ipc_path.path())?;
             server.ipc = Some(ipc);
         }
 
@@ -1612,9 +1686,14 @@ impl Default for WsHttpServers {
 /// Http Servers Enum
 enum WsHttpServerKind {
     /// Http server
-    Plain(Server<Identity, RpcServerMetrics>),
+    Plain(Server<Stack<RateLimitLayer, Stack<RequestLimitLayer, Identity>>, RpcServerMetrics>),
     /// Http server with cors
-    WithCors(Server<Stack<CorsLayer, Identity>, RpcServerMetrics>),
+    WithCors(
+        Server<
+            Stack<RateLimitLayer, Stack<RequestLimitLayer, Stack<CorsLayer, Identity>>>,
+            RpcServerMetrics,
+        >,
+    ),
 }
 
 // === impl WsHttpServerKind ===
@@ -1635,10 +1714,15 @@ impl WsHttpServerKind {
         cors_domains: Option<String>,
         server_kind: ServerKind,
         metrics: RpcServerMetrics,
+        rate_limit: RateLimitLayer,
+        request_limit: RequestLimitLayer,
     ) -> Result<(Self, SocketAddr), RpcError> {
         if let Some(cors) = cors_domains.as_deref().map(cors::create_cors_layer) {
             let cors = cors.map_err(|err| RpcError::Custom(err.to_string()))?;
-            let middleware = tower::ServiceBuilder::new().layer(cors);
+            // the rate limit is innermost, so the server records the remote address of every
+            // request it charges
+            let middleware =
+                tower::ServiceBuilder::new().layer(cors).layer(request_limit).layer(rate_limit);
             let server = builder
                 .set_middleware(middleware)
                 .set_logger(metrics)
@@ -1649,7 +1733,9 @@ impl WsHttpServerKind {
             let server = WsHttpServerKind::WithCors(server);
             Ok((server, local_addr))
         } else {
+            let middleware = tower::ServiceBuilder::new().layer(request_limit).layer(rate_limit);
             let server = builder
+                .set_middleware(middleware)
                 .set_logger(metrics)
                 .build(socket_addr)
                 .await
@@ -1666,7 +1752,7 @@ pub struct RpcServer {
     /// Configured ws,http servers
     ws_http: WsHttpServer,
     /// ipc server
-    ipc: Option<IpcServer>,
+    ipc: Option<IpcServer<Stack<RateLimitLayer, Identity>>>,
 }
 
 // === impl RpcServer ===
@@ -1850,6 +1936,15 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn selection_contains_module() {
+        assert!(RpcModuleSelection::All.contains(&RethRpcModule::Admin));
+        assert!(RpcModuleSelection::Standard.contains(&RethRpcModule::Eth));
+        assert!(!RpcModuleSelection::Standard.contains(&RethRpcModule::Admin));
+        assert!(RpcModuleSelection::Selection(vec![RethRpcModule::Admin])
+            .contains(&RethRpcModule::Admin));
+    }
+
     #[test]
     fn test_rpc_module_str() {
         macro_rules! assert_rpc_module {
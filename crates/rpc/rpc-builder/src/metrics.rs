@@ -6,6 +6,7 @@ use reth_metrics::{
     metrics::{Counter, Histogram},
     Metrics,
 };
+use reth_rpc::RemoteAddrSlot;
 use std::{net::SocketAddr, time::Instant};
 
 /// Metrics for the rpc server
@@ -36,10 +37,12 @@ impl Logger for RpcServerMetrics {
     type Instant = Instant;
     fn on_connect(
         &self,
-        _remote_addr: SocketAddr,
-        _request: &HttpRequest,
+        remote_addr: SocketAddr,
+        request: &HttpRequest,
         transport: TransportProtocol,
     ) {
+        // the rate limiter identifies callers by the remote address
+        RemoteAddrSlot::record(request, remote_addr);
         match transport {
             TransportProtocol::Http => {}
             TransportProtocol::WebSocket => self.ws_session_opened.increment(1),
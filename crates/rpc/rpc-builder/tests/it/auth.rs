@@ -20,6 +20,7 @@ where
     EngineApiClient::get_payload_v2(client, PayloadId::new([0, 0, 0, 0, 0, 0, 0, 0])).await;
     EngineApiClient::get_payload_bodies_by_hash_v1(client, vec![]).await;
     EngineApiClient::get_payload_bodies_by_range_v1(client, 0u64.into(), 1u64.into()).await;
+    EngineApiClient::get_blobs_v1(client, vec![]).await;
     EngineApiClient::exchange_transition_configuration(client, TransitionConfiguration::default())
         .await;
     EngineApiClient::exchange_capabilities(client, vec![]).await;
@@ -109,11 +109,13 @@ where
         .await
         .unwrap();
     EthApiClient::syncing(client).await.unwrap();
+    EthApiClient::sign_transaction(client, call_request.clone()).await.unwrap_err();
     EthApiClient::send_transaction(client, transaction_request).await.unwrap_err();
     EthApiClient::hashrate(client).await.unwrap();
     EthApiClient::submit_hashrate(client, U256::default(), H256::default()).await.unwrap();
     EthApiClient::gas_price(client).await.unwrap_err();
     EthApiClient::max_priority_fee_per_gas(client).await.unwrap_err();
+    EthApiClient::blob_base_fee(client).await.unwrap();
 
     // Unimplemented
     assert!(is_unimplemented(
@@ -128,9 +130,6 @@ where
             .err()
             .unwrap()
     ));
-    assert!(is_unimplemented(
-        EthApiClient::sign_transaction(client, call_request.clone()).await.err().unwrap()
-    ));
 }
 
 async fn test_basic_debug_calls<C>(client: &C)
@@ -280,6 +279,7 @@ async fn test_call_admin_functions_ws() {
     let handle = launch_ws(vec![RethRpcModule::Admin]).await;
     let client = handle.ws_client().await.unwrap();
     test_basic_admin_calls(&client).await;
+    AdminApiClient::subscribe_peer_events(&client, None).await.unwrap();
 }
 
 #[tokio::test(flavor = "multi_thread")]
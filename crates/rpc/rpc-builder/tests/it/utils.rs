@@ -11,7 +11,10 @@ use reth_rpc_builder::{
 };
 use reth_rpc_engine_api::EngineApi;
 use reth_tasks::TokioTaskExecutor;
-use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+use reth_transaction_pool::{
+    blobstore::NoopBlobStore,
+    test_utils::{testing_pool, TestPool},
+};
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use tokio::sync::mpsc::unbounded_channel;
 
@@ -31,6 +34,7 @@ pub async fn launch_auth(secret: JwtSecret) -> AuthServerHandle {
         beacon_engine_handle,
         spawn_test_payload_service().into(),
         Box::<TokioTaskExecutor>::default(),
+        Box::<NoopBlobStore>::default(),
     );
     let module = AuthRpcModule::new(engine_api);
     module.start_server(config).await.unwrap()
@@ -1,5 +1,7 @@
 use crate::{
-    payload::PayloadOrAttributes, EngineApiError, EngineApiMessageVersion, EngineApiResult,
+    payload::PayloadOrAttributes,
+    recorder::{EngineApiCall, EngineApiRecorder, PendingEngineCall},
+    EngineApiError, EngineApiMessageVersion, EngineApiResult,
 };
 use async_trait::async_trait;
 use jsonrpsee_core::RpcResult;
@@ -10,12 +12,14 @@ use reth_primitives::{BlockHash, BlockHashOrNumber, BlockNumber, ChainSpec, Hard
 use reth_provider::{BlockReader, EvmEnvProvider, HeaderProvider, StateProviderFactory};
 use reth_rpc_api::EngineApiServer;
 use reth_rpc_types::engine::{
-    CancunPayloadFields, ExecutionPayload, ExecutionPayloadBodiesV1, ExecutionPayloadEnvelopeV2,
-    ExecutionPayloadEnvelopeV3, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3,
-    ForkchoiceUpdated, PayloadAttributes, PayloadId, PayloadStatus, TransitionConfiguration,
-    CAPABILITIES,
+    BlobAndProofV1, CancunPayloadFields, ExecutionPayload, ExecutionPayloadBodiesV1,
+    ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3, ExecutionPayloadV1, ExecutionPayloadV2,
+    ExecutionPayloadV3, ForkchoiceUpdated, PayloadAttributes, PayloadId, PayloadStatus,
+    TransitionConfiguration, CAPABILITIES,
 };
 use reth_tasks::TaskSpawner;
+use reth_transaction_pool::blobstore::BlobStore;
+use serde::Serialize;
 use std::sync::Arc;
 use tokio::sync::oneshot;
 use tracing::trace;
@@ -26,10 +30,15 @@ pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
 /// The upper limit for payload bodies request.
 const MAX_PAYLOAD_BODIES_LIMIT: u64 = 1024;
 
+/// The upper limit for blobs request.
+const MAX_BLOBS_LIMIT: usize = 128;
+
 /// The Engine API implementation that grants the Consensus layer access to data and
 /// functions in the Execution layer that are crucial for the consensus process.
 pub struct EngineApi<Provider> {
     inner: Arc<EngineApiInner<Provider>>,
+    /// Records served calls, if enabled.
+    recorder: Option<EngineApiRecorder>,
 }
 
 struct EngineApiInner<Provider> {
@@ -43,6 +52,8 @@ struct EngineApiInner<Provider> {
     payload_store: PayloadStore,
     /// For spawning and executing async tasks
     task_spawner: Box<dyn TaskSpawner>,
+    /// The blob store of the transaction pool.
+    blob_store: Box<dyn BlobStore>,
 }
 
 impl<Provider> EngineApi<Provider>
@@ -56,6 +67,7 @@ where
         beacon_consensus: BeaconConsensusEngineHandle,
         payload_store: PayloadStore,
         task_spawner: Box<dyn TaskSpawner>,
+        blob_store: Box<dyn BlobStore>,
     ) -> Self {
         let inner = Arc::new(EngineApiInner {
             provider,
@@ -63,8 +75,32 @@ where
             beacon_consensus,
             payload_store,
             task_spawner,
+            blob_store,
         });
-        Self { inner }
+        Self { inner, recorder: None }
+    }
+
+    /// Records every `newPayload`, `forkchoiceUpdated` and `getPayload` call served by the
+    /// [EngineApiServer] implementation with the given recorder.
+    pub fn with_recorder(mut self, recorder: EngineApiRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Marks the receipt of the call if a recorder is configured.
+    fn start_recording(&self, call: impl FnOnce() -> EngineApiCall) -> Option<PendingEngineCall> {
+        self.recorder.as_ref().map(|recorder| recorder.start(call()))
+    }
+
+    /// Records the result of the call if it was marked by [Self::start_recording].
+    fn finish_recording<T: Serialize>(
+        &self,
+        pending: Option<PendingEngineCall>,
+        result: &EngineApiResult<T>,
+    ) {
+        if let (Some(recorder), Some(pending)) = (&self.recorder, pending) {
+            recorder.finish(pending, result);
+        }
     }
 
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_newpayloadv1>
@@ -318,6 +354,30 @@ where
         Ok(result)
     }
 
+    /// Returns the blobs and proofs for the given versioned hashes from the transaction pool's blob
+    /// store, in the order they were requested.
+    pub fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<H256>,
+    ) -> EngineApiResult<Vec<Option<BlobAndProofV1>>> {
+        if versioned_hashes.len() > MAX_BLOBS_LIMIT {
+            return Err(EngineApiError::BlobRequestTooLarge { len: versioned_hashes.len() })
+        }
+
+        let blobs = self
+            .inner
+            .blob_store
+            .get_by_versioned_hashes(&versioned_hashes)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
+
+        Ok(blobs
+            .into_iter()
+            .map(|blob| {
+                blob.map(|blob| BlobAndProofV1 { blob: Box::new(blob.blob), proof: blob.proof })
+            })
+            .collect())
+    }
+
     /// Called to verify network configuration parameters and ensure that Consensus and Execution
     /// layers are using the latest configuration.
     pub async fn exchange_transition_configuration(
@@ -493,14 +553,20 @@ where
     /// Caution: This should not accept the `withdrawals` field
     async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> RpcResult<PayloadStatus> {
         trace!(target: "rpc::engine", "Serving engine_newPayloadV1");
-        Ok(EngineApi::new_payload_v1(self, payload).await?)
+        let pending = self.start_recording(|| EngineApiCall::NewPayloadV1(payload.clone()));
+        let res = EngineApi::new_payload_v1(self, payload).await;
+        self.finish_recording(pending, &res);
+        Ok(res?)
     }
 
     /// Handler for `engine_newPayloadV2`
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/shanghai.md#engine_newpayloadv2>
     async fn new_payload_v2(&self, payload: ExecutionPayloadV2) -> RpcResult<PayloadStatus> {
         trace!(target: "rpc::engine", "Serving engine_newPayloadV2");
-        Ok(EngineApi::new_payload_v2(self, payload).await?)
+        let pending = self.start_recording(|| EngineApiCall::NewPayloadV2(payload.clone()));
+        let res = EngineApi::new_payload_v2(self, payload).await;
+        self.finish_recording(pending, &res);
+        Ok(res?)
     }
 
     /// Handler for `engine_newPayloadV3`
@@ -512,8 +578,18 @@ where
         parent_beacon_block_root: H256,
     ) -> RpcResult<PayloadStatus> {
         trace!(target: "rpc::engine", "Serving engine_newPayloadV3");
-        Ok(EngineApi::new_payload_v3(self, payload, versioned_hashes, parent_beacon_block_root)
-            .await?)
+        let pending = self.start_recording(|| {
+            EngineApiCall::NewPayloadV3(
+                payload.clone(),
+                versioned_hashes.clone(),
+                parent_beacon_block_root,
+            )
+        });
+        let res =
+            EngineApi::new_payload_v3(self, payload, versioned_hashes, parent_beacon_block_root)
+                .await;
+        self.finish_recording(pending, &res);
+        Ok(res?)
     }
 
     /// Handler for `engine_forkchoiceUpdatedV1`
@@ -526,7 +602,13 @@ where
         payload_attributes: Option<PayloadAttributes>,
     ) -> RpcResult<ForkchoiceUpdated> {
         trace!(target: "rpc::engine", "Serving engine_forkchoiceUpdatedV1");
-        Ok(EngineApi::fork_choice_updated_v1(self, fork_choice_state, payload_attributes).await?)
+        let pending = self.start_recording(|| {
+            EngineApiCall::ForkchoiceUpdatedV1(fork_choice_state, payload_attributes.clone())
+        });
+        let res =
+            EngineApi::fork_choice_updated_v1(self, fork_choice_state, payload_attributes).await;
+        self.finish_recording(pending, &res);
+        Ok(res?)
     }
 
     /// Handler for `engine_forkchoiceUpdatedV2`
@@ -537,7 +619,13 @@ where
         payload_attributes: Option<PayloadAttributes>,
     ) -> RpcResult<ForkchoiceUpdated> {
         trace!(target: "rpc::engine", "Serving engine_forkchoiceUpdatedV2");
-        Ok(EngineApi::fork_choice_updated_v2(self, fork_choice_state, payload_attributes).await?)
+        let pending = self.start_recording(|| {
+            EngineApiCall::ForkchoiceUpdatedV2(fork_choice_state, payload_attributes.clone())
+        });
+        let res =
+            EngineApi::fork_choice_updated_v2(self, fork_choice_state, payload_attributes).await;
+        self.finish_recording(pending, &res);
+        Ok(res?)
     }
 
     /// Handler for `engine_forkchoiceUpdatedV2`
@@ -549,7 +637,13 @@ where
         payload_attributes: Option<PayloadAttributes>,
     ) -> RpcResult<ForkchoiceUpdated> {
         trace!(target: "rpc::engine", "Serving engine_forkchoiceUpdatedV3");
-        Ok(EngineApi::fork_choice_updated_v3(self, fork_choice_state, payload_attributes).await?)
+        let pending = self.start_recording(|| {
+            EngineApiCall::ForkchoiceUpdatedV3(fork_choice_state, payload_attributes.clone())
+        });
+        let res =
+            EngineApi::fork_choice_updated_v3(self, fork_choice_state, payload_attributes).await;
+        self.finish_recording(pending, &res);
+        Ok(res?)
     }
 
     /// Handler for `engine_getPayloadV1`
@@ -565,7 +659,10 @@ where
     /// > Provider software MAY stop the corresponding build process after serving this call.
     async fn get_payload_v1(&self, payload_id: PayloadId) -> RpcResult<ExecutionPayloadV1> {
         trace!(target: "rpc::engine", "Serving engine_getPayloadV1");
-        Ok(EngineApi::get_payload_v1(self, payload_id).await?)
+        let pending = self.start_recording(|| EngineApiCall::GetPayloadV1(payload_id));
+        let res = EngineApi::get_payload_v1(self, payload_id).await;
+        self.finish_recording(pending, &res);
+        Ok(res?)
     }
 
     /// Handler for `engine_getPayloadV2`
@@ -579,7 +676,10 @@ where
     /// > Provider software MAY stop the corresponding build process after serving this call.
     async fn get_payload_v2(&self, payload_id: PayloadId) -> RpcResult<ExecutionPayloadEnvelopeV2> {
         trace!(target: "rpc::engine", "Serving engine_getPayloadV2");
-        Ok(EngineApi::get_payload_v2(self, payload_id).await?)
+        let pending = self.start_recording(|| EngineApiCall::GetPayloadV2(payload_id));
+        let res = EngineApi::get_payload_v2(self, payload_id).await;
+        self.finish_recording(pending, &res);
+        Ok(res?)
     }
 
     /// Handler for `engine_getPayloadV3`
@@ -593,7 +693,10 @@ where
     /// > Provider software MAY stop the corresponding build process after serving this call.
     async fn get_payload_v3(&self, payload_id: PayloadId) -> RpcResult<ExecutionPayloadEnvelopeV3> {
         trace!(target: "rpc::engine", "Serving engine_getPayloadV3");
-        Ok(EngineApi::get_payload_v3(self, payload_id).await?)
+        let pending = self.start_recording(|| EngineApiCall::GetPayloadV3(payload_id));
+        let res = EngineApi::get_payload_v3(self, payload_id).await;
+        self.finish_recording(pending, &res);
+        Ok(res?)
     }
 
     /// Handler for `engine_getPayloadBodiesByHashV1`
@@ -630,6 +733,16 @@ where
         Ok(EngineApi::get_payload_bodies_by_range(self, start.as_u64(), count.as_u64()).await?)
     }
 
+    /// Handler for `engine_getBlobsV1`
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_getblobsv1>
+    async fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<H256>,
+    ) -> RpcResult<Vec<Option<BlobAndProofV1>>> {
+        trace!(target: "rpc::engine", "Serving engine_getBlobsV1");
+        Ok(EngineApi::get_blobs_v1(self, versioned_hashes)?)
+    }
+
     /// Handler for `engine_exchangeTransitionConfigurationV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_exchangeTransitionConfigurationV1>
     async fn exchange_transition_configuration(
@@ -656,13 +769,16 @@ impl<Provider> std::fmt::Debug for EngineApi<Provider> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::recorder::read_recorded_calls;
     use assert_matches::assert_matches;
     use reth_beacon_consensus::BeaconEngineMessage;
     use reth_interfaces::test_utils::generators::random_block;
     use reth_payload_builder::test_utils::spawn_test_payload_service;
     use reth_primitives::{SealedBlock, H256, MAINNET};
     use reth_provider::test_utils::MockEthProvider;
+    use reth_rpc_types::engine::PayloadStatusEnum;
     use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::blobstore::InMemoryBlobStore;
     use std::sync::Arc;
     use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 
@@ -672,14 +788,16 @@ mod tests {
         let payload_store = spawn_test_payload_service();
         let (to_engine, engine_rx) = unbounded_channel();
         let task_executor = Box::<TokioTaskExecutor>::default();
+        let blob_store = InMemoryBlobStore::default();
         let api = EngineApi::new(
             provider.clone(),
             chain_spec.clone(),
             BeaconConsensusEngineHandle::new(to_engine),
             payload_store.into(),
             task_executor,
+            Box::new(blob_store.clone()),
         );
-        let handle = EngineApiTestHandle { chain_spec, provider, from_api: engine_rx };
+        let handle = EngineApiTestHandle { chain_spec, provider, from_api: engine_rx, blob_store };
         (handle, api)
     }
 
@@ -687,6 +805,7 @@ mod tests {
         chain_spec: Arc<ChainSpec>,
         provider: Arc<MockEthProvider>,
         from_api: UnboundedReceiver<BeaconEngineMessage>,
+        blob_store: InMemoryBlobStore,
     }
 
     #[tokio::test]
@@ -699,6 +818,51 @@ mod tests {
         assert_matches!(handle.from_api.recv().await, Some(BeaconEngineMessage::NewPayload { .. }));
     }
 
+    impl EngineApiTestHandle {
+        /// Answers the next `newPayload` message with the given status.
+        async fn respond_to_new_payload(&mut self, status: PayloadStatus) {
+            let Some(BeaconEngineMessage::NewPayload { tx, .. }) = self.from_api.recv().await
+            else {
+                panic!("expected new payload message")
+            };
+            tx.send(Ok(status)).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_recorded_calls() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("engine-api.jsonl");
+        let status = PayloadStatus::from_status(PayloadStatusEnum::Syncing);
+
+        let (mut handle, api) = setup_engine_api();
+        let api = api.with_recorder(EngineApiRecorder::new(&path).unwrap());
+        let payload: ExecutionPayloadV1 = SealedBlock::default().into();
+        let (res, _) = tokio::join!(
+            EngineApiServer::new_payload_v1(&api, payload.clone()),
+            handle.respond_to_new_payload(status.clone())
+        );
+        assert_eq!(res.unwrap(), status);
+        // dropping the recorder waits until the calls are written
+        drop(api);
+
+        let calls = read_recorded_calls(&path).unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].call, EngineApiCall::NewPayloadV1(payload));
+
+        let (mut handle, api) = setup_engine_api();
+        for recorded in calls {
+            let EngineApiCall::NewPayloadV1(payload) = recorded.call else {
+                panic!("unexpected call {:?}", recorded.call)
+            };
+            let (res, _) = tokio::join!(
+                api.new_payload_v1(payload),
+                handle.respond_to_new_payload(status.clone())
+            );
+            assert_eq!(recorded.response, Some(serde_json::to_value(res.unwrap()).unwrap()));
+        }
+    }
+
     // tests covering `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`
     mod get_payload_bodies {
         use super::*;
@@ -810,6 +974,75 @@ mod tests {
         }
     }
 
+    // tests covering `engine_getBlobsV1`
+    mod get_blobs {
+        use super::*;
+        use reth_primitives::{
+            kzg::{Blob, Bytes48, BYTES_PER_BLOB},
+            BlobTransaction, BlobTransactionSidecar, Signature, Transaction, TransactionSigned,
+            TxEip4844,
+        };
+
+        /// Creates a blob transaction with a sidecar that holds `count` blobs.
+        ///
+        /// The commitments and proofs are not valid KZG commitments, which is fine because the
+        /// blob store does not validate them.
+        fn blob_transaction(seed: u8, count: u8) -> BlobTransaction {
+            let sidecar = BlobTransactionSidecar {
+                blobs: (0..count).map(|i| Blob::from([seed + i; BYTES_PER_BLOB])).collect(),
+                commitments: (0..count).map(|i| Bytes48::from([seed + i; 48])).collect(),
+                proofs: (0..count).map(|i| Bytes48::from([!(seed + i); 48])).collect(),
+            };
+            let transaction = Transaction::Eip4844(TxEip4844 {
+                nonce: seed as u64,
+                blob_versioned_hashes: sidecar.versioned_hashes().collect(),
+                ..Default::default()
+            });
+            let signed = TransactionSigned::from_transaction_and_signature(
+                transaction,
+                Signature::default(),
+            );
+            BlobTransaction::try_from_signed(signed, sidecar).unwrap()
+        }
+
+        #[tokio::test]
+        async fn request_too_large() {
+            let (_, api) = setup_engine_api();
+
+            let res = api.get_blobs_v1(vec![H256::zero(); MAX_BLOBS_LIMIT + 1]);
+            assert_matches!(res, Err(EngineApiError::BlobRequestTooLarge { .. }));
+        }
+
+        #[tokio::test]
+        async fn returns_blobs() {
+            let (handle, api) = setup_engine_api();
+
+            let txs = [blob_transaction(1, 2), blob_transaction(10, 1)];
+            for tx in &txs {
+                handle.blob_store.insert(tx.hash, tx.sidecar.clone()).unwrap();
+            }
+
+            let requested = vec![
+                txs[1].transaction.blob_versioned_hashes[0],
+                H256::random(),
+                txs[0].transaction.blob_versioned_hashes[1],
+                txs[0].transaction.blob_versioned_hashes[0],
+            ];
+            let res = api.get_blobs_v1(requested).unwrap();
+
+            let expected = |tx: &BlobTransaction, idx: usize| {
+                Some(BlobAndProofV1 {
+                    blob: Box::new(tx.sidecar.blobs[idx]),
+                    proof: tx.sidecar.proofs[idx],
+                })
+            };
+            assert_eq!(
+                res,
+                vec![expected(&txs[1], 0), None, expected(&txs[0], 1), expected(&txs[0], 0)]
+            );
+        }
+    }
+
     // https://github.com/ethereum/execution-apis/blob/main/src/engine/paris.md#specification-3
     mod exchange_transition_configuration {
         use super::*;
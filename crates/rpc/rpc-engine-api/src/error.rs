@@ -28,6 +28,12 @@ pub enum EngineApiError {
         /// The length that was requested.
         len: u64,
     },
+    /// The blob request length is too large.
+    #[error("Blob request too large: {len}")]
+    BlobRequestTooLarge {
+        /// The length that was requested.
+        len: usize,
+    },
     /// Thrown if engine_getPayloadBodiesByRangeV1 contains an invalid range
     #[error("invalid start or count, start: {start} count: {count}")]
     InvalidBodiesRange {
@@ -101,7 +107,8 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EngineApiError::NoWithdrawalsPostShanghai |
             EngineApiError::HasWithdrawalsPreShanghai => INVALID_PARAMS_CODE,
             EngineApiError::UnknownPayload => UNKNOWN_PAYLOAD_CODE,
-            EngineApiError::PayloadRequestTooLarge { .. } => REQUEST_TOO_LARGE_CODE,
+            EngineApiError::PayloadRequestTooLarge { .. } |
+            EngineApiError::BlobRequestTooLarge { .. } => REQUEST_TOO_LARGE_CODE,
             EngineApiError::UnsupportedFork => UNSUPPORTED_FORK_CODE,
 
             // Error responses from the consensus engine
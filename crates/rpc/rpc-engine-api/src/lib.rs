@@ -26,9 +26,13 @@ mod payload;
 /// Engine API error.
 mod error;
 
+/// Recording of Engine API calls.
+mod recorder;
+
 pub use engine_api::{EngineApi, EngineApiSender};
 pub use error::*;
 pub use message::EngineApiMessageVersion;
+pub use recorder::{read_recorded_calls, EngineApiCall, EngineApiRecorder, RecordedEngineCall};
 
 // re-export server trait for convenience
 pub use reth_rpc_api::EngineApiServer;
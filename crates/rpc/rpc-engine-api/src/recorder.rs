@@ -0,0 +1,271 @@
+use crate::EngineApiResult;
+use reth_interfaces::consensus::ForkchoiceState;
+use reth_primitives::H256;
+use reth_rpc_types::engine::{
+    ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3, PayloadAttributes, PayloadId,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+    thread::JoinHandle,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tracing::warn;
+
+/// An Engine API call that can be recorded and replayed.
+///
+/// Serialized as `{"method": ..., "params": [...]}`, mirroring the JSON-RPC request.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", content = "params")]
+pub enum EngineApiCall {
+    /// `engine_newPayloadV1`
+    #[serde(rename = "engine_newPayloadV1")]
+    NewPayloadV1(ExecutionPayloadV1),
+    /// `engine_newPayloadV2`
+    #[serde(rename = "engine_newPayloadV2")]
+    NewPayloadV2(ExecutionPayloadV2),
+    /// `engine_newPayloadV3`
+    #[serde(rename = "engine_newPayloadV3")]
+    NewPayloadV3(ExecutionPayloadV3, Vec<H256>, H256),
+    /// `engine_forkchoiceUpdatedV1`
+    #[serde(rename = "engine_forkchoiceUpdatedV1")]
+    ForkchoiceUpdatedV1(ForkchoiceState, Option<PayloadAttributes>),
+    /// `engine_forkchoiceUpdatedV2`
+    #[serde(rename = "engine_forkchoiceUpdatedV2")]
+    ForkchoiceUpdatedV2(ForkchoiceState, Option<PayloadAttributes>),
+    /// `engine_forkchoiceUpdatedV3`
+    #[serde(rename = "engine_forkchoiceUpdatedV3")]
+    ForkchoiceUpdatedV3(ForkchoiceState, Option<PayloadAttributes>),
+    /// `engine_getPayloadV1`
+    #[serde(rename = "engine_getPayloadV1")]
+    GetPayloadV1(PayloadId),
+    /// `engine_getPayloadV2`
+    #[serde(rename = "engine_getPayloadV2")]
+    GetPayloadV2(PayloadId),
+    /// `engine_getPayloadV3`
+    #[serde(rename = "engine_getPayloadV3")]
+    GetPayloadV3(PayloadId),
+}
+
+// === impl EngineApiCall ===
+
+impl EngineApiCall {
+    /// Returns the name of the JSON-RPC method.
+    pub fn method(&self) -> &'static str {
+        match self {
+            EngineApiCall::NewPayloadV1(..) => "engine_newPayloadV1",
+            EngineApiCall::NewPayloadV2(..) => "engine_newPayloadV2",
+            EngineApiCall::NewPayloadV3(..) => "engine_newPayloadV3",
+            EngineApiCall::ForkchoiceUpdatedV1(..) => "engine_forkchoiceUpdatedV1",
+            EngineApiCall::ForkchoiceUpdatedV2(..) => "engine_forkchoiceUpdatedV2",
+            EngineApiCall::ForkchoiceUpdatedV3(..) => "engine_forkchoiceUpdatedV3",
+            EngineApiCall::GetPayloadV1(..) => "engine_getPayloadV1",
+            EngineApiCall::GetPayloadV2(..) => "engine_getPayloadV2",
+            EngineApiCall::GetPayloadV3(..) => "engine_getPayloadV3",
+        }
+    }
+}
+
+/// A single entry of the engine API call log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedEngineCall {
+    /// Unix timestamp in milliseconds at which the call was received.
+    pub timestamp: u64,
+    /// Position of the call in the order the calls were received, starting at zero every time the
+    /// file is opened.
+    pub index: u64,
+    /// The call and its parameters.
+    #[serde(flatten)]
+    pub call: EngineApiCall,
+    /// The serialized response, if the call succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<serde_json::Value>,
+    /// The error message, if the call failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A call that was received but hasn't been answered yet.
+#[derive(Debug)]
+pub(crate) struct PendingEngineCall {
+    timestamp: u64,
+    index: u64,
+    call: EngineApiCall,
+}
+
+/// Appends every recorded Engine API call to a file, one JSON object per line.
+///
+/// Calls are written once they are answered, so concurrent calls can end up in the file in a
+/// different order than they were received. [read_recorded_calls] restores the receipt order.
+///
+/// The file is written on a dedicated thread, so recording never blocks the Engine API handlers.
+/// Dropping the recorder waits until all recorded calls are written.
+///
+/// The resulting file can be replayed with `reth debug replay-engine`.
+#[derive(Debug)]
+pub struct EngineApiRecorder {
+    /// The position of the next received call.
+    next_index: AtomicU64,
+    /// The timestamp of the last received call, timestamps never decrease.
+    last_timestamp: AtomicU64,
+    /// Sends answered calls to the writer thread.
+    to_writer: Option<UnboundedSender<RecordedEngineCall>>,
+    /// The thread that writes the calls to the file.
+    writer: Option<JoinHandle<()>>,
+}
+
+// === impl EngineApiRecorder ===
+
+impl EngineApiRecorder {
+    /// Opens the file at the given path for appending, creating it if it doesn't exist.
+    pub fn new(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let (to_writer, from_recorder) = unbounded_channel();
+        let writer = std::thread::Builder::new()
+            .name("engine-api-recorder".to_string())
+            .spawn(move || write_recorded_calls(BufWriter::new(file), from_recorder))?;
+        Ok(Self {
+            next_index: AtomicU64::new(0),
+            last_timestamp: AtomicU64::new(0),
+            to_writer: Some(to_writer),
+            writer: Some(writer),
+        })
+    }
+
+    /// Marks the receipt of the given call.
+    pub(crate) fn start(&self, call: EngineApiCall) -> PendingEngineCall {
+        let index = self.next_index.fetch_add(1, Ordering::Relaxed);
+        let now = unix_timestamp_millis();
+        let timestamp = self.last_timestamp.fetch_max(now, Ordering::Relaxed).max(now);
+        PendingEngineCall { timestamp, index, call }
+    }
+
+    /// Sends the call together with its result to the writer thread.
+    pub(crate) fn finish<T: Serialize>(
+        &self,
+        pending: PendingEngineCall,
+        result: &EngineApiResult<T>,
+    ) {
+        let PendingEngineCall { timestamp, index, call } = pending;
+        let (response, error) = match result {
+            Ok(response) => (serde_json::to_value(response).ok(), None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+        let entry = RecordedEngineCall { timestamp, index, call, response, error };
+        if self.to_writer.as_ref().map_or(true, |to_writer| to_writer.send(entry).is_err()) {
+            warn!(target: "rpc::engine", "Failed to record engine API call, writer exited");
+        }
+    }
+}
+
+impl Drop for EngineApiRecorder {
+    fn drop(&mut self) {
+        // closing the channel makes the writer thread exit once all calls are written
+        self.to_writer.take();
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+/// Writes the received calls to the file until the channel is closed.
+///
+/// The file is flushed whenever there are no more calls to write.
+fn write_recorded_calls(
+    mut writer: BufWriter<File>,
+    mut calls: UnboundedReceiver<RecordedEngineCall>,
+) {
+    while let Some(mut entry) = calls.blocking_recv() {
+        loop {
+            let res = serde_json::to_writer(&mut writer, &entry)
+                .map_err(io::Error::from)
+                .and_then(|_| writer.write_all(b"\n"));
+            if let Err(err) = res {
+                warn!(target: "rpc::engine", ?err, "Failed to record engine API call");
+            }
+            match calls.try_recv() {
+                Ok(next) => entry = next,
+                Err(_) => break,
+            }
+        }
+        if let Err(err) = writer.flush() {
+            warn!(target: "rpc::engine", ?err, "Failed to flush recorded engine API calls");
+        }
+    }
+}
+
+/// Reads all calls from a file written by an [EngineApiRecorder], in the order they were received.
+pub fn read_recorded_calls(path: impl AsRef<Path>) -> io::Result<Vec<RecordedEngineCall>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut calls = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue
+        }
+        let call = serde_json::from_str(&line)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        calls.push(call);
+    }
+    // calls are written once they are answered, not when they are received
+    calls.sort_by_key(|call| (call.timestamp, call.index));
+    Ok(calls)
+}
+
+fn unix_timestamp_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::engine::{PayloadStatus, PayloadStatusEnum};
+
+    #[test]
+    fn recorded_call_roundtrip() {
+        let status = PayloadStatus::from_status(PayloadStatusEnum::Syncing);
+        let entry = RecordedEngineCall {
+            timestamp: 1_700_000_000_000,
+            index: 0,
+            call: EngineApiCall::ForkchoiceUpdatedV2(ForkchoiceState::default(), None),
+            response: Some(serde_json::to_value(&status).unwrap()),
+            error: None,
+        };
+
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["method"], entry.call.method());
+        assert!(json["params"].is_array());
+        assert!(json.get("error").is_none());
+
+        let decoded: RecordedEngineCall = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, entry);
+    }
+
+    #[test]
+    fn reads_calls_in_receipt_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("engine-api.jsonl");
+        let status = PayloadStatus::from_status(PayloadStatusEnum::Syncing);
+        let first = EngineApiCall::ForkchoiceUpdatedV2(ForkchoiceState::default(), None);
+        let second = EngineApiCall::GetPayloadV2(PayloadId::new([1; 8]));
+
+        let recorder = EngineApiRecorder::new(&path).unwrap();
+        let pending_first = recorder.start(first.clone());
+        let pending_second = recorder.start(second.clone());
+        // the second call is answered first
+        recorder.finish(pending_second, &Ok(status.clone()));
+        recorder.finish(pending_first, &Ok(status));
+        drop(recorder);
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.find("engine_getPayloadV2") < written.find("engine_forkchoiceUpdatedV2"));
+
+        let calls = read_recorded_calls(&path).unwrap();
+        assert_eq!(calls.iter().map(|call| &call.call).collect::<Vec<_>>(), [&first, &second]);
+        assert_eq!(calls.iter().map(|call| call.index).collect::<Vec<_>>(), [0, 1]);
+    }
+}
@@ -1,4 +1,4 @@
-use reth_primitives::{NodeRecord, PeerId, H256, U256};
+use reth_primitives::{BlockNumber, NodeRecord, PeerId, H256, U256};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
@@ -88,6 +88,73 @@ pub struct EthProtocolInfo {
     pub genesis: H256,
 }
 
+/// An event about a peer of the node, emitted by the `admin_peerEvents` subscription.
+///
+/// Note: this format is not standardized. Reth follows Geth's format,
+/// see: <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-admin>
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerEvent {
+    /// The kind of the event.
+    #[serde(rename = "type")]
+    pub kind: PeerEventType,
+    /// ID of the peer.
+    pub peer: PeerId,
+    /// The reason the peer was dropped, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The local address of the connection.
+    #[serde(default, rename = "local", skip_serializing_if = "Option::is_none")]
+    pub local_address: Option<SocketAddr>,
+    /// The remote address of the connection.
+    #[serde(default, rename = "remote", skip_serializing_if = "Option::is_none")]
+    pub remote_address: Option<SocketAddr>,
+}
+
+impl PeerEvent {
+    /// Creates a new event of the given kind for the given peer, with no other fields set.
+    pub fn new(kind: PeerEventType, peer: PeerId) -> Self {
+        Self { kind, peer, error: None, local_address: None, remote_address: None }
+    }
+}
+
+/// The kind of a [PeerEvent].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PeerEventType {
+    /// A session with the peer was established.
+    Add,
+    /// The session with the peer was closed.
+    Drop,
+    /// The peer was added to the peer set.
+    ///
+    /// Note: this is not emitted by Geth.
+    PeerAdded,
+    /// The peer was removed from the peer set.
+    ///
+    /// Note: this is not emitted by Geth.
+    PeerRemoved,
+}
+
+/// Represents the `admin_backupDatabase` response, describing the created backup.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    /// Version of the database schema.
+    pub db_version: u64,
+    /// Unix timestamp in seconds at which the backup was created.
+    pub created_at: u64,
+    /// Hash of the genesis block.
+    pub genesis_hash: H256,
+    /// Number of the highest canonical block.
+    pub tip_number: BlockNumber,
+    /// Hash of the highest canonical block.
+    pub tip_hash: H256,
+    /// The block number of the checkpoint of each stage, keyed by the stage id.
+    pub stage_checkpoints: BTreeMap<String, BlockNumber>,
+    /// Size of the copied database file in bytes.
+    pub size: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,4 +168,28 @@ mod tests {
         let de_serialized: NodeInfo = serde_json::from_str(&serialized).unwrap();
         assert_eq!(info, de_serialized)
     }
+
+    #[test]
+    fn test_serialize_peer_event() {
+        let peer = "0x44826a5d6a55f88a18298bca4773fca5749cdc3a5c9f308aa7d810e9b31123f3e7c5fba0b1d70aac5308426f47df2a128a6747040a3815cc7dd7167d03be320d".parse().unwrap();
+
+        let mut event = PeerEvent::new(PeerEventType::Drop, peer);
+        event.error = Some("too many peers".to_string());
+        let serialized = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            serialized,
+            serde_json::json!({
+                "type": "drop",
+                "peer": "0x44826a5d6a55f88a18298bca4773fca5749cdc3a5c9f308aa7d810e9b31123f3e7c5fba0b1d70aac5308426f47df2a128a6747040a3815cc7dd7167d03be320d",
+                "error": "too many peers",
+            })
+        );
+
+        let sample = r#"{"type":"add","peer":"0x44826a5d6a55f88a18298bca4773fca5749cdc3a5c9f308aa7d810e9b31123f3e7c5fba0b1d70aac5308426f47df2a128a6747040a3815cc7dd7167d03be320d","local":"127.0.0.1:30303","remote":"10.0.0.1:30303"}"#;
+        let event: PeerEvent = serde_json::from_str(sample).unwrap();
+        assert_eq!(event.kind, PeerEventType::Add);
+        assert_eq!(event.local_address, Some("127.0.0.1:30303".parse().unwrap()));
+        assert_eq!(event.remote_address, Some("10.0.0.1:30303".parse().unwrap()));
+        assert_eq!(serde_json::to_string(&event).unwrap(), sample);
+    }
 }
@@ -10,7 +10,7 @@ mod transition;
 pub use self::{cancun::*, forkchoice::*, payload::*, transition::*};
 
 /// The list of all supported Engine capabilities available over the engine endpoint.
-pub const CAPABILITIES: [&str; 12] = [
+pub const CAPABILITIES: [&str; 13] = [
     "engine_forkchoiceUpdatedV1",
     "engine_forkchoiceUpdatedV2",
     "engine_forkchoiceUpdatedV3",
@@ -23,4 +23,5 @@ pub const CAPABILITIES: [&str; 12] = [
     "engine_newPayloadV3",
     "engine_getPayloadBodiesByHashV1",
     "engine_getPayloadBodiesByRangeV1",
+    "engine_getBlobsV1",
 ];
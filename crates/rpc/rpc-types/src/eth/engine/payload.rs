@@ -409,6 +409,15 @@ impl From<Vec<BlobTransactionSidecar>> for BlobsBundleV1 {
     }
 }
 
+/// A blob and its proof, as returned by `engine_getBlobsV1`.
+///
+/// See also: <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#blobandproofv1>
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobAndProofV1 {
+    pub blob: Box<Blob>,
+    pub proof: Bytes48,
+}
+
 /// An execution payload, which can be either [ExecutionPayloadV1], [ExecutionPayloadV2], or
 /// [ExecutionPayloadV3].
 #[derive(Clone, Debug, PartialEq, Eq)]
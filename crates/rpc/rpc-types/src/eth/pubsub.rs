@@ -2,7 +2,7 @@
 
 use crate::{
     eth::{Filter, Transaction},
-    Log, RichHeader,
+    Block, Log, RichHeader, TransactionReceipt,
 };
 
 use reth_primitives::H256;
@@ -22,6 +22,8 @@ pub enum SubscriptionResult {
     FullTransaction(Box<Transaction>),
     /// SyncStatus
     SyncState(PubSubSyncStatus),
+    /// Full block with receipts
+    BlockWithReceipts(Box<BlockWithReceipts>),
 }
 
 /// Response type for a SyncStatus subscription
@@ -34,6 +36,18 @@ pub enum PubSubSyncStatus {
     Detailed(SyncStatusMetadata),
 }
 
+/// Response type for a `newBlocksWithReceipts` subscription
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockWithReceipts {
+    /// The block, with full transactions.
+    pub block: Block,
+    /// The receipts of the block's transactions.
+    pub receipts: Vec<TransactionReceipt>,
+    /// Whether the block was removed from the canonical chain by a reorg.
+    pub removed: bool,
+}
+
 /// Sync status infos
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -57,6 +71,7 @@ impl Serialize for SubscriptionResult {
             SubscriptionResult::TransactionHash(ref hash) => hash.serialize(serializer),
             SubscriptionResult::FullTransaction(ref tx) => tx.serialize(serializer),
             SubscriptionResult::SyncState(ref sync) => sync.serialize(serializer),
+            SubscriptionResult::BlockWithReceipts(ref block) => block.serialize(serializer),
         }
     }
 }
@@ -94,6 +109,13 @@ pub enum SubscriptionKind {
     /// indicating that the synchronization has started (true), finished (false) or an object with
     /// various progress indicators.
     Syncing,
+    /// New blocks with receipts subscription.
+    ///
+    /// Reth specific: Fires a notification with the full block and its receipts for each block
+    /// that is added to the canonical chain. In case of a chain reorganization, the blocks that
+    /// are removed from the canonical chain are emitted first, from the old tip down, with the
+    /// removed property set to true, followed by the blocks of the new chain.
+    NewBlocksWithReceipts,
 }
 
 /// Any additional parameters for a subscription.
@@ -167,4 +189,11 @@ mod tests {
         let s: Params = serde_json::from_str("null").unwrap();
         assert_eq!(s, Params::None);
     }
+
+    #[test]
+    fn subscription_kind_serde() {
+        let kind: SubscriptionKind = serde_json::from_str(r#""newBlocksWithReceipts""#).unwrap();
+        assert_eq!(kind, SubscriptionKind::NewBlocksWithReceipts);
+        assert_eq!(serde_json::to_string(&kind).unwrap(), r#""newBlocksWithReceipts""#);
+    }
 }
@@ -1,8 +1,8 @@
 use crate::eth::transaction::typed::{
-    EIP1559TransactionRequest, EIP2930TransactionRequest, LegacyTransactionRequest,
-    TransactionKind, TypedTransactionRequest,
+    EIP1559TransactionRequest, EIP2930TransactionRequest, EIP4844TransactionRequest,
+    LegacyTransactionRequest, TransactionKind, TypedTransactionRequest,
 };
-use reth_primitives::{AccessList, Address, Bytes, U128, U256, U8};
+use reth_primitives::{AccessList, Address, BlobTransactionSidecar, Bytes, H256, U128, U256, U8};
 use serde::{Deserialize, Serialize};
 
 /// Represents _all_ transaction requests received from RPC
@@ -38,6 +38,16 @@ pub struct TransactionRequest {
     /// EIP-2718 type
     #[serde(rename = "type")]
     pub transaction_type: Option<U8>,
+    /// max fee per blob gas for EIP-4844 transactions
+    #[serde(default)]
+    pub max_fee_per_blob_gas: Option<U128>,
+    /// versioned hashes of the blobs of EIP-4844 transactions
+    #[serde(default)]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+    /// blobs, commitments and proofs of EIP-4844 transactions, required to submit them to the
+    /// pool
+    #[serde(default)]
+    pub sidecar: Option<BlobTransactionSidecar>,
 }
 
 // == impl TransactionRequest ==
@@ -46,7 +56,9 @@ impl TransactionRequest {
     /// Converts the request into a [`TypedTransactionRequest`]
     ///
     /// Returns None if mutual exclusive fields `gasPrice` and `max_fee_per_gas` are either missing
-    /// or both set.
+    /// or both set, or if `gasPrice` is set for an EIP-4844 request.
+    ///
+    /// Note: the blob sidecar is not part of the typed request.
     pub fn into_typed_request(self) -> Option<TypedTransactionRequest> {
         let TransactionRequest {
             to,
@@ -58,8 +70,34 @@ impl TransactionRequest {
             data,
             nonce,
             mut access_list,
+            max_fee_per_blob_gas,
+            blob_versioned_hashes,
             ..
         } = self;
+
+        // EIP4844
+        if max_fee_per_blob_gas.is_some() || blob_versioned_hashes.is_some() {
+            if gas_price.is_some() {
+                return None
+            }
+            return Some(TypedTransactionRequest::EIP4844(EIP4844TransactionRequest {
+                nonce: nonce.unwrap_or(U256::ZERO),
+                max_fee_per_gas: max_fee_per_gas.unwrap_or_default(),
+                max_priority_fee_per_gas: max_priority_fee_per_gas.unwrap_or(U128::ZERO),
+                gas_limit: gas.unwrap_or_default(),
+                value: value.unwrap_or(U256::ZERO),
+                input: data.unwrap_or_default(),
+                kind: match to {
+                    Some(to) => TransactionKind::Call(to),
+                    None => TransactionKind::Create,
+                },
+                chain_id: 0,
+                access_list: access_list.unwrap_or_default(),
+                max_fee_per_blob_gas: max_fee_per_blob_gas.unwrap_or_default(),
+                blob_versioned_hashes: blob_versioned_hashes.unwrap_or_default(),
+            }))
+        }
+
         match (gas_price, max_fee_per_gas, access_list.take()) {
             // legacy transaction
             (Some(_), None, None) => {
@@ -114,3 +152,25 @@ impl TransactionRequest {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blob_request_into_typed() {
+        let request: TransactionRequest = serde_json::from_str(
+            r#"{"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","maxFeePerGas":"0x10","maxFeePerBlobGas":"0x1","blobVersionedHashes":["0x0100000000000000000000000000000000000000000000000000000000000000"]}"#,
+        )
+        .unwrap();
+        let Some(TypedTransactionRequest::EIP4844(tx)) = request.clone().into_typed_request()
+        else {
+            panic!("expected EIP-4844 request")
+        };
+        assert_eq!(tx.max_fee_per_blob_gas, U128::from(1));
+        assert_eq!(tx.blob_versioned_hashes.len(), 1);
+
+        let request = TransactionRequest { gas_price: Some(U128::from(1)), ..request };
+        assert!(request.into_typed_request().is_none());
+    }
+}
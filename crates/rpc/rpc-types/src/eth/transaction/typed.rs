@@ -4,7 +4,8 @@
 //! it can be converted into the container type [`TypedTransactionRequest`].
 
 use reth_primitives::{
-    AccessList, Address, Bytes, Transaction, TxEip1559, TxEip2930, TxLegacy, U128, U256,
+    AccessList, Address, Bytes, Transaction, TxEip1559, TxEip2930, TxEip4844, TxLegacy, H256, U128,
+    U256,
 };
 use reth_rlp::{BufMut, Decodable, DecodeError, Encodable, RlpDecodable, RlpEncodable};
 use serde::{Deserialize, Serialize};
@@ -15,11 +16,13 @@ use serde::{Deserialize, Serialize};
 /// 1. Legacy (pre-EIP2718) [`LegacyTransactionRequest`]
 /// 2. EIP2930 (state access lists) [`EIP2930TransactionRequest`]
 /// 3. EIP1559 [`EIP1559TransactionRequest`]
+/// 4. EIP4844 [`EIP4844TransactionRequest`]
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum TypedTransactionRequest {
     Legacy(LegacyTransactionRequest),
     EIP2930(EIP2930TransactionRequest),
     EIP1559(EIP1559TransactionRequest),
+    EIP4844(EIP4844TransactionRequest),
 }
 
 impl TypedTransactionRequest {
@@ -58,6 +61,21 @@ impl TypedTransactionRequest {
                     tx.max_priority_fee_per_gas.to_be_bytes(),
                 ),
             }),
+            TypedTransactionRequest::EIP4844(tx) => Transaction::Eip4844(TxEip4844 {
+                chain_id: tx.chain_id,
+                nonce: u64::from_be_bytes(tx.nonce.to_be_bytes()),
+                max_fee_per_gas: u128::from_be_bytes(tx.max_fee_per_gas.to_be_bytes()),
+                gas_limit: u64::from_be_bytes(tx.gas_limit.to_be_bytes()),
+                to: tx.kind.into(),
+                value: u128::from_be_bytes(tx.value.to_be_bytes()),
+                input: tx.input,
+                access_list: tx.access_list,
+                max_priority_fee_per_gas: u128::from_be_bytes(
+                    tx.max_priority_fee_per_gas.to_be_bytes(),
+                ),
+                blob_versioned_hashes: tx.blob_versioned_hashes,
+                max_fee_per_blob_gas: u128::from_be_bytes(tx.max_fee_per_blob_gas.to_be_bytes()),
+            }),
         }
     }
 }
@@ -101,6 +119,22 @@ pub struct EIP1559TransactionRequest {
     pub access_list: AccessList,
 }
 
+/// Represents an EIP-4844 transaction request
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct EIP4844TransactionRequest {
+    pub chain_id: u64,
+    pub nonce: U256,
+    pub max_priority_fee_per_gas: U128,
+    pub max_fee_per_gas: U128,
+    pub gas_limit: U256,
+    pub kind: TransactionKind,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+    pub max_fee_per_blob_gas: U128,
+    pub blob_versioned_hashes: Vec<H256>,
+}
+
 /// Represents the `to` field of a transaction request
 ///
 /// This determines what kind of transaction this is
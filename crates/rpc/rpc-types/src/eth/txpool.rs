@@ -1,7 +1,7 @@
 //! Types for the `txpool` namespace: <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-txpool>
 
 use crate::Transaction;
-use reth_primitives::{Address, U256, U64};
+use reth_primitives::{Address, H256, U256, U64};
 use serde::{
     de::{self, Deserializer, Visitor},
     Deserialize, Serialize,
@@ -176,6 +176,113 @@ pub struct TxpoolStatus {
     pub queued: U64,
 }
 
+/// Transaction Pool Sender Status
+///
+/// The pool's view of a single sender: the nonces that are missing and why each of the sender's
+/// transactions is not pending.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolSenderStatus {
+    /// on-chain nonce of the sender, as last seen by the pool
+    pub state_nonce: U64,
+    /// nonce ranges without a transaction in the pool, between the on-chain nonce and the highest
+    /// pooled nonce
+    pub nonce_gaps: Vec<TxpoolNonceGap>,
+    /// placement of the sender's transactions, keyed by nonce
+    pub transactions: BTreeMap<String, TxpoolTxPlacement>,
+}
+
+/// An inclusive range of nonces without a transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxpoolNonceGap {
+    /// first missing nonce
+    pub from: U64,
+    /// last missing nonce
+    pub to: U64,
+}
+
+/// The sub-pool a transaction is placed in and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolTxPlacement {
+    /// hash of the transaction
+    pub hash: H256,
+    /// sub-pool that contains the transaction
+    pub subpool: TxpoolSubPool,
+    /// requirements of the pending sub-pool the transaction doesn't meet
+    pub parked_reasons: Vec<TxpoolParkedReason>,
+}
+
+/// The sub-pools of the transaction pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolSubPool {
+    /// ready for inclusion in the next block
+    Pending,
+    /// ready, but the fee cap is below the base fee of the next block
+    BaseFee,
+    /// blocked by a nonce gap, a lack of funds or other requirements
+    Queued,
+}
+
+/// A requirement of the pending sub-pool that a transaction doesn't meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolParkedReason {
+    /// a transaction with a lower nonce of the same sender is missing
+    NonceGap,
+    /// a transaction with a lower nonce of the same sender is parked
+    ParkedAncestor,
+    /// the sender can't pay for this and all prior transactions
+    InsufficientBalance,
+    /// the gas limit exceeds the block gas limit
+    ExceedsBlockGasLimit,
+    /// the fee cap is below the base fee of the next block
+    BaseFeeTooLow,
+}
+
+/// An event of a transaction in the pool, as returned by `txpool_txHistory`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum TxpoolTxEvent {
+    /// added to the pending sub-pool
+    Pending,
+    /// added to the queued sub-pool
+    Queued,
+    /// included in a block
+    Mined {
+        /// hash of the block
+        #[serde(rename = "blockHash")]
+        block_hash: H256,
+    },
+    /// replaced by a transaction with the same sender and nonce
+    Replaced {
+        /// hash of the replacement
+        #[serde(rename = "replacedBy")]
+        replaced_by: H256,
+    },
+    /// removed from the pool
+    Discarded {
+        /// why the transaction was removed
+        reason: TxpoolDiscardReason,
+    },
+}
+
+/// The reason a transaction was removed from the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolDiscardReason {
+    /// evicted because the pool exceeded its size limits, either itself or together with a
+    /// transaction of the same sender with a lower nonce
+    PoolSizeLimit,
+    /// the on-chain nonce of the sender moved past the nonce of the transaction
+    StaleNonce,
+    /// the transaction could not be validated
+    ValidationFailed,
+    /// removed on request, e.g. by the block builder
+    Removed,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -393,6 +500,54 @@ mod tests {
         assert_eq!(deserialized2, deserialized);
     }
 
+    #[test]
+    fn serde_txpool_sender_status() {
+        let txpool_sender_status_json = r#"
+{
+  "stateNonce": "0x1",
+  "nonceGaps": [
+    {
+      "from": "0x2",
+      "to": "0x3"
+    }
+  ],
+  "transactions": {
+    "4": {
+      "hash": "0x3a3c0698552eec2455ed3190eac3996feccc806970a4a056106deaf6ceb1e5e3",
+      "subpool": "queued",
+      "parkedReasons": [
+        "nonceGap",
+        "insufficientBalance"
+      ]
+    }
+  }
+}"#;
+        let deserialized: TxpoolSenderStatus =
+            serde_json::from_str(txpool_sender_status_json).unwrap();
+        assert_eq!(
+            deserialized.transactions["4"].parked_reasons,
+            vec![TxpoolParkedReason::NonceGap, TxpoolParkedReason::InsufficientBalance]
+        );
+        let serialized: String = serde_json::to_string_pretty(&deserialized).unwrap();
+        assert_eq!(txpool_sender_status_json.trim(), serialized);
+    }
+
+    #[test]
+    fn serde_txpool_tx_event() {
+        let replaced_by = H256::from_low_u64_be(1);
+        let event = TxpoolTxEvent::Replaced { replaced_by };
+        let serialized = serde_json::to_value(&event).unwrap();
+        assert_eq!(serialized["event"], "replaced");
+        assert_eq!(serialized["replacedBy"], serde_json::to_value(replaced_by).unwrap());
+        assert_eq!(
+            serde_json::to_value(TxpoolTxEvent::Discarded {
+                reason: TxpoolDiscardReason::PoolSizeLimit
+            })
+            .unwrap(),
+            serde_json::json!({ "event": "discarded", "reason": "poolSizeLimit" })
+        );
+    }
+
     #[test]
     fn serde_txpool_status() {
         let txpool_status_json = r#"
@@ -1,10 +1,24 @@
-use crate::result::ToRpcResult;
+use crate::{
+    eth::pubsub::pipe_from_stream,
+    result::{internal_rpc_err, invalid_params_rpc_err, ToRpcResult},
+};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{core::RpcResult, PendingSubscriptionSink};
 use reth_network_api::{NetworkInfo, PeerKind, Peers};
-use reth_primitives::NodeRecord;
-use reth_rpc_api::AdminApiServer;
-use reth_rpc_types::{NodeInfo, PeerEthProtocolInfo, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo};
+use reth_primitives::{NodeRecord, PeerId};
+use reth_rpc_api::{AdminApiServer, AdminBackupApiServer};
+use reth_rpc_types::{
+    BackupManifest, NodeInfo, PeerEthProtocolInfo, PeerEvent, PeerEventType, PeerInfo,
+    PeerNetworkInfo, PeerProtocolsInfo,
+};
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use std::{
+    net::SocketAddr,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+use tokio::sync::{oneshot, Semaphore};
 
 /// `admin` API implementation.
 ///
@@ -12,12 +26,21 @@ use reth_rpc_types::{NodeInfo, PeerEthProtocolInfo, PeerInfo, PeerNetworkInfo, P
 pub struct AdminApi<N> {
     /// An interface to interact with the network
     network: N,
+    /// The type that's used to spawn subscription tasks.
+    subscription_task_spawner: Box<dyn TaskSpawner>,
 }
 
 impl<N> AdminApi<N> {
     /// Creates a new instance of `AdminApi`.
+    ///
+    /// Subscription tasks are spawned via [tokio::task::spawn]
     pub fn new(network: N) -> Self {
-        AdminApi { network }
+        Self::with_spawner(network, Box::<TokioTaskExecutor>::default())
+    }
+
+    /// Creates a new instance of `AdminApi` that spawns subscription tasks with the given spawner.
+    pub fn with_spawner(network: N, subscription_task_spawner: Box<dyn TaskSpawner>) -> Self {
+        AdminApi { network, subscription_task_spawner }
     }
 }
 
@@ -90,9 +113,48 @@ where
     /// Handler for `admin_peerEvents`
     async fn subscribe_peer_events(
         &self,
-        _pending: jsonrpsee::PendingSubscriptionSink,
+        pending: PendingSubscriptionSink,
+        peers: Option<Vec<PeerId>>,
     ) -> jsonrpsee::core::SubscriptionResult {
-        Err("admin_peerEvents is not implemented yet".into())
+        let sink = pending.accept().await?;
+        let local_addr = self.network.local_addr();
+        let stream = self
+            .network
+            .peer_events()
+            .filter(move |event| {
+                let include = peers.as_ref().map_or(true, |peers| peers.contains(&event.peer_id()));
+                futures::future::ready(include)
+            })
+            .map(move |event| to_rpc_peer_event(event, local_addr));
+        self.subscription_task_spawner.spawn(Box::pin(async move {
+            let _ = pipe_from_stream(sink, stream).await;
+        }));
+
+        Ok(())
+    }
+}
+
+/// Converts a network [PeerEvent](reth_network_api::PeerEvent) into the Geth compatible rpc
+/// [PeerEvent].
+fn to_rpc_peer_event(event: reth_network_api::PeerEvent, local_addr: SocketAddr) -> PeerEvent {
+    match event {
+        reth_network_api::PeerEvent::SessionEstablished { peer_id, remote_addr, .. } => {
+            let mut event = PeerEvent::new(PeerEventType::Add, peer_id);
+            event.local_address = Some(local_addr);
+            event.remote_address = Some(remote_addr);
+            event
+        }
+        reth_network_api::PeerEvent::SessionClosed { peer_id, reason } => {
+            let mut event = PeerEvent::new(PeerEventType::Drop, peer_id);
+            event.error = reason.map(|reason| reason.to_string());
+            event
+        }
+        reth_network_api::PeerEvent::PeerAdded(peer_id) => {
+            PeerEvent::new(PeerEventType::PeerAdded, peer_id)
+        }
+        reth_network_api::PeerEvent::PeerRemoved(peer_id) => {
+            PeerEvent::new(PeerEventType::PeerRemoved, peer_id)
+        }
     }
 }
 
@@ -101,3 +163,148 @@ impl<N> std::fmt::Debug for AdminApi<N> {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
 }
+
+/// Creates backups of the node database for the [AdminBackupApi].
+pub trait DatabaseBackupProvider: Send + Sync + 'static {
+    /// Copies the database into the given directory, which must not contain a database yet, and
+    /// returns the manifest of the backup.
+    fn backup(&self, dest: &Path) -> Result<BackupManifest, String>;
+}
+
+impl<F> DatabaseBackupProvider for F
+where
+    F: Fn(&Path) -> Result<BackupManifest, String> + Send + Sync + 'static,
+{
+    fn backup(&self, dest: &Path) -> Result<BackupManifest, String> {
+        self(dest)
+    }
+}
+
+/// `admin` API implementation for backing up the database.
+///
+/// Backups are only written into the configured backup directory, and only one backup runs at a
+/// time.
+pub struct AdminBackupApi<B> {
+    /// Creates the backups.
+    provider: Arc<B>,
+    /// The directory the requested backup destinations are resolved against.
+    backup_dir: PathBuf,
+    /// The type that's used to spawn the blocking backup tasks.
+    task_spawner: Box<dyn TaskSpawner>,
+    /// Held by the running backup.
+    backup_permit: Arc<Semaphore>,
+}
+
+impl<B> AdminBackupApi<B> {
+    /// Creates a new instance of `AdminBackupApi` that writes backups into the given directory.
+    pub fn new(provider: B, backup_dir: PathBuf, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        AdminBackupApi {
+            provider: Arc::new(provider),
+            backup_dir,
+            task_spawner,
+            backup_permit: Arc::new(Semaphore::new(1)),
+        }
+    }
+}
+
+#[async_trait]
+impl<B> AdminBackupApiServer for AdminBackupApi<B>
+where
+    B: DatabaseBackupProvider,
+{
+    /// Handler for `admin_backupDatabase`
+    async fn backup_database(&self, dest: PathBuf) -> RpcResult<BackupManifest> {
+        let dest = resolve_backup_dest(&self.backup_dir, &dest).ok_or_else(|| {
+            invalid_params_rpc_err("backup destination must be a relative path without `..`")
+        })?;
+        let permit = Arc::clone(&self.backup_permit)
+            .try_acquire_owned()
+            .map_err(|_| internal_rpc_err("a backup is already in progress"))?;
+
+        // Copying the database can take a long time, so it's done on the blocking pool.
+        let (tx, rx) = oneshot::channel();
+        let provider = Arc::clone(&self.provider);
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let result = provider.backup(&dest);
+            drop(permit);
+            let _ = tx.send(result);
+        }));
+        rx.await.map_err(|err| internal_rpc_err(err.to_string()))?.map_err(internal_rpc_err)
+    }
+}
+
+impl<B> std::fmt::Debug for AdminBackupApi<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminBackupApi")
+            .field("backup_dir", &self.backup_dir)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Resolves the requested backup destination inside the backup directory.
+///
+/// Returns `None` if the destination is not a relative path below the backup directory.
+fn resolve_backup_dest(backup_dir: &Path, dest: &Path) -> Option<PathBuf> {
+    let mut components = dest.components().peekable();
+    components.peek()?;
+    components
+        .all(|component| matches!(component, Component::Normal(_)))
+        .then(|| backup_dir.join(dest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::H256;
+    use std::collections::BTreeMap;
+
+    fn manifest() -> BackupManifest {
+        BackupManifest {
+            db_version: 1,
+            created_at: 0,
+            genesis_hash: H256::zero(),
+            tip_number: 0,
+            tip_hash: H256::zero(),
+            stage_checkpoints: BTreeMap::new(),
+            size: 0,
+        }
+    }
+
+    #[test]
+    fn backup_dest_is_resolved_inside_backup_dir() {
+        let dir = Path::new("/backups");
+        assert_eq!(resolve_backup_dest(dir, Path::new("a/b")), Some(PathBuf::from("/backups/a/b")));
+        assert_eq!(resolve_backup_dest(dir, Path::new("")), None);
+        assert_eq!(resolve_backup_dest(dir, Path::new("/etc")), None);
+        assert_eq!(resolve_backup_dest(dir, Path::new("../etc")), None);
+        assert_eq!(resolve_backup_dest(dir, Path::new("a/../../etc")), None);
+    }
+
+    #[tokio::test]
+    async fn only_one_backup_at_a_time() {
+        let (release, wait) = std::sync::mpsc::channel::<()>();
+        let wait = std::sync::Mutex::new(wait);
+        let api = AdminBackupApi::new(
+            move |_: &Path| -> Result<BackupManifest, String> {
+                wait.lock().unwrap().recv().unwrap();
+                Ok(manifest())
+            },
+            PathBuf::from("/backups"),
+            Box::<TokioTaskExecutor>::default(),
+        );
+        let api = Arc::new(api);
+
+        let running = tokio::spawn({
+            let api = Arc::clone(&api);
+            async move { api.backup_database(PathBuf::from("first")).await }
+        });
+        while api.backup_permit.available_permits() > 0 {
+            tokio::task::yield_now().await;
+        }
+        assert!(api.backup_database(PathBuf::from("second")).await.is_err());
+
+        release.send(()).unwrap();
+        assert_eq!(running.await.unwrap().unwrap(), manifest());
+        assert!(api.backup_permit.available_permits() > 0);
+    }
+}
@@ -2,13 +2,13 @@
 
 use crate::{
     eth::{
-        api::transactions::build_transaction_receipt_with_block_receipts,
+        api::transactions::build_block_receipts,
         error::{EthApiError, EthResult},
     },
     EthApi,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{BlockId, BlockNumberOrTag, TransactionMeta};
+use reth_primitives::{BlockId, BlockNumberOrTag};
 
 use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
 use reth_rpc_types::{Index, RichBlock, TransactionReceipt};
@@ -70,28 +70,7 @@ where
         }
 
         if let Some((block, receipts)) = block_and_receipts {
-            let block_number = block.number;
-            let base_fee = block.base_fee_per_gas;
-            let block_hash = block.hash;
-            let excess_blob_gas = block.excess_blob_gas;
-            let receipts = block
-                .body
-                .into_iter()
-                .zip(receipts.clone())
-                .enumerate()
-                .map(|(idx, (tx, receipt))| {
-                    let meta = TransactionMeta {
-                        tx_hash: tx.hash,
-                        index: idx as u64,
-                        block_hash,
-                        block_number,
-                        base_fee,
-                        excess_blob_gas,
-                    };
-                    build_transaction_receipt_with_block_receipts(tx, meta, receipt, &receipts)
-                })
-                .collect::<EthResult<Vec<_>>>();
-            return receipts.map(Some)
+            return build_block_receipts(block, receipts).map(Some)
         }
 
         Ok(None)
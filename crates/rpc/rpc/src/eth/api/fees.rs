@@ -6,7 +6,9 @@ use crate::{
 };
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
-    basefee::calculate_next_block_base_fee, BlockNumberOrTag, SealedHeader, U256,
+    basefee::calculate_next_block_base_fee,
+    constants::eip4844::{BLOB_TX_MIN_BLOB_GASPRICE, MAX_DATA_GAS_PER_BLOCK},
+    BlockNumberOrTag, SealedHeader, U128, U256,
 };
 use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
 use reth_rpc_types::{FeeHistory, TxGasAndReward};
@@ -36,6 +38,52 @@ where
         self.gas_oracle().suggest_tip_cap().await
     }
 
+    /// Returns the `(max_fee_per_gas, max_priority_fee_per_gas)` for a dynamic fee transaction,
+    /// filling in suggestions for the values that are not set.
+    ///
+    /// The suggested max fee leaves room for the base fee to double.
+    pub(crate) async fn dynamic_fees(
+        &self,
+        max_fee_per_gas: Option<U128>,
+        max_priority_fee_per_gas: Option<U128>,
+    ) -> EthResult<(U128, U128)> {
+        let max_priority_fee_per_gas = match max_priority_fee_per_gas {
+            Some(tip) => tip,
+            None => U128::from(self.suggested_priority_fee().await?),
+        };
+        let max_fee_per_gas = match max_fee_per_gas {
+            Some(max_fee) => max_fee,
+            None => {
+                let base_fee = self
+                    .provider()
+                    .latest_header()?
+                    .and_then(|header| {
+                        header.next_block_base_fee(self.provider().chain_spec().base_fee_params)
+                    })
+                    .unwrap_or_default();
+                U128::from(base_fee) * U128::from(2) + max_priority_fee_per_gas
+            }
+        };
+        Ok((max_fee_per_gas, max_priority_fee_per_gas))
+    }
+
+    /// Returns the blob fee of the next block, derived from the latest block.
+    ///
+    /// Before Cancun this is the minimum blob fee.
+    pub(crate) fn blob_base_fee(&self) -> EthResult<U256> {
+        Ok(self
+            .provider()
+            .latest_header()?
+            .and_then(|header| header.next_block_blob_fee())
+            .map(|fee| U256::from(fee.to::<u128>()))
+            .unwrap_or(U256::from(BLOB_TX_MIN_BLOB_GASPRICE)))
+    }
+
+    /// Returns a suggestion for the max fee per blob gas based on recent blocks.
+    pub(crate) async fn suggested_blob_fee(&self) -> EthResult<U256> {
+        self.gas_oracle().suggest_blob_fee_cap().await
+    }
+
     /// Reports the fee history, for the given amount of blocks, up until the newest block
     /// provided.
     pub(crate) async fn fee_history(
@@ -99,11 +147,20 @@ where
         // Collect base fees, gas usage ratios and (optionally) reward percentile data
         let mut base_fee_per_gas: Vec<U256> = Vec::new();
         let mut gas_used_ratio: Vec<f64> = Vec::new();
+        let mut base_fee_per_blob_gas: Vec<U256> = Vec::new();
+        let mut blob_gas_used_ratio: Vec<f64> = Vec::new();
         let mut rewards: Vec<Vec<U256>> = Vec::new();
         for header in &headers {
             base_fee_per_gas
                 .push(U256::try_from(header.base_fee_per_gas.unwrap_or_default()).unwrap());
             gas_used_ratio.push(header.gas_used as f64 / header.gas_limit as f64);
+            // zero for pre-EIP-4844 blocks
+            base_fee_per_blob_gas.push(
+                header.blob_fee().map(|fee| U256::from(fee.to::<u128>())).unwrap_or_default(),
+            );
+            blob_gas_used_ratio.push(
+                header.blob_gas_used.unwrap_or_default() as f64 / MAX_DATA_GAS_PER_BLOCK as f64,
+            );
 
             // Percentiles were specified, so we need to collect reward percentile ino
             if let Some(percentiles) = &reward_percentiles {
@@ -123,10 +180,18 @@ where
             last_header.base_fee_per_gas.unwrap_or_default(),
             chain_spec.base_fee_params,
         )));
+        base_fee_per_blob_gas.push(
+            last_header
+                .next_block_blob_fee()
+                .map(|fee| U256::from(fee.to::<u128>()))
+                .unwrap_or_default(),
+        );
 
         Ok(FeeHistory {
             base_fee_per_gas,
             gas_used_ratio,
+            base_fee_per_blob_gas,
+            blob_gas_used_ratio,
             oldest_block: U256::from(start_block),
             reward: reward_percentiles.map(|_| rewards),
         })
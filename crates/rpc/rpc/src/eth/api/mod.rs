@@ -8,9 +8,10 @@ use crate::eth::{
     cache::EthStateCache,
     error::{EthApiError, EthResult},
     gas_oracle::GasPriceOracle,
-    signer::EthSigner,
+    signer::{EthSigner, KeystoreSigner},
 };
 use async_trait::async_trait;
+use parking_lot::RwLock;
 use reth_interfaces::Result;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
@@ -40,6 +41,7 @@ mod state;
 mod transactions;
 
 use crate::TracingCallPool;
+pub(crate) use transactions::build_block_receipts;
 pub use transactions::{EthTransactions, TransactionSource};
 
 /// `Eth` API trait.
@@ -190,6 +192,13 @@ where
     pub fn pool(&self) -> &Pool {
         &self.inner.pool
     }
+
+    /// Adds the accounts of the given [KeystoreSigner] to the accounts this type can sign for.
+    ///
+    /// The signers are shared by all clones of this type.
+    pub fn add_keystore_signer(&self, signer: KeystoreSigner) {
+        self.inner.signers.write().push(Arc::new(signer));
+    }
 }
 
 // === State access helpers ===
@@ -357,7 +366,7 @@ where
     }
 
     fn accounts(&self) -> Vec<Address> {
-        self.inner.signers.iter().flat_map(|s| s.accounts()).collect()
+        self.inner.signers.read().iter().flat_map(|s| s.accounts()).collect()
     }
 
     fn is_syncing(&self) -> bool {
@@ -422,7 +431,7 @@ struct EthApiInner<Provider, Pool, Network> {
     /// An interface to interact with the network
     network: Network,
     /// All configured Signers
-    signers: Vec<Box<dyn EthSigner>>,
+    signers: RwLock<Vec<Arc<dyn EthSigner>>>,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
     /// The async gas oracle frontend for gas price suggestions
@@ -4,7 +4,7 @@
 use super::EthApiSpec;
 use crate::{
     eth::{
-        api::{EthApi, EthTransactions},
+        api::{sign::into_transaction_request, EthApi, EthTransactions},
         revm_utils::EvmOverrides,
     },
     result::{internal_rpc_err, ToRpcResult},
@@ -292,6 +292,12 @@ where
         return Ok(EthApi::suggested_priority_fee(self).await?)
     }
 
+    /// Handler for: `eth_blobBaseFee`
+    async fn blob_base_fee(&self) -> Result<U256> {
+        trace!(target: "rpc::eth", "Serving eth_blobBaseFee");
+        Ok(EthApi::blob_base_fee(self)?)
+    }
+
     // FeeHistory is calculated based on lazy evaluation of fees for historical blocks, and further
     // caching of it in the LRU cache.
     // When new RPC call is executed, the cache gets locked, we check it for the historical fees
@@ -356,8 +362,11 @@ where
     }
 
     /// Handler for: `eth_signTransaction`
-    async fn sign_transaction(&self, _transaction: CallRequest) -> Result<Bytes> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn sign_transaction(&self, request: CallRequest) -> Result<Bytes> {
+        trace!(target: "rpc::eth", ?request, "Serving eth_signTransaction");
+        let request = into_transaction_request(request, self.chain_id())?;
+        let transaction = EthTransactions::sign_transaction(self, request).await?;
+        Ok(transaction.envelope_encoded().into())
     }
 
     /// Handler for: `eth_signTypedData`
@@ -397,8 +406,12 @@ mod tests {
     use reth_interfaces::test_utils::{generators, generators::Rng};
     use reth_network_api::noop::NoopNetwork;
     use reth_primitives::{
-        basefee::calculate_next_block_base_fee, constants::ETHEREUM_BLOCK_GAS_LIMIT, BaseFeeParams,
-        Block, BlockNumberOrTag, Header, TransactionSigned, H256, U256,
+        basefee::calculate_next_block_base_fee,
+        constants::{
+            eip4844::{DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK, TARGET_DATA_GAS_PER_BLOCK},
+            ETHEREUM_BLOCK_GAS_LIMIT,
+        },
+        BaseFeeParams, Block, BlockNumberOrTag, Header, TransactionSigned, H256, U256,
     };
     use reth_provider::{
         test_utils::{MockEthProvider, NoopProvider},
@@ -624,4 +637,48 @@ mod tests {
             "all: no percentiles were requested, so there should be no rewards result"
         );
     }
+
+    #[tokio::test]
+    async fn test_fee_history_blob_gas() {
+        let newest_block = 100;
+        let mock_provider = MockEthProvider::default();
+
+        let mut headers = Vec::new();
+        for i in 0..4u64 {
+            let header = Header {
+                number: newest_block - 3 + i,
+                gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+                gas_used: ETHEREUM_BLOCK_GAS_LIMIT / 2,
+                base_fee_per_gas: Some(7),
+                excess_blob_gas: Some(i * 4 * TARGET_DATA_GAS_PER_BLOCK),
+                blob_gas_used: Some(i * DATA_GAS_PER_BLOB),
+                ..Default::default()
+            };
+            let hash = H256::random();
+            mock_provider.add_block(hash, Block { header: header.clone(), ..Default::default() });
+            mock_provider.add_header(hash, header.clone());
+            headers.push(header);
+        }
+
+        let mut base_fees_per_blob_gas = headers
+            .iter()
+            .map(|header| U256::from(header.blob_fee().unwrap().to::<u128>()))
+            .collect::<Vec<_>>();
+        let next_blob_fee =
+            U256::from(headers.last().unwrap().next_block_blob_fee().unwrap().to::<u128>());
+        base_fees_per_blob_gas.push(next_blob_fee);
+        let blob_gas_used_ratios = headers
+            .iter()
+            .map(|header| header.blob_gas_used.unwrap() as f64 / MAX_DATA_GAS_PER_BLOCK as f64)
+            .collect::<Vec<_>>();
+
+        let eth_api = build_test_eth_api(mock_provider);
+        let fee_history = eth_api.fee_history(4, newest_block.into(), None).await.unwrap();
+        assert_eq!(fee_history.base_fee_per_blob_gas, base_fees_per_blob_gas);
+        assert_eq!(fee_history.blob_gas_used_ratio, blob_gas_used_ratios);
+        assert!(base_fees_per_blob_gas.windows(2).all(|fees| fees[0] <= fees[1]));
+
+        assert_eq!(eth_api.blob_base_fee().unwrap(), next_blob_fee);
+        assert!(eth_api.suggested_blob_fee().await.unwrap() >= next_blob_fee);
+    }
 }
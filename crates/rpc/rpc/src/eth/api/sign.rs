@@ -1,41 +1,122 @@
 //! Contains RPC handler implementations specific to sign endpoints
 use crate::{
     eth::{
-        error::{EthResult, SignError},
+        error::{EthResult, RpcInvalidTransactionError, SignError},
         signer::EthSigner,
     },
     EthApi,
 };
 use ethers_core::types::transaction::eip712::TypedData;
-use reth_primitives::{Address, Bytes};
+use reth_primitives::{Address, Bytes, U128, U256, U64};
+use reth_rpc_types::{CallRequest, TransactionRequest};
 use serde_json::Value;
-use std::ops::Deref;
+use std::sync::Arc;
 
 impl<Provider, Pool, Network> EthApi<Provider, Pool, Network> {
     pub(crate) async fn sign(&self, account: Address, message: Bytes) -> EthResult<Bytes> {
         let signer = self.find_signer(&account)?;
         let signature = signer.sign(account, &message).await?;
-        let bytes = hex::encode(signature.to_bytes()).as_bytes().into();
-        Ok(bytes)
+        Ok(signature.to_bytes().to_vec().into())
     }
 
     pub(crate) async fn sign_typed_data(&self, data: Value, account: Address) -> EthResult<Bytes> {
         let signer = self.find_signer(&account)?;
         let data = serde_json::from_value::<TypedData>(data).map_err(|_| SignError::TypedData)?;
         let signature = signer.sign_typed_data(account, &data)?;
-        let bytes = hex::encode(signature.to_bytes()).as_bytes().into();
-        Ok(bytes)
+        Ok(signature.to_bytes().to_vec().into())
     }
 
-    pub(crate) fn find_signer(
-        &self,
-        account: &Address,
-    ) -> Result<&(dyn EthSigner + 'static), SignError> {
+    pub(crate) fn find_signer(&self, account: &Address) -> Result<Arc<dyn EthSigner>, SignError> {
         self.inner
             .signers
+            .read()
             .iter()
             .find(|signer| signer.is_signer_for(account))
-            .map(|signer| signer.deref())
+            .cloned()
             .ok_or(SignError::NoAccount)
     }
 }
+
+/// Converts the [CallRequest] of `eth_signTransaction` into a [TransactionRequest].
+///
+/// Fails if the request is for another chain or a fee does not fit into 128 bits.
+pub(crate) fn into_transaction_request(
+    request: CallRequest,
+    chain_id: U64,
+) -> EthResult<TransactionRequest> {
+    let CallRequest {
+        from,
+        to,
+        gas_price,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        gas,
+        value,
+        input,
+        nonce,
+        chain_id: request_chain_id,
+        access_list,
+        transaction_type,
+    } = request;
+
+    if request_chain_id.map_or(false, |id| id != chain_id) {
+        return Err(RpcInvalidTransactionError::InvalidChainId.into())
+    }
+
+    let fee = |fee: Option<U256>, err: RpcInvalidTransactionError| {
+        fee.map(|fee| u128::try_from(fee).map(U128::from).map_err(|_| err)).transpose()
+    };
+
+    Ok(TransactionRequest {
+        from,
+        to,
+        gas_price: fee(gas_price, RpcInvalidTransactionError::FeeCapVeryHigh)?,
+        max_fee_per_gas: fee(max_fee_per_gas, RpcInvalidTransactionError::FeeCapVeryHigh)?,
+        max_priority_fee_per_gas: fee(
+            max_priority_fee_per_gas,
+            RpcInvalidTransactionError::TipVeryHigh,
+        )?,
+        gas,
+        value,
+        data: input.try_into_unique_input()?,
+        nonce,
+        access_list,
+        transaction_type,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::error::EthApiError;
+    use reth_rpc_types::CallInput;
+
+    #[test]
+    fn test_into_transaction_request() {
+        let request = CallRequest {
+            max_fee_per_gas: Some(U256::from(20)),
+            max_priority_fee_per_gas: Some(U256::from(1)),
+            input: CallInput { input: Some(Bytes::from(vec![1, 2])), data: None },
+            chain_id: Some(U64::from(1)),
+            ..Default::default()
+        };
+        let tx = into_transaction_request(request.clone(), U64::from(1)).unwrap();
+        assert_eq!(tx.max_fee_per_gas, Some(U128::from(20)));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(U128::from(1)));
+        assert_eq!(tx.data, Some(Bytes::from(vec![1, 2])));
+
+        let err = into_transaction_request(request.clone(), U64::from(5)).unwrap_err();
+        assert!(matches!(
+            err,
+            EthApiError::InvalidTransaction(RpcInvalidTransactionError::InvalidChainId)
+        ));
+
+        let request = CallRequest { max_fee_per_gas: Some(U256::MAX), ..request };
+        let err = into_transaction_request(request, U64::from(1)).unwrap_err();
+        assert!(matches!(
+            err,
+            EthApiError::InvalidTransaction(RpcInvalidTransactionError::FeeCapVeryHigh)
+        ));
+    }
+}
@@ -15,8 +15,8 @@ use async_trait::async_trait;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
     constants::eip4844::blob_fee,
-    Address, BlockId, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction, Header,
-    IntoRecoveredTransaction, Receipt, SealedBlock,
+    Address, BlobTransaction, BlockId, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction,
+    Header, IntoRecoveredTransaction, PooledTransactionsElement, Receipt, SealedBlock,
     TransactionKind::{Call, Create},
     TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, H256, U128, U256, U64,
 };
@@ -132,8 +132,16 @@ pub trait EthTransactions: Send + Sync {
 
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
+    ///
+    /// EIP-4844 transactions must include their blob sidecar.
     async fn send_transaction(&self, request: TransactionRequest) -> EthResult<H256>;
 
+    /// Fills in the nonce, gas limit and fees that are missing from the request and signs it with a
+    /// matching signer, if any.
+    ///
+    /// The blob sidecar of EIP-4844 requests is ignored.
+    async fn sign_transaction(&self, request: TransactionRequest) -> EthResult<TransactionSigned>;
+
     /// Prepares the state and env for the given [CallRequest] at the given [BlockId] and executes
     /// the closure on a new task returning the result of the closure.
     async fn spawn_with_call_at<F, R>(
@@ -437,6 +445,40 @@ where
     }
 
     async fn send_transaction(&self, mut request: TransactionRequest) -> EthResult<H256> {
+        let sidecar = request.sidecar.take();
+        let signed_tx = self.sign_transaction(request).await?;
+
+        let transaction = match sidecar {
+            Some(sidecar) => BlobTransaction::try_from_signed(signed_tx, sidecar)
+                .map(PooledTransactionsElement::BlobTransaction)
+                .map_err(|_| {
+                    EthApiError::InvalidParams(
+                        "blob sidecar is only supported for EIP-4844 transactions".to_string(),
+                    )
+                })?,
+            None if signed_tx.is_eip4844() => {
+                return Err(EthApiError::InvalidParams(
+                    "EIP-4844 transactions require a blob sidecar".to_string(),
+                ))
+            }
+            None => signed_tx.into(),
+        };
+
+        let recovered =
+            transaction.try_into_ecrecovered().or(Err(EthApiError::InvalidTransactionSignature))?;
+
+        let pool_transaction = <Pool::Transaction>::from_recovered_transaction(recovered);
+
+        // submit the transaction to the pool with a `Local` origin
+        let hash = self.pool().add_transaction(TransactionOrigin::Local, pool_transaction).await?;
+
+        Ok(hash)
+    }
+
+    async fn sign_transaction(
+        &self,
+        mut request: TransactionRequest,
+    ) -> EthResult<TransactionSigned> {
         let from = match request.from {
             Some(from) => from,
             None => return Err(SignError::NoAccount.into()),
@@ -450,67 +492,87 @@ where
         }
 
         let chain_id = self.chain_id();
-        // TODO: we need an oracle to fetch the gas price of the current chain
-        let gas_price = request.gas_price.unwrap_or_default();
-        let max_fee_per_gas = request.max_fee_per_gas.unwrap_or_default();
-
-        let estimated_gas = self
-            .estimate_gas_at(
-                CallRequest {
-                    from: Some(from),
-                    to: request.to,
-                    gas: request.gas,
-                    gas_price: Some(U256::from(gas_price)),
-                    max_fee_per_gas: Some(U256::from(max_fee_per_gas)),
-                    value: request.value,
-                    input: request.data.clone().into(),
-                    nonce: request.nonce,
-                    chain_id: Some(chain_id),
-                    access_list: request.access_list.clone(),
-                    max_priority_fee_per_gas: Some(U256::from(max_fee_per_gas)),
-                    transaction_type: None,
-                },
-                BlockId::Number(BlockNumberOrTag::Pending),
-            )
-            .await?;
-        let gas_limit = estimated_gas;
+        let TransactionRequest {
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            max_fee_per_blob_gas,
+            ..
+        } = request;
+
+        let gas_limit = match request.gas {
+            Some(gas) => gas,
+            None => {
+                self.estimate_gas_at(
+                    CallRequest {
+                        from: Some(from),
+                        to: request.to,
+                        gas: None,
+                        gas_price: gas_price.map(U256::from),
+                        max_fee_per_gas: max_fee_per_gas.map(U256::from),
+                        value: request.value,
+                        input: request.data.clone().into(),
+                        nonce: request.nonce,
+                        chain_id: Some(chain_id),
+                        access_list: request.access_list.clone(),
+                        max_priority_fee_per_gas: max_priority_fee_per_gas.map(U256::from),
+                        transaction_type: None,
+                    },
+                    BlockId::Number(BlockNumberOrTag::Pending),
+                )
+                .await?
+            }
+        };
 
         let transaction = match request.into_typed_request() {
             Some(TypedTransactionRequest::Legacy(mut m)) => {
                 m.chain_id = Some(chain_id.as_u64());
                 m.gas_limit = gas_limit;
-                m.gas_price = gas_price;
+                m.gas_price = match gas_price {
+                    Some(gas_price) => gas_price,
+                    None => U128::from(self.gas_price().await?),
+                };
 
                 TypedTransactionRequest::Legacy(m)
             }
             Some(TypedTransactionRequest::EIP2930(mut m)) => {
                 m.chain_id = chain_id.as_u64();
                 m.gas_limit = gas_limit;
-                m.gas_price = gas_price;
+                m.gas_price = match gas_price {
+                    Some(gas_price) => gas_price,
+                    None => U128::from(self.gas_price().await?),
+                };
 
                 TypedTransactionRequest::EIP2930(m)
             }
             Some(TypedTransactionRequest::EIP1559(mut m)) => {
+                let (max_fee_per_gas, max_priority_fee_per_gas) =
+                    self.dynamic_fees(max_fee_per_gas, max_priority_fee_per_gas).await?;
                 m.chain_id = chain_id.as_u64();
                 m.gas_limit = gas_limit;
                 m.max_fee_per_gas = max_fee_per_gas;
+                m.max_priority_fee_per_gas = max_priority_fee_per_gas;
 
                 TypedTransactionRequest::EIP1559(m)
             }
+            Some(TypedTransactionRequest::EIP4844(mut m)) => {
+                let (max_fee_per_gas, max_priority_fee_per_gas) =
+                    self.dynamic_fees(max_fee_per_gas, max_priority_fee_per_gas).await?;
+                m.chain_id = chain_id.as_u64();
+                m.gas_limit = gas_limit;
+                m.max_fee_per_gas = max_fee_per_gas;
+                m.max_priority_fee_per_gas = max_priority_fee_per_gas;
+                m.max_fee_per_blob_gas = match max_fee_per_blob_gas {
+                    Some(max_fee_per_blob_gas) => max_fee_per_blob_gas,
+                    None => U128::from(self.suggested_blob_fee().await?),
+                };
+
+                TypedTransactionRequest::EIP4844(m)
+            }
             None => return Err(EthApiError::ConflictingFeeFieldsInRequest),
         };
 
-        let signed_tx = self.sign_request(&from, transaction)?;
-
-        let recovered =
-            signed_tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
-
-        let pool_transaction = <Pool::Transaction>::from_recovered_transaction(recovered.into());
-
-        // submit the transaction to the pool with a `Local` origin
-        let hash = self.pool().add_transaction(TransactionOrigin::Local, pool_transaction).await?;
-
-        Ok(hash)
+        self.sign_request(&from, transaction)
     }
 
     async fn spawn_with_call_at<F, R>(
@@ -716,15 +778,8 @@ where
         from: &Address,
         request: TypedTransactionRequest,
     ) -> EthResult<TransactionSigned> {
-        for signer in self.inner.signers.iter() {
-            if signer.is_signer_for(from) {
-                return match signer.sign_transaction(request, from) {
-                    Ok(tx) => Ok(tx),
-                    Err(e) => Err(e.into()),
-                }
-            }
-        }
-        Err(EthApiError::InvalidTransactionSignature)
+        let signer = self.find_signer(from)?;
+        Ok(signer.sign_transaction(request, from)?)
     }
 
     /// Get Transaction by [BlockId] and the index of the transaction within that Block.
@@ -845,6 +900,34 @@ impl From<TransactionSource> for Transaction {
     }
 }
 
+/// Helper function to construct the transaction receipts of a block
+pub(crate) fn build_block_receipts(
+    block: SealedBlock,
+    receipts: Vec<Receipt>,
+) -> EthResult<Vec<TransactionReceipt>> {
+    let block_number = block.number;
+    let base_fee = block.base_fee_per_gas;
+    let block_hash = block.hash;
+    let excess_blob_gas = block.excess_blob_gas;
+    block
+        .body
+        .into_iter()
+        .zip(receipts.clone())
+        .enumerate()
+        .map(|(idx, (tx, receipt))| {
+            let meta = TransactionMeta {
+                tx_hash: tx.hash,
+                index: idx as u64,
+                block_hash,
+                block_number,
+                base_fee,
+                excess_blob_gas,
+            };
+            build_transaction_receipt_with_block_receipts(tx, meta, receipt, &receipts)
+        })
+        .collect()
+}
+
 /// Helper function to construct a transaction receipt
 pub(crate) fn build_transaction_receipt_with_block_receipts(
     tx: TransactionSigned,
@@ -12,7 +12,7 @@ use reth_transaction_pool::error::{
     Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolTransactionError,
 };
 use revm::primitives::{EVMError, ExecutionResult, Halt, OutOfGasError};
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 /// Result alias
 pub type EthResult<T> = Result<T, EthApiError>;
@@ -553,6 +553,29 @@ pub enum SignError {
     NoChainId,
 }
 
+/// Errors that can occur when loading a [KeystoreSigner](crate::eth::KeystoreSigner).
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreSignerError {
+    /// Failed to read the keystore directory or password file.
+    #[error("failed to read {0:?}: {1}")]
+    Io(PathBuf, #[source] std::io::Error),
+    /// Failed to decrypt a key file.
+    #[error("failed to decrypt key file {0:?}: {1}")]
+    Keystore(PathBuf, #[source] eth_keystore::KeystoreError),
+    /// None of the passwords could decrypt the key file.
+    #[error("no password matches key file {0:?}")]
+    WrongPassword(PathBuf),
+    /// The decrypted key is not a valid secp256k1 secret key.
+    #[error("invalid secret key in {0:?}")]
+    InvalidKey(PathBuf),
+    /// The keystore directory doesn't contain any key files.
+    #[error("no key files found in {0:?}")]
+    Empty(PathBuf),
+    /// No passwords were provided.
+    #[error("no keystore passwords provided")]
+    NoPasswords,
+}
+
 /// Converts the evm [ExecutionResult] into a result where `Ok` variant is the output bytes if it is
 /// [ExecutionResult::Success].
 pub(crate) fn ensure_success(result: ExecutionResult) -> EthResult<Bytes> {
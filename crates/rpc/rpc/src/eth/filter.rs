@@ -8,9 +8,10 @@ use crate::{
     EthSubscriptionIdProvider,
 };
 use async_trait::async_trait;
+use futures::{Stream, StreamExt};
 use jsonrpsee::{core::RpcResult, server::IdProvider};
-use reth_primitives::{BlockHashOrNumber, Receipt, SealedBlock};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider};
+use reth_primitives::{BlockHashOrNumber, BlockId, Receipt, SealedBlock, H256};
+use reth_provider::{BlockReaderIdExt, CanonStateNotification, Chain, EvmEnvProvider};
 use reth_rpc_api::EthFilterApiServer;
 use reth_rpc_types::{Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log};
 use reth_tasks::TaskSpawner;
@@ -63,27 +64,34 @@ impl<Provider, Pool> EthFilter<Provider, Pool> {
 
 impl<Provider, Pool> EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReaderIdExt + EvmEnvProvider + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns all the filter changes for the given id, if any
     pub async fn filter_changes(&self, id: FilterId) -> Result<FilterChanges, FilterError> {
-        let info = self.inner.provider.chain_info()?;
-        let best_number = info.best_number;
-
-        let (start_block, kind) = {
+        let (info, start_block, kind, removed_logs) = {
             let mut filters = self.inner.active_filters.inner.lock().await;
             let filter = filters.get_mut(&id).ok_or(FilterError::FilterNotFound(id))?;
 
+            // the chain is read while holding the lock, so a concurrent reorg either rewinds the
+            // filter before this poll or sees the new tip recorded below
+            let info = self.inner.provider.chain_info()?;
+
+            // if the last returned block is no longer canonical, the reorg is not processed yet
+            // and the filter is rewound here instead
+            self.rewind_to_canonical_ancestor(filter)?;
+
             // update filter
             // we fetch all changes from [filter.block..best_block], so we advance the filter's
             // block to `best_block +1`
-            let mut block = best_number + 1;
+            let mut block = info.best_number + 1;
             std::mem::swap(&mut filter.block, &mut block);
+            filter.block_hash = Some(info.best_hash);
             filter.last_poll_timestamp = Instant::now();
 
-            (block, filter.kind.clone())
+            (info, block, filter.kind.clone(), std::mem::take(&mut filter.removed_logs))
         };
+        let best_number = info.best_number;
 
         match kind {
             FilterKind::PendingTransaction => {
@@ -121,15 +129,72 @@ where
                     }
                 };
 
-                let logs = self
-                    .inner
-                    .get_logs_in_block_range(&filter, from_block_number, to_block_number)
-                    .await?;
+                // logs of reorged blocks come first, marked as removed
+                let mut logs = removed_logs;
+                logs.extend(
+                    self.inner
+                        .get_logs_in_block_range(&filter, from_block_number, to_block_number)
+                        .await?,
+                );
                 Ok(FilterChanges::Logs(logs))
             }
         }
     }
 
+    /// Rewinds the filter to the last canonical ancestor of the last block it returned changes
+    /// for, if that block is no longer canonical.
+    ///
+    /// The logs of the non canonical blocks that a log filter already returned are queued as
+    /// removed, like [ActiveFilters::on_reorg] does.
+    fn rewind_to_canonical_ancestor(&self, filter: &mut ActiveFilter) -> Result<(), FilterError> {
+        let Some(mut hash) = filter.block_hash else { return Ok(()) };
+        let mut number = filter.block.saturating_sub(1);
+        let params = match filter.kind {
+            FilterKind::Log(ref log_filter) => Some(FilteredParams::new(Some(*log_filter.clone()))),
+            _ => None,
+        };
+
+        // removed logs of the reverted blocks, newest block first
+        let mut reverted = Vec::new();
+        while self.inner.provider.block_hash(number)? != Some(hash) {
+            trace!(target: "rpc::eth::filter", number, ?hash, "rewinding filter past reorged block");
+            let Some(block) = self.inner.provider.block_by_hash(hash)? else {
+                // the reverted block is gone, so the canonical block at its height is the first
+                // one that was not returned yet
+                filter.block = number;
+                filter.block_hash = None;
+                break
+            };
+
+            if let Some(ref params) = params {
+                if params.filter_block_range(number) && params.filter_block_hash(hash) {
+                    let receipts =
+                        self.inner.provider.receipts_by_block_id(BlockId::Hash(hash.into()))?;
+                    let mut logs = Vec::new();
+                    logs_utils::append_matching_block_logs(
+                        &mut logs,
+                        params,
+                        (number, hash).into(),
+                        block.body.iter().map(|tx| tx.hash()).zip(receipts.unwrap_or_default()),
+                        true,
+                    );
+                    reverted.push(logs);
+                }
+            }
+
+            if number == 0 {
+                break
+            }
+            hash = block.parent_hash;
+            number -= 1;
+            filter.block = number + 1;
+            filter.block_hash = Some(hash);
+        }
+
+        filter.removed_logs.extend(reverted.into_iter().rev().flatten());
+        Ok(())
+    }
+
     /// Returns an array of all logs matching filter with given id.
     ///
     /// Returns an error if no matching log filter exists.
@@ -156,7 +221,7 @@ where
 #[async_trait]
 impl<Provider, Pool> EthFilterApiServer for EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReaderIdExt + EvmEnvProvider + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Handler for `eth_newFilter`
@@ -251,7 +316,7 @@ struct EthFilterInner<Provider, Pool> {
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReaderIdExt + EvmEnvProvider + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
@@ -304,8 +369,10 @@ where
             id.clone(),
             ActiveFilter {
                 block: last_poll_block_number,
+                block_hash: None,
                 last_poll_timestamp: Instant::now(),
                 kind,
+                removed_logs: Vec::new(),
             },
         );
         Ok(id)
@@ -400,15 +467,77 @@ pub struct ActiveFilters {
     inner: Arc<Mutex<HashMap<FilterId, ActiveFilter>>>,
 }
 
+impl ActiveFilters {
+    /// Rewinds all filters that already returned changes for blocks of the reverted chain.
+    ///
+    /// The logs of the reverted blocks that an affected log filter already returned are returned
+    /// again with `removed: true` on the next poll, followed by the logs of the new canonical
+    /// blocks.
+    pub async fn on_reorg(&self, reverted: &Chain) {
+        let mut filters = self.inner.lock().await;
+        for (id, filter) in filters.iter_mut() {
+            // the filter is only affected if its last seen canonical block was reverted
+            let Some(last_seen) = filter.block_hash.and_then(|hash| reverted.block_number(hash))
+            else {
+                continue
+            };
+            trace!(target: "rpc::eth::filter", ?id, last_seen, "rewinding filter after reorg");
+
+            if let FilterKind::Log(ref log_filter) = filter.kind {
+                let params = FilteredParams::new(Some(*log_filter.clone()));
+                for (_, block) in reverted.blocks().range(..=last_seen) {
+                    // only blocks within the filter's range had their logs returned
+                    if !params.filter_block_range(block.number) ||
+                        !params.filter_block_hash(block.hash)
+                    {
+                        continue
+                    }
+                    let Some(receipts) = reverted.receipts_by_block_hash(block.hash) else {
+                        continue
+                    };
+                    logs_utils::append_matching_block_logs(
+                        &mut filter.removed_logs,
+                        &params,
+                        block.num_hash(),
+                        block.body.iter().map(|tx| tx.hash()).zip(receipts.iter().cloned()),
+                        true,
+                    );
+                }
+            }
+
+            // the new canonical blocks are fetched on the next poll
+            filter.block = filter.block.min(reverted.first().number);
+            filter.block_hash = Some(reverted.fork_block_hash());
+        }
+    }
+}
+
+/// Awaits for new chain events and rewinds the active filters on reorgs, see
+/// [ActiveFilters::on_reorg].
+pub async fn filter_reorgs_task<St>(active_filters: ActiveFilters, mut events: St)
+where
+    St: Stream<Item = CanonStateNotification> + Unpin + 'static,
+{
+    while let Some(event) = events.next().await {
+        if let Some(reverted) = event.reverted() {
+            active_filters.on_reorg(&reverted).await;
+        }
+    }
+}
+
 /// An installed filter
 #[derive(Debug)]
 struct ActiveFilter {
     /// At which block the filter was polled last.
     block: u64,
+    /// Hash of the last canonical block the filter returned changes for, if polled yet.
+    block_hash: Option<H256>,
     /// Last time this filter was polled.
     last_poll_timestamp: Instant,
     /// What kind of filter it is.
     kind: FilterKind,
+    /// Logs of reorged blocks that are returned as removed on the next poll.
+    removed_logs: Vec<Log>,
 }
 
 #[derive(Clone, Debug)]
@@ -488,6 +617,11 @@ impl Iterator for BlockRangeInclusiveIter {
 mod tests {
     use super::*;
     use rand::{thread_rng, Rng};
+    use reth_interfaces::test_utils::{generators, generators::random_block};
+    use reth_primitives::{Address, SealedBlockWithSenders};
+    use reth_provider::{test_utils::MockEthProvider, PostState};
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::test_utils::testing_pool;
 
     #[test]
     fn test_block_range_iter() {
@@ -511,4 +645,105 @@ mod tests {
             assert_eq!(end, *range.end());
         }
     }
+
+    #[tokio::test]
+    async fn test_reorg_rewinds_polled_log_filter() {
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, Some(H256::random()), Some(1), Some(0));
+        let fork_hash = block.parent_hash;
+        let block_hash = block.hash;
+
+        let mut state = PostState::new();
+        state.add_receipt(
+            1,
+            Receipt {
+                logs: vec![reth_primitives::Log {
+                    address: Address::from_low_u64_be(1),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+        let reverted = Chain::new(vec![(SealedBlockWithSenders { block, senders: vec![] }, state)]);
+
+        let active_filters = ActiveFilters::default();
+        let id = FilterId::Num(0);
+        active_filters.inner.lock().await.insert(
+            id.clone(),
+            ActiveFilter {
+                block: 2,
+                block_hash: Some(block_hash),
+                last_poll_timestamp: Instant::now(),
+                kind: FilterKind::Log(Box::default()),
+                removed_logs: Vec::new(),
+            },
+        );
+        // the reverted block is outside of this filter's range
+        let out_of_range = FilterId::Num(1);
+        active_filters.inner.lock().await.insert(
+            out_of_range.clone(),
+            ActiveFilter {
+                block: 2,
+                block_hash: Some(block_hash),
+                last_poll_timestamp: Instant::now(),
+                kind: FilterKind::Log(Box::new(Filter::new().from_block(2u64))),
+                removed_logs: Vec::new(),
+            },
+        );
+
+        active_filters.on_reorg(&reverted).await;
+
+        let filters = active_filters.inner.lock().await;
+        let filter = filters.get(&id).unwrap();
+        assert_eq!(filter.block, 1);
+        assert_eq!(filter.block_hash, Some(fork_hash));
+        assert_eq!(filter.removed_logs.len(), 1);
+        assert!(filter.removed_logs[0].removed);
+        assert_eq!(filter.removed_logs[0].block_hash, Some(block_hash));
+
+        let filter = filters.get(&out_of_range).unwrap();
+        assert_eq!(filter.block, 1);
+        assert!(filter.removed_logs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_poll_rewinds_filter_past_unknown_block() {
+        let mut rng = generators::rng();
+        let provider = MockEthProvider::default();
+        let mut parent_hash = H256::zero();
+        let mut hashes = Vec::new();
+        for number in 0..3 {
+            let block = random_block(&mut rng, number, Some(parent_hash), Some(0), Some(0));
+            parent_hash = block.hash;
+            hashes.push(block.hash);
+            provider.add_block(block.hash, block.unseal());
+        }
+        let eth_filter = EthFilter::new(
+            provider.clone(),
+            testing_pool(),
+            EthStateCache::spawn(provider, Default::default()),
+            usize::MAX,
+            Box::<TokioTaskExecutor>::default(),
+        );
+
+        // the last returned block is canonical
+        let mut filter = ActiveFilter {
+            block: 2,
+            block_hash: Some(hashes[1]),
+            last_poll_timestamp: Instant::now(),
+            kind: FilterKind::Log(Box::default()),
+            removed_logs: Vec::new(),
+        };
+        eth_filter.rewind_to_canonical_ancestor(&mut filter).unwrap();
+        assert_eq!(filter.block, 2);
+        assert_eq!(filter.block_hash, Some(hashes[1]));
+
+        // the last returned block was reorged and is no longer known
+        filter.block = 3;
+        filter.block_hash = Some(H256::random());
+        eth_filter.rewind_to_canonical_ancestor(&mut filter).unwrap();
+        assert_eq!(filter.block, 2);
+        assert_eq!(filter.block_hash, None);
+        assert!(filter.removed_logs.is_empty());
+    }
 }
@@ -4,7 +4,10 @@ use crate::eth::{
     cache::EthStateCache,
     error::{EthApiError, EthResult, RpcInvalidTransactionError},
 };
-use reth_primitives::{constants::GWEI_TO_WEI, BlockNumberOrTag, H256, U256};
+use reth_primitives::{
+    constants::{eip4844::BLOB_TX_MIN_BLOB_GASPRICE, GWEI_TO_WEI},
+    BlockNumberOrTag, H256, U256,
+};
 use reth_provider::BlockReaderIdExt;
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
@@ -90,6 +93,8 @@ pub struct GasPriceOracle<Provider> {
     oracle_config: GasPriceOracleConfig,
     /// The latest calculated price and its block hash
     last_price: Mutex<GasPriceOracleResult>,
+    /// The latest calculated blob fee and its block hash
+    last_blob_fee: Mutex<GasPriceOracleResult>,
 }
 
 impl<Provider> GasPriceOracle<Provider>
@@ -108,7 +113,13 @@ where
             oracle_config.percentile = 100;
         }
 
-        Self { provider, oracle_config, last_price: Default::default(), cache }
+        Self {
+            provider,
+            oracle_config,
+            last_price: Default::default(),
+            last_blob_fee: Default::default(),
+            cache,
+        }
     }
 
     /// Returns the configuration of the gas price oracle.
@@ -188,6 +199,51 @@ where
         Ok(price)
     }
 
+    /// Suggests a max fee per blob gas based on the blob fees of recent blocks.
+    ///
+    /// This is the configured percentile of the blob fees of the last `blocks` blocks, but at least
+    /// the blob fee of the next block. Before Cancun this is the minimum blob fee.
+    pub async fn suggest_blob_fee_cap(&self) -> EthResult<U256> {
+        let header = self
+            .provider
+            .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+
+        let mut last_blob_fee = self.last_blob_fee.lock().await;
+
+        // if we have stored a last blob fee, then we check whether or not it was for the same head
+        if last_blob_fee.block_hash == header.hash {
+            return Ok(last_blob_fee.price)
+        }
+
+        let mut price = header
+            .next_block_blob_fee()
+            .map(|fee| U256::from(fee.to::<u128>()))
+            .unwrap_or(U256::from(BLOB_TX_MIN_BLOB_GASPRICE));
+
+        let start_block =
+            header.number.saturating_sub(self.oracle_config.blocks.saturating_sub(1) as u64);
+        let mut results = self
+            .provider
+            .sealed_headers_range(start_block..=header.number)?
+            .iter()
+            .filter_map(|header| header.blob_fee())
+            .map(|fee| U256::from(fee.to::<u128>()))
+            .collect::<Vec<_>>();
+
+        // take the configured percentile, but never go below the blob fee of the next block
+        if !results.is_empty() {
+            results.sort_unstable();
+            let percentile =
+                results[(results.len() - 1) * self.oracle_config.percentile as usize / 100];
+            price = price.max(percentile);
+        }
+
+        *last_blob_fee = GasPriceOracleResult { block_hash: header.hash, price };
+
+        Ok(price)
+    }
+
     /// Get the `limit` lowest effective tip values for the given block. If the oracle has a
     /// configured `ignore_price` threshold, then tip values under that threshold will be ignored
     /// before returning a result.
@@ -7,12 +7,13 @@ mod filter;
 pub mod gas_oracle;
 mod id_provider;
 mod logs_utils;
-mod pubsub;
+pub(crate) mod pubsub;
 pub mod revm_utils;
 mod signer;
 pub(crate) mod utils;
 
 pub use api::{EthApi, EthApiSpec, EthTransactions, TransactionSource, RPC_DEFAULT_GAS_CAP};
-pub use filter::EthFilter;
+pub use filter::{filter_reorgs_task, EthFilter};
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pubsub::EthPubSub;
+pub use signer::KeystoreSigner;
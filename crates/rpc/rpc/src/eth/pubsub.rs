@@ -1,19 +1,25 @@
 //! `eth_` PubSub RPC handler implementation
-use crate::{eth::logs_utils, result::invalid_params_rpc_err};
+use crate::{
+    eth::{
+        api::build_block_receipts,
+        error::{EthApiError, EthResult},
+        logs_utils,
+    },
+    result::invalid_params_rpc_err,
+};
 use futures::StreamExt;
 use jsonrpsee::{server::SubscriptionMessage, PendingSubscriptionSink, SubscriptionSink};
 use reth_network_api::NetworkInfo;
-use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider};
+use reth_primitives::{IntoRecoveredTransaction, SealedBlockWithSenders, TxHash};
+use reth_provider::{BlockReader, CanonStateSubscriptions, Chain, EvmEnvProvider};
 use reth_rpc_api::EthPubSubApiServer;
 use reth_rpc_types::{
     pubsub::{
-        Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
-        SyncStatusMetadata,
+        BlockWithReceipts, Params, PubSubSyncStatus, SubscriptionKind,
+        SubscriptionResult as EthSubscriptionResult, SyncStatusMetadata,
     },
     FilteredParams, Header, Log,
 };
-use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{NewTransactionEvent, TransactionPool};
 use serde::Serialize;
 use std::sync::Arc;
@@ -29,8 +35,6 @@ use tokio_stream::{
 pub struct EthPubSub<Provider, Pool, Events, Network> {
     /// All nested fields bundled together.
     inner: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
-    /// The type that's used to spawn subscription tasks.
-    subscription_task_spawner: Box<dyn TaskSpawner>,
 }
 
 // === impl EthPubSub ===
@@ -38,27 +42,10 @@ pub struct EthPubSub<Provider, Pool, Events, Network> {
 impl<Provider, Pool, Events, Network> EthPubSub<Provider, Pool, Events, Network> {
     /// Creates a new, shareable instance.
     ///
-    /// Subscription tasks are spawned via [tokio::task::spawn]
+    /// Every subscription is served on its own task by the server.
     pub fn new(provider: Provider, pool: Pool, chain_events: Events, network: Network) -> Self {
-        Self::with_spawner(
-            provider,
-            pool,
-            chain_events,
-            network,
-            Box::<TokioTaskExecutor>::default(),
-        )
-    }
-
-    /// Creates a new, shareable instance.
-    pub fn with_spawner(
-        provider: Provider,
-        pool: Pool,
-        chain_events: Events,
-        network: Network,
-        subscription_task_spawner: Box<dyn TaskSpawner>,
-    ) -> Self {
         let inner = EthPubSubInner { provider, pool, chain_events, network };
-        Self { inner: Arc::new(inner), subscription_task_spawner }
+        Self { inner: Arc::new(inner) }
     }
 }
 
@@ -72,6 +59,9 @@ where
     Network: NetworkInfo + Clone + 'static,
 {
     /// Handler for `eth_subscribe`
+    ///
+    /// If the subscription fails after it was accepted, the error is sent to the subscriber and
+    /// the subscription is closed.
     async fn subscribe(
         &self,
         pending: PendingSubscriptionSink,
@@ -79,11 +69,8 @@ where
         params: Option<Params>,
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
-        let pubsub = self.inner.clone();
-        self.subscription_task_spawner.spawn(Box::pin(async move {
-            let _ = handle_accepted(pubsub, sink, kind, params).await;
-        }));
-
+        // the server already runs this on a separate task until the subscription is closed
+        handle_accepted(self.inner.clone(), sink, kind, params).await?;
         Ok(())
     }
 }
@@ -184,11 +171,42 @@ where
 
             Ok(())
         }
+        SubscriptionKind::NewBlocksWithReceipts => {
+            if params.is_some() {
+                return Err(invalid_params_rpc_err("Invalid params for newBlocksWithReceipts").into())
+            }
+            let stream = pubsub.blocks_with_receipts_stream().map(|block| {
+                block.map(|block| EthSubscriptionResult::BlockWithReceipts(Box::new(block)))
+            });
+            pipe_from_fallible_stream(accepted_sink, stream).await
+        }
     }
 }
 
+/// Converts a block of the given chain into a [BlockWithReceipts].
+///
+/// Returns an error if the chain has no receipts for the block.
+fn block_with_receipts<Provider: BlockReader>(
+    provider: &Provider,
+    chain: &Chain,
+    block: &SealedBlockWithSenders,
+    removed: bool,
+) -> EthResult<BlockWithReceipts> {
+    let receipts =
+        chain.receipts_by_block_hash(block.hash).ok_or(EthApiError::InternalEthError)?.to_vec();
+    let receipts = build_block_receipts(block.block.clone(), receipts)?;
+    // the total difficulty of reverted blocks may not be known anymore
+    let total_difficulty = provider.header_td(&block.hash)?.unwrap_or_default();
+    let block = reth_rpc_types_compat::block::from_block_full(
+        block.block.clone().unseal(),
+        total_difficulty,
+        Some(block.hash),
+    )?;
+    Ok(BlockWithReceipts { block, receipts, removed })
+}
+
 /// Pipes all stream items to the subscription sink.
-async fn pipe_from_stream<T, St>(
+pub(crate) async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
     mut stream: St,
 ) -> Result<(), jsonrpsee::core::Error>
@@ -219,6 +237,42 @@ where
     }
 }
 
+/// Pipes all stream items to the subscription sink until the stream yields an error.
+///
+/// The error is returned, so that it's sent to the subscriber.
+async fn pipe_from_fallible_stream<T, E, St>(
+    sink: SubscriptionSink,
+    mut stream: St,
+) -> Result<(), jsonrpsee::core::Error>
+where
+    St: Stream<Item = Result<T, E>> + Unpin,
+    T: Serialize,
+    E: Into<jsonrpsee::core::Error>,
+{
+    loop {
+        tokio::select! {
+            _ = sink.closed() => {
+                // connection dropped
+                break Ok(())
+            },
+            maybe_item = stream.next() => {
+                let item = match maybe_item {
+                    Some(Ok(item)) => item,
+                    Some(Err(err)) => break Err(err.into()),
+                    None => {
+                        // stream ended
+                        break Ok(())
+                    },
+                };
+                let msg = SubscriptionMessage::from_json(&item)?;
+                if sink.send(msg).await.is_err() {
+                    break Ok(());
+                }
+            }
+        }
+    }
+}
+
 impl<Provider, Pool, Events, Network> std::fmt::Debug
     for EthPubSub<Provider, Pool, Events, Network>
 {
@@ -298,6 +352,38 @@ where
         })
     }
 
+    /// Returns a stream that yields all new canonical blocks with their receipts.
+    ///
+    /// On reorgs, the reverted blocks are yielded first, from the old tip down. Blocks that can't
+    /// be converted are yielded as errors.
+    fn blocks_with_receipts_stream(&self) -> impl Stream<Item = EthResult<BlockWithReceipts>>
+    where
+        Provider: Clone,
+    {
+        let provider = self.provider.clone();
+        self.chain_events.canonical_state_stream().flat_map(move |notification| {
+            let mut blocks = Vec::new();
+            if let Some(reverted) = notification.reverted() {
+                blocks.extend(
+                    reverted
+                        .blocks()
+                        .values()
+                        .rev()
+                        .map(|block| block_with_receipts(&provider, &reverted, block, true)),
+                );
+            }
+            if let Some(committed) = notification.committed() {
+                blocks.extend(
+                    committed
+                        .blocks()
+                        .values()
+                        .map(|block| block_with_receipts(&provider, &committed, block, false)),
+                );
+            }
+            futures::stream::iter(blocks)
+        })
+    }
+
     /// Returns a stream that yields all logs that match the given filter.
     fn log_stream(&self, filter: FilteredParams) -> impl Stream<Item = Log> {
         BroadcastStream::new(self.chain_events.subscribe_to_canonical_state())
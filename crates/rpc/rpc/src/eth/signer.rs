@@ -1,15 +1,18 @@
 //! An abstraction over ethereum signers.
 
-use crate::eth::error::SignError;
+use crate::eth::error::{KeystoreSignerError, SignError};
 use ethers_core::{
     types::transaction::eip712::{Eip712, TypedData},
     utils::hash_message,
 };
-use reth_primitives::{sign_message, Address, Signature, TransactionSigned, H256};
+use reth_primitives::{
+    public_key_to_address, sign_message, Address, Signature, TransactionSigned, H256,
+};
 use reth_rpc_types::TypedTransactionRequest;
 
-use secp256k1::SecretKey;
-use std::collections::HashMap;
+use secp256k1::{SecretKey, SECP256K1};
+use std::{collections::HashMap, fs, path::Path};
+use tracing::debug;
 
 type Result<T> = std::result::Result<T, SignError>;
 
@@ -45,6 +48,19 @@ pub(crate) struct DevSigner {
 }
 
 impl DevSigner {
+    /// Creates a signer for the given secret keys.
+    pub(crate) fn new(keys: impl IntoIterator<Item = SecretKey>) -> Self {
+        let mut addresses = Vec::new();
+        let mut accounts = HashMap::new();
+        for key in keys {
+            let address = public_key_to_address(key.public_key(SECP256K1));
+            if accounts.insert(address, key).is_none() {
+                addresses.push(address);
+            }
+        }
+        Self { addresses, accounts }
+    }
+
     fn get_key(&self, account: Address) -> Result<&SecretKey> {
         self.accounts.get(&account).ok_or(SignError::NoAccount)
     }
@@ -89,6 +105,123 @@ impl EthSigner for DevSigner {
         self.sign_hash(encoded, address)
     }
 }
+
+/// Signs with the accounts of a local keystore.
+///
+/// The keys are loaded from a directory of Web3 Secret Storage files, as written by geth or
+/// `ethers`, and decrypted once on startup.
+pub struct KeystoreSigner {
+    inner: DevSigner,
+}
+
+impl KeystoreSigner {
+    /// Decrypts all key files in the `keystore` directory.
+    ///
+    /// Every file is tried with every password, it's an error if a file can't be decrypted with
+    /// any of them.
+    pub fn from_dir(
+        keystore: impl AsRef<Path>,
+        passwords: &[String],
+    ) -> std::result::Result<Self, KeystoreSignerError> {
+        let keystore = keystore.as_ref();
+        if passwords.is_empty() {
+            return Err(KeystoreSignerError::NoPasswords)
+        }
+
+        let entries = fs::read_dir(keystore)
+            .map_err(|err| KeystoreSignerError::Io(keystore.to_path_buf(), err))?;
+        let mut keys = Vec::new();
+        for entry in entries {
+            let path =
+                entry.map_err(|err| KeystoreSignerError::Io(keystore.to_path_buf(), err))?.path();
+            // skip nested directories and hidden files
+            let hidden = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map_or(true, |name| name.starts_with('.'));
+            if !path.is_file() || hidden {
+                continue
+            }
+
+            let mut secret = None;
+            for password in passwords {
+                match eth_keystore::decrypt_key(&path, password) {
+                    Ok(key) => {
+                        secret = Some(key);
+                        break
+                    }
+                    // wrong password
+                    Err(eth_keystore::KeystoreError::MacMismatch) => continue,
+                    Err(err) => return Err(KeystoreSignerError::Keystore(path, err)),
+                }
+            }
+            let secret = secret.ok_or_else(|| KeystoreSignerError::WrongPassword(path.clone()))?;
+            let key = SecretKey::from_slice(&secret)
+                .map_err(|_| KeystoreSignerError::InvalidKey(path.clone()))?;
+            debug!(target: "rpc::eth", ?path, "Decrypted keystore file");
+            keys.push(key);
+        }
+
+        if keys.is_empty() {
+            return Err(KeystoreSignerError::Empty(keystore.to_path_buf()))
+        }
+
+        Ok(Self { inner: DevSigner::new(keys) })
+    }
+
+    /// Same as [KeystoreSigner::from_dir], but reads the passwords from a file with one password
+    /// per line.
+    pub fn from_dir_with_password_file(
+        keystore: impl AsRef<Path>,
+        password_file: impl AsRef<Path>,
+    ) -> std::result::Result<Self, KeystoreSignerError> {
+        let password_file = password_file.as_ref();
+        let passwords = fs::read_to_string(password_file)
+            .map_err(|err| KeystoreSignerError::Io(password_file.to_path_buf(), err))?;
+        let passwords = passwords.lines().map(str::to_string).collect::<Vec<_>>();
+        Self::from_dir(keystore, &passwords)
+    }
+
+    /// Returns the addresses of all loaded accounts.
+    pub fn addresses(&self) -> Vec<Address> {
+        self.inner.accounts()
+    }
+}
+
+impl std::fmt::Debug for KeystoreSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // never print the secret keys
+        f.debug_struct("KeystoreSigner").field("addresses", &self.inner.addresses).finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl EthSigner for KeystoreSigner {
+    fn accounts(&self) -> Vec<Address> {
+        self.inner.accounts()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.inner.is_signer_for(addr)
+    }
+
+    async fn sign(&self, address: Address, message: &[u8]) -> Result<Signature> {
+        self.inner.sign(address, message).await
+    }
+
+    fn sign_transaction(
+        &self,
+        request: TypedTransactionRequest,
+        address: &Address,
+    ) -> Result<TransactionSigned> {
+        self.inner.sign_transaction(request, address)
+    }
+
+    fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature> {
+        self.inner.sign_typed_data(address, payload)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -210,4 +343,42 @@ mod test {
         };
         assert_eq!(sig, expected)
     }
+
+    #[test]
+    fn test_keystore_signer() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret =
+            SecretKey::from_str("4646464646464646464646464646464646464646464646464646464646464646")
+                .unwrap();
+        eth_keystore::encrypt_key(
+            dir.path(),
+            &mut rand::thread_rng(),
+            secret.secret_bytes(),
+            "password",
+            None,
+        )
+        .unwrap();
+
+        let err = KeystoreSigner::from_dir(dir.path(), &["wrong".to_string()]).unwrap_err();
+        assert!(matches!(err, KeystoreSignerError::WrongPassword(_)));
+
+        let passwords = ["wrong".to_string(), "password".to_string()];
+        let signer = KeystoreSigner::from_dir(dir.path(), &passwords).unwrap();
+        let address = public_key_to_address(secret.public_key(SECP256K1));
+        assert_eq!(signer.addresses(), vec![address]);
+
+        let request = TypedTransactionRequest::EIP1559(reth_rpc_types::EIP1559TransactionRequest {
+            chain_id: 1,
+            nonce: U256::from(1),
+            max_priority_fee_per_gas: reth_primitives::U128::from(1),
+            max_fee_per_gas: reth_primitives::U128::from(2),
+            gas_limit: U256::from(21_000),
+            kind: reth_rpc_types::TransactionKind::Call(Address::from_low_u64_be(2)),
+            value: U256::from(1),
+            input: Default::default(),
+            access_list: Default::default(),
+        });
+        let tx = signer.sign_transaction(request, &address).unwrap();
+        assert_eq!(tx.recover_signer(), Some(address));
+    }
 }
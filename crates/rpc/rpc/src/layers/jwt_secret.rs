@@ -2,7 +2,7 @@ use hex::encode as hex_encode;
 use jsonwebtoken::{decode, errors::ErrorKind, Algorithm, DecodingKey, Validation};
 use rand::Rng;
 use reth_primitives::{fs, fs::FsPathError};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     path::Path,
     time::{Duration, SystemTime, UNIX_EPOCH},
@@ -114,28 +114,50 @@ impl JwtSecret {
     ///
     /// See also: [JWT Claims - Engine API specs](https://github.com/ethereum/execution-apis/blob/main/src/engine/authentication.md#jwt-claims)
     pub fn validate(&self, jwt: String) -> Result<(), JwtError> {
+        self.decode_claims::<Claims>(&jwt, |claims| claims)?;
+        Ok(())
+    }
+
+    /// Validates a JWT token like [Self::validate] and returns its `sub` (subject) claim, if any.
+    pub fn validate_subject(&self, jwt: &str) -> Result<Option<String>, JwtError> {
+        /// The claims of a JWT that identifies its subject.
+        #[derive(Deserialize)]
+        struct SubjectClaims {
+            #[serde(flatten)]
+            claims: Claims,
+            sub: Option<String>,
+        }
+
+        Ok(self.decode_claims::<SubjectClaims>(jwt, |claims| &claims.claims)?.sub)
+    }
+
+    /// Decodes the claims of the JWT token and validates the signature and the `iat` claim.
+    fn decode_claims<T: DeserializeOwned>(
+        &self,
+        jwt: &str,
+        claims: impl FnOnce(&T) -> &Claims,
+    ) -> Result<T, JwtError> {
         let mut validation = Validation::new(JWT_SIGNATURE_ALGO);
         // ensure that the JWT has an `iat` claim
         validation.set_required_spec_claims(&["iat"]);
         let bytes = &self.0;
 
-        match decode::<Claims>(&jwt, &DecodingKey::from_secret(bytes), &validation) {
+        match decode::<T>(jwt, &DecodingKey::from_secret(bytes), &validation) {
             Ok(token) => {
-                if !token.claims.is_within_time_window() {
-                    Err(JwtError::InvalidIssuanceTimestamp)?
+                if !claims(&token.claims).is_within_time_window() {
+                    return Err(JwtError::InvalidIssuanceTimestamp)
                 }
+                Ok(token.claims)
             }
-            Err(err) => match *err.kind() {
-                ErrorKind::InvalidSignature => Err(JwtError::InvalidSignature)?,
-                ErrorKind::InvalidAlgorithm => Err(JwtError::UnsupportedSignatureAlgorithm)?,
+            Err(err) => Err(match *err.kind() {
+                ErrorKind::InvalidSignature => JwtError::InvalidSignature,
+                ErrorKind::InvalidAlgorithm => JwtError::UnsupportedSignatureAlgorithm,
                 _ => {
                     let detail = format!("{err:?}");
-                    Err(JwtError::JwtDecodingError(detail))?
+                    JwtError::JwtDecodingError(detail)
                 }
-            },
-        };
-
-        Ok(())
+            }),
+        }
     }
 
     /// Generates a random [`JwtSecret`][crate::layers::JwtSecret]
@@ -3,9 +3,18 @@ use http::{HeaderMap, Response};
 mod auth_layer;
 mod jwt_secret;
 mod jwt_validator;
+mod rate_limit;
+mod request_limit;
 pub use auth_layer::AuthLayer;
 pub use jwt_secret::{Claims, JwtError, JwtSecret};
 pub use jwt_validator::JwtAuthValidator;
+pub use rate_limit::{
+    MethodCosts, RateLimitConfig, RateLimitLayer, RateLimitService, RemoteAddrSlot,
+    DEFAULT_RATE_LIMIT_CAPACITY, DEFAULT_RATE_LIMIT_MAX_CALLERS,
+    DEFAULT_RATE_LIMIT_MAX_REQUEST_SIZE, DEFAULT_RATE_LIMIT_REFILL_PER_SECOND,
+    RATE_LIMIT_EXCEEDED_CODE,
+};
+pub use request_limit::{RequestLimitConfig, RequestLimitLayer, RequestLimitService};
 
 /// General purpose trait to validate Http Authorization
 /// headers. It's supposed to be integrated as a validator
@@ -0,0 +1,847 @@
+use crate::JwtSecret;
+use http::{header, HeaderMap, Method, Request, Response, StatusCode};
+use http_body::Limited;
+use hyper::Body;
+use reth_metrics::{metrics::Counter, Metrics};
+use schnellru::{ByLength, LruMap};
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    pin::Pin,
+    sync::{Arc, Mutex, OnceLock},
+    task::{Context, Poll},
+    time::Instant,
+};
+use tower::{Layer, Service};
+use tracing::trace;
+
+/// The JSON-RPC error code returned for throttled calls.
+///
+/// See also <https://eips.ethereum.org/EIPS/eip-1474#error-codes>
+pub const RATE_LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The default number of compute units a caller can spend at once.
+pub const DEFAULT_RATE_LIMIT_CAPACITY: u64 = 10_000;
+
+/// The default number of compute units that are restored per second.
+pub const DEFAULT_RATE_LIMIT_REFILL_PER_SECOND: u64 = 1_000;
+
+/// The default number of callers for which quotas are tracked.
+pub const DEFAULT_RATE_LIMIT_MAX_CALLERS: u32 = 10_000;
+
+/// The default max size of a request body that is inspected, 15MB.
+pub const DEFAULT_RATE_LIMIT_MAX_REQUEST_SIZE: u32 = 15 * 1024 * 1024;
+
+/// The compute cost of rpc methods.
+///
+/// Methods without a configured cost have the default cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodCosts {
+    /// The cost of methods that are not configured.
+    default_cost: u64,
+    /// The cost by method name.
+    costs: HashMap<String, u64>,
+}
+
+impl MethodCosts {
+    /// Creates a new instance where every method has the given cost.
+    pub fn new(default_cost: u64) -> Self {
+        Self { default_cost, costs: HashMap::new() }
+    }
+
+    /// Sets the cost of the given method.
+    pub fn with_cost(mut self, method: impl Into<String>, cost: u64) -> Self {
+        self.costs.insert(method.into(), cost);
+        self
+    }
+
+    /// Returns the cost of the given method.
+    pub fn cost(&self, method: &str) -> u64 {
+        self.costs.get(method).copied().unwrap_or(self.default_cost)
+    }
+
+    /// Returns the methods with a configured cost.
+    fn methods(&self) -> impl Iterator<Item = &str> + '_ {
+        self.costs.keys().map(String::as_str)
+    }
+}
+
+impl Default for MethodCosts {
+    /// Cheap lookups cost 1 compute unit, calls that execute transactions or scan ranges of blocks
+    /// are more expensive, and tracing is the most expensive.
+    fn default() -> Self {
+        [
+            ("eth_call", 20),
+            ("eth_estimateGas", 20),
+            ("eth_createAccessList", 20),
+            ("eth_feeHistory", 10),
+            ("eth_getLogs", 50),
+            ("eth_getFilterLogs", 50),
+            ("eth_getProof", 20),
+            ("eth_getBlockReceipts", 20),
+            ("eth_sendRawTransaction", 10),
+            ("debug_traceTransaction", 300),
+            ("debug_traceCall", 300),
+            ("debug_traceCallMany", 1000),
+            ("debug_traceBlock", 1000),
+            ("debug_traceBlockByHash", 1000),
+            ("debug_traceBlockByNumber", 1000),
+            ("trace_call", 300),
+            ("trace_callMany", 1000),
+            ("trace_rawTransaction", 300),
+            ("trace_replayTransaction", 300),
+            ("trace_replayBlockTransactions", 1000),
+            ("trace_transaction", 300),
+            ("trace_get", 300),
+            ("trace_block", 1000),
+            ("trace_filter", 1000),
+        ]
+        .into_iter()
+        .fold(MethodCosts::new(1), |costs, (method, cost)| costs.with_cost(method, cost))
+    }
+}
+
+/// Configuration of the [RateLimitLayer].
+///
+/// Every caller has a token bucket of compute units that holds up to `capacity` units and is
+/// refilled with `refill_per_second` units per second. Every call consumes the cost of its method,
+/// see [MethodCosts].
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    /// The number of compute units a caller can spend at once.
+    pub capacity: u64,
+    /// The number of compute units that are restored per second.
+    pub refill_per_second: u64,
+    /// The compute cost of the methods.
+    pub costs: MethodCosts,
+    /// The secret used to validate JWTs of callers.
+    ///
+    /// If set, callers that provide a valid JWT with a `sub` claim are identified by the subject.
+    pub jwt_secret: Option<JwtSecret>,
+    /// Whether callers are identified by the `X-Forwarded-For` and `X-Real-IP` headers.
+    ///
+    /// This must only be enabled if the server is exclusively reachable through a reverse proxy
+    /// that sets these headers, otherwise callers can claim any address.
+    pub trust_forwarded_headers: bool,
+    /// The number of callers identified by their address for which quotas are tracked, the least
+    /// recently seen callers are forgotten first.
+    ///
+    /// Quotas of callers identified by their JWT are never forgotten.
+    pub max_callers: u32,
+    /// The max size of a request body that is inspected.
+    ///
+    /// This should be the max request size of the server.
+    pub max_request_size: u32,
+}
+
+impl RateLimitConfig {
+    /// Creates a new config with the given capacity and refill rate.
+    pub fn new(capacity: u64, refill_per_second: u64) -> Self {
+        Self { capacity, refill_per_second, ..Default::default() }
+    }
+
+    /// Sets the compute cost of the methods.
+    pub fn with_costs(mut self, costs: MethodCosts) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    /// Sets the secret used to validate JWTs of callers.
+    pub fn with_jwt_secret(mut self, secret: JwtSecret) -> Self {
+        self.jwt_secret = Some(secret);
+        self
+    }
+
+    /// Sets whether callers are identified by the `X-Forwarded-For` and `X-Real-IP` headers.
+    ///
+    /// See also [RateLimitConfig::trust_forwarded_headers].
+    pub fn with_trust_forwarded_headers(mut self, trust: bool) -> Self {
+        self.trust_forwarded_headers = trust;
+        self
+    }
+
+    /// Sets the number of callers for which quotas are tracked.
+    pub fn with_max_callers(mut self, max_callers: u32) -> Self {
+        self.max_callers = max_callers;
+        self
+    }
+
+    /// Sets the max size of a request body that is inspected.
+    pub fn with_max_request_size(mut self, max_request_size: u32) -> Self {
+        self.max_request_size = max_request_size;
+        self
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_RATE_LIMIT_CAPACITY,
+            refill_per_second: DEFAULT_RATE_LIMIT_REFILL_PER_SECOND,
+            costs: MethodCosts::default(),
+            jwt_secret: None,
+            trust_forwarded_headers: false,
+            max_callers: DEFAULT_RATE_LIMIT_MAX_CALLERS,
+            max_request_size: DEFAULT_RATE_LIMIT_MAX_REQUEST_SIZE,
+        }
+    }
+}
+
+/// Identifies the caller of a request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Caller {
+    /// The subject of the caller's JWT.
+    Subject(String),
+    /// The IP address of the caller, either of the connection or as forwarded by a trusted proxy.
+    Ip(IpAddr),
+    /// A caller connected over IPC, all of them share a quota.
+    Ipc,
+    /// A caller whose address is unknown, all of them share a quota.
+    Unknown,
+}
+
+impl Caller {
+    /// Identifies the caller of a request by its headers.
+    ///
+    /// A valid JWT takes precedence over the `X-Forwarded-For` and `X-Real-IP` headers, which are
+    /// only used if `trust_forwarded_headers` is set.
+    fn from_headers(
+        headers: &HeaderMap,
+        jwt_secret: Option<&JwtSecret>,
+        trust_forwarded_headers: bool,
+    ) -> Option<Self> {
+        if let Some(subject) = jwt_secret.and_then(|secret| {
+            let jwt = get_bearer(headers)?;
+            secret.validate_subject(jwt).ok().flatten()
+        }) {
+            return Some(Caller::Subject(subject))
+        }
+
+        if !trust_forwarded_headers {
+            return None
+        }
+        headers
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .or_else(|| headers.get("x-real-ip").and_then(|value| value.to_str().ok()))
+            .and_then(|ip| ip.trim().parse().ok())
+            .map(Caller::Ip)
+    }
+}
+
+impl fmt::Display for Caller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Caller::Subject(subject) => write!(f, "sub:{subject}"),
+            Caller::Ip(ip) => write!(f, "ip:{ip}"),
+            Caller::Ipc => f.write_str("ipc"),
+            Caller::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+/// Request extension that receives the remote address of an Http request.
+///
+/// jsonrpsee only passes the remote address of a connection to the server's
+/// [Logger](jsonrpsee::server::logger::Logger), so the [RateLimitService] attaches a slot to every
+/// request before handing it to the server and the logger fills it in with
+/// [RemoteAddrSlot::record].
+#[derive(Debug, Clone, Default)]
+pub struct RemoteAddrSlot(Arc<OnceLock<SocketAddr>>);
+
+impl RemoteAddrSlot {
+    /// Records the remote address of the request if it has a slot.
+    ///
+    /// This should be called by the [Logger](jsonrpsee::server::logger::Logger) of the server
+    /// when a request is received.
+    pub fn record<B>(request: &Request<B>, remote_addr: SocketAddr) {
+        if let Some(slot) = request.extensions().get::<RemoteAddrSlot>() {
+            let _ = slot.0.set(remote_addr);
+        }
+    }
+
+    /// Returns the recorded address.
+    fn get(&self) -> Option<SocketAddr> {
+        self.0.get().copied()
+    }
+}
+
+/// Retrieves the bearer token from the authorization header.
+fn get_bearer(headers: &HeaderMap) -> Option<&str> {
+    let auth = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    auth.strip_prefix("Bearer ")
+}
+
+/// Metrics of the rate limiter, either in total or per method.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.rate_limit")]
+struct RateLimitMetrics {
+    /// The number of calls
+    calls: Counter,
+    /// The number of calls that were rejected because the quota was exceeded
+    throttled_calls: Counter,
+    /// The number of compute units that were spent
+    compute_units: Counter,
+}
+
+/// A token bucket of compute units.
+#[derive(Debug)]
+struct TokenBucket {
+    /// The number of available units.
+    tokens: f64,
+    /// When the bucket was last refilled.
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a new, full bucket.
+    fn new(capacity: u64, now: Instant) -> Self {
+        Self { tokens: capacity as f64, last_refill: now }
+    }
+
+    /// Refills the bucket and consumes the given cost if enough units are available.
+    ///
+    /// Returns `false` if the cost exceeds the available units.
+    fn try_consume(
+        &mut self,
+        cost: u64,
+        capacity: u64,
+        refill_per_second: u64,
+        now: Instant,
+    ) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_per_second as f64).min(capacity as f64);
+        self.last_refill = now;
+
+        if self.tokens < cost as f64 {
+            return false
+        }
+        self.tokens -= cost as f64;
+        true
+    }
+}
+
+/// The shared state of the rate limiter.
+struct RateLimiter {
+    config: RateLimitConfig,
+    /// Quotas of the callers identified by their JWT.
+    ///
+    /// The subjects are issued by the operator, so these are never evicted.
+    subjects: Mutex<HashMap<String, TokenBucket>>,
+    /// Quotas of the most recently seen callers that are not identified by their JWT.
+    callers: Mutex<LruMap<Caller, TokenBucket, ByLength>>,
+    /// Metrics of all calls.
+    metrics: RateLimitMetrics,
+    /// Metrics of the methods with a configured cost.
+    methods: HashMap<String, RateLimitMetrics>,
+    /// Metrics of all other methods.
+    other_methods: RateLimitMetrics,
+}
+
+impl RateLimiter {
+    fn new(config: RateLimitConfig) -> Self {
+        let callers = Mutex::new(LruMap::new(ByLength::new(config.max_callers)));
+        let methods = config
+            .costs
+            .methods()
+            .map(|method| {
+                let metrics = RateLimitMetrics::new_with_labels(&[("method", method.to_string())]);
+                (method.to_string(), metrics)
+            })
+            .collect();
+        Self {
+            config,
+            subjects: Mutex::new(HashMap::new()),
+            callers,
+            metrics: RateLimitMetrics::default(),
+            methods,
+            other_methods: RateLimitMetrics::new_with_labels(&[("method", "other")]),
+        }
+    }
+
+    /// Returns the cost of the call, calls without a method have the default cost.
+    fn cost(&self, call: &Call) -> u64 {
+        call.method
+            .as_deref()
+            .map_or(self.config.costs.default_cost, |method| self.config.costs.cost(method))
+    }
+
+    /// Charges the caller the cost of the given calls.
+    ///
+    /// Returns `false` if the caller's quota is exceeded, in which case nothing is charged.
+    fn try_charge(&self, caller: Caller, calls: &[Call]) -> bool {
+        let cost = calls.iter().map(|call| self.cost(call)).fold(0u64, u64::saturating_add);
+        let RateLimitConfig { capacity, refill_per_second, .. } = self.config;
+
+        let allowed = {
+            let now = Instant::now();
+            if let Caller::Subject(subject) = &caller {
+                let mut subjects = self.subjects.lock().unwrap();
+                let bucket = subjects
+                    .entry(subject.clone())
+                    .or_insert_with(|| TokenBucket::new(capacity, now));
+                bucket.try_consume(cost, capacity, refill_per_second, now)
+            } else {
+                let mut callers = self.callers.lock().unwrap();
+                let bucket = callers
+                    .get_or_insert(caller.clone(), || TokenBucket::new(capacity, now))
+                    .expect("capacity is not zero");
+                bucket.try_consume(cost, capacity, refill_per_second, now)
+            }
+        };
+
+        self.metrics.calls.increment(calls.len() as u64);
+        if allowed {
+            self.metrics.compute_units.increment(cost);
+        } else {
+            self.metrics.throttled_calls.increment(calls.len() as u64);
+        }
+        for call in calls {
+            let metrics = call
+                .method
+                .as_ref()
+                .and_then(|method| self.methods.get(method))
+                .unwrap_or(&self.other_methods);
+            metrics.calls.increment(1);
+            if allowed {
+                metrics.compute_units.increment(self.cost(call));
+            } else {
+                metrics.throttled_calls.increment(1);
+            }
+        }
+
+        if !allowed {
+            trace!(target: "rpc::rate_limit", %caller, cost, "Rate limit exceeded");
+        }
+        allowed
+    }
+}
+
+/// A single call of a JSON-RPC request.
+#[derive(Debug, Clone, Default)]
+struct Call {
+    /// The called method, if the call has one.
+    method: Option<String>,
+    /// The id of the call, if any.
+    id: Option<serde_json::Value>,
+}
+
+impl Call {
+    fn from_value(value: &serde_json::Value) -> Self {
+        Self {
+            method: value.get("method").and_then(|method| method.as_str()).map(str::to_string),
+            id: value.get("id").cloned(),
+        }
+    }
+}
+
+/// The calls of a JSON-RPC request.
+///
+/// The request is parsed leniently so that every call the server may execute is charged, even if
+/// other calls of the same batch are malformed.
+#[derive(Debug)]
+struct Calls {
+    /// The calls, never empty.
+    calls: Vec<Call>,
+    /// Whether the request is a batch.
+    batch: bool,
+}
+
+impl Calls {
+    fn parse(body: &[u8]) -> Self {
+        match serde_json::from_slice::<serde_json::Value>(body) {
+            Ok(serde_json::Value::Array(calls)) if !calls.is_empty() => {
+                Self { calls: calls.iter().map(Call::from_value).collect(), batch: true }
+            }
+            Ok(call) => Self { calls: vec![Call::from_value(&call)], batch: false },
+            // malformed requests are rejected by the server, but they are charged as one call
+            Err(_) => Self { calls: vec![Call::default()], batch: false },
+        }
+    }
+}
+
+/// Returns the JSON-RPC response for a throttled request, with an error for every call.
+fn throttled_response_body(calls: Calls) -> serde_json::Value {
+    let error = |id: Option<serde_json::Value>| {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": RATE_LIMIT_EXCEEDED_CODE, "message": "rate limit exceeded" },
+        })
+    };
+    let Calls { calls, batch } = calls;
+    if batch {
+        calls.into_iter().map(|call| error(call.id)).collect()
+    } else {
+        calls.into_iter().next().map(|call| error(call.id)).unwrap_or_default()
+    }
+}
+
+/// Builds the Http response for a throttled request.
+fn throttled_response(calls: Calls) -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::TOO_MANY_REQUESTS)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(throttled_response_body(calls).to_string()))
+        .expect("response is valid")
+}
+
+/// This is a middleware layer for the Http and IPC servers that enforces compute unit quotas per
+/// caller.
+///
+/// Every method has a compute cost, see [MethodCosts]. Each caller has a token bucket of compute
+/// units, calls that exceed the remaining quota are rejected with the
+/// [RATE_LIMIT_EXCEEDED_CODE] JSON-RPC error and a `429` Http status.
+///
+/// Http callers are identified by the subject of their JWT, if a [JwtSecret] is configured, then
+/// by the `X-Forwarded-For` or `X-Real-IP` header, if
+/// [RateLimitConfig::trust_forwarded_headers] is set, and otherwise by the remote address of the
+/// connection. The remote address is provided by the server's logger, see [RemoteAddrSlot]. All
+/// IPC callers share a quota.
+///
+/// Note: websocket messages can't be inspected by middleware, so this must not be used for a
+/// server that accepts websocket connections.
+#[derive(Clone, Default)]
+#[allow(missing_debug_implementations)]
+pub struct RateLimitLayer {
+    /// The rate limiter, if enabled.
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl RateLimitLayer {
+    /// Creates a new layer that enforces the given quotas.
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self { limiter: Some(Arc::new(RateLimiter::new(config))) }
+    }
+
+    /// Creates a new layer that doesn't limit any requests.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService { limiter: self.limiter.clone(), inner }
+    }
+}
+
+/// The service created by the [RateLimitLayer].
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct RateLimitService<S> {
+    /// The rate limiter, if enabled.
+    limiter: Option<Arc<RateLimiter>>,
+    /// Recipient of the requests that are within the quota.
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for RateLimitService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let limiter = match self.limiter.clone() {
+            // websocket upgrades and health checks are not charged
+            Some(limiter) if req.method() == Method::POST => limiter,
+            _ => return Box::pin(self.inner.call(req)),
+        };
+
+        // the inner service was polled ready, so it is the one that must be called
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let caller = Caller::from_headers(
+                req.headers(),
+                limiter.config.jwt_secret.as_ref(),
+                limiter.config.trust_forwarded_headers,
+            );
+            let (parts, body) = req.into_parts();
+            let bytes = match hyper::body::to_bytes(Limited::new(
+                body,
+                limiter.config.max_request_size as usize,
+            ))
+            .await
+            {
+                Ok(bytes) => bytes,
+                Err(_) => {
+                    return Ok(Response::builder()
+                        .status(StatusCode::PAYLOAD_TOO_LARGE)
+                        .body(Body::empty())
+                        .expect("response is valid"))
+                }
+            };
+
+            let calls = Calls::parse(&bytes);
+
+            // The server records the remote address when it receives the request. The returned
+            // future does nothing until it is polled, so it is dropped if the caller is throttled.
+            let remote_addr = RemoteAddrSlot::default();
+            let mut req = Request::from_parts(parts, Body::from(bytes));
+            req.extensions_mut().insert(remote_addr.clone());
+            let response = inner.call(req);
+
+            let caller = caller
+                .or_else(|| remote_addr.get().map(|addr| Caller::Ip(addr.ip())))
+                .unwrap_or(Caller::Unknown);
+            if !limiter.try_charge(caller, &calls.calls) {
+                return Ok(throttled_response(calls))
+            }
+            response.await
+        })
+    }
+}
+
+impl<S> Service<String> for RateLimitService<S>
+where
+    S: Service<String, Response = Option<String>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Option<String>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: String) -> Self::Future {
+        let Some(limiter) = self.limiter.as_ref() else {
+            return Box::pin(self.inner.call(request))
+        };
+
+        let calls = Calls::parse(request.as_bytes());
+        if !limiter.try_charge(Caller::Ipc, &calls.calls) {
+            let response = throttled_response_body(calls).to_string();
+            return Box::pin(async move { Ok(Some(response)) })
+        }
+        Box::pin(self.inner.call(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Claims;
+    use std::{
+        convert::Infallible,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+
+    const SECRET: &str = "f79ae8046bc11c9927afe911db7143c51a806c4a537cc08e0d37140b0192f430";
+
+    #[test]
+    fn token_bucket_refills() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(10, now);
+        assert!(bucket.try_consume(10, 10, 5, now));
+        assert!(!bucket.try_consume(1, 10, 5, now));
+
+        // half a second restores 2.5 units
+        let now = now + Duration::from_millis(500);
+        assert!(bucket.try_consume(2, 10, 5, now));
+        assert!(!bucket.try_consume(1, 10, 5, now));
+
+        // the bucket never holds more than its capacity
+        let now = now + Duration::from_secs(60);
+        assert!(bucket.try_consume(10, 10, 5, now));
+        assert!(!bucket.try_consume(1, 10, 5, now));
+    }
+
+    #[test]
+    fn charges_method_costs() {
+        let costs = MethodCosts::new(1).with_cost("debug_traceTransaction", 8);
+        let limiter = RateLimiter::new(RateLimitConfig::new(10, 0).with_costs(costs));
+        let call = |method: &str| Call { method: Some(method.to_string()), id: None };
+
+        let alice = Caller::Subject("alice".to_string());
+        assert!(limiter.try_charge(alice.clone(), &[call("debug_traceTransaction")]));
+        // a batch is rejected as a whole
+        assert!(!limiter
+            .try_charge(alice.clone(), &[call("eth_blockNumber"), call("debug_traceTransaction")]));
+        assert!(limiter.try_charge(alice.clone(), &[call("eth_blockNumber"), call("eth_chainId")]));
+        assert!(!limiter.try_charge(alice, &[call("eth_blockNumber")]));
+
+        // callers have separate quotas
+        let ip = Caller::Ip("10.0.0.1".parse().unwrap());
+        assert!(limiter.try_charge(ip, &[call("debug_traceTransaction")]));
+        assert!(limiter.try_charge(Caller::Ipc, &[call("debug_traceTransaction")]));
+        // calls without a method have the default cost
+        assert!(limiter.try_charge(Caller::Unknown, &[(); 10].map(|_| Call::default())));
+        assert!(!limiter.try_charge(Caller::Unknown, &[Call::default()]));
+    }
+
+    #[test]
+    fn subjects_are_not_evicted() {
+        let limiter = RateLimiter::new(RateLimitConfig::new(10, 0).with_max_callers(1));
+        let call = [(); 10].map(|_| Call { method: Some("eth_blockNumber".to_string()), id: None });
+
+        let alice = Caller::Subject("alice".to_string());
+        assert!(limiter.try_charge(alice.clone(), &call));
+        for ip in ["10.0.0.1", "10.0.0.2"] {
+            assert!(limiter.try_charge(Caller::Ip(ip.parse().unwrap()), &call));
+        }
+        assert!(!limiter.try_charge(alice, &call[..1]));
+        // the quota of the first address was forgotten
+        assert!(limiter.try_charge(Caller::Ip("10.0.0.1".parse().unwrap()), &call));
+    }
+
+    #[test]
+    fn parses_calls_leniently() {
+        let calls = Calls::parse(br#"[{"jsonrpc":"2.0","id":1,"method":"eth_call"},{"id":2}]"#);
+        assert!(calls.batch);
+        assert_eq!(calls.calls.len(), 2);
+        assert_eq!(calls.calls[0].method.as_deref(), Some("eth_call"));
+        assert_eq!(calls.calls[1].method, None);
+        assert_eq!(calls.calls[1].id, Some(serde_json::json!(2)));
+
+        for body in [&b"not json"[..], b"[]", b"42"] {
+            let calls = Calls::parse(body);
+            assert!(!calls.batch);
+            assert_eq!(calls.calls.len(), 1);
+            assert_eq!(calls.calls[0].method, None);
+        }
+    }
+
+    /// Records the remote address like the server's logger and responds with an empty object.
+    #[derive(Clone)]
+    struct Respond(SocketAddr);
+
+    impl Service<Request<Body>> for Respond {
+        type Response = Response<Body>;
+        type Error = Infallible;
+        type Future = futures::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: Request<Body>) -> Self::Future {
+            RemoteAddrSlot::record(&req, self.0);
+            futures::future::ready(Ok(Response::new(Body::from("{}"))))
+        }
+    }
+
+    impl Service<String> for Respond {
+        type Response = Option<String>;
+        type Error = Infallible;
+        type Future = futures::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: String) -> Self::Future {
+            futures::future::ready(Ok(Some("{}".to_string())))
+        }
+    }
+
+    #[tokio::test]
+    async fn limits_http_callers_by_remote_address() {
+        let layer = RateLimitLayer::new(RateLimitConfig::new(2, 0));
+        let first = "10.0.0.1:4000".parse().unwrap();
+        // the second call is malformed, but it is still charged
+        let request = || {
+            let body = r#"[{"jsonrpc":"2.0","id":1,"method":"eth_chainId"},{"id":2}]"#;
+            Request::post("/").body(Body::from(body)).unwrap()
+        };
+
+        let response = layer.layer(Respond(first)).call(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        // a new connection from the same address shares the quota
+        let response = layer.layer(Respond(first)).call(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let second = "10.0.0.2:4000".parse().unwrap();
+        let response = layer.layer(Respond(second)).call(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn limits_ipc_callers() {
+        let layer = RateLimitLayer::new(RateLimitConfig::new(1, 0));
+        let mut service = layer.layer(Respond("127.0.0.1:0".parse().unwrap()));
+        let request = r#"{"jsonrpc":"2.0","id":1,"method":"eth_chainId"}"#;
+
+        assert_eq!(service.call(request.to_string()).await.unwrap().unwrap(), "{}");
+        let response = service.call(request.to_string()).await.unwrap();
+        let response: serde_json::Value = serde_json::from_str(&response.unwrap()).unwrap();
+        assert_eq!(response["error"]["code"], RATE_LIMIT_EXCEEDED_CODE);
+    }
+
+    #[test]
+    fn identifies_callers() {
+        let secret = JwtSecret::from_hex(SECRET).unwrap();
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let ip = Caller::Ip("10.0.0.1".parse().unwrap());
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(Caller::from_headers(&headers, Some(&secret), true), None);
+
+        headers.insert("x-forwarded-for", "10.0.0.1, 10.0.0.2".parse().unwrap());
+        assert_eq!(Caller::from_headers(&headers, Some(&secret), true), Some(ip.clone()));
+        // forwarded addresses are only used behind a trusted proxy
+        assert_eq!(Caller::from_headers(&headers, Some(&secret), false), None);
+
+        // a JWT without subject doesn't identify the caller
+        let jwt = secret.encode(&Claims { iat, exp: None }).unwrap();
+        headers.insert(header::AUTHORIZATION, format!("Bearer {jwt}").parse().unwrap());
+        assert_eq!(Caller::from_headers(&headers, Some(&secret), true), Some(ip.clone()));
+
+        let claims = serde_json::json!({ "iat": iat, "sub": "alice" });
+        let jwt = jsonwebtoken::encode(
+            &jsonwebtoken::Header::default(),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret(&hex::decode(SECRET).unwrap()),
+        )
+        .unwrap();
+        headers.insert(header::AUTHORIZATION, format!("Bearer {jwt}").parse().unwrap());
+        let alice = Some(Caller::Subject("alice".to_string()));
+        assert_eq!(Caller::from_headers(&headers, Some(&secret), true), alice);
+        assert_eq!(Caller::from_headers(&headers, Some(&secret), false), alice);
+        // subjects are only trusted if the JWT can be validated
+        assert_eq!(
+            Caller::from_headers(&headers, Some(&JwtSecret::random()), true),
+            Some(ip.clone())
+        );
+        assert_eq!(Caller::from_headers(&headers, None, true), Some(ip));
+    }
+
+    #[test]
+    fn throttled_response_has_error_per_call() {
+        let calls = Calls::parse(
+            br#"[{"jsonrpc":"2.0","id":1,"method":"eth_call"},{"jsonrpc":"2.0","id":"a","method":"trace_block"}]"#,
+        );
+        let error = serde_json::json!({ "code": -32005, "message": "rate limit exceeded" });
+        assert_eq!(
+            throttled_response_body(calls),
+            serde_json::json!([
+                { "jsonrpc": "2.0", "id": 1, "error": error },
+                { "jsonrpc": "2.0", "id": "a", "error": error },
+            ])
+        );
+
+        let call = Calls::parse(br#"{"jsonrpc":"2.0","id":7,"method":"eth_call","params":[]}"#);
+        assert_eq!(
+            throttled_response_body(call),
+            serde_json::json!({ "jsonrpc": "2.0", "id": 7, "error": error })
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use http::{header, Method, Request, Response, StatusCode};
+use http_body::Body as _;
+use hyper::Body;
+use jsonrpsee::types::{
+    error::{reject_too_big_batch_response, SERVER_IS_BUSY_CODE, SERVER_IS_BUSY_MSG},
+    ErrorObject,
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::Semaphore;
+use tower::{Layer, Service};
+use tracing::trace;
+
+/// The limits enforced by the [RequestLimitLayer].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimitConfig {
+    /// Maximum number of requests that are handled concurrently per connection.
+    pub max_concurrent_requests: u32,
+    /// Maximum size of a batch response in bytes.
+    pub max_batch_response_size: u32,
+}
+
+impl RequestLimitConfig {
+    /// Creates a new config with the given limits.
+    pub fn new(max_concurrent_requests: u32, max_batch_response_size: u32) -> Self {
+        Self { max_concurrent_requests, max_batch_response_size }
+    }
+}
+
+/// Returns a JSON-RPC error response without an id.
+fn error_response(status: StatusCode, error: ErrorObject<'_>) -> Response<Body> {
+    let body = serde_json::json!({ "jsonrpc": "2.0", "id": null, "error": error });
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("response is valid")
+}
+
+/// This is an Http middleware layer that limits the requests of a connection.
+///
+/// Requests that arrive while a connection already has
+/// [RequestLimitConfig::max_concurrent_requests] requests in flight are rejected with a "server is
+/// busy" JSON-RPC error and a `503` Http status. Batch responses that exceed
+/// [RequestLimitConfig::max_batch_response_size] are replaced with a "batch response too big"
+/// JSON-RPC error.
+///
+/// Note: this only inspects Http `POST` requests, calls over websocket connections are not limited.
+#[derive(Clone, Debug, Default)]
+pub struct RequestLimitLayer {
+    /// The limits, if enabled.
+    config: Option<RequestLimitConfig>,
+}
+
+impl RequestLimitLayer {
+    /// Creates a new layer that enforces the given limits.
+    pub fn new(config: RequestLimitConfig) -> Self {
+        Self { config: Some(config) }
+    }
+
+    /// Creates a new layer that doesn't limit any requests.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Layer<S> for RequestLimitLayer {
+    type Service = RequestLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        // the server creates a new service for every connection
+        let limits = self.config.map(|config| {
+            (config, Arc::new(Semaphore::new(config.max_concurrent_requests as usize)))
+        });
+        RequestLimitService { limits, inner }
+    }
+}
+
+/// The service created by the [RequestLimitLayer].
+#[derive(Clone, Debug)]
+pub struct RequestLimitService<S> {
+    /// The limits and the permits for the requests of the connection, if enabled.
+    limits: Option<(RequestLimitConfig, Arc<Semaphore>)>,
+    /// Recipient of the requests that are within the limits.
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for RequestLimitService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let (config, permits) = match self.limits.clone() {
+            // websocket upgrades and health checks are not limited
+            Some(limits) if req.method() == Method::POST => limits,
+            _ => return Box::pin(self.inner.call(req)),
+        };
+
+        let Ok(permit) = permits.try_acquire_owned() else {
+            trace!(target: "rpc::request_limit", "too many concurrent requests on connection");
+            let response = error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                ErrorObject::owned(SERVER_IS_BUSY_CODE, SERVER_IS_BUSY_MSG, None::<()>),
+            );
+            return Box::pin(async move { Ok(response) })
+        };
+
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let response = fut.await?;
+            drop(permit);
+
+            let max_size = config.max_batch_response_size as usize;
+            if response.body().size_hint().lower() <= max_size as u64 {
+                return Ok(response)
+            }
+
+            // only batch responses are subject to the budget, single responses are limited by
+            // the server
+            let (parts, body) = response.into_parts();
+            let bytes = match hyper::body::to_bytes(body).await {
+                Ok(bytes) => bytes,
+                Err(_) => {
+                    return Ok(Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::empty())
+                        .expect("response is valid"))
+                }
+            };
+            let is_batch = bytes.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'[');
+            if is_batch && bytes.len() > max_size {
+                trace!(target: "rpc::request_limit", size=bytes.len(), "batch response too big");
+                return Ok(error_response(parts.status, reject_too_big_batch_response(max_size)))
+            }
+
+            let mut response = Response::from_parts(parts, Body::from(bytes));
+            response.headers_mut().remove(header::CONTENT_LENGTH);
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::types::error::TOO_BIG_BATCH_RESPONSE_CODE;
+    use std::convert::Infallible;
+
+    /// Responds to every request with the given body.
+    #[derive(Clone)]
+    struct Respond(&'static str);
+
+    impl Service<Request<Body>> for Respond {
+        type Response = Response<Body>;
+        type Error = Infallible;
+        type Future = futures::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: Request<Body>) -> Self::Future {
+            futures::future::ready(Ok(Response::new(Body::from(self.0))))
+        }
+    }
+
+    fn post() -> Request<Body> {
+        Request::post("/").body(Body::empty()).unwrap()
+    }
+
+    async fn error_code(response: Response<Body>) -> i64 {
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        body["error"]["code"].as_i64().unwrap()
+    }
+
+    #[tokio::test]
+    async fn limits_concurrent_requests_per_connection() {
+        let layer = RequestLimitLayer::new(RequestLimitConfig::new(1, 1024));
+        let mut service = layer.layer(Respond("{}"));
+
+        let pending = service.call(post());
+        let busy = service.call(post()).await.unwrap();
+        assert_eq!(busy.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(error_code(busy).await, SERVER_IS_BUSY_CODE as i64);
+
+        // other connections have their own limit
+        let mut other = layer.layer(Respond("{}"));
+        assert_eq!(other.call(post()).await.unwrap().status(), StatusCode::OK);
+
+        assert_eq!(pending.await.unwrap().status(), StatusCode::OK);
+        assert_eq!(service.call(post()).await.unwrap().status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn limits_batch_response_size() {
+        let layer = RequestLimitLayer::new(RequestLimitConfig::new(1, 4));
+
+        let response = layer.layer(Respond("[1,2,3]")).call(post()).await.unwrap();
+        assert_eq!(error_code(response).await, TOO_BIG_BATCH_RESPONSE_CODE as i64);
+
+        let response = layer.layer(Respond("[1]")).call(post()).await.unwrap();
+        assert_eq!(hyper::body::to_bytes(response.into_body()).await.unwrap(), "[1]");
+
+        // single responses are not subject to the batch budget
+        let response = layer.layer(Respond("{\"a\":1}")).call(post()).await.unwrap();
+        assert_eq!(hyper::body::to_bytes(response.into_body()).await.unwrap(), "{\"a\":1}");
+    }
+}
@@ -44,11 +44,17 @@ pub mod tracing_call;
 mod txpool;
 mod web3;
 
-pub use admin::AdminApi;
+pub use admin::{AdminApi, AdminBackupApi, DatabaseBackupProvider};
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthApiSpec, EthFilter, EthPubSub, EthSubscriptionIdProvider};
-pub use layers::{AuthLayer, AuthValidator, Claims, JwtAuthValidator, JwtError, JwtSecret};
+pub use layers::{
+    AuthLayer, AuthValidator, Claims, JwtAuthValidator, JwtError, JwtSecret, MethodCosts,
+    RateLimitConfig, RateLimitLayer, RateLimitService, RemoteAddrSlot, RequestLimitConfig,
+    RequestLimitLayer, RequestLimitService, DEFAULT_RATE_LIMIT_CAPACITY,
+    DEFAULT_RATE_LIMIT_MAX_CALLERS, DEFAULT_RATE_LIMIT_MAX_REQUEST_SIZE,
+    DEFAULT_RATE_LIMIT_REFILL_PER_SECOND, RATE_LIMIT_EXCEEDED_CODE,
+};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
 pub use reth::RethApi;
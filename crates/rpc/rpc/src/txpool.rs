@@ -1,12 +1,19 @@
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult as Result;
-use reth_primitives::{Address, U256, U64};
+use reth_primitives::{Address, H256, U256, U64};
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_types::{
-    txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus},
+    txpool::{
+        TxpoolContent, TxpoolContentFrom, TxpoolDiscardReason, TxpoolInspect, TxpoolInspectSummary,
+        TxpoolNonceGap, TxpoolParkedReason, TxpoolSenderStatus, TxpoolStatus, TxpoolSubPool,
+        TxpoolTxEvent, TxpoolTxPlacement,
+    },
     Transaction,
 };
-use reth_transaction_pool::{AllPoolTransactions, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    AllPoolTransactions, DiscardReason, ParkedReason, PoolTransaction, SenderPoolStatus, SubPool,
+    TransactionEvent, TransactionPool,
+};
 use std::collections::BTreeMap;
 use tracing::trace;
 
@@ -55,6 +62,82 @@ where
 
         content
     }
+
+    fn sender_status(&self, from: Address) -> Option<TxpoolSenderStatus> {
+        let SenderPoolStatus { state_nonce, nonce_gaps, transactions } =
+            self.pool.get_sender_status(from)?;
+
+        let nonce_gaps = nonce_gaps
+            .into_iter()
+            .map(|gap| TxpoolNonceGap { from: U64::from(*gap.start()), to: U64::from(*gap.end()) })
+            .collect();
+
+        let transactions = transactions
+            .into_iter()
+            .map(|placement| {
+                let key = placement.transaction.nonce().to_string();
+                let subpool = match placement.subpool {
+                    SubPool::Pending => TxpoolSubPool::Pending,
+                    SubPool::BaseFee => TxpoolSubPool::BaseFee,
+                    SubPool::Queued => TxpoolSubPool::Queued,
+                };
+                let parked_reasons = placement
+                    .parked_reasons
+                    .into_iter()
+                    .map(|reason| match reason {
+                        ParkedReason::NonceGap => TxpoolParkedReason::NonceGap,
+                        ParkedReason::ParkedAncestor => TxpoolParkedReason::ParkedAncestor,
+                        ParkedReason::InsufficientBalance => {
+                            TxpoolParkedReason::InsufficientBalance
+                        }
+                        ParkedReason::ExceedsBlockGasLimit => {
+                            TxpoolParkedReason::ExceedsBlockGasLimit
+                        }
+                        ParkedReason::BaseFeeTooLow => TxpoolParkedReason::BaseFeeTooLow,
+                    })
+                    .collect();
+                let placement = TxpoolTxPlacement {
+                    hash: *placement.transaction.hash(),
+                    subpool,
+                    parked_reasons,
+                };
+                (key, placement)
+            })
+            .collect();
+
+        Some(TxpoolSenderStatus { state_nonce: U64::from(state_nonce), nonce_gaps, transactions })
+    }
+
+    fn tx_history(&self, hash: H256) -> Vec<TxpoolTxEvent> {
+        self.pool
+            .transaction_event_history(hash)
+            .into_iter()
+            .filter_map(|event| {
+                let event = match event {
+                    TransactionEvent::Pending => TxpoolTxEvent::Pending,
+                    TransactionEvent::Queued => TxpoolTxEvent::Queued,
+                    TransactionEvent::Mined(block_hash) => TxpoolTxEvent::Mined { block_hash },
+                    TransactionEvent::Replaced(replaced_by) => {
+                        TxpoolTxEvent::Replaced { replaced_by }
+                    }
+                    TransactionEvent::Discarded(reason) => {
+                        let reason = match reason {
+                            DiscardReason::PoolSizeLimit => TxpoolDiscardReason::PoolSizeLimit,
+                            DiscardReason::StaleNonce => TxpoolDiscardReason::StaleNonce,
+                            DiscardReason::ValidationFailed => {
+                                TxpoolDiscardReason::ValidationFailed
+                            }
+                            DiscardReason::Removed => TxpoolDiscardReason::Removed,
+                        };
+                        TxpoolTxEvent::Discarded { reason }
+                    }
+                    // rejected submissions and propagation aren't recorded in the history
+                    TransactionEvent::Invalid | TransactionEvent::Propagated(_) => return None,
+                };
+                Some(event)
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -138,6 +221,22 @@ where
         trace!(target: "rpc::eth", "Serving txpool_inspect");
         Ok(self.content())
     }
+
+    /// Returns the nonce gaps of the given sender and why each of its transactions isn't pending.
+    ///
+    /// Handler for `txpool_senderStatus`
+    async fn txpool_sender_status(&self, from: Address) -> Result<Option<TxpoolSenderStatus>> {
+        trace!(target: "rpc::eth", ?from, "Serving txpool_senderStatus");
+        Ok(self.sender_status(from))
+    }
+
+    /// Returns the recorded events of the transaction, oldest first.
+    ///
+    /// Handler for `txpool_txHistory`
+    async fn txpool_tx_history(&self, hash: H256) -> Result<Vec<TxpoolTxEvent>> {
+        trace!(target: "rpc::eth", ?hash, "Serving txpool_txHistory");
+        Ok(self.tx_history(hash))
+    }
 }
 
 impl<Pool> std::fmt::Debug for TxPoolApi<Pool> {
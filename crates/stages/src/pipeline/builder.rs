@@ -5,6 +5,10 @@ use reth_db::database::Database;
 use reth_primitives::{stage::StageId, BlockNumber, ChainSpec, H256};
 use tokio::sync::watch;
 
+/// The default maximum number of blocks that are unwound in one batch, see
+/// [PipelineBuilder::with_unwind_batch_size].
+pub const DEFAULT_UNWIND_BATCH_SIZE: u64 = 10_000;
+
 /// Builds a [`Pipeline`].
 #[must_use = "call `build` to construct the pipeline"]
 pub struct PipelineBuilder<DB>
@@ -18,6 +22,10 @@ where
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<H256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// Whether consecutive stages that access disjoint tables are executed in lockstep.
+    lockstep_stages: bool,
+    /// The maximum number of blocks that are unwound before the stage checkpoints are committed.
+    unwind_batch_size: u64,
 }
 
 impl<DB> PipelineBuilder<DB>
@@ -67,11 +75,30 @@ where
         self
     }
 
+    /// Set whether consecutive stages that access disjoint tables are executed in lockstep, on a
+    /// shared write transaction.
+    ///
+    /// Enabled by default.
+    pub fn with_lockstep_stages(mut self, lockstep_stages: bool) -> Self {
+        self.lockstep_stages = lockstep_stages;
+        self
+    }
+
+    /// Set the maximum number of blocks that are unwound in one batch.
+    ///
+    /// All stages are unwound by at most this many blocks before their checkpoints are committed
+    /// and the next batch starts. Defaults to [DEFAULT_UNWIND_BATCH_SIZE].
+    pub fn with_unwind_batch_size(mut self, unwind_batch_size: u64) -> Self {
+        self.unwind_batch_size = unwind_batch_size.max(1);
+        self
+    }
+
     /// Builds the final [`Pipeline`] using the given database.
     ///
     /// Note: it's expected that this is either an [Arc](std::sync::Arc) or an Arc wrapper type.
     pub fn build(self, db: DB, chain_spec: Arc<ChainSpec>) -> Pipeline<DB> {
-        let Self { stages, max_block, tip_tx, metrics_tx } = self;
+        let Self { stages, max_block, tip_tx, metrics_tx, lockstep_stages, unwind_batch_size } =
+            self;
         Pipeline {
             db,
             chain_spec,
@@ -81,13 +108,22 @@ where
             listeners: Default::default(),
             progress: Default::default(),
             metrics_tx,
+            lockstep_stages,
+            unwind_batch_size,
         }
     }
 }
 
 impl<DB: Database> Default for PipelineBuilder<DB> {
     fn default() -> Self {
-        Self { stages: Vec::new(), max_block: None, tip_tx: None, metrics_tx: None }
+        Self {
+            stages: Vec::new(),
+            max_block: None,
+            tip_tx: None,
+            metrics_tx: None,
+            lockstep_stages: true,
+            unwind_batch_size: DEFAULT_UNWIND_BATCH_SIZE,
+        }
     }
 }
 
@@ -96,6 +132,8 @@ impl<DB: Database> std::fmt::Debug for PipelineBuilder<DB> {
         f.debug_struct("PipelineBuilder")
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
+            .field("lockstep_stages", &self.lockstep_stages)
+            .field("unwind_batch_size", &self.unwind_batch_size)
             .finish()
     }
 }
@@ -1,5 +1,5 @@
 use crate::stage::{ExecOutput, UnwindInput, UnwindOutput};
-use reth_primitives::stage::{StageCheckpoint, StageId};
+use reth_primitives::stage::{EntitiesCheckpoint, StageCheckpoint, StageId};
 
 /// An event emitted by a [Pipeline][crate::Pipeline].
 ///
@@ -38,6 +38,8 @@ pub enum PipelineEvent {
         stage_id: StageId,
         /// The unwind parameters.
         input: UnwindInput,
+        /// Progress of the whole unwind in blocks, across all batches.
+        progress: EntitiesCheckpoint,
     },
     /// Emitted when a stage has been unwound.
     Unwound {
@@ -6,11 +6,15 @@ use futures_util::Future;
 use reth_db::database::Database;
 use reth_interfaces::executor::BlockExecutionError;
 use reth_primitives::{
-    constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH, listener::EventListeners, stage::StageId,
+    constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH,
+    listener::EventListeners,
+    stage::{EntitiesCheckpoint, StageCheckpoint, StageId},
     BlockNumber, ChainSpec, H256,
 };
-use reth_provider::{ProviderFactory, StageCheckpointReader, StageCheckpointWriter};
-use std::{pin::Pin, sync::Arc};
+use reth_provider::{
+    DatabaseProviderRW, ProviderFactory, StageCheckpointReader, StageCheckpointWriter,
+};
+use std::{ops::Range, pin::Pin, sync::Arc};
 use tokio::sync::watch;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::*;
@@ -82,12 +86,28 @@ pub type PipelineWithResult<DB> = (Pipeline<DB>, Result<ControlFlow, PipelineErr
 ///   LoopDone --> |Target block not reached| RunLoop
 /// ```
 ///
+/// # Lockstep stages
+///
+/// Consecutive stages that declare the [tables][Stage::tables] they access and don't conflict are
+/// executed in lockstep: every round executes each of them once on a shared write transaction,
+/// which is committed at the end of the round. A later stage doesn't have to wait until an earlier
+/// one is done, it syncs up to the checkpoint the stage before it reached in the same round, see
+/// [PipelineBuilder::with_lockstep_stages].
+///
+/// The stages are not executed concurrently: MDBX allows a single write transaction at a time, and
+/// [Stage::execute] reads and writes on the same transaction, so there is no part of a stage that
+/// could run on a separate read transaction. Lockstep execution saves the commits of the stages
+/// that wait for each other, and lets a later stage start before an earlier one is done.
+///
 /// # Unwinding
 ///
 /// In case of a validation error (as determined by the consensus engine) in one of the stages, the
 /// pipeline will unwind the stages in reverse order of execution. It is also possible to
 /// request an unwind manually (see [Pipeline::unwind]).
 ///
+/// Large unwinds are split into batches, the checkpoints of all stages are committed after every
+/// batch (see [PipelineBuilder::with_unwind_batch_size]).
+///
 /// # Defaults
 ///
 /// The [DefaultStages](crate::sets::DefaultStages) are used to fully sync reth.
@@ -107,6 +127,10 @@ pub struct Pipeline<DB: Database> {
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<H256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// Whether consecutive stages that access disjoint tables are executed in lockstep.
+    lockstep_stages: bool,
+    /// The maximum number of blocks that are unwound before the stage checkpoints are committed.
+    unwind_batch_size: u64,
 }
 
 impl<DB> Pipeline<DB>
@@ -213,37 +237,66 @@ where
     /// the unwind.
     pub async fn run_loop(&mut self) -> Result<ControlFlow, PipelineError> {
         let mut previous_stage = None;
-        for stage_index in 0..self.stages.len() {
-            let stage = &self.stages[stage_index];
-            let stage_id = stage.id();
-
-            trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
-            let next = self
-                .execute_stage_to_completion(previous_stage, stage_index)
+        for group in self.stage_groups() {
+            let stage_ids =
+                self.stages[group.clone()].iter().map(|stage| stage.id()).collect::<Vec<_>>();
+            let ctx = StageContext {
+                db: &self.db,
+                chain_spec: &self.chain_spec,
+                max_block: self.max_block,
+                total_stages: self.stages.len(),
+                metrics_tx: self.metrics_tx.as_ref(),
+            };
+
+            let flows = if let [stage_id] = stage_ids[..] {
+                let stage_index = group.start;
+                trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
+                let next = execute_stage_to_completion(
+                    &ctx,
+                    &mut self.stages[stage_index],
+                    stage_index,
+                    previous_stage,
+                    &mut self.listeners,
+                )
                 .instrument(info_span!("execute", stage = %stage_id))
                 .await?;
 
-            trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
-
-            match next {
-                ControlFlow::NoProgress { block_number } => {
-                    if let Some(block_number) = block_number {
-                        self.progress.update(block_number);
+                trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
+                vec![next]
+            } else {
+                debug!(target: "sync::pipeline", stages = ?stage_ids, "Executing stages in lockstep");
+                execute_stages_in_lockstep(
+                    &ctx,
+                    &mut self.stages[group.clone()],
+                    group.start,
+                    previous_stage,
+                    &mut self.listeners,
+                )
+                .await?
+            };
+
+            for next in flows {
+                match next {
+                    ControlFlow::NoProgress { block_number } => {
+                        if let Some(block_number) = block_number {
+                            self.progress.update(block_number);
+                        }
+                    }
+                    ControlFlow::Continue { block_number } => self.progress.update(block_number),
+                    ControlFlow::Unwind { target, bad_block } => {
+                        self.unwind(target, Some(bad_block.number)).await?;
+                        return Ok(ControlFlow::Unwind { target, bad_block })
                     }
-                }
-                ControlFlow::Continue { block_number } => self.progress.update(block_number),
-                ControlFlow::Unwind { target, bad_block } => {
-                    self.unwind(target, Some(bad_block.number)).await?;
-                    return Ok(ControlFlow::Unwind { target, bad_block })
                 }
             }
 
             let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
 
+            let last_stage_id = *stage_ids.last().expect("groups are not empty");
             previous_stage = Some(
                 factory
                     .provider()?
-                    .get_stage_checkpoint(stage_id)?
+                    .get_stage_checkpoint(last_stage_id)?
                     .unwrap_or_default()
                     .block_number,
             );
@@ -252,13 +305,82 @@ where
         Ok(self.progress.next_ctrl())
     }
 
+    /// Splits the stages into groups of consecutive stages that are executed together.
+    ///
+    /// A stage joins the group of the stages before it if all of them declare their
+    /// [tables][Stage::tables] and none of them conflict. All other stages form a group on their
+    /// own, as do all stages if lockstep execution is disabled.
+    fn stage_groups(&self) -> Vec<Range<usize>> {
+        let tables = self.stages.iter().map(|stage| stage.tables()).collect::<Vec<_>>();
+        let mut groups: Vec<Range<usize>> = Vec::new();
+        for (stage_index, stage_tables) in tables.iter().enumerate() {
+            let joins_group = self.lockstep_stages &&
+                groups.last().map_or(false, |group| {
+                    tables[group.clone()].iter().all(|earlier| match (earlier, stage_tables) {
+                        (Some(earlier), Some(later)) => !earlier.conflicts_with(later),
+                        _ => false,
+                    })
+                });
+            match groups.last_mut() {
+                Some(group) if joins_group => group.end = stage_index + 1,
+                _ => groups.push(stage_index..stage_index + 1),
+            }
+        }
+        groups
+    }
+
     /// Unwind the stages to the target block.
     ///
     /// If the unwind is due to a bad block the number of that block should be specified.
+    ///
+    /// The unwind is split into batches of at most [PipelineBuilder::with_unwind_batch_size]
+    /// blocks: all stages are unwound to the target of the batch and their checkpoints are
+    /// committed before the next batch starts. An interrupted unwind leaves every stage at a
+    /// committed checkpoint, so it can be resumed by unwinding to the same target again.
     pub async fn unwind(
         &mut self,
         to: BlockNumber,
         bad_block: Option<BlockNumber>,
+    ) -> Result<(), PipelineError> {
+        // The highest checkpoint of all stages determines the number of blocks to unwind.
+        let from = {
+            let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
+            let provider = factory.provider()?;
+            let mut from = to;
+            for stage in &self.stages {
+                let checkpoint = provider.get_stage_checkpoint(stage.id())?.unwrap_or_default();
+                from = from.max(checkpoint.block_number);
+            }
+            from
+        };
+        let mut progress = EntitiesCheckpoint { processed: 0, total: from - to };
+
+        let mut batch_to = from;
+        loop {
+            batch_to = batch_to.saturating_sub(self.unwind_batch_size).max(to);
+            if batch_to != to {
+                debug!(target: "sync::pipeline", from, to, batch_to, "Unwinding batch");
+            }
+
+            self.unwind_stages(batch_to, bad_block, progress).await?;
+
+            progress.processed = from - batch_to;
+            if batch_to == to {
+                break
+            }
+            info!(target: "sync::pipeline", unwind_to = to, progress = %progress, "Unwound batch");
+        }
+
+        Ok(())
+    }
+
+    /// Unwinds all stages in reverse order of execution to the target block, and commits the
+    /// checkpoint of every stage.
+    async fn unwind_stages(
+        &mut self,
+        to: BlockNumber,
+        bad_block: Option<BlockNumber>,
+        progress: EntitiesCheckpoint,
     ) -> Result<(), PipelineError> {
         // Unwind stages in reverse order of execution
         let unwind_pipeline = self.stages.iter_mut().rev();
@@ -281,7 +403,7 @@ where
             debug!(target: "sync::pipeline", from = %checkpoint, %to, ?bad_block, "Starting unwind");
             while checkpoint.block_number > to {
                 let input = UnwindInput { checkpoint, unwind_to: to, bad_block };
-                self.listeners.notify(PipelineEvent::Unwinding { stage_id, input });
+                self.listeners.notify(PipelineEvent::Unwinding { stage_id, input, progress });
 
                 let output = stage.unwind(&provider_rw, input).await;
                 match output {
@@ -322,176 +444,347 @@ where
 
         Ok(())
     }
+}
 
-    async fn execute_stage_to_completion(
-        &mut self,
-        previous_stage: Option<BlockNumber>,
-        stage_index: usize,
-    ) -> Result<ControlFlow, PipelineError> {
-        let total_stages = self.stages.len();
+/// The state that's shared by all stages during a pass of the pipeline.
+struct StageContext<'a, DB> {
+    db: &'a DB,
+    chain_spec: &'a Arc<ChainSpec>,
+    max_block: Option<BlockNumber>,
+    total_stages: usize,
+    metrics_tx: Option<&'a MetricEventsSender>,
+}
 
-        let stage = &mut self.stages[stage_index];
-        let stage_id = stage.id();
-        let mut made_progress = false;
-        let target = self.max_block.or(previous_stage);
+/// Executes the stage until it is done, and commits its progress after every execution.
+async fn execute_stage_to_completion<DB: Database>(
+    ctx: &StageContext<'_, DB>,
+    stage: &mut BoxedStage<DB>,
+    stage_index: usize,
+    previous_stage: Option<BlockNumber>,
+    listeners: &mut EventListeners<PipelineEvent>,
+) -> Result<ControlFlow, PipelineError> {
+    let total_stages = ctx.total_stages;
+
+    let stage_id = stage.id();
+    let mut made_progress = false;
+    let target = ctx.max_block.or(previous_stage);
+
+    let factory = ProviderFactory::new(ctx.db, ctx.chain_spec.clone());
+    let mut provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
+
+    loop {
+        let prev_checkpoint = provider_rw.get_stage_checkpoint(stage_id)?;
+
+        let stage_reached_max_block = prev_checkpoint
+            .zip(ctx.max_block)
+            .map_or(false, |(prev_progress, target)| prev_progress.block_number >= target);
+        if stage_reached_max_block {
+            warn!(
+                target: "sync::pipeline",
+                stage = %stage_id,
+                max_block = ctx.max_block,
+                prev_block = prev_checkpoint.map(|progress| progress.block_number),
+                "Stage reached target block, skipping."
+            );
+            listeners.notify(PipelineEvent::Skipped { stage_id });
 
-        let factory = ProviderFactory::new(&self.db, self.chain_spec.clone());
-        let mut provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
+            // We reached the maximum block, so we skip the stage
+            return Ok(ControlFlow::NoProgress {
+                block_number: prev_checkpoint.map(|progress| progress.block_number),
+            })
+        }
 
-        loop {
+        listeners.notify(PipelineEvent::Running {
+            pipeline_position: stage_index + 1,
+            pipeline_total: total_stages,
+            stage_id,
+            checkpoint: prev_checkpoint,
+        });
+
+        match stage.execute(&provider_rw, ExecInput { target, checkpoint: prev_checkpoint }).await {
+            Ok(out @ ExecOutput { checkpoint, done }) => {
+                made_progress |=
+                    checkpoint.block_number != prev_checkpoint.unwrap_or_default().block_number;
+                debug!(
+                    target: "sync::pipeline",
+                    stage = %stage_id,
+                    progress = checkpoint.block_number,
+                    %checkpoint,
+                    %done,
+                    "Stage committed progress"
+                );
+                if let Some(metrics_tx) = ctx.metrics_tx {
+                    let _ = metrics_tx.send(MetricEvent::StageCheckpoint {
+                        stage_id,
+                        checkpoint,
+                        max_block_number: target,
+                    });
+                }
+                provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
+
+                listeners.notify(PipelineEvent::Ran {
+                    pipeline_position: stage_index + 1,
+                    pipeline_total: total_stages,
+                    stage_id,
+                    result: out.clone(),
+                });
+
+                // TODO: Make the commit interval configurable
+                provider_rw.commit()?;
+                provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
+
+                if done {
+                    let block_number = checkpoint.block_number;
+                    return Ok(if made_progress {
+                        ControlFlow::Continue { block_number }
+                    } else {
+                        ControlFlow::NoProgress { block_number: Some(block_number) }
+                    })
+                }
+            }
+            Err(err) => {
+                listeners.notify(PipelineEvent::Error { stage_id });
+
+                // On recoverable errors the transaction is discarded and the stage runs again.
+                match on_stage_error(&factory, stage_id, err, prev_checkpoint, provider_rw)? {
+                    Some(out) => return Ok(out),
+                    None => {
+                        provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Executes the stages of a group in lockstep, until all of them are done.
+///
+/// Every round executes each stage that is not done yet once, in order, on a write transaction
+/// that is shared by all stages of the group and committed at the end of the round. The stages run
+/// one after another, because MDBX only has a single writer. A stage syncs
+/// up to the checkpoint the stage before it reached in the same round, or the maximum block of
+/// the pipeline.
+///
+/// Returns the control flow of every stage that finished, stops at the first stage that requests
+/// an unwind.
+async fn execute_stages_in_lockstep<DB: Database>(
+    ctx: &StageContext<'_, DB>,
+    stages: &mut [BoxedStage<DB>],
+    first_stage_index: usize,
+    previous_stage: Option<BlockNumber>,
+    listeners: &mut EventListeners<PipelineEvent>,
+) -> Result<Vec<ControlFlow>, PipelineError> {
+    let total_stages = ctx.total_stages;
+    let factory = ProviderFactory::new(ctx.db, ctx.chain_spec.clone());
+    let mut flows: Vec<Option<ControlFlow>> = stages.iter().map(|_| None).collect();
+    let mut made_progress = vec![false; stages.len()];
+
+    'round: while flows.iter().any(Option::is_none) {
+        let provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
+        // only applied once the round is committed
+        let mut finished = Vec::new();
+        let mut ran = Vec::new();
+        let mut target = previous_stage;
+        // a stage is only done once the stage before it is done, otherwise its target may still
+        // move
+        let mut previous_done = true;
+
+        for (offset, stage) in stages.iter_mut().enumerate() {
+            let stage_index = first_stage_index + offset;
+            let stage_id = stage.id();
             let prev_checkpoint = provider_rw.get_stage_checkpoint(stage_id)?;
+            let stage_target = ctx.max_block.or(target);
+            target = Some(prev_checkpoint.unwrap_or_default().block_number);
+
+            if flows[offset].is_some() {
+                continue
+            }
 
             let stage_reached_max_block = prev_checkpoint
-                .zip(self.max_block)
+                .zip(ctx.max_block)
                 .map_or(false, |(prev_progress, target)| prev_progress.block_number >= target);
             if stage_reached_max_block {
                 warn!(
                     target: "sync::pipeline",
                     stage = %stage_id,
-                    max_block = self.max_block,
+                    max_block = ctx.max_block,
                     prev_block = prev_checkpoint.map(|progress| progress.block_number),
                     "Stage reached target block, skipping."
                 );
-                self.listeners.notify(PipelineEvent::Skipped { stage_id });
-
-                // We reached the maximum block, so we skip the stage
-                return Ok(ControlFlow::NoProgress {
-                    block_number: prev_checkpoint.map(|progress| progress.block_number),
-                })
+                listeners.notify(PipelineEvent::Skipped { stage_id });
+                finished.push((
+                    offset,
+                    ControlFlow::NoProgress {
+                        block_number: prev_checkpoint.map(|progress| progress.block_number),
+                    },
+                ));
+                continue
             }
 
-            self.listeners.notify(PipelineEvent::Running {
+            listeners.notify(PipelineEvent::Running {
                 pipeline_position: stage_index + 1,
                 pipeline_total: total_stages,
                 stage_id,
                 checkpoint: prev_checkpoint,
             });
 
+            let input = ExecInput { target: stage_target, checkpoint: prev_checkpoint };
             match stage
-                .execute(&provider_rw, ExecInput { target, checkpoint: prev_checkpoint })
+                .execute(&provider_rw, input)
+                .instrument(info_span!("execute", stage = %stage_id))
                 .await
             {
                 Ok(out @ ExecOutput { checkpoint, done }) => {
-                    made_progress |=
-                        checkpoint.block_number != prev_checkpoint.unwrap_or_default().block_number;
                     debug!(
                         target: "sync::pipeline",
                         stage = %stage_id,
                         progress = checkpoint.block_number,
                         %checkpoint,
                         %done,
-                        "Stage committed progress"
+                        "Stage executed in lockstep"
                     );
-                    if let Some(metrics_tx) = &mut self.metrics_tx {
+                    if let Some(metrics_tx) = ctx.metrics_tx {
                         let _ = metrics_tx.send(MetricEvent::StageCheckpoint {
                             stage_id,
                             checkpoint,
-                            max_block_number: target,
+                            max_block_number: stage_target,
                         });
                     }
                     provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
+                    target = Some(checkpoint.block_number);
 
-                    self.listeners.notify(PipelineEvent::Ran {
-                        pipeline_position: stage_index + 1,
-                        pipeline_total: total_stages,
-                        stage_id,
-                        result: out.clone(),
-                    });
-
-                    // TODO: Make the commit interval configurable
-                    provider_rw.commit()?;
-                    provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
-
-                    if done {
+                    let progressed =
+                        checkpoint.block_number != prev_checkpoint.unwrap_or_default().block_number;
+                    previous_done &= done;
+                    if previous_done {
                         let block_number = checkpoint.block_number;
-                        return Ok(if made_progress {
-                            ControlFlow::Continue { block_number }
-                        } else {
-                            ControlFlow::NoProgress { block_number: Some(block_number) }
-                        })
+                        finished.push((
+                            offset,
+                            if made_progress[offset] || progressed {
+                                ControlFlow::Continue { block_number }
+                            } else {
+                                ControlFlow::NoProgress { block_number: Some(block_number) }
+                            },
+                        ));
                     }
+                    ran.push((offset, stage_index, stage_id, progressed, out));
                 }
                 Err(err) => {
-                    self.listeners.notify(PipelineEvent::Error { stage_id });
-
-                    let out = if let StageError::DetachedHead { local_head, header, error } = err {
-                        warn!(target: "sync::pipeline", stage = %stage_id, ?local_head, ?header, ?error, "Stage encountered detached head");
-
-                        // We unwind because of a detached head.
-                        let unwind_to = local_head
-                            .number
-                            .saturating_sub(BEACON_CONSENSUS_REORG_UNWIND_DEPTH)
-                            .max(1);
-                        Ok(ControlFlow::Unwind { target: unwind_to, bad_block: local_head })
-                    } else if let StageError::Validation { block, error } = err {
-                        warn!(
-                            target: "sync::pipeline",
-                            stage = %stage_id,
-                            bad_block = %block.number,
-                            "Stage encountered a validation error: {error}"
-                        );
-
-                        // FIXME: When handling errors, we do not commit the database transaction.
-                        // This leads to the Merkle stage not clearing its
-                        // checkpoint, and restarting from an invalid place.
-                        drop(provider_rw);
-                        provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
-                        provider_rw
-                            .save_stage_checkpoint_progress(StageId::MerkleExecute, vec![])?;
-                        provider_rw.save_stage_checkpoint(
-                            StageId::MerkleExecute,
-                            prev_checkpoint.unwrap_or_default(),
-                        )?;
-                        provider_rw.commit()?;
-
-                        // We unwind because of a validation error. If the unwind itself fails,
-                        // we bail entirely, otherwise we restart the execution loop from the
-                        // beginning.
-                        Ok(ControlFlow::Unwind {
-                            target: prev_checkpoint.unwrap_or_default().block_number,
-                            bad_block: block,
-                        })
-                    } else if let StageError::ExecutionError {
-                        block,
-                        error: BlockExecutionError::Validation(error),
-                    } = err
-                    {
-                        warn!(
-                            target: "sync::pipeline",
-                            stage = %stage_id,
-                            bad_block = %block.number,
-                            "Stage encountered an execution error: {error}"
-                        );
-
-                        // We unwind because of an execution error. If the unwind itself fails, we
-                        // bail entirely, otherwise we restart the execution loop from the
-                        // beginning.
-                        Ok(ControlFlow::Unwind {
-                            target: prev_checkpoint.unwrap_or_default().block_number,
-                            bad_block: block,
-                        })
-                    } else if err.is_fatal() {
-                        error!(
-                            target: "sync::pipeline",
-                            stage = %stage_id,
-                            "Stage encountered a fatal error: {err}."
-                        );
-                        Err(err.into())
-                    } else {
-                        // On other errors we assume they are recoverable if we discard the
-                        // transaction and run the stage again.
-                        warn!(
-                            target: "sync::pipeline",
-                            stage = %stage_id,
-                            "Stage encountered a non-fatal error: {err}. Retrying..."
-                        );
-                        continue
-                    };
-                    return out
+                    listeners.notify(PipelineEvent::Error { stage_id });
+
+                    // The progress of the other stages in this round is discarded together with
+                    // the transaction.
+                    match on_stage_error(&factory, stage_id, err, prev_checkpoint, provider_rw)? {
+                        Some(out) => {
+                            flows[offset] = Some(out);
+                            return Ok(flows.into_iter().flatten().collect())
+                        }
+                        None => continue 'round,
+                    }
                 }
             }
         }
+
+        provider_rw.commit()?;
+
+        for (offset, stage_index, stage_id, progressed, result) in ran {
+            made_progress[offset] |= progressed;
+            listeners.notify(PipelineEvent::Ran {
+                pipeline_position: stage_index + 1,
+                pipeline_total: total_stages,
+                stage_id,
+                result,
+            });
+        }
+        for (offset, flow) in finished {
+            flows[offset] = Some(flow);
+        }
     }
+
+    Ok(flows.into_iter().flatten().collect())
+}
+
+/// Handles an error of a stage execution, the transaction the stage was executed on is discarded.
+///
+/// Returns the control flow the pipeline continues with, or `None` if the error is recoverable and
+/// the stage should be executed again.
+fn on_stage_error<DB: Database>(
+    factory: &ProviderFactory<&DB>,
+    stage_id: StageId,
+    err: StageError,
+    prev_checkpoint: Option<StageCheckpoint>,
+    provider_rw: DatabaseProviderRW<'_, &DB>,
+) -> Result<Option<ControlFlow>, PipelineError> {
+    drop(provider_rw);
+
+    let out = if let StageError::DetachedHead { local_head, header, error } = err {
+        warn!(target: "sync::pipeline", stage = %stage_id, ?local_head, ?header, ?error, "Stage encountered detached head");
+
+        // We unwind because of a detached head.
+        let unwind_to =
+            local_head.number.saturating_sub(BEACON_CONSENSUS_REORG_UNWIND_DEPTH).max(1);
+        ControlFlow::Unwind { target: unwind_to, bad_block: local_head }
+    } else if let StageError::Validation { block, error } = err {
+        warn!(
+            target: "sync::pipeline",
+            stage = %stage_id,
+            bad_block = %block.number,
+            "Stage encountered a validation error: {error}"
+        );
+
+        // FIXME: When handling errors, we do not commit the database transaction.
+        // This leads to the Merkle stage not clearing its
+        // checkpoint, and restarting from an invalid place.
+        let provider_rw = factory.provider_rw().map_err(PipelineError::Interface)?;
+        provider_rw.save_stage_checkpoint_progress(StageId::MerkleExecute, vec![])?;
+        provider_rw
+            .save_stage_checkpoint(StageId::MerkleExecute, prev_checkpoint.unwrap_or_default())?;
+        provider_rw.commit()?;
+
+        // We unwind because of a validation error. If the unwind itself fails,
+        // we bail entirely, otherwise we restart the execution loop from the
+        // beginning.
+        ControlFlow::Unwind {
+            target: prev_checkpoint.unwrap_or_default().block_number,
+            bad_block: block,
+        }
+    } else if let StageError::ExecutionError {
+        block,
+        error: BlockExecutionError::Validation(error),
+    } = err
+    {
+        warn!(
+            target: "sync::pipeline",
+            stage = %stage_id,
+            bad_block = %block.number,
+            "Stage encountered an execution error: {error}"
+        );
+
+        // We unwind because of an execution error. If the unwind itself fails, we
+        // bail entirely, otherwise we restart the execution loop from the
+        // beginning.
+        ControlFlow::Unwind {
+            target: prev_checkpoint.unwrap_or_default().block_number,
+            bad_block: block,
+        }
+    } else if err.is_fatal() {
+        error!(
+            target: "sync::pipeline",
+            stage = %stage_id,
+            "Stage encountered a fatal error: {err}."
+        );
+        return Err(err.into())
+    } else {
+        // On other errors we assume they are recoverable if we discard the
+        // transaction and run the stage again.
+        warn!(
+            target: "sync::pipeline",
+            stage = %stage_id,
+            "Stage encountered a non-fatal error: {err}. Retrying..."
+        );
+        return Ok(None)
+    };
+    Ok(Some(out))
 }
 
 impl<DB: Database> std::fmt::Debug for Pipeline<DB> {
@@ -500,6 +793,8 @@ impl<DB: Database> std::fmt::Debug for Pipeline<DB> {
             .field("stages", &self.stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>())
             .field("max_block", &self.max_block)
             .field("listeners", &self.listeners)
+            .field("lockstep_stages", &self.lockstep_stages)
+            .field("unwind_batch_size", &self.unwind_batch_size)
             .finish()
     }
 }
@@ -507,9 +802,9 @@ impl<DB: Database> std::fmt::Debug for Pipeline<DB> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{test_utils::TestStage, UnwindOutput};
+    use crate::{test_utils::TestStage, StageTables, UnwindOutput};
     use assert_matches::assert_matches;
-    use reth_db::test_utils::create_test_rw_db;
+    use reth_db::{test_utils::create_test_rw_db, Tables};
     use reth_interfaces::{
         consensus,
         provider::ProviderError,
@@ -682,7 +977,8 @@ mod tests {
                         checkpoint: StageCheckpoint::new(20),
                         unwind_to: 1,
                         bad_block: None
-                    }
+                    },
+                    progress: EntitiesCheckpoint { processed: 0, total: 99 }
                 },
                 PipelineEvent::Unwound {
                     stage_id: StageId::Other("C"),
@@ -694,7 +990,8 @@ mod tests {
                         checkpoint: StageCheckpoint::new(10),
                         unwind_to: 1,
                         bad_block: None
-                    }
+                    },
+                    progress: EntitiesCheckpoint { processed: 0, total: 99 }
                 },
                 PipelineEvent::Unwound {
                     stage_id: StageId::Other("B"),
@@ -706,7 +1003,8 @@ mod tests {
                         checkpoint: StageCheckpoint::new(100),
                         unwind_to: 1,
                         bad_block: None
-                    }
+                    },
+                    progress: EntitiesCheckpoint { processed: 0, total: 99 }
                 },
                 PipelineEvent::Unwound {
                     stage_id: StageId::Other("A"),
@@ -782,7 +1080,8 @@ mod tests {
                         checkpoint: StageCheckpoint::new(100),
                         unwind_to: 50,
                         bad_block: None
-                    }
+                    },
+                    progress: EntitiesCheckpoint { processed: 0, total: 50 }
                 },
                 PipelineEvent::Unwound {
                     stage_id: StageId::Other("A"),
@@ -792,6 +1091,75 @@ mod tests {
         );
     }
 
+    /// Unwinds a pipeline in batches.
+    ///
+    /// - Stage A is unwound from block 100 to 10 in batches of 40 blocks
+    /// - Stage B is skipped in the first batch, since it's below the target of the batch
+    #[tokio::test]
+    async fn unwind_pipeline_in_batches() {
+        let db = create_test_rw_db();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(100), done: true }))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(60) }))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(20) }))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(10) })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(30), done: true }))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(20) }))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(10) })),
+            )
+            .with_max_block(30)
+            .with_lockstep_stages(false)
+            .with_unwind_batch_size(40)
+            .build(db, MAINNET.clone());
+        let events = pipeline.events();
+
+        tokio::spawn(async move {
+            pipeline.run().await.expect("Could not run pipeline");
+            pipeline.unwind(10, None).await.expect("Could not unwind pipeline");
+        });
+
+        let unwinding = |stage, checkpoint, unwind_to, processed| PipelineEvent::Unwinding {
+            stage_id: StageId::Other(stage),
+            input: UnwindInput {
+                checkpoint: StageCheckpoint::new(checkpoint),
+                unwind_to,
+                bad_block: None,
+            },
+            progress: EntitiesCheckpoint { processed, total: 90 },
+        };
+        let unwound = |stage, checkpoint| PipelineEvent::Unwound {
+            stage_id: StageId::Other(stage),
+            result: UnwindOutput { checkpoint: StageCheckpoint::new(checkpoint) },
+        };
+
+        let events = events.collect::<Vec<PipelineEvent>>().await;
+        assert_eq!(
+            events[4..],
+            vec![
+                // First batch
+                PipelineEvent::Skipped { stage_id: StageId::Other("B") },
+                unwinding("A", 100, 60, 0),
+                unwound("A", 60),
+                // Second batch
+                unwinding("B", 30, 20, 40),
+                unwound("B", 20),
+                unwinding("A", 60, 20, 40),
+                unwound("A", 20),
+                // Last batch
+                unwinding("B", 20, 10, 80),
+                unwound("B", 10),
+                unwinding("A", 20, 10, 80),
+                unwound("A", 10),
+            ]
+        );
+    }
+
     /// Runs a pipeline that unwinds during sync.
     ///
     /// The flow is:
@@ -862,7 +1230,8 @@ mod tests {
                         checkpoint: StageCheckpoint::new(10),
                         unwind_to: 0,
                         bad_block: Some(5)
-                    }
+                    },
+                    progress: EntitiesCheckpoint { processed: 0, total: 10 }
                 },
                 PipelineEvent::Unwound {
                     stage_id: StageId::Other("A"),
@@ -896,6 +1265,103 @@ mod tests {
         );
     }
 
+    /// Runs a pipeline with stages that access disjoint tables.
+    ///
+    /// - Stage A may access any table and is executed on its own
+    /// - Stages B and C access disjoint tables and are executed in lockstep, C syncs up to the
+    ///   checkpoint B reached in the same round until B is done
+    /// - Stage D writes a table that B and C read and is executed on its own
+    #[tokio::test]
+    async fn run_pipeline_with_lockstep_stages() {
+        let db = create_test_rw_db();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .with_tables(StageTables::new(&[Tables::Headers], &[Tables::TxHashNumber]))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: false }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("C"))
+                    .with_tables(StageTables::new(&[Tables::Headers], &[Tables::AccountHistory]))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(5), done: true }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("D"))
+                    .with_tables(StageTables::new(&[], &[Tables::Headers]))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .build(db.clone(), MAINNET.clone());
+        assert_eq!(pipeline.stage_groups(), vec![0..1, 1..3, 3..4]);
+
+        let events = pipeline.events();
+        tokio::spawn(async move {
+            pipeline.run_loop().await.unwrap();
+        });
+        let events = events.collect::<Vec<PipelineEvent>>().await;
+
+        let running =
+            |stage_id, pipeline_position, checkpoint: Option<u64>| PipelineEvent::Running {
+                pipeline_position,
+                pipeline_total: 4,
+                stage_id: StageId::Other(stage_id),
+                checkpoint: checkpoint.map(StageCheckpoint::new),
+            };
+        let ran = |stage_id, pipeline_position, checkpoint, done| PipelineEvent::Ran {
+            pipeline_position,
+            pipeline_total: 4,
+            stage_id: StageId::Other(stage_id),
+            result: ExecOutput { checkpoint: StageCheckpoint::new(checkpoint), done },
+        };
+        assert_eq!(
+            events,
+            vec![
+                running("A", 1, None),
+                ran("A", 1, 10, true),
+                // first round, C syncs up to the checkpoint of B but is not done before B is
+                running("B", 2, None),
+                running("C", 3, None),
+                ran("B", 2, 5, false),
+                ran("C", 3, 5, true),
+                running("B", 2, Some(5)),
+                running("C", 3, Some(5)),
+                ran("B", 2, 10, true),
+                ran("C", 3, 10, true),
+                running("D", 4, None),
+                ran("D", 4, 10, true),
+            ]
+        );
+
+        let provider = ProviderFactory::new(&db, MAINNET.clone()).provider().unwrap();
+        for stage_id in ["A", "B", "C", "D"] {
+            assert_eq!(
+                provider.get_stage_checkpoint(StageId::Other(stage_id)).unwrap(),
+                Some(StageCheckpoint::new(10))
+            );
+        }
+
+        // without lockstep stages every stage is executed on its own
+        let pipeline = Pipeline::builder()
+            .add_stage(TestStage::new(StageId::Other("A")))
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .with_tables(StageTables::new(&[], &[Tables::TxHashNumber])),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("C"))
+                    .with_tables(StageTables::new(&[], &[Tables::AccountHistory])),
+            )
+            .with_lockstep_stages(false)
+            .build(create_test_rw_db(), MAINNET.clone());
+        assert_eq!(pipeline.stage_groups(), vec![0..1, 1..2, 2..3]);
+    }
+
     /// Checks that the pipeline re-runs stages on non-fatal errors and stops on fatal ones.
     #[tokio::test]
     async fn pipeline_error_handling() {
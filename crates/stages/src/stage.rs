@@ -1,6 +1,6 @@
 use crate::error::StageError;
 use async_trait::async_trait;
-use reth_db::database::Database;
+use reth_db::{database::Database, Tables};
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     BlockNumber, TxNumber,
@@ -178,6 +178,32 @@ pub struct UnwindOutput {
     pub checkpoint: StageCheckpoint,
 }
 
+/// The tables a [Stage] reads from and writes to, see [Stage::tables].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StageTables {
+    /// Tables the stage only reads from.
+    pub reads: &'static [Tables],
+    /// Tables the stage writes to.
+    pub writes: &'static [Tables],
+}
+
+impl StageTables {
+    /// Create a new set of tables.
+    pub const fn new(reads: &'static [Tables], writes: &'static [Tables]) -> Self {
+        Self { reads, writes }
+    }
+
+    /// Returns true if the stages must not be executed in lockstep, because one of them writes to
+    /// a table that the other one accesses.
+    pub fn conflicts_with(&self, other: &StageTables) -> bool {
+        let accesses = |tables: &StageTables, table: &Tables| {
+            tables.reads.contains(table) || tables.writes.contains(table)
+        };
+        self.writes.iter().any(|table| accesses(other, table)) ||
+            other.writes.iter().any(|table| accesses(self, table))
+    }
+}
+
 /// A stage is a segmented part of the syncing process of the node.
 ///
 /// Each stage takes care of a well-defined task, such as downloading headers or executing
@@ -186,7 +212,9 @@ pub struct UnwindOutput {
 /// Stages must have a unique [ID][StageId] and implement a way to "roll forwards"
 /// ([Stage::execute]) and a way to "roll back" ([Stage::unwind]).
 ///
-/// Stages are executed as part of a pipeline where they are executed serially.
+/// Stages are executed as part of a pipeline where they are executed serially, unless they
+/// declare the [tables][Stage::tables] they access and can be executed in lockstep with their
+/// neighbours.
 ///
 /// Stages receive [`DatabaseProviderRW`].
 #[async_trait]
@@ -196,6 +224,18 @@ pub trait Stage<DB: Database>: Send + Sync {
     /// Stage IDs must be unique.
     fn id(&self) -> StageId;
 
+    /// The tables the stage reads from and writes to, when executing and unwinding.
+    ///
+    /// The pipeline executes consecutive stages with non-conflicting tables in lockstep, on a
+    /// shared write transaction. Stages that return `None` may access any table and are always
+    /// executed on their own.
+    ///
+    /// Stage checkpoints and prune checkpoints are keyed by the stage and do not need to be
+    /// declared.
+    fn tables(&self) -> Option<StageTables> {
+        None
+    }
+
     /// Execute the stage.
     async fn execute(
         &mut self,
@@ -1,4 +1,4 @@
-use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use crate::{ExecInput, ExecOutput, Stage, StageError, StageTables, UnwindInput, UnwindOutput};
 use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
 use reth_db::{
@@ -6,7 +6,7 @@ use reth_db::{
     database::Database,
     tables,
     transaction::{DbTx, DbTxMut},
-    RawKey, RawTable,
+    RawKey, RawTable, Tables,
 };
 use reth_interfaces::db::DatabaseError;
 use reth_primitives::{
@@ -132,6 +132,13 @@ impl<DB: Database> Stage<DB> for AccountHashingStage {
         StageId::AccountHashing
     }
 
+    fn tables(&self) -> Option<StageTables> {
+        Some(StageTables::new(
+            &[Tables::PlainAccountState, Tables::AccountChangeSet],
+            &[Tables::HashedAccount],
+        ))
+    }
+
     /// Execute the stage.
     async fn execute(
         &mut self,
@@ -1,4 +1,4 @@
-use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use crate::{ExecInput, ExecOutput, Stage, StageError, StageTables, UnwindInput, UnwindOutput};
 use num_traits::Zero;
 use reth_db::{
     cursor::DbDupCursorRO,
@@ -6,6 +6,7 @@ use reth_db::{
     models::BlockNumberAddress,
     tables,
     transaction::{DbTx, DbTxMut},
+    Tables,
 };
 use reth_interfaces::db::DatabaseError;
 use reth_primitives::{
@@ -51,6 +52,13 @@ impl<DB: Database> Stage<DB> for StorageHashingStage {
         StageId::StorageHashing
     }
 
+    fn tables(&self) -> Option<StageTables> {
+        Some(StageTables::new(
+            &[Tables::PlainStorageState, Tables::StorageChangeSet],
+            &[Tables::HashedStorage],
+        ))
+    }
+
     /// Execute the stage.
     async fn execute(
         &mut self,
@@ -1,5 +1,5 @@
-use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
-use reth_db::database::Database;
+use crate::{ExecInput, ExecOutput, Stage, StageError, StageTables, UnwindInput, UnwindOutput};
+use reth_db::{database::Database, Tables};
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     PruneCheckpoint, PruneModes, PrunePart,
@@ -42,6 +42,10 @@ impl<DB: Database> Stage<DB> for IndexAccountHistoryStage {
         StageId::IndexAccountHistory
     }
 
+    fn tables(&self) -> Option<StageTables> {
+        Some(StageTables::new(&[Tables::AccountChangeSet], &[Tables::AccountHistory]))
+    }
+
     /// Execute the stage.
     async fn execute(
         &mut self,
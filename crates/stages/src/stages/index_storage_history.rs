@@ -1,5 +1,5 @@
-use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
-use reth_db::{database::Database, models::BlockNumberAddress};
+use crate::{ExecInput, ExecOutput, Stage, StageError, StageTables, UnwindInput, UnwindOutput};
+use reth_db::{database::Database, models::BlockNumberAddress, Tables};
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     PruneCheckpoint, PruneModes, PrunePart,
@@ -41,6 +41,10 @@ impl<DB: Database> Stage<DB> for IndexStorageHistoryStage {
         StageId::IndexStorageHistory
     }
 
+    fn tables(&self) -> Option<StageTables> {
+        Some(StageTables::new(&[Tables::StorageChangeSet], &[Tables::StorageHistory]))
+    }
+
     /// Execute the stage.
     async fn execute(
         &mut self,
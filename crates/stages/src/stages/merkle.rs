@@ -1,9 +1,10 @@
-use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use crate::{ExecInput, ExecOutput, Stage, StageError, StageTables, UnwindInput, UnwindOutput};
 use reth_codecs::Compact;
 use reth_db::{
     database::Database,
     tables,
     transaction::{DbTx, DbTxMut},
+    Tables,
 };
 use reth_interfaces::consensus;
 use reth_primitives::{
@@ -146,6 +147,20 @@ impl<DB: Database> Stage<DB> for MerkleStage {
         }
     }
 
+    fn tables(&self) -> Option<StageTables> {
+        Some(StageTables::new(
+            &[
+                Tables::HashedAccount,
+                Tables::HashedStorage,
+                Tables::AccountChangeSet,
+                Tables::StorageChangeSet,
+                Tables::CanonicalHeaders,
+                Tables::Headers,
+            ],
+            &[Tables::AccountsTrie, Tables::StoragesTrie],
+        ))
+    }
+
     /// Execute the stage.
     async fn execute(
         &mut self,
@@ -1,4 +1,4 @@
-use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use crate::{ExecInput, ExecOutput, Stage, StageError, StageTables, UnwindInput, UnwindOutput};
 use itertools::Itertools;
 use rayon::prelude::*;
 use reth_db::{
@@ -6,7 +6,7 @@ use reth_db::{
     database::Database,
     tables,
     transaction::{DbTx, DbTxMut},
-    DatabaseError,
+    DatabaseError, Tables,
 };
 use reth_interfaces::provider::ProviderError;
 use reth_primitives::{
@@ -52,6 +52,13 @@ impl<DB: Database> Stage<DB> for TransactionLookupStage {
         StageId::TransactionLookup
     }
 
+    fn tables(&self) -> Option<StageTables> {
+        Some(StageTables::new(
+            &[Tables::BlockBodyIndices, Tables::Transactions],
+            &[Tables::TxHashNumber],
+        ))
+    }
+
     /// Write transaction hash -> id entries
     async fn execute(
         &mut self,
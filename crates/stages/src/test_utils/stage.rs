@@ -1,4 +1,4 @@
-use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use crate::{ExecInput, ExecOutput, Stage, StageError, StageTables, UnwindInput, UnwindOutput};
 use reth_db::database::Database;
 use reth_primitives::stage::StageId;
 use reth_provider::DatabaseProviderRW;
@@ -9,11 +9,12 @@ pub struct TestStage {
     id: StageId,
     exec_outputs: VecDeque<Result<ExecOutput, StageError>>,
     unwind_outputs: VecDeque<Result<UnwindOutput, StageError>>,
+    tables: Option<StageTables>,
 }
 
 impl TestStage {
     pub fn new(id: StageId) -> Self {
-        Self { id, exec_outputs: VecDeque::new(), unwind_outputs: VecDeque::new() }
+        Self { id, exec_outputs: VecDeque::new(), unwind_outputs: VecDeque::new(), tables: None }
     }
 
     pub fn with_exec(mut self, exec_outputs: VecDeque<Result<ExecOutput, StageError>>) -> Self {
@@ -29,6 +30,11 @@ impl TestStage {
         self
     }
 
+    pub fn with_tables(mut self, tables: StageTables) -> Self {
+        self.tables = Some(tables);
+        self
+    }
+
     pub fn add_exec(mut self, output: Result<ExecOutput, StageError>) -> Self {
         self.exec_outputs.push_back(output);
         self
@@ -46,6 +52,10 @@ impl<DB: Database> Stage<DB> for TestStage {
         self.id
     }
 
+    fn tables(&self) -> Option<StageTables> {
+        self.tables
+    }
+
     async fn execute(
         &mut self,
         _: &DatabaseProviderRW<'_, &DB>,
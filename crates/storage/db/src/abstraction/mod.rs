@@ -6,6 +6,8 @@ pub mod cursor;
 pub mod database;
 /// mock
 pub mod mock;
+/// Database wrapper that records statistics of all operations.
+pub mod profiling;
 /// Table traits
 pub mod table;
 /// Transaction database traits.
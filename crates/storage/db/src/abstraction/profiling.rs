@@ -0,0 +1,552 @@
+//! A [Database] wrapper that records statistics of all database operations.
+//!
+//! All operations are forwarded to the wrapped database through the [RawTable] and [RawDupSort]
+//! view of the table, so that the exact number of bytes that are read from and written to every
+//! table can be recorded. Encoding and decoding of keys and values is included in the recorded
+//! read and write times.
+
+use crate::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    database::{Database, DatabaseGAT},
+    table::{DupSort, Table, TableImporter},
+    tables::{RawDupSort, RawKey, RawTable, RawValue},
+    transaction::{DbTx, DbTxGAT, DbTxMut, DbTxMutGAT},
+    DatabaseError,
+};
+use parking_lot::Mutex;
+use std::{
+    collections::BTreeMap,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Database operation statistics of a single table.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TableProfile {
+    /// Number of values read with [DbTx::get].
+    pub reads: u64,
+    /// Number of values written or deleted with [DbTxMut::put], [DbTxMut::delete] and
+    /// [DbTxMut::clear].
+    pub writes: u64,
+    /// Number of read operations on cursors. Every entry returned by a walker is one operation.
+    pub cursor_reads: u64,
+    /// Number of write operations on cursors.
+    pub cursor_writes: u64,
+    /// Number of key and value bytes read from the table.
+    pub bytes_read: u64,
+    /// Number of key and value bytes written to the table.
+    pub bytes_written: u64,
+    /// Time spent in read operations.
+    pub read_time: Duration,
+    /// Time spent in write operations.
+    pub write_time: Duration,
+}
+
+impl TableProfile {
+    /// Returns the total number of cursor operations.
+    pub fn cursor_ops(&self) -> u64 {
+        self.cursor_reads + self.cursor_writes
+    }
+}
+
+/// Statistics of all operations on a [ProfiledDatabase].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DatabaseProfile {
+    /// Statistics per table name.
+    pub tables: BTreeMap<&'static str, TableProfile>,
+    /// Number of committed transactions.
+    pub commits: u64,
+    /// Time spent committing transactions.
+    pub commit_time: Duration,
+}
+
+impl DatabaseProfile {
+    /// Returns the time spent in read operations across all tables.
+    pub fn read_time(&self) -> Duration {
+        self.tables.values().map(|table| table.read_time).sum()
+    }
+
+    /// Returns the time spent in write operations across all tables.
+    pub fn write_time(&self) -> Duration {
+        self.tables.values().map(|table| table.write_time).sum()
+    }
+}
+
+/// Shared handle to the statistics collected by a [ProfiledDatabase] and all of its
+/// transactions and cursors.
+#[derive(Debug, Default, Clone)]
+pub struct Profiler {
+    profile: Arc<Mutex<DatabaseProfile>>,
+}
+
+// === impl Profiler ===
+
+impl Profiler {
+    /// Returns the statistics collected so far.
+    pub fn snapshot(&self) -> DatabaseProfile {
+        self.profile.lock().clone()
+    }
+
+    /// Clears all collected statistics.
+    pub fn reset(&self) {
+        *self.profile.lock() = DatabaseProfile::default();
+    }
+
+    fn record(&self, table: &'static str, f: impl FnOnce(&mut TableProfile)) {
+        f(self.profile.lock().tables.entry(table).or_default())
+    }
+
+    fn record_commit(&self, elapsed: Duration) {
+        let mut profile = self.profile.lock();
+        profile.commits += 1;
+        profile.commit_time += elapsed;
+    }
+}
+
+/// A [Database] that records statistics of all operations on the wrapped database.
+///
+/// The wrapper can be used in place of the wrapped database, the collected statistics are
+/// available via [ProfiledDatabase::profiler].
+#[derive(Debug)]
+pub struct ProfiledDatabase<DB> {
+    inner: DB,
+    profiler: Profiler,
+}
+
+// === impl ProfiledDatabase ===
+
+impl<DB> ProfiledDatabase<DB> {
+    /// Wraps the given database.
+    pub fn new(inner: DB) -> Self {
+        Self { inner, profiler: Profiler::default() }
+    }
+
+    /// Returns the handle to the collected statistics.
+    pub fn profiler(&self) -> &Profiler {
+        &self.profiler
+    }
+
+    /// Returns the wrapped database.
+    pub fn inner(&self) -> &DB {
+        &self.inner
+    }
+}
+
+impl<'a, DB: Database> DatabaseGAT<'a> for ProfiledDatabase<DB> {
+    type TX = ProfiledTx<<DB as DatabaseGAT<'a>>::TX>;
+    type TXMut = ProfiledTx<<DB as DatabaseGAT<'a>>::TXMut>;
+}
+
+impl<DB: Database> Database for ProfiledDatabase<DB> {
+    fn tx(&self) -> Result<<Self as DatabaseGAT<'_>>::TX, DatabaseError> {
+        Ok(ProfiledTx { inner: self.inner.tx()?, profiler: self.profiler.clone() })
+    }
+
+    fn tx_mut(&self) -> Result<<Self as DatabaseGAT<'_>>::TXMut, DatabaseError> {
+        Ok(ProfiledTx { inner: self.inner.tx_mut()?, profiler: self.profiler.clone() })
+    }
+}
+
+/// A transaction of a [ProfiledDatabase].
+#[derive(Debug)]
+pub struct ProfiledTx<TX> {
+    inner: TX,
+    profiler: Profiler,
+}
+
+impl<'a, TX: DbTxGAT<'a>> DbTxGAT<'a> for ProfiledTx<TX> {
+    type Cursor<T: Table> =
+        ProfiledCursor<<TX as DbTxGAT<'a>>::Cursor<RawTable<T>>, T, RawTable<T>>;
+    type DupCursor<T: DupSort> =
+        ProfiledCursor<<TX as DbTxGAT<'a>>::DupCursor<RawDupSort<T>>, T, RawDupSort<T>>;
+}
+
+impl<'a, TX: DbTxMutGAT<'a>> DbTxMutGAT<'a> for ProfiledTx<TX> {
+    type CursorMut<T: Table> =
+        ProfiledCursor<<TX as DbTxMutGAT<'a>>::CursorMut<RawTable<T>>, T, RawTable<T>>;
+    type DupCursorMut<T: DupSort> =
+        ProfiledCursor<<TX as DbTxMutGAT<'a>>::DupCursorMut<RawDupSort<T>>, T, RawDupSort<T>>;
+}
+
+impl<'tx, TX: DbTx<'tx>> DbTx<'tx> for ProfiledTx<TX> {
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<T::Value>, DatabaseError> {
+        let start = Instant::now();
+        let value = self.inner.get::<RawTable<T>>(RawKey::new(key))?;
+        let bytes = value.as_ref().map_or(0, |value| value.raw_value().len());
+        let value = value.map(|value| value.value()).transpose();
+        self.profiler.record(T::NAME, |table| {
+            table.reads += 1;
+            table.bytes_read += bytes as u64;
+            table.read_time += start.elapsed();
+        });
+        value
+    }
+
+    fn commit(self) -> Result<bool, DatabaseError> {
+        let start = Instant::now();
+        let res = self.inner.commit();
+        self.profiler.record_commit(start.elapsed());
+        res
+    }
+
+    fn drop(self) {
+        self.inner.drop()
+    }
+
+    fn cursor_read<T: Table>(&self) -> Result<<Self as DbTxGAT<'_>>::Cursor<T>, DatabaseError> {
+        Ok(ProfiledCursor::new(self.inner.cursor_read::<RawTable<T>>()?, self.profiler.clone()))
+    }
+
+    fn cursor_dup_read<T: DupSort>(
+        &self,
+    ) -> Result<<Self as DbTxGAT<'_>>::DupCursor<T>, DatabaseError> {
+        Ok(ProfiledCursor::new(
+            self.inner.cursor_dup_read::<RawDupSort<T>>()?,
+            self.profiler.clone(),
+        ))
+    }
+
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        self.inner.entries::<T>()
+    }
+}
+
+impl<'tx, TX: DbTxMut<'tx>> DbTxMut<'tx> for ProfiledTx<TX> {
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let start = Instant::now();
+        let (key, value) = (RawKey::new(key), RawValue::new(value));
+        let bytes = key.raw_key().len() + value.raw_value().len();
+        self.inner.put::<RawTable<T>>(key, value)?;
+        self.profiler.record(T::NAME, |table| {
+            table.writes += 1;
+            table.bytes_written += bytes as u64;
+            table.write_time += start.elapsed();
+        });
+        Ok(())
+    }
+
+    fn delete<T: Table>(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        let start = Instant::now();
+        let deleted =
+            self.inner.delete::<RawTable<T>>(RawKey::new(key), value.map(RawValue::new))?;
+        self.profiler.record(T::NAME, |table| {
+            table.writes += 1;
+            table.write_time += start.elapsed();
+        });
+        Ok(deleted)
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        let start = Instant::now();
+        self.inner.clear::<T>()?;
+        self.profiler.record(T::NAME, |table| {
+            table.writes += 1;
+            table.write_time += start.elapsed();
+        });
+        Ok(())
+    }
+
+    fn cursor_write<T: Table>(
+        &self,
+    ) -> Result<<Self as DbTxMutGAT<'_>>::CursorMut<T>, DatabaseError> {
+        Ok(ProfiledCursor::new(self.inner.cursor_write::<RawTable<T>>()?, self.profiler.clone()))
+    }
+
+    fn cursor_dup_write<T: DupSort>(
+        &self,
+    ) -> Result<<Self as DbTxMutGAT<'_>>::DupCursorMut<T>, DatabaseError> {
+        Ok(ProfiledCursor::new(
+            self.inner.cursor_dup_write::<RawDupSort<T>>()?,
+            self.profiler.clone(),
+        ))
+    }
+}
+
+impl<'a, TX> TableImporter<'a> for ProfiledTx<TX> where TX: for<'b> DbTxMut<'b> {}
+
+/// A cursor over table `T` of a [ProfiledTx].
+///
+/// The wrapped cursor `C` operates on the raw view `R` of the table.
+#[derive(Debug)]
+pub struct ProfiledCursor<C, T, R> {
+    inner: C,
+    profiler: Profiler,
+    _tables: PhantomData<(T, R)>,
+}
+
+impl<C, T, R> ProfiledCursor<C, T, R>
+where
+    T: Table,
+    R: Table<Key = RawKey<T::Key>, Value = RawValue<T::Value>>,
+{
+    fn new(inner: C, profiler: Profiler) -> Self {
+        Self { inner, profiler, _tables: PhantomData }
+    }
+
+    /// Runs a read operation on the wrapped cursor and decodes the returned entry.
+    fn read_row(&mut self, op: impl FnOnce(&mut C) -> PairResult<R>) -> PairResult<T> {
+        let start = Instant::now();
+        let row = op(&mut self.inner)?;
+        let bytes =
+            row.as_ref().map_or(0, |(key, value)| key.raw_key().len() + value.raw_value().len());
+        let row = row.map(|(key, value)| Ok((key.key()?, value.value()?))).transpose();
+        self.record_read(bytes, start);
+        row
+    }
+
+    /// Runs a read operation on the wrapped cursor and decodes the returned value.
+    fn read_value(&mut self, op: impl FnOnce(&mut C) -> ValueOnlyResult<R>) -> ValueOnlyResult<T> {
+        let start = Instant::now();
+        let value = op(&mut self.inner)?;
+        let bytes = value.as_ref().map_or(0, |value| value.raw_value().len());
+        let value = value.map(|value| value.value()).transpose();
+        self.record_read(bytes, start);
+        value
+    }
+
+    /// Runs a write operation on the wrapped cursor.
+    fn write_row(
+        &mut self,
+        key: T::Key,
+        value: T::Value,
+        op: impl FnOnce(&mut C, R::Key, R::Value) -> Result<(), DatabaseError>,
+    ) -> Result<(), DatabaseError> {
+        let start = Instant::now();
+        let (key, value) = (RawKey::new(key), RawValue::new(value));
+        let bytes = key.raw_key().len() + value.raw_value().len();
+        op(&mut self.inner, key, value)?;
+        self.record_write(bytes, start);
+        Ok(())
+    }
+
+    /// Runs a delete operation on the wrapped cursor.
+    fn delete(
+        &mut self,
+        op: impl FnOnce(&mut C) -> Result<(), DatabaseError>,
+    ) -> Result<(), DatabaseError> {
+        let start = Instant::now();
+        op(&mut self.inner)?;
+        self.record_write(0, start);
+        Ok(())
+    }
+
+    fn record_read(&self, bytes: usize, start: Instant) {
+        self.profiler.record(T::NAME, |table| {
+            table.cursor_reads += 1;
+            table.bytes_read += bytes as u64;
+            table.read_time += start.elapsed();
+        });
+    }
+
+    fn record_write(&self, bytes: usize, start: Instant) {
+        self.profiler.record(T::NAME, |table| {
+            table.cursor_writes += 1;
+            table.bytes_written += bytes as u64;
+            table.write_time += start.elapsed();
+        });
+    }
+}
+
+impl<'tx, C, T, R> DbCursorRO<'tx, T> for ProfiledCursor<C, T, R>
+where
+    T: Table,
+    R: Table<Key = RawKey<T::Key>, Value = RawValue<T::Value>>,
+    C: DbCursorRO<'tx, R>,
+{
+    fn first(&mut self) -> PairResult<T> {
+        self.read_row(|cursor| cursor.first())
+    }
+
+    fn seek_exact(&mut self, key: T::Key) -> PairResult<T> {
+        self.read_row(|cursor| cursor.seek_exact(RawKey::new(key)))
+    }
+
+    fn seek(&mut self, key: T::Key) -> PairResult<T> {
+        self.read_row(|cursor| cursor.seek(RawKey::new(key)))
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        self.read_row(|cursor| cursor.next())
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        self.read_row(|cursor| cursor.prev())
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        self.read_row(|cursor| cursor.last())
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        self.read_row(|cursor| cursor.current())
+    }
+
+    fn walk<'cursor>(
+        &'cursor mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<Walker<'cursor, 'tx, T, Self>, DatabaseError> {
+        let start = match start_key {
+            Some(key) => self.seek(key),
+            None => self.first(),
+        }
+        .transpose();
+
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range<'cursor>(
+        &'cursor mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'cursor, 'tx, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back<'cursor>(
+        &'cursor mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'cursor, 'tx, T, Self>, DatabaseError> {
+        let start = match start_key {
+            Some(key) => self.seek(key),
+            None => self.last(),
+        }
+        .transpose();
+
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<'tx, C, T, R> DbDupCursorRO<'tx, T> for ProfiledCursor<C, T, R>
+where
+    T: DupSort,
+    R: DupSort<Key = RawKey<T::Key>, Value = RawValue<T::Value>, SubKey = RawKey<T::SubKey>>,
+    C: DbDupCursorRO<'tx, R>,
+{
+    fn next_dup(&mut self) -> PairResult<T> {
+        self.read_row(|cursor| cursor.next_dup())
+    }
+
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        self.read_row(|cursor| cursor.next_no_dup())
+    }
+
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        self.read_value(|cursor| cursor.next_dup_val())
+    }
+
+    fn seek_by_key_subkey(&mut self, key: T::Key, subkey: T::SubKey) -> ValueOnlyResult<T> {
+        self.read_value(|cursor| cursor.seek_by_key_subkey(RawKey::new(key), RawKey::new(subkey)))
+    }
+
+    fn walk_dup<'cursor>(
+        &'cursor mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'cursor, 'tx, T, Self>, DatabaseError> {
+        // the wrapped walker only positions the cursor and returns the start entry, all further
+        // entries are read through this cursor.
+        let start = self
+            .read_row(|cursor| {
+                cursor.walk_dup(key.map(RawKey::new), subkey.map(RawKey::new))?.next().transpose()
+            })
+            .transpose();
+
+        Ok(DupWalker { cursor: self, start, _tx_phantom: PhantomData })
+    }
+}
+
+impl<'tx, C, T, R> DbCursorRW<'tx, T> for ProfiledCursor<C, T, R>
+where
+    T: Table,
+    R: Table<Key = RawKey<T::Key>, Value = RawValue<T::Value>>,
+    C: DbCursorRW<'tx, R>,
+{
+    fn upsert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        self.write_row(key, value, |cursor, key, value| cursor.upsert(key, value))
+    }
+
+    fn insert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        self.write_row(key, value, |cursor, key, value| cursor.insert(key, value))
+    }
+
+    fn append(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        self.write_row(key, value, |cursor, key, value| cursor.append(key, value))
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        self.delete(|cursor| cursor.delete_current())
+    }
+}
+
+impl<'tx, C, T, R> DbDupCursorRW<'tx, T> for ProfiledCursor<C, T, R>
+where
+    T: DupSort,
+    R: DupSort<Key = RawKey<T::Key>, Value = RawValue<T::Value>, SubKey = RawKey<T::SubKey>>,
+    C: DbDupCursorRW<'tx, R>,
+{
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        self.delete(|cursor| cursor.delete_current_duplicates())
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        self.write_row(key, value, |cursor, key, value| cursor.append_dup(key, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tables::CanonicalHeaders, test_utils::create_test_rw_db};
+    use reth_primitives::H256;
+
+    #[test]
+    fn records_table_operations() {
+        let db = ProfiledDatabase::new(create_test_rw_db());
+
+        let tx = db.tx_mut().unwrap();
+        tx.put::<CanonicalHeaders>(1, H256::zero()).unwrap();
+        let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+        cursor.append(2, H256::zero()).unwrap();
+        drop(cursor);
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        assert_eq!(tx.get::<CanonicalHeaders>(1).unwrap(), Some(H256::zero()));
+        let rows = tx.cursor_read::<CanonicalHeaders>().unwrap().walk(None).unwrap().count();
+        assert_eq!(rows, 2);
+        tx.commit().unwrap();
+
+        let profile = db.profiler().snapshot();
+        assert_eq!(profile.commits, 2);
+        let table = profile.tables[CanonicalHeaders::NAME];
+        assert_eq!(table.reads, 1);
+        assert_eq!(table.writes, 1);
+        assert_eq!(table.cursor_writes, 1);
+        // two walked entries and the final `next` past the end
+        assert_eq!(table.cursor_reads, 3);
+        // 8 byte keys and 32 byte values
+        assert_eq!(table.bytes_written, 80);
+        assert_eq!(table.bytes_read, 112);
+
+        db.profiler().reset();
+        assert_eq!(db.profiler().snapshot(), DatabaseProfile::default());
+    }
+}
@@ -0,0 +1,276 @@
+//! Consistent copies of a database that is in use.
+
+use super::{Env, EnvKind};
+use crate::{
+    cursor::DbCursorRO,
+    database::Database,
+    tables,
+    transaction::DbTx,
+    version::{create_db_version_file, DB_VERSION},
+    DatabaseError,
+};
+use reth_libmdbx::{EnvironmentKind, NoWriteMap};
+use reth_primitives::{stage::StageCheckpoint, BlockNumber, H256};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Name of the MDBX data file inside a database directory.
+const DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// Name of the manifest file inside a backup directory.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Describes a backup of the node database.
+///
+/// The manifest is written next to the copied database and is checked against the copy before the
+/// backup is restored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    /// Version of the database schema.
+    pub db_version: u64,
+    /// Unix timestamp in seconds at which the backup was created.
+    pub created_at: u64,
+    /// Hash of the genesis block.
+    pub genesis_hash: H256,
+    /// Number of the highest canonical block.
+    pub tip_number: BlockNumber,
+    /// Hash of the highest canonical block.
+    pub tip_hash: H256,
+    /// Checkpoints of all stages, keyed by the stage id.
+    pub stage_checkpoints: BTreeMap<String, StageCheckpoint>,
+    /// Size of the copied database file in bytes.
+    pub size: u64,
+}
+
+/// Errors that can occur while creating or restoring a backup.
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    /// The destination already contains a database.
+    #[error("{0:?} already contains a database")]
+    DestinationExists(PathBuf),
+    /// The backup was created with a different database version.
+    #[error("backup has database version {got}, expected {expected}")]
+    VersionMismatch {
+        /// The database version of the backup.
+        got: u64,
+        /// The current database version.
+        expected: u64,
+    },
+    /// The backup belongs to a different chain.
+    #[error("backup has genesis hash {got:?}, expected {expected:?}")]
+    GenesisMismatch {
+        /// The genesis hash of the backup.
+        got: H256,
+        /// The genesis hash of the chain the backup is restored for.
+        expected: H256,
+    },
+    /// The copied database does not match the manifest.
+    #[error("backup does not match its manifest: {0}")]
+    ManifestMismatch(&'static str),
+    /// The manifest could not be read or written.
+    #[error("invalid manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+    /// Copying the environment failed.
+    #[error("failed to copy database: {0}")]
+    Copy(#[from] reth_libmdbx::Error),
+    /// Low-level database error.
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+    /// Filesystem error.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Creates consistent copies of a database while it is in use.
+pub trait DatabaseBackup: Send + Sync {
+    /// Copies the database into the given directory and writes a [BackupManifest] next to it.
+    ///
+    /// The directory is created if it does not exist, but must not contain a database yet.
+    fn backup(&self, dest: &Path) -> Result<BackupManifest, BackupError>;
+}
+
+impl<T: DatabaseBackup> DatabaseBackup for Arc<T> {
+    fn backup(&self, dest: &Path) -> Result<BackupManifest, BackupError> {
+        (**self).backup(dest)
+    }
+}
+
+impl<E: EnvironmentKind> DatabaseBackup for Env<E> {
+    fn backup(&self, dest: &Path) -> Result<BackupManifest, BackupError> {
+        let data_file = dest.join(DATA_FILE_NAME);
+        if data_file.exists() {
+            return Err(BackupError::DestinationExists(dest.to_path_buf()))
+        }
+        fs::create_dir_all(dest)?;
+
+        // The copy is made under a read transaction, so it is consistent even while the node keeps
+        // writing. The manifest is read from the copy to describe exactly the copied state.
+        self.inner.copy(&data_file, false)?;
+        let copy = Env::<NoWriteMap>::open(dest, EnvKind::RO, None)?;
+        let (genesis_hash, (tip_number, tip_hash), stage_checkpoints) = read_contents(&copy)?;
+        drop(copy);
+
+        let manifest = BackupManifest {
+            db_version: DB_VERSION,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+            genesis_hash,
+            tip_number,
+            tip_hash,
+            stage_checkpoints,
+            size: fs::metadata(&data_file)?.len(),
+        };
+        fs::write(dest.join(MANIFEST_FILE_NAME), serde_json::to_vec_pretty(&manifest)?)?;
+
+        Ok(manifest)
+    }
+}
+
+/// Reads the [BackupManifest] of the backup in the given directory.
+pub fn read_backup_manifest(backup: &Path) -> Result<BackupManifest, BackupError> {
+    Ok(serde_json::from_slice(&fs::read(backup.join(MANIFEST_FILE_NAME))?)?)
+}
+
+/// Checks that the backup in the given directory matches its manifest and can be used with the
+/// current database version.
+pub fn validate_backup(backup: &Path) -> Result<BackupManifest, BackupError> {
+    let manifest = read_backup_manifest(backup)?;
+    if manifest.db_version != DB_VERSION {
+        return Err(BackupError::VersionMismatch { got: manifest.db_version, expected: DB_VERSION })
+    }
+    if fs::metadata(backup.join(DATA_FILE_NAME))?.len() != manifest.size {
+        return Err(BackupError::ManifestMismatch("size"))
+    }
+
+    let env = Env::<NoWriteMap>::open(backup, EnvKind::RO, None)?;
+    let (genesis_hash, (tip_number, tip_hash), stage_checkpoints) = read_contents(&env)?;
+    if genesis_hash != manifest.genesis_hash {
+        return Err(BackupError::ManifestMismatch("genesis hash"))
+    }
+    if tip_number != manifest.tip_number || tip_hash != manifest.tip_hash {
+        return Err(BackupError::ManifestMismatch("chain tip"))
+    }
+    if stage_checkpoints != manifest.stage_checkpoints {
+        return Err(BackupError::ManifestMismatch("stage checkpoints"))
+    }
+
+    Ok(manifest)
+}
+
+/// Validates the backup in the given directory and restores it as the database at `db_path`, which
+/// must not contain a database yet.
+///
+/// The backup must belong to the chain with the given genesis hash.
+pub fn restore_backup(
+    backup: &Path,
+    db_path: &Path,
+    genesis_hash: H256,
+) -> Result<BackupManifest, BackupError> {
+    let manifest = validate_backup(backup)?;
+    if manifest.genesis_hash != genesis_hash {
+        return Err(BackupError::GenesisMismatch {
+            got: manifest.genesis_hash,
+            expected: genesis_hash,
+        })
+    }
+
+    let data_file = db_path.join(DATA_FILE_NAME);
+    if data_file.exists() {
+        return Err(BackupError::DestinationExists(db_path.to_path_buf()))
+    }
+    fs::create_dir_all(db_path)?;
+    fs::copy(backup.join(DATA_FILE_NAME), data_file)?;
+    create_db_version_file(db_path)?;
+
+    Ok(manifest)
+}
+
+/// Returns the genesis hash, the chain tip and the stage checkpoints of the database.
+#[allow(clippy::type_complexity)]
+fn read_contents<E: EnvironmentKind>(
+    env: &Env<E>,
+) -> Result<(H256, (BlockNumber, H256), BTreeMap<String, StageCheckpoint>), BackupError> {
+    let tx = env.tx()?;
+    let mut canonical = tx.cursor_read::<tables::CanonicalHeaders>()?;
+    let (_, genesis_hash) =
+        canonical.first()?.ok_or(BackupError::ManifestMismatch("missing genesis"))?;
+    let tip = canonical.last()?.unwrap_or((0, genesis_hash));
+    let stage_checkpoints =
+        tx.cursor_read::<tables::SyncStage>()?.walk(None)?.collect::<Result<_, _>>()?;
+    Ok((genesis_hash, tip, stage_checkpoints))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_rw_db, transaction::DbTxMut};
+
+    #[test]
+    fn backup_and_restore() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        tx.put::<tables::CanonicalHeaders>(0, H256::from_low_u64_be(1)).unwrap();
+        tx.put::<tables::CanonicalHeaders>(5, H256::from_low_u64_be(2)).unwrap();
+        tx.put::<tables::SyncStage>("Headers".to_string(), StageCheckpoint::new(5)).unwrap();
+        tx.commit().unwrap();
+
+        let backup = tempfile::tempdir().unwrap();
+        let manifest = db.backup(backup.path()).unwrap();
+        assert_eq!(manifest.genesis_hash, H256::from_low_u64_be(1));
+        assert_eq!((manifest.tip_number, manifest.tip_hash), (5, H256::from_low_u64_be(2)));
+        assert_eq!(
+            manifest.stage_checkpoints,
+            BTreeMap::from([("Headers".to_string(), StageCheckpoint::new(5))])
+        );
+        assert_eq!(read_backup_manifest(backup.path()).unwrap(), manifest);
+
+        // A second backup into the same directory is rejected.
+        assert!(matches!(db.backup(backup.path()), Err(BackupError::DestinationExists(_))));
+
+        // The backup can't be restored for another chain.
+        let restored = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            restore_backup(backup.path(), restored.path(), H256::from_low_u64_be(3)),
+            Err(BackupError::GenesisMismatch { .. })
+        ));
+
+        assert_eq!(
+            restore_backup(backup.path(), restored.path(), H256::from_low_u64_be(1)).unwrap(),
+            manifest
+        );
+        let env = Env::<NoWriteMap>::open(restored.path(), EnvKind::RO, None).unwrap();
+        assert_eq!(
+            env.tx().unwrap().get::<tables::CanonicalHeaders>(5).unwrap(),
+            Some(H256::from_low_u64_be(2))
+        );
+    }
+
+    #[test]
+    fn restore_rejects_modified_manifest() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        tx.put::<tables::CanonicalHeaders>(0, H256::from_low_u64_be(1)).unwrap();
+        tx.commit().unwrap();
+
+        let backup = tempfile::tempdir().unwrap();
+        let mut manifest = db.backup(backup.path()).unwrap();
+        manifest.tip_number = 10;
+        fs::write(backup.path().join(MANIFEST_FILE_NAME), serde_json::to_vec(&manifest).unwrap())
+            .unwrap();
+
+        let restored = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            restore_backup(backup.path(), restored.path(), H256::from_low_u64_be(1)),
+            Err(BackupError::ManifestMismatch("chain tip"))
+        ));
+    }
+}
@@ -0,0 +1,412 @@
+//! Cursor over a table of the in-memory database.
+
+use super::{
+    tx::{TransactionKind, RW},
+    Entry, TableEntries, KEY_EXISTS, KEY_MISMATCH, NOT_FOUND,
+};
+use crate::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    table::{Compress, Decompress, DupSort, Encode, Table},
+    tables::utils::decoder,
+    DatabaseError,
+};
+use parking_lot::RwLock;
+use reth_interfaces::db::DatabaseWriteOperation;
+use std::{
+    borrow::Cow,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    sync::Arc,
+};
+
+/// Position of a [Cursor].
+#[derive(Debug)]
+enum Position {
+    /// The cursor was not positioned yet.
+    Unset,
+    /// The cursor is at the given entry.
+    ///
+    /// If the entry was deleted, the cursor behaves as if it was between its neighbours, so the
+    /// next entry is returned by [DbCursorRO::current] and [DbCursorRO::next].
+    At(Entry),
+    /// The cursor moved past the last entry.
+    Eof,
+}
+
+/// Cursor over a table of the in-memory database, with the same semantics as the MDBX cursor.
+#[derive(Debug)]
+pub struct Cursor<'tx, K: TransactionKind, T: Table> {
+    /// The tables of the transaction.
+    tables: &'tx RwLock<Vec<Arc<TableEntries>>>,
+    /// Index of the table in the transaction tables.
+    table: usize,
+    /// Whether the table is a `DupSort` table.
+    dupsort: bool,
+    /// Current position of the cursor.
+    position: Position,
+    _phantom: PhantomData<(K, T)>,
+}
+
+impl<'tx, K: TransactionKind, T: Table> Cursor<'tx, K, T> {
+    /// Creates a new cursor over the table at index `table`.
+    pub(crate) fn new(
+        tables: &'tx RwLock<Vec<Arc<TableEntries>>>,
+        table: usize,
+        dupsort: bool,
+    ) -> Self {
+        Self { tables, table, dupsort, position: Position::Unset, _phantom: PhantomData }
+    }
+
+    /// Runs `f` with the entries of the table.
+    fn read<R>(&self, f: impl FnOnce(&TableEntries) -> R) -> R {
+        f(&self.tables.read()[self.table])
+    }
+
+    /// Moves the cursor to the given entry and returns it decoded. If there is no entry, the
+    /// cursor is moved to `otherwise`.
+    fn move_to(&mut self, entry: Option<Entry>, otherwise: Option<Position>) -> PairResult<T> {
+        match entry {
+            Some(entry) => {
+                let decoded = decode::<T>(&entry);
+                self.position = Position::At(entry);
+                decoded.map(Some)
+            }
+            None => {
+                if let Some(position) = otherwise {
+                    self.position = position;
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns the entry the cursor points to.
+    fn current_entry(&self) -> Option<Entry> {
+        match &self.position {
+            Position::At(entry) => self.read(|entries| {
+                entries.range((Bound::Included(entry), Bound::Unbounded)).next().cloned()
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the first entry with a key greater than or equal to `key`.
+    fn seek_entry(&self, key: &[u8]) -> Option<Entry> {
+        self.read(|entries| entries.range(key_start(key)..).next().cloned())
+    }
+
+    /// Moves the cursor to the first entry with a key greater than or equal to `key`, returning
+    /// the encoded key and the entry.
+    fn seek_raw(&mut self, key: T::Key) -> (Vec<u8>, Option<Entry>) {
+        let key = key.encode().as_ref().to_vec();
+        let entry = self.seek_entry(&key);
+        self.position = match &entry {
+            Some(entry) => Position::At(entry.clone()),
+            None => Position::Eof,
+        };
+        (key, entry)
+    }
+}
+
+impl<'tx, K: TransactionKind, T: Table> DbCursorRO<'tx, T> for Cursor<'tx, K, T> {
+    fn first(&mut self) -> PairResult<T> {
+        let entry = self.read(|entries| entries.first().cloned());
+        self.move_to(entry, Some(Position::Unset))
+    }
+
+    fn seek_exact(&mut self, key: T::Key) -> PairResult<T> {
+        // Like MDBX, the cursor is positioned at the next greater key if the key does not exist.
+        let (key, entry) = self.seek_raw(key);
+        entry.filter(|(k, _)| *k == key).as_ref().map(decode::<T>).transpose()
+    }
+
+    fn seek(&mut self, key: T::Key) -> PairResult<T> {
+        let (_, entry) = self.seek_raw(key);
+        entry.as_ref().map(decode::<T>).transpose()
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        let entry = match &self.position {
+            Position::Unset => self.read(|entries| entries.first().cloned()),
+            Position::At(current) => self.read(|entries| {
+                entries.range((Bound::Excluded(current), Bound::Unbounded)).next().cloned()
+            }),
+            Position::Eof => None,
+        };
+        self.move_to(entry, Some(Position::Eof))
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        let entry = match &self.position {
+            Position::Unset | Position::Eof => self.read(|entries| entries.last().cloned()),
+            Position::At(current) => {
+                self.read(|entries| entries.range(..current).next_back().cloned())
+            }
+        };
+        self.move_to(entry, None)
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        let entry = self.read(|entries| entries.last().cloned());
+        self.move_to(entry, Some(Position::Unset))
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        self.current_entry().as_ref().map(decode::<T>).transpose()
+    }
+
+    fn walk<'cursor>(
+        &'cursor mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<Walker<'cursor, 'tx, T, Self>, DatabaseError>
+    where
+        Self: Sized,
+    {
+        let start = match start_key {
+            Some(key) => self.seek(key),
+            None => self.first(),
+        }
+        .transpose();
+
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range<'cursor>(
+        &'cursor mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'cursor, 'tx, T, Self>, DatabaseError>
+    where
+        Self: Sized,
+    {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back<'cursor>(
+        &'cursor mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'cursor, 'tx, T, Self>, DatabaseError>
+    where
+        Self: Sized,
+    {
+        let start = match start_key {
+            Some(key) => self.seek(key),
+            None => self.last(),
+        }
+        .transpose();
+
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<'tx, K: TransactionKind, T: DupSort> DbDupCursorRO<'tx, T> for Cursor<'tx, K, T> {
+    fn next_dup(&mut self) -> PairResult<T> {
+        let entry = match &self.position {
+            Position::Unset => self.read(|entries| entries.first().cloned()),
+            Position::At(current) => self.read(|entries| {
+                entries
+                    .range((Bound::Excluded(current), Bound::Unbounded))
+                    .next()
+                    .filter(|(key, _)| *key == current.0)
+                    .cloned()
+            }),
+            Position::Eof => None,
+        };
+        self.move_to(entry, None)
+    }
+
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        let entry = match &self.position {
+            Position::Unset => self.read(|entries| entries.first().cloned()),
+            Position::At((key, _)) => {
+                self.read(|entries| entries.range(key_start(&next_key(key))..).next().cloned())
+            }
+            Position::Eof => None,
+        };
+        self.move_to(entry, Some(Position::Eof))
+    }
+
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        Ok(self.next_dup()?.map(|(_, value)| value))
+    }
+
+    fn seek_by_key_subkey(&mut self, key: T::Key, subkey: T::SubKey) -> ValueOnlyResult<T> {
+        let key = key.encode().as_ref().to_vec();
+        let subkey = subkey.encode().as_ref().to_vec();
+        let entry = self.read(|entries| {
+            entries.range((key.clone(), subkey)..).next().filter(|(k, _)| *k == key).cloned()
+        });
+        Ok(self.move_to(entry, None)?.map(|(_, value)| value))
+    }
+
+    fn walk_dup<'cursor>(
+        &'cursor mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'cursor, 'tx, T, Self>, DatabaseError> {
+        let start = match (key, subkey) {
+            (Some(key), Some(subkey)) => {
+                self.seek_by_key_subkey(key.clone(), subkey)?.map(|value| Ok((key, value)))
+            }
+            (Some(key), None) => self.seek_exact(key).transpose(),
+            (None, Some(subkey)) => {
+                if let Some((key, _)) = self.first()? {
+                    self.seek_by_key_subkey(key.clone(), subkey)?.map(|value| Ok((key, value)))
+                } else {
+                    Some(Err(DatabaseError::Read(NOT_FOUND)))
+                }
+            }
+            (None, None) => self.first().transpose(),
+        };
+
+        Ok(DupWalker::<'cursor, 'tx, T, Self> { cursor: self, start, _tx_phantom: PhantomData {} })
+    }
+}
+
+impl<'tx, T: Table> Cursor<'tx, RW, T> {
+    /// Runs `f` with mutable access to the entries of the table.
+    fn write<R>(&self, f: impl FnOnce(&mut TableEntries) -> R) -> R {
+        f(Arc::make_mut(&mut self.tables.write()[self.table]))
+    }
+
+    /// Returns a write error for the given key.
+    fn write_error(code: i32, operation: DatabaseWriteOperation, key: &[u8]) -> DatabaseError {
+        DatabaseError::Write { code, operation, table_name: T::NAME, key: Box::from(key) }
+    }
+}
+
+impl<'tx, T: Table> DbCursorRW<'tx, T> for Cursor<'tx, RW, T> {
+    /// For a `DupSort` table, `upsert` adds the value to the duplicates of the key, like the MDBX
+    /// cursor does.
+    fn upsert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let entry = (key.encode().as_ref().to_vec(), value.compress().as_ref().to_vec());
+        let dupsort = self.dupsort;
+        self.write(|entries| upsert(entries, dupsort, entry.clone()));
+        self.position = Position::At(entry);
+        Ok(())
+    }
+
+    fn insert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode().as_ref().to_vec();
+        if let Some(existing) = self.seek_entry(&key).filter(|(k, _)| *k == key) {
+            self.position = Position::At(existing);
+            return Err(Self::write_error(KEY_EXISTS, DatabaseWriteOperation::CursorInsert, &key))
+        }
+
+        let entry = (key, value.compress().as_ref().to_vec());
+        self.write(|entries| entries.insert(entry.clone()));
+        self.position = Position::At(entry);
+        Ok(())
+    }
+
+    /// Appends the entry to the end of the table. For a `DupSort` table, the key may be equal to
+    /// the last key of the table.
+    fn append(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode().as_ref().to_vec();
+        if let Some(last) = self.read(|entries| entries.last().cloned()) {
+            if last.0 > key || (!self.dupsort && last.0 == key) {
+                self.position = Position::At(last);
+                return Err(Self::write_error(
+                    KEY_MISMATCH,
+                    DatabaseWriteOperation::CursorAppend,
+                    &key,
+                ))
+            }
+        }
+
+        let entry = (key, value.compress().as_ref().to_vec());
+        self.write(|entries| entries.insert(entry.clone()));
+        self.position = Position::At(entry);
+        Ok(())
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        if let Some(current) = self.current_entry() {
+            self.write(|entries| entries.remove(&current));
+            self.position = Position::At(current);
+        }
+        Ok(())
+    }
+}
+
+impl<'tx, T: DupSort> DbDupCursorRW<'tx, T> for Cursor<'tx, RW, T> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        if let Some(current) = self.current_entry() {
+            self.write(|entries| remove_key(entries, &current.0));
+            self.position = Position::At(current);
+        }
+        Ok(())
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let entry = (key.encode().as_ref().to_vec(), value.compress().as_ref().to_vec());
+        let last_dup = self.read(|entries| {
+            entries.range(key_start(&entry.0)..key_start(&next_key(&entry.0))).next_back().cloned()
+        });
+        if let Some(last_dup) = last_dup.filter(|last_dup| *last_dup >= entry) {
+            self.position = Position::At(last_dup);
+            return Err(Self::write_error(
+                KEY_MISMATCH,
+                DatabaseWriteOperation::CursorAppendDup,
+                &entry.0,
+            ))
+        }
+
+        self.write(|entries| entries.insert(entry.clone()));
+        self.position = Position::At(entry);
+        Ok(())
+    }
+}
+
+/// Decodes a raw entry of table `T`.
+fn decode<T: Table>((key, value): &Entry) -> Result<(T::Key, T::Value), DatabaseError> {
+    decoder::<T>((Cow::Borrowed(key), Cow::Borrowed(value)))
+}
+
+/// Decodes the value of a raw entry of table `T`.
+pub(crate) fn decode_value<T: Table>((_, value): &Entry) -> Result<T::Value, DatabaseError> {
+    T::Value::decompress(value)
+}
+
+/// Returns the smallest possible entry with the given key.
+pub(crate) fn key_start(key: &[u8]) -> Entry {
+    (key.to_vec(), Vec::new())
+}
+
+/// Returns the smallest key that is greater than the given key.
+fn next_key(key: &[u8]) -> Vec<u8> {
+    let mut next = Vec::with_capacity(key.len() + 1);
+    next.extend_from_slice(key);
+    next.push(0);
+    next
+}
+
+/// Inserts the entry. Other values of the key are replaced, unless the table is a `DupSort` table.
+pub(crate) fn upsert(entries: &mut TableEntries, dupsort: bool, entry: Entry) {
+    if !dupsort {
+        remove_key(entries, &entry.0);
+    }
+    entries.insert(entry);
+}
+
+/// Removes all entries with the given key, returning whether there were any.
+pub(crate) fn remove_key(entries: &mut TableEntries, key: &[u8]) -> bool {
+    let removed =
+        entries.range(key_start(key)..key_start(&next_key(key))).cloned().collect::<Vec<_>>();
+    for entry in &removed {
+        entries.remove(entry);
+    }
+    !removed.is_empty()
+}
@@ -0,0 +1,163 @@
+//! In-memory database backend.
+//!
+//! Tables are kept as ordered sets of encoded `(key, value)` pairs, so keys and duplicate values
+//! are ordered the same way as in MDBX, and cursors follow the MDBX cursor semantics. This makes
+//! it a drop-in replacement for tests that don't need the database on disk.
+
+use crate::{
+    database::{Database, DatabaseGAT},
+    tables::NUM_TABLES,
+    DatabaseError,
+};
+use parking_lot::{Condvar, Mutex, RwLock};
+use std::{collections::BTreeSet, fmt, sync::Arc};
+use tx::{Tx, RO, RW};
+
+pub mod cursor;
+pub mod tx;
+
+/// Encoded `(key, value)` pair.
+pub(crate) type Entry = (Vec<u8>, Vec<u8>);
+
+/// Entries of a table, ordered by key and then by value.
+pub(crate) type TableEntries = BTreeSet<Entry>;
+
+/// Error code for inserting a key that already exists, same as `MDBX_KEYEXIST`.
+const KEY_EXISTS: i32 = -30799;
+/// Error code for a missing entry, same as `MDBX_NOTFOUND`.
+const NOT_FOUND: i32 = -30798;
+/// Error code for appending an entry out of order, same as `MDBX_EKEYMISMATCH`.
+const KEY_MISMATCH: i32 = -30418;
+
+/// Database that keeps all tables in memory.
+///
+/// Like MDBX, it allows any number of concurrent readers and a single writer. Readers see the
+/// tables as of the last commit when they were opened. A write transaction copies every table it
+/// modifies on first write, so it's meant for tests and benchmarks rather than full nodes.
+pub struct MemoryDatabase {
+    /// Committed tables, indexed by [Tables](crate::Tables).
+    tables: RwLock<Vec<Arc<TableEntries>>>,
+    /// Whether a write transaction is open.
+    writer: Mutex<bool>,
+    /// Notified when the write transaction is closed.
+    writer_released: Condvar,
+}
+
+// === impl MemoryDatabase ===
+
+impl MemoryDatabase {
+    /// Creates a new database with all tables empty.
+    pub fn new() -> Self {
+        Self {
+            tables: RwLock::new((0..NUM_TABLES).map(|_| Arc::default()).collect()),
+            writer: Mutex::new(false),
+            writer_released: Condvar::new(),
+        }
+    }
+
+    /// Blocks until no other write transaction is open.
+    fn acquire_writer(&self) {
+        let mut writer = self.writer.lock();
+        while *writer {
+            self.writer_released.wait(&mut writer);
+        }
+        *writer = true;
+    }
+
+    /// Called when the write transaction is closed.
+    fn release_writer(&self) {
+        *self.writer.lock() = false;
+        self.writer_released.notify_one();
+    }
+
+    /// Replaces the committed tables.
+    fn replace_tables(&self, tables: Vec<Arc<TableEntries>>) {
+        *self.tables.write() = tables;
+    }
+}
+
+impl Default for MemoryDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for MemoryDatabase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryDatabase").finish_non_exhaustive()
+    }
+}
+
+impl<'a> DatabaseGAT<'a> for MemoryDatabase {
+    type TX = Tx<'a, RO>;
+    type TXMut = Tx<'a, RW>;
+}
+
+impl Database for MemoryDatabase {
+    fn tx(&self) -> Result<<Self as DatabaseGAT<'_>>::TX, DatabaseError> {
+        Ok(Tx::new(self, self.tables.read().clone()))
+    }
+
+    fn tx_mut(&self) -> Result<<Self as DatabaseGAT<'_>>::TXMut, DatabaseError> {
+        self.acquire_writer();
+        Ok(Tx::new(self, self.tables.read().clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tables::CanonicalHeaders,
+        test_utils::create_test_memory_db,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::H256;
+
+    #[test]
+    fn db_transaction_isolation() {
+        let db = create_test_memory_db();
+        db.update(|tx| tx.put::<CanonicalHeaders>(0, H256::zero()).unwrap()).unwrap();
+
+        let reader = db.tx().unwrap();
+        let tx = db.tx_mut().unwrap();
+        tx.put::<CanonicalHeaders>(1, H256::zero()).unwrap();
+        tx.delete::<CanonicalHeaders>(0, None).unwrap();
+        assert_eq!(tx.get::<CanonicalHeaders>(0), Ok(None));
+        tx.commit().unwrap();
+
+        // Readers keep the snapshot they were opened with
+        assert_eq!(reader.get::<CanonicalHeaders>(0), Ok(Some(H256::zero())));
+        assert_eq!(reader.get::<CanonicalHeaders>(1), Ok(None));
+        assert_eq!(db.tx().unwrap().get::<CanonicalHeaders>(1), Ok(Some(H256::zero())));
+
+        // Uncommitted changes are discarded
+        let tx = db.tx_mut().unwrap();
+        tx.clear::<CanonicalHeaders>().unwrap();
+        drop(tx);
+        assert_eq!(db.tx().unwrap().entries::<CanonicalHeaders>(), Ok(1));
+    }
+
+    #[test]
+    fn db_single_writer() {
+        let db = create_test_memory_db();
+        let tx = db.tx_mut().unwrap();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let handle = {
+            let db = Arc::clone(&db);
+            std::thread::spawn(move || {
+                let tx = db.tx_mut().unwrap();
+                sender.send(tx.get::<CanonicalHeaders>(0).unwrap()).unwrap();
+            })
+        };
+
+        tx.put::<CanonicalHeaders>(0, H256::zero()).unwrap();
+        assert!(receiver.try_recv().is_err());
+        tx.commit().unwrap();
+
+        // The second writer only starts after the first one committed
+        assert_eq!(receiver.recv().unwrap(), Some(H256::zero()));
+        handle.join().unwrap();
+    }
+}
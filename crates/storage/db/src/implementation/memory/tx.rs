@@ -0,0 +1,169 @@
+//! Transactions of the in-memory database.
+
+use super::{
+    cursor::{decode_value, key_start, remove_key, upsert, Cursor},
+    MemoryDatabase, TableEntries,
+};
+use crate::{
+    table::{Compress, DupSort, Encode, Table, TableImporter},
+    tables::{TableType, Tables},
+    transaction::{DbTx, DbTxGAT, DbTxMut, DbTxMutGAT},
+    DatabaseError,
+};
+use parking_lot::RwLock;
+use std::{str::FromStr, sync::Arc};
+
+/// Kind of a [Tx], either [RO] or [RW].
+pub trait TransactionKind: Send + Sync + std::fmt::Debug + 'static {
+    /// Whether the transaction can only read.
+    const IS_READ_ONLY: bool;
+}
+
+/// Marker for read-only transactions.
+#[derive(Debug)]
+pub struct RO;
+
+/// Marker for read-write transactions.
+#[derive(Debug)]
+pub struct RW;
+
+impl TransactionKind for RO {
+    const IS_READ_ONLY: bool = true;
+}
+
+impl TransactionKind for RW {
+    const IS_READ_ONLY: bool = false;
+}
+
+/// Transaction of the in-memory database.
+///
+/// The transaction works on a snapshot of the tables taken when it was opened. Tables are copied
+/// on the first write, and replace the tables of the database on commit.
+#[derive(Debug)]
+pub struct Tx<'a, K: TransactionKind> {
+    /// The database the transaction belongs to.
+    db: &'a MemoryDatabase,
+    /// The tables as seen by this transaction.
+    tables: RwLock<Vec<Arc<TableEntries>>>,
+    _kind: std::marker::PhantomData<K>,
+}
+
+impl<'a, K: TransactionKind> Tx<'a, K> {
+    /// Creates a new transaction on the given snapshot of the tables.
+    pub(crate) fn new(db: &'a MemoryDatabase, tables: Vec<Arc<TableEntries>>) -> Self {
+        Self { db, tables: RwLock::new(tables), _kind: std::marker::PhantomData }
+    }
+
+    /// Returns the index of the table and whether it's a `DupSort` table.
+    fn table<T: Table>() -> (usize, bool) {
+        let table = Tables::from_str(T::NAME).expect("Requested table should be part of `Tables`.");
+        (table as usize, matches!(table.table_type(), TableType::DupSort))
+    }
+
+    /// Create db Cursor
+    pub fn new_cursor<T: Table>(&self) -> Cursor<'_, K, T> {
+        let (table, dupsort) = Self::table::<T>();
+        Cursor::new(&self.tables, table, dupsort)
+    }
+}
+
+impl<K: TransactionKind> Drop for Tx<'_, K> {
+    fn drop(&mut self) {
+        if !K::IS_READ_ONLY {
+            self.db.release_writer();
+        }
+    }
+}
+
+impl<'a, K: TransactionKind> DbTxGAT<'a> for Tx<'_, K> {
+    type Cursor<T: Table> = Cursor<'a, K, T>;
+    type DupCursor<T: DupSort> = Cursor<'a, K, T>;
+}
+
+impl<'a, K: TransactionKind> DbTxMutGAT<'a> for Tx<'_, K> {
+    type CursorMut<T: Table> = Cursor<'a, RW, T>;
+    type DupCursorMut<T: DupSort> = Cursor<'a, RW, T>;
+}
+
+impl<'a> TableImporter<'a> for Tx<'_, RW> {}
+
+impl<'tx, K: TransactionKind> DbTx<'tx> for Tx<'tx, K> {
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<<T as Table>::Value>, DatabaseError> {
+        let key = key.encode();
+        let (table, _) = Self::table::<T>();
+        self.tables.read()[table]
+            .range(key_start(key.as_ref())..)
+            .next()
+            .filter(|(k, _)| k.as_slice() == key.as_ref())
+            .map(decode_value::<T>)
+            .transpose()
+    }
+
+    fn commit(mut self) -> Result<bool, DatabaseError> {
+        if !K::IS_READ_ONLY {
+            self.db.replace_tables(std::mem::take(self.tables.get_mut()));
+        }
+        Ok(false)
+    }
+
+    fn drop(self) {
+        drop(self)
+    }
+
+    fn cursor_read<T: Table>(&self) -> Result<<Self as DbTxGAT<'_>>::Cursor<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+
+    fn cursor_dup_read<T: DupSort>(
+        &self,
+    ) -> Result<<Self as DbTxGAT<'_>>::DupCursor<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        let (table, _) = Self::table::<T>();
+        Ok(self.tables.read()[table].len())
+    }
+}
+
+impl DbTxMut<'_> for Tx<'_, RW> {
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let (table, dupsort) = Self::table::<T>();
+        let entry = (key.encode().as_ref().to_vec(), value.compress().as_ref().to_vec());
+        upsert(Arc::make_mut(&mut self.tables.write()[table]), dupsort, entry);
+        Ok(())
+    }
+
+    fn delete<T: Table>(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        let (table, _) = Self::table::<T>();
+        let key = key.encode().as_ref().to_vec();
+        let mut tables = self.tables.write();
+        let entries = Arc::make_mut(&mut tables[table]);
+        Ok(match value {
+            Some(value) => entries.remove(&(key, value.compress().as_ref().to_vec())),
+            None => remove_key(entries, &key),
+        })
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        let (table, _) = Self::table::<T>();
+        self.tables.write()[table] = Arc::default();
+        Ok(())
+    }
+
+    fn cursor_write<T: Table>(
+        &self,
+    ) -> Result<<Self as DbTxMutGAT<'_>>::CursorMut<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+
+    fn cursor_dup_write<T: DupSort>(
+        &self,
+    ) -> Result<<Self as DbTxMutGAT<'_>>::DupCursorMut<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+}
@@ -72,6 +72,7 @@
 pub mod abstraction;
 
 mod implementation;
+pub mod migration;
 pub mod tables;
 mod utils;
 pub mod version;
@@ -83,6 +84,11 @@ pub mod mdbx {
     pub use reth_libmdbx::*;
 }
 
+/// In-memory database, mostly useful for tests.
+pub mod memory {
+    pub use crate::implementation::memory::*;
+}
+
 pub use abstraction::*;
 pub use reth_interfaces::db::{DatabaseError, DatabaseWriteOperation};
 pub use tables::*;
@@ -187,6 +193,11 @@ pub mod test_utils {
         Arc::new(init_db(path.as_ref(), None).expect(ERROR_DB_CREATION))
     }
 
+    /// Create in-memory database for testing
+    pub fn create_test_memory_db() -> Arc<memory::MemoryDatabase> {
+        Arc::new(memory::MemoryDatabase::new())
+    }
+
     /// Create read only database for testing
     pub fn create_test_ro_db() -> Arc<DatabaseEnvRO> {
         let path = tempfile::TempDir::new().expect(ERROR_TEMPDIR).into_path();
@@ -0,0 +1,441 @@
+//! Migrations that upgrade the database from one version to the next.
+//!
+//! Every change to the encoding of a table bumps [DB_VERSION] and adds a [Migration] from the
+//! previous version to [migrations]. Databases created with an older version can then be upgraded
+//! with `reth db migrate` instead of being resynced.
+
+use crate::{
+    cursor::DbCursorRO,
+    database::Database,
+    table::{Decode, Table},
+    tables::{self, RawKey, RawTable, RawValue},
+    transaction::{DbTx, DbTxMut},
+    version::{get_db_version, write_db_version_file, DatabaseVersionError, DB_VERSION},
+    DatabaseError,
+};
+use reth_codecs::Compact;
+use std::{fmt, io, marker::PhantomData, path::Path};
+
+/// Default number of entries migrated per transaction.
+pub const MIGRATION_BATCH_SIZE: usize = 50_000;
+
+/// The key under which the version of a completed migration is stored in
+/// [tables::SyncStageProgress], until the version file is updated.
+const MIGRATED_VERSION_KEY: &str = "MigratedDbVersion";
+
+/// Records in `tx` that the database was migrated to `version`.
+///
+/// Must be written in the last transaction of a [Migration], so the migration isn't applied twice
+/// if the process stops before the version file is updated.
+pub fn write_migrated_version<TX: DbTxMut>(tx: &TX, version: u64) -> Result<(), DatabaseError> {
+    tx.put::<tables::SyncStageProgress>(
+        MIGRATED_VERSION_KEY.to_string(),
+        version.to_be_bytes().to_vec(),
+    )
+}
+
+/// Returns the version recorded by [write_migrated_version], if any.
+fn migrated_version<DB: Database>(db: &DB) -> Result<Option<u64>, DatabaseError> {
+    let version =
+        db.view(|tx| tx.get::<tables::SyncStageProgress>(MIGRATED_VERSION_KEY.into()))??;
+    Ok(version.and_then(|bytes| Some(u64::from_be_bytes(bytes.try_into().ok()?))))
+}
+
+/// Errors that can occur while migrating the database.
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    /// There is no migration from the given version.
+    #[error("No migration from database version {version} available.")]
+    MissingMigration {
+        /// The version without a migration.
+        version: u64,
+    },
+    /// The database is newer than the versions known to this build.
+    #[error("Database version {version} is newer than the latest known version {target}.")]
+    UnsupportedVersion {
+        /// The version of the database.
+        version: u64,
+        /// The version the database was supposed to be migrated to.
+        target: u64,
+    },
+    /// The version file could not be read.
+    #[error(transparent)]
+    Version(#[from] DatabaseVersionError),
+    /// Low-level database error.
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+    /// The version file could not be written.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Progress of a running [Migration], reported after every committed batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationProgress {
+    /// The version the migration upgrades from.
+    pub from_version: u64,
+    /// The table that is migrated.
+    pub table: &'static str,
+    /// Number of entries of the table migrated so far in this run.
+    pub migrated: usize,
+    /// Whether all entries of the table are migrated.
+    pub done: bool,
+}
+
+/// Upgrades the database from [Migration::from_version] to the next version.
+pub trait Migration<DB: Database>: Send + Sync {
+    /// The version this migration upgrades from.
+    fn from_version(&self) -> u64;
+
+    /// Short description of the changes.
+    fn description(&self) -> &'static str;
+
+    /// Runs the migration.
+    ///
+    /// Implementations commit in batches, and resume where they stopped if they are interrupted.
+    /// The last transaction must record the new version with [write_migrated_version].
+    fn migrate(
+        &self,
+        db: &DB,
+        on_progress: &mut dyn FnMut(MigrationProgress),
+    ) -> Result<(), DatabaseError>;
+}
+
+/// A [Migration] that re-encodes all values of a table, decoding them with the [Compact] codec of
+/// the previous value type and encoding them with the codec of the current one.
+///
+/// Keys are left unchanged, so this only supports tables that are not `DupSort`.
+pub struct ReencodeTable<T: Table, Old> {
+    from_version: u64,
+    description: &'static str,
+    convert: fn(Old) -> T::Value,
+    batch_size: usize,
+    _phantom: PhantomData<T>,
+}
+
+// === impl ReencodeTable ===
+
+impl<T: Table, Old> ReencodeTable<T, Old> {
+    /// Creates a new migration of table `T` from `from_version`, that converts every old value
+    /// with `convert`.
+    pub fn new(from_version: u64, description: &'static str, convert: fn(Old) -> T::Value) -> Self {
+        Self {
+            from_version,
+            description,
+            convert,
+            batch_size: MIGRATION_BATCH_SIZE,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Sets the number of entries migrated per transaction.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// The key under which the last migrated key is stored in [tables::SyncStageProgress].
+    ///
+    /// It's written in the same transaction as the entries, so an interrupted migration never
+    /// decodes an entry that is already in the new format.
+    fn progress_key(&self) -> String {
+        format!("Migration{}{}", self.from_version, T::NAME)
+    }
+}
+
+impl<T: Table, Old> fmt::Debug for ReencodeTable<T, Old> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReencodeTable")
+            .field("table", &T::NAME)
+            .field("from_version", &self.from_version)
+            .field("description", &self.description)
+            .field("batch_size", &self.batch_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<DB, T, Old> Migration<DB> for ReencodeTable<T, Old>
+where
+    DB: Database,
+    T: Table,
+    Old: Compact + Send + Sync,
+{
+    fn from_version(&self) -> u64 {
+        self.from_version
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn migrate(
+        &self,
+        db: &DB,
+        on_progress: &mut dyn FnMut(MigrationProgress),
+    ) -> Result<(), DatabaseError> {
+        let progress_key = self.progress_key();
+        let mut migrated = 0;
+        loop {
+            let tx = db.tx_mut()?;
+            let mut cursor = tx.cursor_read::<RawTable<T>>()?;
+            let mut entry = match tx.get::<tables::SyncStageProgress>(progress_key.clone())? {
+                Some(last) => {
+                    cursor.seek_exact(RawKey::decode(last)?)?;
+                    cursor.next()?
+                }
+                None => cursor.first()?,
+            };
+
+            let mut batch = Vec::with_capacity(self.batch_size);
+            while let Some((key, value)) = entry {
+                if batch.len() == self.batch_size {
+                    entry = Some((key, value));
+                    break
+                }
+                let raw = value.raw_value();
+                let (old, _) = Old::from_compact(raw, raw.len());
+                batch.push((key, RawValue::new((self.convert)(old))));
+                entry = cursor.next()?;
+            }
+            drop(cursor);
+
+            let done = entry.is_none();
+            if done {
+                tx.delete::<tables::SyncStageProgress>(progress_key.clone(), None)?;
+                write_migrated_version(&tx, self.from_version + 1)?;
+            } else if let Some((last, _)) = batch.last() {
+                tx.put::<tables::SyncStageProgress>(progress_key.clone(), last.raw_key().clone())?;
+            }
+            migrated += batch.len();
+            for (key, value) in batch {
+                tx.put::<RawTable<T>>(key, value)?;
+            }
+            tx.commit()?;
+
+            on_progress(MigrationProgress {
+                from_version: self.from_version,
+                table: T::NAME,
+                migrated,
+                done,
+            });
+            if done {
+                return Ok(())
+            }
+        }
+    }
+}
+
+/// Returns all known migrations, ordered by the version they upgrade from.
+pub fn migrations<DB: Database>() -> Vec<Box<dyn Migration<DB>>> {
+    Vec::new()
+}
+
+/// Applies the migrations needed to upgrade the database at `db_path` to the `target` version.
+///
+/// The version file is updated after every applied migration. A migration that completed before
+/// the version file was updated is not applied again. Returns the number of applied migrations.
+pub fn migrate<DB: Database>(
+    db: &DB,
+    db_path: &Path,
+    migrations: &[Box<dyn Migration<DB>>],
+    target: u64,
+    mut on_progress: impl FnMut(MigrationProgress),
+) -> Result<usize, MigrationError> {
+    let mut version = get_db_version(db_path)?;
+    if version > target {
+        return Err(MigrationError::UnsupportedVersion { version, target })
+    }
+
+    let mut applied = 0;
+    while version < target {
+        // the entries must not be decoded again if they are already in the new format
+        if migrated_version(db)? != Some(version + 1) {
+            let migration = migrations
+                .iter()
+                .find(|migration| migration.from_version() == version)
+                .ok_or(MigrationError::MissingMigration { version })?;
+            migration.migrate(db, &mut on_progress)?;
+            applied += 1;
+        }
+        version += 1;
+        write_db_version_file(db_path, version)?;
+
+        let tx = db.tx_mut()?;
+        tx.delete::<tables::SyncStageProgress>(MIGRATED_VERSION_KEY.to_string(), None)?;
+        tx.commit()?;
+    }
+
+    Ok(applied)
+}
+
+/// Applies all known [migrations] to upgrade the database at `db_path` to [DB_VERSION].
+pub fn migrate_to_latest<DB: Database>(
+    db: &DB,
+    db_path: &Path,
+    on_progress: impl FnMut(MigrationProgress),
+) -> Result<usize, MigrationError> {
+    migrate(db, db_path, &migrations(), DB_VERSION, on_progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        table::Decompress, tables::PlainAccountState, test_utils::create_test_rw_db_with_path,
+        DatabaseEnv,
+    };
+    use reth_codecs::main_codec;
+    use reth_primitives::{Account, Address, U256};
+    use std::sync::Arc;
+
+    /// Account encoding before the bytecode hash was added.
+    #[main_codec]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    struct AccountV0 {
+        nonce: u64,
+        balance: U256,
+    }
+
+    fn account_v1(old: AccountV0) -> Account {
+        Account { nonce: old.nonce, balance: old.balance, bytecode_hash: None }
+    }
+
+    fn reencode_accounts() -> Box<dyn Migration<Arc<DatabaseEnv>>> {
+        Box::new(
+            ReencodeTable::<PlainAccountState, _>::new(
+                0,
+                "Add bytecode hash to accounts",
+                account_v1,
+            )
+            .with_batch_size(3),
+        )
+    }
+
+    /// Writes `count` accounts in the [AccountV0] format.
+    fn write_old_accounts<DB: Database>(db: &DB, count: u64) {
+        let tx = db.tx_mut().unwrap();
+        for i in 0..count {
+            let mut buf = Vec::new();
+            AccountV0 { nonce: i, balance: U256::from(i * 10) }.to_compact(&mut buf);
+            tx.put::<RawTable<PlainAccountState>>(
+                RawKey::new(Address::from_low_u64_be(i)),
+                RawValue::decompress(buf).unwrap(),
+            )
+            .unwrap();
+        }
+        tx.commit().unwrap();
+    }
+
+    #[test]
+    fn reencode_table_in_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = create_test_rw_db_with_path(dir.path());
+        write_db_version_file(dir.path(), 0).unwrap();
+        write_old_accounts(&db, 10);
+
+        let mut reports = Vec::new();
+        let applied =
+            migrate(&db, dir.path(), &[reencode_accounts()], 1, |progress| reports.push(progress))
+                .unwrap();
+        assert_eq!(applied, 1);
+        assert_eq!(get_db_version(dir.path()).unwrap(), 1);
+        assert_eq!(
+            reports.iter().map(|progress| progress.migrated).collect::<Vec<_>>(),
+            vec![3, 6, 9, 10]
+        );
+        assert!(reports.last().unwrap().done);
+
+        let tx = db.tx().unwrap();
+        for i in 0..10 {
+            assert_eq!(
+                tx.get::<PlainAccountState>(Address::from_low_u64_be(i)).unwrap(),
+                Some(Account { nonce: i, balance: U256::from(i * 10), bytecode_hash: None })
+            );
+        }
+        assert_eq!(
+            tx.get::<tables::SyncStageProgress>("Migration0PlainAccountState".into()).unwrap(),
+            None
+        );
+        assert_eq!(tx.get::<tables::SyncStageProgress>(MIGRATED_VERSION_KEY.into()).unwrap(), None);
+    }
+
+    #[test]
+    fn skip_migration_completed_before_version_update() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = create_test_rw_db_with_path(dir.path());
+        write_db_version_file(dir.path(), 0).unwrap();
+        write_old_accounts(&db, 5);
+
+        // Simulate a run that committed the migration and stopped before the version file was
+        // updated.
+        reencode_accounts().migrate(&db, &mut |_| {}).unwrap();
+        assert_eq!(migrated_version(&db).unwrap(), Some(1));
+
+        let applied = migrate(&db, dir.path(), &[reencode_accounts()], 1, |_| {}).unwrap();
+        assert_eq!(applied, 0);
+        assert_eq!(get_db_version(dir.path()).unwrap(), 1);
+        assert_eq!(migrated_version(&db).unwrap(), None);
+
+        let tx = db.tx().unwrap();
+        for i in 0..5 {
+            assert_eq!(
+                tx.get::<PlainAccountState>(Address::from_low_u64_be(i)).unwrap(),
+                Some(Account { nonce: i, balance: U256::from(i * 10), bytecode_hash: None })
+            );
+        }
+    }
+
+    #[test]
+    fn resume_interrupted_migration() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = create_test_rw_db_with_path(dir.path());
+        write_old_accounts(&db, 5);
+
+        // Simulate a run that committed the first two entries and was interrupted.
+        let tx = db.tx_mut().unwrap();
+        for i in 0..2 {
+            let old =
+                tx.get::<RawTable<PlainAccountState>>(RawKey::new(Address::from_low_u64_be(i)));
+            let raw = old.unwrap().unwrap();
+            let (old, _) = AccountV0::from_compact(raw.raw_value(), raw.raw_value().len());
+            tx.put::<PlainAccountState>(Address::from_low_u64_be(i), account_v1(old)).unwrap();
+        }
+        tx.put::<tables::SyncStageProgress>(
+            "Migration0PlainAccountState".into(),
+            RawKey::new(Address::from_low_u64_be(1)).raw_key().clone(),
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        reencode_accounts().migrate(&db, &mut |_| {}).unwrap();
+
+        let tx = db.tx().unwrap();
+        for i in 0..5 {
+            assert_eq!(
+                tx.get::<PlainAccountState>(Address::from_low_u64_be(i)).unwrap().unwrap().nonce,
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn migrate_rejects_unknown_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = create_test_rw_db_with_path(dir.path());
+
+        write_db_version_file(dir.path(), 0).unwrap();
+        assert!(matches!(
+            migrate(&db, dir.path(), &[], 1, |_| {}),
+            Err(MigrationError::MissingMigration { version: 0 })
+        ));
+
+        write_db_version_file(dir.path(), 2).unwrap();
+        assert!(matches!(
+            migrate(&db, dir.path(), &[reencode_accounts()], 1, |_| {}),
+            Err(MigrationError::UnsupportedVersion { version: 2, target: 1 })
+        ));
+
+        write_db_version_file(dir.path(), 1).unwrap();
+        assert_eq!(migrate(&db, dir.path(), &[], 1, |_| {}).unwrap(), 0);
+    }
+}
@@ -21,7 +21,8 @@ pub enum DatabaseVersionError {
     MalformedFile,
     #[error(
     "Breaking database change detected. \
-            Your database version (v{version}) is incompatible with the latest database version (v{}).",
+            Your database version (v{version}) is incompatible with the latest database version (v{}). \
+            Older databases can be upgraded with `reth db migrate`.",
         DB_VERSION.to_string()
     )]
     VersionMismatch { version: u64 },
@@ -62,7 +63,14 @@ pub fn get_db_version<P: AsRef<Path>>(db_path: P) -> Result<u64, DatabaseVersion
 /// This function will create a file if it does not exist,
 /// and will entirely replace its contents if it does.
 pub fn create_db_version_file<P: AsRef<Path>>(db_path: P) -> io::Result<()> {
-    fs::write(db_version_file_path(db_path), DB_VERSION.to_string())
+    write_db_version_file(db_path, DB_VERSION)
+}
+
+/// Writes the given version to the database version file with [DB_VERSION_FILE_NAME] name.
+///
+/// Used to record the progress of migrations, see [crate::migration].
+pub fn write_db_version_file<P: AsRef<Path>>(db_path: P, version: u64) -> io::Result<()> {
+    fs::write(db_version_file_path(db_path), version.to_string())
 }
 
 /// Returns a database version file path.
@@ -209,6 +209,34 @@ where
 
         Ok(freelist)
     }
+
+    /// Copies the environment to the data file at the given path, which must not exist yet.
+    ///
+    /// The copy is made under a read transaction, so it is consistent even while other
+    /// transactions write to the environment. If `compact` is set, free pages are omitted and the
+    /// remaining pages are renumbered sequentially, so the copy only contains the pages in use.
+    ///
+    /// The path may not contain the null character.
+    pub fn copy(&self, dest: &Path, compact: bool) -> Result<()> {
+        let dest = CString::new(path_to_bytes(dest)).map_err(|_| Error::Invalid)?;
+        let flags = if compact { ffi::MDBX_CP_COMPACT } else { ffi::MDBX_CP_DEFAULTS };
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env(), dest.as_ptr(), flags) })?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_ref().as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(windows)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+    // really help.
+    path.as_ref().to_string_lossy().to_string().into_bytes()
 }
 
 /// Environment statistics.
@@ -466,20 +494,6 @@ where
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
                 let path = match CString::new(path_to_bytes(path)) {
                     Ok(path) => path,
                     Err(_) => return Err(Error::Invalid),
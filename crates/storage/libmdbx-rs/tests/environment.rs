@@ -156,3 +156,34 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_compact() {
+    let dir = tempdir().unwrap();
+    let env = Environment::new().open(dir.path()).unwrap();
+
+    // Write a few small values and delete half of them.
+    for i in 0..64 {
+        let mut value = [0u8; 8];
+        LittleEndian::write_u64(&mut value, i);
+        let tx = env.begin_rw_txn().expect("begin_rw_txn");
+        tx.put(tx.open_db(None).unwrap().dbi(), value, value, WriteFlags::default())
+            .expect("tx.put");
+        tx.commit().expect("tx.commit");
+    }
+    for i in 0..32 {
+        let mut value = [0u8; 8];
+        LittleEndian::write_u64(&mut value, i);
+        let tx = env.begin_rw_txn().expect("begin_rw_txn");
+        tx.del(tx.open_db(None).unwrap().dbi(), value, None).expect("tx.del");
+        tx.commit().expect("tx.commit");
+    }
+
+    let copy_dir = tempdir().unwrap();
+    env.copy(&copy_dir.path().join("mdbx.dat"), true).unwrap();
+
+    // The compacted copy holds the same entries without free pages.
+    let copy = Environment::new().open(copy_dir.path()).unwrap();
+    assert_eq!(copy.stat().unwrap().entries(), 32);
+    assert_eq!(copy.freelist().unwrap(), 0);
+}
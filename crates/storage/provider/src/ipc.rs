@@ -0,0 +1,416 @@
+//! Cross-process [CanonStateNotification]s over a local unix socket.
+//!
+//! The [CanonStateIpcServer] publishes the canonical state notifications of a node, the
+//! [CanonStateIpcClient] connects to it and implements [CanonStateSubscriptions], so that
+//! components that run in a separate process on top of a read-only database, like a sidecar RPC
+//! server or an indexer, receive the same commit and reorg events as in-process components.
+//!
+//! Every notification is sent as a frame: the big endian `u32` length of the message, followed by
+//! the RLP encoded message that contains all reverted and committed blocks with their senders and
+//! receipts.
+//!
+//! Note: state changes are not transmitted, the [PostState] of the received chains only contains
+//! the receipts of the blocks. Notifications that are published while a client is not connected
+//! are not replayed, instead the client closes all subscriptions when it reconnects, so that
+//! subscribers notice the gap and can resubscribe.
+
+use crate::{
+    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
+    CanonStateSubscriptions, Chain, PostState,
+};
+use parking_lot::Mutex;
+use reth_primitives::{Address, ReceiptWithBloom, SealedBlock, SealedBlockWithSenders};
+use reth_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{UnixListener, UnixStream},
+    sync::broadcast,
+    task::JoinHandle,
+};
+use tokio_stream::{Stream, StreamExt};
+use tracing::{debug, trace};
+
+/// Number of notifications that are buffered per connected client.
+///
+/// Clients that fall further behind are disconnected.
+const CLIENT_BUFFER: usize = 256;
+
+/// Maximum size of a single notification frame.
+const MAX_FRAME_SIZE: usize = 512 * 1024 * 1024;
+
+/// How long the [CanonStateIpcClient] waits before it tries to reconnect.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Publishes [CanonStateNotification]s to all clients that connect to the socket.
+#[derive(Debug)]
+pub struct CanonStateIpcServer {
+    path: PathBuf,
+    listener: UnixListener,
+}
+
+// === impl CanonStateIpcServer ===
+
+impl CanonStateIpcServer {
+    /// Binds the unix socket at the given path.
+    ///
+    /// A stale socket file at the path is removed first.
+    pub fn bind(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        let listener = UnixListener::bind(&path)?;
+        Ok(Self { path, listener })
+    }
+
+    /// Returns the path of the socket.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Publishes all notifications of the stream, until the stream ends.
+    pub async fn serve<St>(self, mut notifications: St)
+    where
+        St: Stream<Item = CanonStateNotification> + Unpin,
+    {
+        let (frames, _) = broadcast::channel::<Arc<Vec<u8>>>(CLIENT_BUFFER);
+        loop {
+            tokio::select! {
+                conn = self.listener.accept() => match conn {
+                    Ok((stream, _)) => {
+                        trace!(target: "provider::ipc", "client connected");
+                        tokio::spawn(forward_frames(stream, frames.subscribe()));
+                    }
+                    Err(err) => {
+                        debug!(target: "provider::ipc", ?err, "failed to accept client");
+                    }
+                },
+                notification = notifications.next() => {
+                    let Some(notification) = notification else { break };
+                    // skip encoding if no client is connected
+                    if frames.receiver_count() > 0 {
+                        let _ = frames.send(Arc::new(encode_frame(&notification)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Drop for CanonStateIpcServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Writes all frames to the connected client.
+async fn forward_frames(mut stream: UnixStream, mut frames: broadcast::Receiver<Arc<Vec<u8>>>) {
+    loop {
+        match frames.recv().await {
+            Ok(frame) => {
+                if let Err(err) = stream.write_all(&frame).await {
+                    trace!(target: "provider::ipc", ?err, "client disconnected");
+                    return
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                debug!(target: "provider::ipc", skipped, "client lagging behind, disconnecting");
+                return
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+/// A [CanonStateSubscriptions] implementation that receives the notifications from a
+/// [CanonStateIpcServer] in another process.
+///
+/// The connection is established in the background and re-established if it is lost. The
+/// notifications published in the meantime are missed, so all subscriptions are closed on
+/// reconnect and subscribers must subscribe again.
+#[derive(Debug, Clone)]
+pub struct CanonStateIpcClient {
+    /// Sender of the current subscriptions, replaced on reconnect.
+    notifications: Arc<Mutex<CanonStateNotificationSender>>,
+    _task: Arc<ClientTask>,
+}
+
+// === impl CanonStateIpcClient ===
+
+impl CanonStateIpcClient {
+    /// Spawns the task that connects to the socket at the given path.
+    ///
+    /// The task is stopped once all clones of the client are dropped.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of a tokio runtime.
+    pub fn spawn(path: impl Into<PathBuf>) -> Self {
+        let notifications = Arc::new(Mutex::new(broadcast::channel(CLIENT_BUFFER).0));
+        let task = tokio::spawn(run_client(path.into(), Arc::clone(&notifications)));
+        Self { notifications, _task: Arc::new(ClientTask(task)) }
+    }
+}
+
+impl CanonStateSubscriptions for CanonStateIpcClient {
+    fn subscribe_to_canonical_state(&self) -> CanonStateNotifications {
+        self.notifications.lock().subscribe()
+    }
+}
+
+/// Aborts the client task on drop.
+#[derive(Debug)]
+struct ClientTask(JoinHandle<()>);
+
+impl Drop for ClientTask {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Connects to the socket and forwards all received notifications, reconnects if the connection
+/// is lost.
+///
+/// The subscriptions are closed on reconnect, because the notifications that were published while
+/// the client was disconnected are missed.
+async fn run_client(path: PathBuf, notifications: Arc<Mutex<CanonStateNotificationSender>>) {
+    let mut connected_before = false;
+    loop {
+        match UnixStream::connect(&path).await {
+            Ok(stream) => {
+                debug!(target: "provider::ipc", ?path, "connected to canonical state notifications");
+                if connected_before {
+                    // dropping the previous sender closes all of its subscriptions
+                    *notifications.lock() = broadcast::channel(CLIENT_BUFFER).0;
+                }
+                connected_before = true;
+
+                let sender = notifications.lock().clone();
+                if let Err(err) = read_frames(stream, &sender).await {
+                    debug!(target: "provider::ipc", ?err, ?path, "lost connection");
+                }
+            }
+            Err(err) => {
+                trace!(target: "provider::ipc", ?err, ?path, "failed to connect");
+            }
+        }
+        tokio::time::sleep(RECONNECT_INTERVAL).await;
+    }
+}
+
+/// Reads frames until the connection is closed.
+async fn read_frames(
+    mut stream: UnixStream,
+    notifications: &CanonStateNotificationSender,
+) -> io::Result<()> {
+    loop {
+        let mut len = [0u8; 4];
+        stream.read_exact(&mut len).await?;
+        let len = u32::from_be_bytes(len) as usize;
+        if len > MAX_FRAME_SIZE {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "notification frame too large"))
+        }
+
+        let mut buf = vec![0u8; len];
+        stream.read_exact(&mut buf).await?;
+        let msg = IpcNotification::decode(&mut buf.as_slice())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+
+        // no subscribers is not an error
+        let _ = notifications.send(msg.into());
+    }
+}
+
+/// Encodes the notification as length prefixed frame.
+fn encode_frame(notification: &CanonStateNotification) -> Vec<u8> {
+    let msg = IpcNotification::from(notification);
+    let len = msg.length();
+    let mut frame = Vec::with_capacity(4 + len);
+    frame.extend_from_slice(&(len as u32).to_be_bytes());
+    msg.encode(&mut frame);
+    frame
+}
+
+/// The message that's sent for every [CanonStateNotification].
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct IpcNotification {
+    /// Whether this is a reorg.
+    reorg: bool,
+    /// Reverted blocks, empty for commits.
+    reverted: Vec<IpcBlock>,
+    /// Committed blocks.
+    committed: Vec<IpcBlock>,
+}
+
+impl From<&CanonStateNotification> for IpcNotification {
+    fn from(notification: &CanonStateNotification) -> Self {
+        match notification {
+            CanonStateNotification::Commit { new } => {
+                Self { reorg: false, reverted: Vec::new(), committed: IpcBlock::from_chain(new) }
+            }
+            CanonStateNotification::Reorg { old, new } => Self {
+                reorg: true,
+                reverted: IpcBlock::from_chain(old),
+                committed: IpcBlock::from_chain(new),
+            },
+        }
+    }
+}
+
+impl From<IpcNotification> for CanonStateNotification {
+    fn from(msg: IpcNotification) -> Self {
+        let new = Arc::new(IpcBlock::into_chain(msg.committed));
+        if msg.reorg {
+            let old = Arc::new(IpcBlock::into_chain(msg.reverted));
+            CanonStateNotification::Reorg { old, new }
+        } else {
+            CanonStateNotification::Commit { new }
+        }
+    }
+}
+
+/// A block of a transmitted chain.
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct IpcBlock {
+    block: SealedBlock,
+    senders: Vec<Address>,
+    receipts: Vec<ReceiptWithBloom>,
+}
+
+impl IpcBlock {
+    fn from_chain(chain: &Chain) -> Vec<Self> {
+        chain
+            .blocks()
+            .iter()
+            .map(|(number, block)| IpcBlock {
+                block: block.block.clone(),
+                senders: block.senders.clone(),
+                receipts: chain
+                    .state()
+                    .receipts(*number)
+                    .iter()
+                    .cloned()
+                    .map(|receipt| receipt.with_bloom())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn into_chain(blocks: Vec<Self>) -> Chain {
+        Chain::new(
+            blocks
+                .into_iter()
+                .map(|IpcBlock { block, senders, receipts }| {
+                    let mut state = PostState::new();
+                    for receipt in receipts {
+                        state.add_receipt(block.number, receipt.receipt);
+                    }
+                    (SealedBlockWithSenders { block, senders }, state)
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, Receipt};
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    fn chain(number: u64) -> Arc<Chain> {
+        let block = SealedBlock {
+            header: Header { number, ..Default::default() }.seal_slow(),
+            ..Default::default()
+        };
+        let mut state = PostState::new();
+        state.add_receipt(number, Receipt { cumulative_gas_used: 21_000, ..Default::default() });
+        Arc::new(Chain::new(vec![(SealedBlockWithSenders { block, senders: vec![] }, state)]))
+    }
+
+    #[test]
+    fn notification_roundtrip() {
+        let notifications = [
+            CanonStateNotification::Commit { new: chain(1) },
+            CanonStateNotification::Reorg { old: chain(1), new: chain(2) },
+        ];
+        for notification in notifications {
+            let frame = encode_frame(&notification);
+            assert_eq!(
+                u32::from_be_bytes(frame[..4].try_into().unwrap()) as usize,
+                frame.len() - 4
+            );
+            let decoded = IpcNotification::decode(&mut &frame[4..]).unwrap();
+            assert_eq!(CanonStateNotification::from(decoded), notification);
+        }
+    }
+
+    /// Publishes the notification until the subscription receives it.
+    async fn publish_until_received(
+        tx: &mpsc::Sender<CanonStateNotification>,
+        notifications: &mut CanonStateNotifications,
+        notification: CanonStateNotification,
+    ) {
+        // notifications are only delivered once the client is connected
+        loop {
+            tx.send(notification.clone()).await.unwrap();
+            if let Ok(received) =
+                tokio::time::timeout(Duration::from_millis(100), notifications.recv()).await
+            {
+                assert_eq!(received.unwrap(), notification);
+                break
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn server_client() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("canon.ipc");
+
+        let server = CanonStateIpcServer::bind(&path).unwrap();
+        let (tx, rx) = mpsc::channel(1);
+        tokio::spawn(server.serve(ReceiverStream::new(rx)));
+
+        let client = CanonStateIpcClient::spawn(&path);
+        let mut notifications = client.subscribe_to_canonical_state();
+        let notification = CanonStateNotification::Commit { new: chain(1) };
+        publish_until_received(&tx, &mut notifications, notification).await;
+    }
+
+    #[tokio::test]
+    async fn reconnect_closes_subscriptions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("canon.ipc");
+
+        let server = CanonStateIpcServer::bind(&path).unwrap();
+        let (tx, rx) = mpsc::channel(1);
+        let serve = tokio::spawn(server.serve(ReceiverStream::new(rx)));
+
+        let client = CanonStateIpcClient::spawn(&path);
+        let mut notifications = client.subscribe_to_canonical_state();
+        let notification = CanonStateNotification::Commit { new: chain(1) };
+        publish_until_received(&tx, &mut notifications, notification).await;
+
+        // restart the server, the client misses everything published in between
+        serve.abort();
+        let _ = serve.await;
+        let server = CanonStateIpcServer::bind(&path).unwrap();
+        let (tx, rx) = mpsc::channel(1);
+        tokio::spawn(server.serve(ReceiverStream::new(rx)));
+
+        assert_eq!(notifications.recv().await, Err(broadcast::error::RecvError::Closed));
+        let mut notifications = client.subscribe_to_canonical_state();
+        let notification = CanonStateNotification::Commit { new: chain(2) };
+        publish_until_received(&tx, &mut notifications, notification).await;
+    }
+}
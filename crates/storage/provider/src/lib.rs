@@ -23,10 +23,10 @@ mod traits;
 pub use traits::{
     AccountExtReader, AccountReader, BlockExecutionWriter, BlockExecutor, BlockHashReader,
     BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, BlockWriter,
-    BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotification,
-    CanonStateNotificationSender, CanonStateNotifications, CanonStateSubscriptions,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, ExecutorFactory, HashingWriter,
-    HeaderProvider, HistoryWriter, PostStateDataProvider, PruneCheckpointReader,
+    BlockchainTreePendingStateProvider, BytecodeReader, CanonChainTracker, CanonStateNotification,
+    CanonStateNotificationSender, CanonStateNotificationStream, CanonStateNotifications,
+    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, ExecutorFactory,
+    HashingWriter, HeaderProvider, HistoryWriter, PostStateDataProvider, PruneCheckpointReader,
     PruneCheckpointWriter, ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader,
     StageCheckpointWriter, StateProvider, StateProviderBox, StateProviderFactory,
     StateRootProvider, StorageReader, TransactionsProvider, WithdrawalsProvider,
@@ -36,7 +36,8 @@ pub use traits::{
 pub mod providers;
 pub use providers::{
     DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW, HistoricalStateProvider,
-    HistoricalStateProviderRef, LatestStateProvider, LatestStateProviderRef, ProviderFactory,
+    HistoricalStateProviderRef, LatestStateProvider, LatestStateProviderRef, LowestAvailableBlocks,
+    ProviderFactory,
 };
 
 /// Execution result
@@ -52,3 +53,6 @@ pub use reth_interfaces::provider::ProviderError;
 
 pub mod chain;
 pub use chain::{Chain, DisplayBlocksChain};
+
+#[cfg(unix)]
+pub mod ipc;
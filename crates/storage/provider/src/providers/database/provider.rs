@@ -4,9 +4,9 @@ use crate::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
     AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
-    EvmEnvProvider, HashingWriter, HeaderProvider, HistoryWriter, PostState, ProviderError,
-    PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader, StorageReader,
-    TransactionsProvider, WithdrawalsProvider,
+    BytecodeReader, EvmEnvProvider, HashingWriter, HeaderProvider, HistoryWriter, PostState,
+    ProviderError, PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader,
+    StorageReader, TransactionsProvider, WithdrawalsProvider,
 };
 use itertools::{izip, Itertools};
 use reth_db::{
@@ -30,7 +30,7 @@ use reth_primitives::{
     keccak256,
     stage::{StageCheckpoint, StageId},
     trie::Nibbles,
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
+    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bytecode,
     ChainInfo, ChainSpec, Hardfork, Head, Header, PruneCheckpoint, PruneModes, PrunePart, Receipt,
     SealedBlock, SealedBlockWithSenders, SealedHeader, StorageEntry, TransactionMeta,
     TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber,
@@ -1262,6 +1262,15 @@ impl<'this, TX: DbTx<'this>> ReceiptProvider for DatabaseProvider<'this, TX> {
     }
 }
 
+impl<'this, TX: DbTx<'this>> BytecodeReader for DatabaseProvider<'this, TX> {
+    fn bytecode_by_code_hash(&self, code_hash: H256) -> Result<Option<Bytecode>> {
+        Ok(self
+            .tx
+            .get::<tables::Bytecodes>(code_hash)?
+            .map(|bytecode| bytecode.with_code_hash(code_hash)))
+    }
+}
+
 impl<'this, TX: DbTx<'this>> WithdrawalsProvider for DatabaseProvider<'this, TX> {
     fn withdrawals_by_block(
         &self,
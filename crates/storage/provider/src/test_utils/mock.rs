@@ -1,9 +1,9 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, EvmEnvProvider, HeaderProvider, PostState, PostStateDataProvider,
-    ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
-    TransactionsProvider, WithdrawalsProvider,
+    BytecodeReader, ChainSpecProvider, EvmEnvProvider, HeaderProvider, PostState,
+    PostStateDataProvider, ReceiptProviderIdExt, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, TransactionsProvider, WithdrawalsProvider,
 };
 use parking_lot::Mutex;
 use reth_db::models::StoredBlockBodyIndices;
@@ -471,6 +471,12 @@ impl EvmEnvProvider for MockEthProvider {
     }
 }
 
+impl BytecodeReader for MockEthProvider {
+    fn bytecode_by_code_hash(&self, code_hash: H256) -> Result<Option<Bytecode>> {
+        StateProvider::bytecode_by_hash(self, code_hash)
+    }
+}
+
 impl StateProviderFactory for MockEthProvider {
     fn latest(&self) -> Result<StateProviderBox<'_>> {
         Ok(Box::new(self.clone()))
@@ -1,7 +1,7 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PostState,
+    BytecodeReader, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PostState,
     PruneCheckpointReader, ReceiptProviderIdExt, StageCheckpointReader, StateProvider,
     StateProviderBox, StateProviderFactory, StateRootProvider, TransactionsProvider,
     WithdrawalsProvider,
@@ -349,6 +349,12 @@ impl StageCheckpointReader for NoopProvider {
     }
 }
 
+impl BytecodeReader for NoopProvider {
+    fn bytecode_by_code_hash(&self, _code_hash: H256) -> Result<Option<Bytecode>> {
+        Ok(None)
+    }
+}
+
 impl WithdrawalsProvider for NoopProvider {
     fn latest_withdrawal(&self) -> Result<Option<reth_primitives::Withdrawal>> {
         Ok(None)
@@ -0,0 +1,14 @@
+use reth_interfaces::Result;
+use reth_primitives::{Bytecode, H256};
+
+/// Client trait for fetching contract [Bytecode] by its code hash.
+///
+/// Unlike [StateProvider](crate::StateProvider) this is not bound to the state at a specific
+/// block, since bytecode is content addressed.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait BytecodeReader: Send + Sync {
+    /// Get contract bytecode by its code hash.
+    ///
+    /// Returns `None` if the bytecode is unknown.
+    fn bytecode_by_code_hash(&self, code_hash: H256) -> Result<Option<Bytecode>>;
+}
@@ -61,7 +61,7 @@ impl Stream for CanonStateNotificationStream {
 /// Chain action that is triggered when a new block is imported or old block is reverted.
 /// and will return all [`crate::PostState`] and [`reth_primitives::SealedBlockWithSenders`] of both
 /// reverted and committed blocks.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[allow(missing_docs)]
 pub enum CanonStateNotification {
     /// Chain got extended without reorg and only new chain is returned.
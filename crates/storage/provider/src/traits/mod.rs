@@ -12,6 +12,9 @@ pub use block::{BlockExecutionWriter, BlockReader, BlockReaderIdExt, BlockSource
 mod block_hash;
 pub use block_hash::BlockHashReader;
 
+mod bytecode;
+pub use bytecode::BytecodeReader;
+
 mod block_id;
 pub use block_id::{BlockIdReader, BlockNumReader};
 
@@ -44,8 +47,8 @@ pub use executor::{BlockExecutor, ExecutorFactory};
 
 mod chain;
 pub use chain::{
-    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
-    CanonStateSubscriptions,
+    CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
+    CanonStateNotifications, CanonStateSubscriptions,
 };
 
 mod spec;
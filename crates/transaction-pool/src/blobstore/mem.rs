@@ -1,8 +1,8 @@
-use crate::blobstore::{BlobStore, BlobStoreError, BlobTransactionSidecar};
+use crate::blobstore::{BlobAndProof, BlobStore, BlobStoreError, BlobTransactionSidecar};
 use parking_lot::RwLock;
 use reth_primitives::H256;
 use std::{
-    collections::HashMap,
+    collections::{hash_map::Entry, HashMap},
     sync::{atomic::AtomicUsize, Arc},
 };
 
@@ -15,7 +15,7 @@ pub struct InMemoryBlobStore {
 #[derive(Debug, Default)]
 struct InMemoryBlobStoreInner {
     /// Storage for all blob data.
-    store: RwLock<HashMap<H256, BlobTransactionSidecar>>,
+    store: RwLock<BlobStorage>,
     data_size: AtomicUsize,
     num_blobs: AtomicUsize,
 }
@@ -39,7 +39,7 @@ impl InMemoryBlobStoreInner {
 impl BlobStore for InMemoryBlobStore {
     fn insert(&self, tx: H256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
         let mut store = self.inner.store.write();
-        self.inner.add_size(insert_size(&mut store, tx, data));
+        self.inner.add_size(store.insert(tx, data));
         self.inner.update_len(store.len());
         Ok(())
     }
@@ -51,7 +51,7 @@ impl BlobStore for InMemoryBlobStore {
         let mut store = self.inner.store.write();
         let mut total_add = 0;
         for (tx, data) in txs {
-            let add = insert_size(&mut store, tx, data);
+            let add = store.insert(tx, data);
             total_add += add;
         }
         self.inner.add_size(total_add);
@@ -61,7 +61,7 @@ impl BlobStore for InMemoryBlobStore {
 
     fn delete(&self, tx: H256) -> Result<(), BlobStoreError> {
         let mut store = self.inner.store.write();
-        let sub = remove_size(&mut store, &tx);
+        let sub = store.remove(&tx);
         self.inner.sub_size(sub);
         self.inner.update_len(store.len());
         Ok(())
@@ -74,7 +74,7 @@ impl BlobStore for InMemoryBlobStore {
         let mut store = self.inner.store.write();
         let mut total_sub = 0;
         for tx in txs {
-            total_sub += remove_size(&mut store, &tx);
+            total_sub += store.remove(&tx);
         }
         self.inner.sub_size(total_sub);
         self.inner.update_len(store.len());
@@ -84,7 +84,7 @@ impl BlobStore for InMemoryBlobStore {
     // Retrieves the decoded blob data for the given transaction hash.
     fn get(&self, tx: H256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
         let store = self.inner.store.read();
-        Ok(store.get(&tx).cloned())
+        Ok(store.sidecars.get(&tx).cloned())
     }
 
     fn get_all(
@@ -94,7 +94,7 @@ impl BlobStore for InMemoryBlobStore {
         let mut items = Vec::with_capacity(txs.len());
         let store = self.inner.store.read();
         for tx in txs {
-            if let Some(item) = store.get(&tx) {
+            if let Some(item) = store.sidecars.get(&tx) {
                 items.push((tx, item.clone()));
             }
         }
@@ -106,7 +106,7 @@ impl BlobStore for InMemoryBlobStore {
         let mut items = Vec::with_capacity(txs.len());
         let store = self.inner.store.read();
         for tx in txs {
-            if let Some(item) = store.get(&tx) {
+            if let Some(item) = store.sidecars.get(&tx) {
                 items.push(item.clone());
             } else {
                 return Err(BlobStoreError::MissingSidecar(tx))
@@ -116,6 +116,23 @@ impl BlobStore for InMemoryBlobStore {
         Ok(items)
     }
 
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[H256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        let store = self.inner.store.read();
+        let result = versioned_hashes
+            .iter()
+            .map(|versioned_hash| {
+                let (tx, idx) = store.versioned_hashes.get(versioned_hash)?.first()?;
+                let sidecar = store.sidecars.get(tx)?;
+                Some(BlobAndProof { blob: sidecar.blobs[*idx], proof: sidecar.proofs[*idx] })
+            })
+            .collect();
+
+        Ok(result)
+    }
+
     fn data_size_hint(&self) -> Option<usize> {
         Some(self.inner.data_size.load(std::sync::atomic::Ordering::Relaxed))
     }
@@ -125,22 +142,87 @@ impl BlobStore for InMemoryBlobStore {
     }
 }
 
-/// Removes the given blob from the store and returns the size of the blob that was removed.
-#[inline]
-fn remove_size(store: &mut HashMap<H256, BlobTransactionSidecar>, tx: &H256) -> usize {
-    store.remove(tx).map(|rem| rem.size()).unwrap_or_default()
+/// The stored blobs, indexed by transaction hash and by versioned hash.
+#[derive(Debug, Default)]
+struct BlobStorage {
+    /// All blob sidecars, by transaction hash.
+    sidecars: HashMap<H256, BlobTransactionSidecar>,
+    /// The transactions that carry a blob and the index of the blob in their sidecar, by the
+    /// versioned hash of the blob.
+    versioned_hashes: HashMap<H256, Vec<(H256, usize)>>,
 }
 
-/// Inserts the given blob into the store and returns the size of the blob that was added
-///
-/// We don't need to handle the size updates for replacements because transactions are unique.
-#[inline]
-fn insert_size(
-    store: &mut HashMap<H256, BlobTransactionSidecar>,
-    tx: H256,
-    blob: BlobTransactionSidecar,
-) -> usize {
-    let add = blob.size();
-    store.insert(tx, blob).map(|rem| rem.size());
-    add
+impl BlobStorage {
+    fn len(&self) -> usize {
+        self.sidecars.len()
+    }
+
+    /// Inserts the given blob into the store and returns the size of the blob that was added
+    ///
+    /// We don't need to handle the size updates for replacements because transactions are unique.
+    fn insert(&mut self, tx: H256, blob: BlobTransactionSidecar) -> usize {
+        let add = blob.size();
+        // drop the index entries of a replaced sidecar
+        self.remove(&tx);
+        for (idx, versioned_hash) in blob.versioned_hashes().enumerate() {
+            let txs = self.versioned_hashes.entry(versioned_hash).or_default();
+            if !txs.contains(&(tx, idx)) {
+                txs.push((tx, idx));
+            }
+        }
+        self.sidecars.insert(tx, blob);
+        add
+    }
+
+    /// Removes the given blob from the store and returns the size of the blob that was removed.
+    fn remove(&mut self, tx: &H256) -> usize {
+        let Some(blob) = self.sidecars.remove(tx) else { return 0 };
+        for versioned_hash in blob.versioned_hashes() {
+            if let Entry::Occupied(mut entry) = self.versioned_hashes.entry(versioned_hash) {
+                entry.get_mut().retain(|(hash, _)| hash != tx);
+                if entry.get().is_empty() {
+                    entry.remove();
+                }
+            }
+        }
+        blob.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::kzg::{Blob, Bytes48, BYTES_PER_BLOB};
+
+    fn sidecar(seeds: &[u8]) -> BlobTransactionSidecar {
+        BlobTransactionSidecar {
+            blobs: seeds.iter().map(|seed| Blob::from([*seed; BYTES_PER_BLOB])).collect(),
+            commitments: seeds.iter().map(|seed| Bytes48::from([*seed; 48])).collect(),
+            proofs: seeds.iter().map(|seed| Bytes48::from([!*seed; 48])).collect(),
+        }
+    }
+
+    #[test]
+    fn get_by_versioned_hashes() {
+        let store = InMemoryBlobStore::default();
+        let (tx1, tx2) = (H256::random(), H256::random());
+        let (sidecar1, sidecar2) = (sidecar(&[1, 2]), sidecar(&[2]));
+        let hashes1 = sidecar1.versioned_hashes().collect::<Vec<_>>();
+        store.insert(tx1, sidecar1.clone()).unwrap();
+        store.insert(tx2, sidecar2).unwrap();
+
+        let blob = |sidecar: &BlobTransactionSidecar, idx: usize| {
+            Some(BlobAndProof { blob: sidecar.blobs[idx], proof: sidecar.proofs[idx] })
+        };
+        let res = store.get_by_versioned_hashes(&[hashes1[1], H256::random(), hashes1[0]]).unwrap();
+        assert_eq!(res, vec![blob(&sidecar1, 1), None, blob(&sidecar1, 0)]);
+
+        // the shared blob is still available from the remaining transaction
+        store.delete(tx1).unwrap();
+        let res = store.get_by_versioned_hashes(&hashes1).unwrap();
+        assert_eq!(res, vec![None, blob(&sidecar1, 1)]);
+
+        store.delete(tx2).unwrap();
+        assert!(store.inner.store.read().versioned_hashes.is_empty());
+    }
 }
@@ -2,7 +2,10 @@
 
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
-use reth_primitives::{BlobTransactionSidecar, H256};
+use reth_primitives::{
+    kzg::{Blob, Bytes48},
+    BlobTransactionSidecar, H256,
+};
 use std::fmt;
 pub use tracker::{BlobStoreCanonTracker, BlobStoreUpdates};
 
@@ -47,6 +50,15 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     /// Returns an error if any of the blobs are not found in the blob store.
     fn get_exact(&self, txs: Vec<H256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
 
+    /// Returns the blobs and proofs for the given versioned hashes, in the order they were
+    /// requested.
+    ///
+    /// The entry is `None` if no blob with the versioned hash is in the store.
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[H256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError>;
+
     /// Data size of all transactions in the blob store.
     fn data_size_hint(&self) -> Option<usize>;
 
@@ -54,6 +66,15 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     fn blobs_len(&self) -> usize;
 }
 
+/// A blob and its KZG proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobAndProof {
+    /// The blob data.
+    pub blob: Blob,
+    /// The KZG proof of the blob.
+    pub proof: Bytes48,
+}
+
 /// Error variants that can occur when interacting with a blob store.
 #[derive(Debug, thiserror::Error)]
 pub enum BlobStoreError {
@@ -175,15 +175,16 @@ pub use crate::{
     error::PoolResult,
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
     pool::{
-        state::SubPool, AllTransactionsEvents, FullTransactionEvent, TransactionEvent,
+        state::{ParkedReason, SubPool},
+        AllTransactionsEvents, DiscardReason, FullTransactionEvent, TransactionEvent,
         TransactionEvents,
     },
     traits::{
         AllPoolTransactions, BestTransactions, BlockInfo, CanonicalStateUpdate, ChangedAccount,
         EthBlobTransactionSidecar, EthPoolTransaction, EthPooledTransaction,
         GetPooledTransactionLimit, NewTransactionEvent, PoolSize, PoolTransaction, PropagateKind,
-        PropagatedTransactions, TransactionListenerKind, TransactionOrigin, TransactionPool,
-        TransactionPoolExt,
+        PropagatedTransactions, SenderPoolStatus, TransactionListenerKind, TransactionOrigin,
+        TransactionPlacement, TransactionPool, TransactionPoolExt,
     },
     validate::{
         EthTransactionValidator, TransactionValidationOutcome, TransactionValidationTaskExecutor,
@@ -471,6 +472,14 @@ where
         self.pool.unique_senders()
     }
 
+    fn get_sender_status(&self, sender: Address) -> Option<SenderPoolStatus<Self::Transaction>> {
+        self.pool.get_sender_status(sender)
+    }
+
+    fn transaction_event_history(&self, tx_hash: TxHash) -> Vec<TransactionEvent> {
+        self.pool.transaction_event_history(&tx_hash)
+    }
+
     fn get_blob(&self, tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
         self.pool.blob_store().get(tx_hash)
     }
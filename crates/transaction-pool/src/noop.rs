@@ -10,8 +10,8 @@ use crate::{
     validate::ValidTransaction,
     AllPoolTransactions, AllTransactionsEvents, BestTransactions, BlockInfo, EthPooledTransaction,
     NewTransactionEvent, PoolResult, PoolSize, PoolTransaction, PropagatedTransactions,
-    TransactionEvents, TransactionOrigin, TransactionPool, TransactionValidationOutcome,
-    TransactionValidator, ValidPoolTransaction,
+    SenderPoolStatus, TransactionEvent, TransactionEvents, TransactionOrigin, TransactionPool,
+    TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
 };
 use reth_primitives::{Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash};
 use std::{collections::HashSet, marker::PhantomData, sync::Arc};
@@ -182,6 +182,14 @@ impl TransactionPool for NoopTransactionPool {
         Default::default()
     }
 
+    fn get_sender_status(&self, _sender: Address) -> Option<SenderPoolStatus<Self::Transaction>> {
+        None
+    }
+
+    fn transaction_event_history(&self, _tx_hash: TxHash) -> Vec<TransactionEvent> {
+        vec![]
+    }
+
     fn get_blob(&self, _tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
         Ok(None)
     }
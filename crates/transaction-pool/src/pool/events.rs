@@ -68,9 +68,9 @@ pub enum TransactionEvent {
     ///
     /// E.g. same (sender + nonce) pair
     Replaced(TxHash),
-    /// Transaction was dropped due to configured limits.
-    Discarded,
-    /// Transaction became invalid indefinitely.
+    /// Transaction was removed from the pool or dropped before it was added, for the given reason.
+    Discarded(DiscardReason),
+    /// Transaction failed validation and was not added to the pool.
     Invalid,
     /// Transaction was propagated to peers.
     Propagated(Arc<Vec<PropagateKind>>),
@@ -84,7 +84,24 @@ impl TransactionEvent {
             self,
             TransactionEvent::Replaced(_) |
                 TransactionEvent::Mined(_) |
-                TransactionEvent::Discarded
+                TransactionEvent::Discarded(_) |
+                TransactionEvent::Invalid
         )
     }
 }
+
+/// The reason a transaction was discarded.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DiscardReason {
+    /// The pool exceeded its size limits and the transaction, or a transaction of the same sender
+    /// with a lower nonce, was among the worst ones.
+    PoolSizeLimit,
+    /// The on-chain nonce of the sender moved past the nonce of the transaction.
+    StaleNonce,
+    /// The transaction could not be validated.
+    ValidationFailed,
+    /// The transaction was removed on request, see
+    /// [TransactionPool::remove_transactions](crate::TransactionPool::remove_transactions).
+    Removed,
+}
@@ -1,14 +1,14 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, TransactionEvent},
+    pool::events::{DiscardReason, FullTransactionEvent, TransactionEvent},
     traits::PropagateKind,
     PoolTransaction, ValidPoolTransaction,
 };
 use futures_util::Stream;
 use reth_primitives::{TxHash, H256};
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, VecDeque},
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
@@ -20,6 +20,12 @@ use tokio::sync::mpsc::{
 /// The size of the event channel used to propagate transaction events.
 const TX_POOL_EVENT_CHANNEL_SIZE: usize = 1024;
 
+/// The number of transactions for which the event history is retained.
+const TX_EVENT_HISTORY_LIMIT: usize = 4096;
+
+/// The number of events that are retained per transaction.
+const TX_EVENT_HISTORY_PER_TX_LIMIT: usize = 16;
+
 /// A Stream that receives [TransactionEvent] only for the transaction with the given hash.
 #[derive(Debug)]
 #[must_use = "streams do nothing unless polled"]
@@ -71,6 +77,8 @@ pub(crate) struct PoolEventBroadcast<T: PoolTransaction> {
     all_events_broadcaster: AllPoolEventsBroadcaster<T>,
     /// All listeners for events for a certain transaction hash.
     broadcasters_by_hash: HashMap<TxHash, PoolEventBroadcaster>,
+    /// Recent events by transaction hash.
+    history: TransactionEventHistory,
 }
 
 impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
@@ -78,6 +86,7 @@ impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
         Self {
             all_events_broadcaster: AllPoolEventsBroadcaster::default(),
             broadcasters_by_hash: HashMap::default(),
+            history: TransactionEventHistory::default(),
         }
     }
 }
@@ -90,6 +99,8 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         event: TransactionEvent,
         pool_event: FullTransactionEvent<T>,
     ) {
+        self.history.record(*hash, &event);
+
         // Broadcast to all listeners for the transaction hash.
         if let Entry::Occupied(mut sink) = self.broadcasters_by_hash.entry(*hash) {
             sink.get_mut().broadcast(event.clone());
@@ -103,6 +114,11 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         self.all_events_broadcaster.broadcast(pool_event);
     }
 
+    /// Returns the recorded events of the transaction, oldest first.
+    pub(crate) fn history(&self, tx_hash: &TxHash) -> Vec<TransactionEvent> {
+        self.history.get(tx_hash)
+    }
+
     /// Create a new subscription for the given transaction hash.
     pub(crate) fn subscribe(&mut self, tx_hash: TxHash) -> TransactionEvents {
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
@@ -161,8 +177,17 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     }
 
     /// Notify listeners about a transaction that was discarded.
-    pub(crate) fn discarded(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+    pub(crate) fn discarded(&mut self, tx: &TxHash, reason: DiscardReason) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Discarded(reason),
+            FullTransactionEvent::Discarded(*tx),
+        );
+    }
+
+    /// Notify listeners about a transaction that failed validation.
+    pub(crate) fn invalid(&mut self, tx: &TxHash) {
+        self.broadcast_event(tx, TransactionEvent::Invalid, FullTransactionEvent::Invalid(*tx));
     }
 
     /// Notify listeners that the transaction was mined
@@ -220,3 +245,93 @@ impl PoolEventBroadcaster {
         self.senders.retain(|sender| sender.send(event.clone()).is_ok())
     }
 }
+
+/// A bounded history of [TransactionEvent]s by transaction hash.
+///
+/// Only transactions that were added to the pool are tracked. Once [TX_EVENT_HISTORY_LIMIT]
+/// transactions are tracked, the history of the transaction that was seen first is dropped.
+#[derive(Default, Debug)]
+struct TransactionEventHistory {
+    /// Recorded events by transaction hash.
+    events: HashMap<TxHash, Vec<TransactionEvent>>,
+    /// Tracked transaction hashes in the order they were first seen.
+    order: VecDeque<TxHash>,
+}
+
+impl TransactionEventHistory {
+    /// Records the event for the transaction.
+    fn record(&mut self, hash: TxHash, event: &TransactionEvent) {
+        // propagation doesn't change the state of the transaction in the pool, and a rejected
+        // submission doesn't affect a transaction with the same hash that is already in the pool
+        if matches!(
+            event,
+            TransactionEvent::Propagated(_) |
+                TransactionEvent::Invalid |
+                TransactionEvent::Discarded(DiscardReason::ValidationFailed)
+        ) {
+            return
+        }
+
+        match self.events.entry(hash) {
+            Entry::Occupied(mut entry) => {
+                let events = entry.get_mut();
+                if events.len() >= TX_EVENT_HISTORY_PER_TX_LIMIT {
+                    events.remove(0);
+                }
+                events.push(event.clone());
+            }
+            Entry::Vacant(entry) => {
+                // only transactions that were added to the pool are tracked
+                if !matches!(event, TransactionEvent::Pending | TransactionEvent::Queued) {
+                    return
+                }
+                entry.insert(vec![event.clone()]);
+                self.order.push_back(hash);
+                if self.order.len() > TX_EVENT_HISTORY_LIMIT {
+                    if let Some(oldest) = self.order.pop_front() {
+                        self.events.remove(&oldest);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the recorded events of the transaction, oldest first.
+    fn get(&self, hash: &TxHash) -> Vec<TransactionEvent> {
+        self.events.get(hash).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_history_is_bounded() {
+        let mut history = TransactionEventHistory::default();
+        let first = TxHash::from_low_u64_be(0);
+        history.record(first, &TransactionEvent::Queued);
+        history.record(first, &TransactionEvent::Propagated(Default::default()));
+        history.record(first, &TransactionEvent::Replaced(TxHash::from_low_u64_be(1)));
+        assert_eq!(
+            history.get(&first),
+            vec![TransactionEvent::Queued, TransactionEvent::Replaced(TxHash::from_low_u64_be(1))]
+        );
+
+        // rejected submissions aren't tracked
+        history.record(first, &TransactionEvent::Invalid);
+        let unknown = TxHash::from_low_u64_be(u64::MAX);
+        history.record(unknown, &TransactionEvent::Invalid);
+        history.record(unknown, &TransactionEvent::Discarded(DiscardReason::ValidationFailed));
+        history.record(unknown, &TransactionEvent::Discarded(DiscardReason::PoolSizeLimit));
+        assert!(history.get(&unknown).is_empty());
+        assert_eq!(history.get(&first).len(), 2);
+
+        for i in 1..=TX_EVENT_HISTORY_LIMIT as u64 {
+            history.record(TxHash::from_low_u64_be(i), &TransactionEvent::Pending);
+        }
+        assert!(history.get(&first).is_empty());
+        assert_eq!(history.events.len(), TX_EVENT_HISTORY_LIMIT);
+        assert_eq!(history.get(&TxHash::from_low_u64_be(1)), vec![TransactionEvent::Pending]);
+    }
+}
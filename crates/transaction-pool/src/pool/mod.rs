@@ -75,7 +75,7 @@ use crate::{
     },
     traits::{
         AllPoolTransactions, BlockInfo, NewTransactionEvent, PoolSize, PoolTransaction,
-        PropagatedTransactions, TransactionOrigin,
+        PropagatedTransactions, SenderPoolStatus, TransactionOrigin,
     },
     validate::{TransactionValidationOutcome, ValidPoolTransaction},
     CanonicalStateUpdate, ChangedAccount, PoolConfig, TransactionOrdering, TransactionValidator,
@@ -96,7 +96,7 @@ use tokio::sync::mpsc;
 use tracing::{debug, trace, warn};
 
 mod events;
-pub use events::{FullTransactionEvent, TransactionEvent};
+pub use events::{DiscardReason, FullTransactionEvent, TransactionEvent};
 
 mod listener;
 use crate::{
@@ -354,7 +354,7 @@ where
         let mut listener = self.event_listener.write();
 
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::StaleNonce));
 
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
         // called during txpool maintenance when the pool drifted.
@@ -435,12 +435,12 @@ where
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(tx.hash());
+                listener.invalid(tx.hash());
                 Err(PoolError::InvalidTransaction(*tx.hash(), err))
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(&tx_hash);
+                listener.discarded(&tx_hash, DiscardReason::ValidationFailed);
                 Err(PoolError::Other(tx_hash, err))
             }
         }
@@ -477,7 +477,7 @@ where
         }
 
         let mut listener = self.event_listener.write();
-        discarded.iter().for_each(|tx| listener.discarded(tx));
+        discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::PoolSizeLimit));
 
         // It may happen that a newly added transaction is immediately discarded, so we need to
         // adjust the result here
@@ -587,7 +587,7 @@ where
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::StaleNonce));
     }
 
     /// Fire events for the newly added transaction if there are any.
@@ -600,7 +600,9 @@ where
 
                 listener.pending(transaction.hash(), replaced.clone());
                 promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-                discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+                discarded
+                    .iter()
+                    .for_each(|tx| listener.discarded(tx.hash(), DiscardReason::StaleNonce));
             }
             AddedTransaction::Parked { transaction, replaced, .. } => {
                 listener.queued(transaction.hash());
@@ -654,7 +656,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -685,6 +687,20 @@ where
         self.pool.read().get_transactions_by_sender(sender_id)
     }
 
+    /// Returns the sub-pool placement of all transactions sent by the given sender.
+    pub(crate) fn get_sender_status(
+        &self,
+        sender: Address,
+    ) -> Option<SenderPoolStatus<T::Transaction>> {
+        let sender_id = self.identifiers.read().sender_id(&sender)?;
+        self.pool.read().get_sender_status(sender_id)
+    }
+
+    /// Returns the recorded events of the transaction, oldest first.
+    pub(crate) fn transaction_event_history(&self, tx_hash: &TxHash) -> Vec<TransactionEvent> {
+        self.event_listener.read().history(tx_hash)
+    }
+
     /// Returns all the transactions belonging to the hashes.
     ///
     /// If no transaction exists, it is skipped.
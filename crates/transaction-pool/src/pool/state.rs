@@ -46,6 +46,36 @@ impl TxState {
     pub(crate) fn has_nonce_gap(&self) -> bool {
         !self.intersects(TxState::NO_NONCE_GAPS)
     }
+
+    /// Returns the requirements of the pending sub-pool that this state doesn't meet.
+    pub(crate) fn parked_reasons(&self) -> Vec<ParkedReason> {
+        [
+            (TxState::NO_NONCE_GAPS, ParkedReason::NonceGap),
+            (TxState::NO_PARKED_ANCESTORS, ParkedReason::ParkedAncestor),
+            (TxState::ENOUGH_BALANCE, ParkedReason::InsufficientBalance),
+            (TxState::NOT_TOO_MUCH_GAS, ParkedReason::ExceedsBlockGasLimit),
+            (TxState::ENOUGH_FEE_CAP_BLOCK, ParkedReason::BaseFeeTooLow),
+        ]
+        .into_iter()
+        .filter(|(bit, _)| !self.contains(*bit))
+        .map(|(_, reason)| reason)
+        .collect()
+    }
+}
+
+/// A requirement of the pending sub-pool that a transaction doesn't meet.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParkedReason {
+    /// A transaction with a lower nonce of the same sender is missing.
+    NonceGap,
+    /// A transaction with a lower nonce of the same sender is parked.
+    ParkedAncestor,
+    /// The sender's balance can't cover the cost of this and all prior transactions.
+    InsufficientBalance,
+    /// The gas limit of the transaction exceeds the block's gas limit.
+    ExceedsBlockGasLimit,
+    /// The fee cap of the transaction is below the base fee of the pending block.
+    BaseFeeTooLow,
 }
 
 /// Identifier for the transaction Sub-pool
@@ -150,4 +180,22 @@ mod tests {
         assert_eq!(SubPool::Pending, state.into());
         assert!(state.is_pending());
     }
+
+    #[test]
+    fn test_parked_reasons() {
+        assert!(TxState::PENDING_POOL_BITS.parked_reasons().is_empty());
+
+        let state = TxState::BASE_FEE_POOL_BITS;
+        assert_eq!(state.parked_reasons(), vec![ParkedReason::BaseFeeTooLow]);
+
+        let state = TxState::NO_PARKED_ANCESTORS | TxState::NOT_TOO_MUCH_GAS;
+        assert_eq!(
+            state.parked_reasons(),
+            vec![
+                ParkedReason::NonceGap,
+                ParkedReason::InsufficientBalance,
+                ParkedReason::BaseFeeTooLow
+            ]
+        );
+    }
 }
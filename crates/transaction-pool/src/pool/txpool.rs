@@ -13,7 +13,7 @@ use crate::{
         update::{Destination, PoolUpdate},
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
-    traits::{BlockInfo, PoolSize},
+    traits::{BlockInfo, PoolSize, SenderPoolStatus, TransactionPlacement},
     PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
@@ -296,6 +296,35 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.all_transactions.txs_iter(sender).map(|(_, tx)| Arc::clone(&tx.transaction)).collect()
     }
 
+    /// Returns the sub-pool placement of all transactions sent from the given sender and the
+    /// nonces missing between the sender's on-chain nonce and its highest pooled nonce.
+    pub(crate) fn get_sender_status(
+        &self,
+        sender: SenderId,
+    ) -> Option<SenderPoolStatus<T::Transaction>> {
+        let mut txs = self.all_transactions.txs_iter(sender).peekable();
+        txs.peek()?;
+
+        let state_nonce =
+            self.sender_info.get(&sender).map(|info| info.state_nonce).unwrap_or_default();
+        let mut nonce_gaps = Vec::new();
+        let mut transactions = Vec::new();
+        let mut next_nonce = state_nonce;
+        for (id, tx) in txs {
+            if id.nonce > next_nonce {
+                nonce_gaps.push(next_nonce..=id.nonce - 1);
+            }
+            next_nonce = id.nonce + 1;
+            transactions.push(TransactionPlacement {
+                transaction: Arc::clone(&tx.transaction),
+                subpool: tx.subpool,
+                parked_reasons: tx.state.parked_reasons(),
+            });
+        }
+
+        Some(SenderPoolStatus { state_nonce, nonce_gaps, transactions })
+    }
+
     /// Updates the transactions for the changed senders.
     pub(crate) fn update_accounts(
         &mut self,
@@ -1633,6 +1662,7 @@ impl SenderInfo {
 mod tests {
     use super::*;
     use crate::{
+        pool::state::ParkedReason,
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory},
         traits::TransactionOrigin,
     };
@@ -1715,6 +1745,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn sender_status_nonce_gaps() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::mock();
+
+        let tx = MockTransaction::eip1559().inc_price().inc_limit();
+        let first = f.validated(tx.clone());
+        let gapped = f.validated(tx.skip(2));
+        pool.add_transaction(first.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        pool.add_transaction(gapped.clone(), on_chain_balance, on_chain_nonce).unwrap();
+
+        let status = pool.get_sender_status(first.sender_id()).unwrap();
+        assert_eq!(status.state_nonce, on_chain_nonce);
+        assert_eq!(status.nonce_gaps, vec![1..=2]);
+        assert_eq!(status.transactions.len(), 2);
+        assert_eq!(status.transactions[0].transaction.hash(), first.hash());
+
+        let gapped_placement = &status.transactions[1];
+        assert_eq!(gapped_placement.transaction.hash(), gapped.hash());
+        assert_eq!(gapped_placement.subpool, SubPool::Queued);
+        assert!(gapped_placement.parked_reasons.contains(&ParkedReason::NonceGap));
+    }
+
     #[test]
     fn insert_replace() {
         let on_chain_balance = U256::ZERO;
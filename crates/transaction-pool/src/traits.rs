@@ -1,6 +1,9 @@
 use crate::{
     error::PoolResult,
-    pool::{state::SubPool, TransactionEvents},
+    pool::{
+        state::{ParkedReason, SubPool},
+        TransactionEvent, TransactionEvents,
+    },
     validate::ValidPoolTransaction,
     AllTransactionsEvents,
 };
@@ -16,6 +19,7 @@ use reth_rlp::Encodable;
 use std::{
     collections::{HashMap, HashSet},
     fmt,
+    ops::RangeInclusive,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
@@ -297,6 +301,18 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Returns a set of all senders of transactions in the pool
     fn unique_senders(&self) -> HashSet<Address>;
 
+    /// Returns the sub-pool placement of all transactions sent by the given sender and the nonces
+    /// that are missing between the sender's on-chain nonce and its highest pooled nonce.
+    ///
+    /// Returns `None` if the pool holds no transactions of the sender.
+    fn get_sender_status(&self, sender: Address) -> Option<SenderPoolStatus<Self::Transaction>>;
+
+    /// Returns the recorded events of a transaction that is or recently was in the pool, oldest
+    /// first.
+    ///
+    /// The history is bounded, so events of transactions that were seen long ago are dropped.
+    fn transaction_event_history(&self, tx_hash: TxHash) -> Vec<TransactionEvent>;
+
     /// Returns the [BlobTransactionSidecar] for the given transaction hash if it exists in the blob
     /// store.
     fn get_blob(&self, tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError>;
@@ -396,6 +412,31 @@ impl<T: PoolTransaction> Default for AllPoolTransactions<T> {
     }
 }
 
+/// The pool's view of the transactions of a single sender.
+#[derive(Debug, Clone)]
+pub struct SenderPoolStatus<T: PoolTransaction> {
+    /// The on-chain nonce of the sender, as last seen by the pool.
+    pub state_nonce: u64,
+    /// Nonce ranges between the on-chain nonce and the highest pooled nonce without a transaction
+    /// in the pool.
+    pub nonce_gaps: Vec<RangeInclusive<u64>>,
+    /// All transactions of the sender, ordered by nonce.
+    pub transactions: Vec<TransactionPlacement<T>>,
+}
+
+/// Describes in which sub-pool a transaction is placed and why.
+#[derive(Debug, Clone)]
+pub struct TransactionPlacement<T: PoolTransaction> {
+    /// The transaction.
+    pub transaction: Arc<ValidPoolTransaction<T>>,
+    /// The sub-pool that currently contains the transaction.
+    pub subpool: SubPool,
+    /// The requirements of the pending sub-pool that the transaction doesn't meet.
+    ///
+    /// Empty if the transaction is pending.
+    pub parked_reasons: Vec<ParkedReason>,
+}
+
 /// Represents a transaction that was propagated over the network.
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
 pub struct PropagatedTransactions(pub HashMap<TxHash, Vec<PropagateKind>>);
@@ -2,8 +2,8 @@ use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
     test_utils::{testing_pool, testing_pool_with_validator, MockTransactionFactory},
-    FullTransactionEvent, TransactionEvent, TransactionListenerKind, TransactionOrigin,
-    TransactionPool,
+    DiscardReason, FullTransactionEvent, TransactionEvent, TransactionListenerKind,
+    TransactionOrigin, TransactionPool,
 };
 use std::{future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
@@ -23,6 +23,27 @@ async fn txpool_listener_by_hash() {
     assert_matches!(events.next().await, Some(TransactionEvent::Pending));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_history_of_removed_transaction() {
+    let txpool = testing_pool();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let hash = *transaction.hash();
+
+    let mut events = txpool
+        .add_transaction_and_subscribe(TransactionOrigin::External, transaction.transaction.clone())
+        .await
+        .unwrap();
+    txpool.remove_transactions(vec![hash]);
+
+    assert_matches!(events.next().await, Some(TransactionEvent::Pending));
+    assert_matches!(events.next().await, Some(TransactionEvent::Discarded(DiscardReason::Removed)));
+    assert_eq!(
+        txpool.transaction_event_history(hash),
+        vec![TransactionEvent::Pending, TransactionEvent::Discarded(DiscardReason::Removed)]
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn txpool_listener_all() {
     let txpool = testing_pool();
@@ -11,6 +11,9 @@
 //! ```sh
 //! cast rpc myrpcExt_customMethod
 //! ```
+//!
+//! New blocks are received from a node that was started with
+//! `--notifications.ipcpath $RETH_NOTIFICATIONS_PATH`.
 use reth::{
     primitives::ChainSpecBuilder,
     providers::{providers::BlockchainProvider, ProviderFactory},
@@ -33,7 +36,7 @@ use reth::{
 };
 
 // Configuring the network parts, ideally also wouldn't ned to think about this.
-use reth::{providers::test_utils::TestCanonStateSubscriptions, tasks::TokioTaskExecutor};
+use reth::{providers::ipc::CanonStateIpcClient, tasks::TokioTaskExecutor};
 use std::{path::Path, sync::Arc};
 
 use myrpc_ext::{MyRpcExt, MyRpcExtApiServer};
@@ -74,7 +77,8 @@ async fn main() -> eyre::Result<()> {
         .with_noop_pool()
         .with_noop_network()
         .with_executor(TokioTaskExecutor::default())
-        .with_events(TestCanonStateSubscriptions::default());
+        // Canonical state notifications of the node that writes to the DB
+        .with_events(CanonStateIpcClient::spawn(std::env::var("RETH_NOTIFICATIONS_PATH")?));
 
     // Pick which namespaces to expose.
     let config = TransportRpcModuleConfig::default().with_http([RethRpcModule::Eth]);